@@ -1,16 +1,24 @@
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 
-use fluxora_stream::{ContractError as StreamContractErr, CreateStreamParams};
+use fluxora_stream::{
+    ContractError as StreamContractErr, CreateStreamParams, StreamKind, StreamScheduleTemplate,
+};
 use soroban_sdk::{
     contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, vec,
-    Address, Env, Vec,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, Vec,
 };
 
 #[contractclient(name = "FluxoraStreamClient")]
 pub trait FluxoraStreamInterface {
     fn version(env: Env) -> u32;
     fn is_paused(env: Env) -> bool;
+    fn init_with_creator(
+        env: Env,
+        token: Address,
+        admin: Address,
+        creator: Address,
+    ) -> Result<(), fluxora_stream::ContractError>;
     fn create_stream(
         env: Env,
         sender: Address,
@@ -21,6 +29,22 @@ pub trait FluxoraStreamInterface {
         sender: Address,
         streams: Vec<CreateStreamParams>,
     ) -> Result<Vec<u64>, fluxora_stream::ContractError>;
+    fn register_stream_template(
+        env: Env,
+        owner: Address,
+        start_delay: u64,
+        cliff_delay: u64,
+        duration: u64,
+    ) -> Result<u64, fluxora_stream::ContractError>;
+    fn delete_stream_template(
+        env: Env,
+        owner: Address,
+        template_id: u64,
+    ) -> Result<(), fluxora_stream::ContractError>;
+    fn get_stream_template(
+        env: Env,
+        template_id: u64,
+    ) -> Result<StreamScheduleTemplate, fluxora_stream::ContractError>;
 }
 
 /// Maximum number of stream IDs returned per page in `get_factory_streams_paginated`.
@@ -29,6 +53,19 @@ pub trait FluxoraStreamInterface {
 /// consistent across both contracts.
 pub const MAX_PAGE_SIZE: u32 = 100;
 
+/// Number of `(stream_contract, stream_id)` keys stored per page of a sender or
+/// recipient record index.
+///
+/// Appends only rewrite the last page, so the cost of recording a stream does
+/// not grow with the number of streams already indexed.
+pub const RECORD_PAGE_SIZE: u32 = 50;
+
+/// Number of instance addresses stored per page of the global or per-tenant
+/// instance index.
+///
+/// Deployment appends only rewrite the last page, like the record indexes.
+pub const INSTANCE_PAGE_SIZE: u32 = 50;
+
 /// Instance TTL threshold (ledgers). Below this value the entry will be extended.
 /// Mirrors governance contract to keep TTL semantics consistent across contracts.
 pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -50,6 +87,13 @@ pub const PERSISTENT_BUMP_AMOUNT: u32 = 120_960;
 /// cannot silently make factory-routed stream creation impractical forever.
 pub const MAX_MIN_DURATION_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
 
+/// Number of sub-windows a sender quota window is split into.
+///
+/// Usage is recorded per sub-window of `ceil(window_secs / SENDER_QUOTA_BUCKETS)`
+/// seconds, so each sender stores at most a dozen buckets however many streams
+/// it creates.
+pub const SENDER_QUOTA_BUCKETS: u64 = 10;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum FactoryError {
@@ -93,6 +137,35 @@ pub enum FactoryError {
     /// [`StreamContractError`] so callers can distinguish admin input
     /// validation failures from genuine downstream cross-contract errors.
     InvalidRateBounds = 18,
+    /// `deploy_stream_instance` was called before governance pinned a stream
+    /// WASM hash with `set_stream_wasm_hash`.
+    StreamWasmNotSet = 19,
+    /// The `(tenant_admin, salt)` pair already produced a registered instance.
+    InstanceAlreadyDeployed = 20,
+    /// The target of `create_stream_on_instance` was not deployed by this factory.
+    InstanceNotRegistered = 21,
+    /// The stream would push the sender's deposits in the rolling quota window
+    /// past its `max_deposit`.
+    SenderDepositQuotaExceeded = 22,
+    /// The stream would push the sender's stream count in the rolling quota
+    /// window past its `max_streams`.
+    SenderStreamQuotaExceeded = 23,
+    /// A sender quota with a non-zero window must have a positive
+    /// `max_deposit` and `max_streams`.
+    InvalidSenderQuota = 24,
+    /// The caller does not hold the [`CreatorRole`] the operation requires.
+    CreatorRoleRequired = 25,
+    /// The operation exceeds the cap attached to the caller's role grant.
+    RoleCapExceeded = 26,
+    /// Role caps must be strictly positive.
+    InvalidRoleCap = 27,
+    /// The template was not registered through this factory on the current
+    /// stream contract.
+    TemplateNotRegistered = 28,
+    /// An allowlist expiry must be later than the current ledger timestamp.
+    InvalidAllowlistExpiry = 29,
+    /// `create_streams_with_proofs` needs exactly one proof per stream.
+    ProofCountMismatch = 30,
 }
 
 #[contracttype]
@@ -111,6 +184,95 @@ pub enum DataKey {
     MinRatePerSecond,
     /// Optional upper bound on rate_per_second (inclusive). When absent, no upper bound.
     MaxRatePerSecond,
+    /// Governance-pinned WASM hash used by `deploy_stream_instance`.
+    StreamWasmHash,
+    /// Number of stream instances deployed by this factory (`u32`).
+    StreamInstanceCount,
+    /// Number of instances deployed for one tenant admin (`u32`).
+    TenantInstanceCount(Address),
+    /// Persistent reverse lookup from a deployed instance to its tenant admin.
+    InstanceTenant(Address),
+    /// Default [`SenderQuota`] applied to every sender without an override.
+    /// When absent, senders are unlimited.
+    SenderQuota,
+    /// Per-sender [`SenderQuota`] that replaces the default for that sender.
+    SenderQuotaOverride(Address),
+    /// Per-sender [`SenderUsage`] over the rolling quota window.
+    SenderUsage(Address),
+    /// Boolean flag: when `true`, creation paths require the sender to hold
+    /// the matching [`CreatorRole`]. Missing means `false`.
+    SenderRolesEnforced,
+    /// Cap attached to `account`'s grant of a [`CreatorRole`]; presence means
+    /// the role is granted.
+    RoleGrant(Address, CreatorRole),
+    /// Template manager that registered a factory-owned template.
+    FactoryTemplate(u64),
+    /// Number of live factory templates registered by a template manager.
+    ManagerTemplateCount(Address),
+    /// Ledger timestamp at which an `Allowlist(Address)` entry stops counting.
+    /// Missing means the entry does not expire.
+    AllowlistExpiry(Address),
+    /// Merkle root of bulk-allowlisted recipients (see [`allowlist_leaf`]).
+    AllowlistRoot,
+    /// [`FactoryStreamRecord`] for a stream, keyed by `(stream_contract, stream_id)`
+    /// because stream IDs restart on a new stream contract.
+    StreamRecord(Address, u64),
+    /// Number of streams a sender created through the factory (`u32`).
+    SenderRecordCount(Address),
+    /// Number of factory streams paying a recipient (`u32`).
+    RecipientRecordCount(Address),
+    /// Page of up to [`RECORD_PAGE_SIZE`] ordered `(stream_contract, stream_id)`
+    /// keys of the streams a sender created through the factory.
+    SenderRecordPage(Address, u32),
+    /// Page of up to [`RECORD_PAGE_SIZE`] ordered `(stream_contract, stream_id)`
+    /// keys of the factory streams paying a recipient.
+    RecipientRecordPage(Address, u32),
+    /// Page of up to [`INSTANCE_PAGE_SIZE`] stream instances deployed by this
+    /// factory, in deployment order.
+    StreamInstancePage(u32),
+    /// Page of up to [`INSTANCE_PAGE_SIZE`] instances deployed for one tenant
+    /// admin, in deployment order.
+    TenantInstancePage(Address, u32),
+}
+
+/// Origin of a stream created through `create_stream` or `create_streams`.
+///
+/// Written once after the stream is created and never updated, so it keeps
+/// describing the creation even after the stream's sender or recipient change
+/// on the stream contract, or the factory migrates to a new stream contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactoryStreamRecord {
+    pub stream_id: u64,
+    /// Stream contract that holds the stream.
+    pub stream_contract: Address,
+    pub sender: Address,
+    pub recipient: Address,
+    /// Ledger timestamp of creation.
+    pub created_at: u64,
+    /// `sha256` of the [`FactoryPolicy`] XDR in force at creation; compare
+    /// with [`FluxoraFactory::get_policy_hash`].
+    pub policy_hash: BytesN<32>,
+}
+
+/// Permissions the admin can grant so operators can use the factory without
+/// holding the admin key. Each grant carries its own cap.
+///
+/// | Role | Unlocks | Cap |
+/// |------|---------|-----|
+/// | `Creator` | `create_stream`, `create_stream_on_instance`, `create_stream_from_template` | Max deposit per stream |
+/// | `BatchCreator` | `create_streams` | Max aggregate deposit per batch |
+/// | `TemplateManager` | `register_factory_template`, `delete_factory_template` (own templates only) | Max live templates registered |
+///
+/// The two creator roles are only checked while sender roles are enforced
+/// (see [`FluxoraFactory::set_sender_roles_enforced`]); template management
+/// always requires `TemplateManager`.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CreatorRole {
+    Creator = 0,
+    BatchCreator = 1,
+    TemplateManager = 2,
 }
 
 /// Load and authorize the current factory admin.
@@ -234,6 +396,166 @@ fn append_stream_ids_batch(env: &Env, stream_ids: &Vec<u64>) {
     );
 }
 
+/// `sha256` of `policy` encoded as XDR.
+fn policy_hash(env: &Env, policy: &FactoryPolicy) -> BytesN<32> {
+    env.crypto().sha256(&policy.clone().to_xdr(env)).into()
+}
+
+/// Write the [`FactoryStreamRecord`] for each created stream and index it
+/// under its sender and recipient.
+///
+/// Called after the registry append on both creation paths. The sender index
+/// is written once for the whole batch.
+fn record_factory_streams(
+    env: &Env,
+    policy: &FactoryPolicy,
+    sender: &Address,
+    streams: &Vec<CreateStreamParams>,
+    stream_ids: &Vec<u64>,
+) {
+    let policy_hash = policy_hash(env, policy);
+    let created_at = env.ledger().timestamp();
+    let mut sender_refs = Vec::new(env);
+
+    for (params, stream_id) in streams.iter().zip(stream_ids.iter()) {
+        let record = FactoryStreamRecord {
+            stream_id,
+            stream_contract: policy.stream_contract.clone(),
+            sender: sender.clone(),
+            recipient: params.recipient.clone(),
+            created_at,
+            policy_hash: policy_hash.clone(),
+        };
+        let record_key = DataKey::StreamRecord(policy.stream_contract.clone(), stream_id);
+        env.storage().persistent().set(&record_key, &record);
+        extend_persistent(env, &record_key);
+
+        let stream_ref = (policy.stream_contract.clone(), stream_id);
+        sender_refs.push_back(stream_ref.clone());
+        append_record_refs(
+            env,
+            RecordIndex::Recipient,
+            &params.recipient,
+            &vec![env, stream_ref],
+        );
+    }
+
+    append_record_refs(env, RecordIndex::Sender, sender, &sender_refs);
+}
+
+/// Which paged record index a lookup or append targets.
+#[derive(Clone, Copy)]
+enum RecordIndex {
+    Sender,
+    Recipient,
+}
+
+impl RecordIndex {
+    fn count_key(self, owner: &Address) -> DataKey {
+        match self {
+            Self::Sender => DataKey::SenderRecordCount(owner.clone()),
+            Self::Recipient => DataKey::RecipientRecordCount(owner.clone()),
+        }
+    }
+
+    fn page_key(self, owner: &Address, page: u32) -> DataKey {
+        match self {
+            Self::Sender => DataKey::SenderRecordPage(owner.clone(), page),
+            Self::Recipient => DataKey::RecipientRecordPage(owner.clone(), page),
+        }
+    }
+}
+
+/// Number of entries in `owner`'s record index.
+fn record_count(env: &Env, index: RecordIndex, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&index.count_key(owner))
+        .unwrap_or(0)
+}
+
+/// Append `refs` to `owner`'s record index.
+///
+/// Entry `i` lives on page `i / RECORD_PAGE_SIZE`, so only the last page and
+/// any pages the append opens are read or written.
+fn append_record_refs(env: &Env, index: RecordIndex, owner: &Address, refs: &Vec<(Address, u64)>) {
+    let mut count = record_count(env, index, owner);
+    let mut page_no = count / RECORD_PAGE_SIZE;
+    let mut page: Vec<(Address, u64)> = env
+        .storage()
+        .persistent()
+        .get(&index.page_key(owner, page_no))
+        .unwrap_or_else(|| vec![env]);
+
+    for stream_ref in refs.iter() {
+        if page.len() == RECORD_PAGE_SIZE {
+            let page_key = index.page_key(owner, page_no);
+            env.storage().persistent().set(&page_key, &page);
+            extend_persistent(env, &page_key);
+            page_no += 1;
+            page = vec![env];
+        }
+        page.push_back(stream_ref);
+        count += 1;
+    }
+
+    let page_key = index.page_key(owner, page_no);
+    env.storage().persistent().set(&page_key, &page);
+    extend_persistent(env, &page_key);
+    let count_key = index.count_key(owner);
+    env.storage().persistent().set(&count_key, &count);
+    extend_persistent(env, &count_key);
+}
+
+/// Load records `[start_index, start_index + limit)` of `owner`'s record index,
+/// with `limit` capped at [`MAX_PAGE_SIZE`]. Only the pages spanning that range
+/// are read.
+fn load_record_page(
+    env: &Env,
+    index: RecordIndex,
+    owner: &Address,
+    start_index: u32,
+    limit: u32,
+) -> Vec<FactoryStreamRecord> {
+    let mut records = Vec::new(env);
+    let end = start_index
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(record_count(env, index, owner));
+
+    let mut next = start_index;
+    while next < end {
+        let page: Vec<(Address, u64)> = env
+            .storage()
+            .persistent()
+            .get(&index.page_key(owner, next / RECORD_PAGE_SIZE))
+            .unwrap_or_else(|| vec![env]);
+        let from = next % RECORD_PAGE_SIZE;
+        let to = (from + (end - next)).min(page.len());
+        if to <= from {
+            break;
+        }
+        for (stream_contract, stream_id) in page.slice(from..to).iter() {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StreamRecord(stream_contract, stream_id))
+            {
+                records.push_back(record);
+            }
+        }
+        next += to - from;
+    }
+    records
+}
+
+fn extend_persistent(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
 /// Bump the persistent TTL on the factory stream ID registry if it exists.
 ///
 /// Called during [`set_stream_contract`] migration to ensure the existing
@@ -256,6 +578,410 @@ fn bump_registry_ttl(env: &Env) {
     }
 }
 
+/// Which paged instance index a lookup or append targets.
+#[derive(Clone)]
+enum InstanceIndex {
+    All,
+    Tenant(Address),
+}
+
+impl InstanceIndex {
+    fn count_key(&self) -> DataKey {
+        match self {
+            Self::All => DataKey::StreamInstanceCount,
+            Self::Tenant(tenant_admin) => DataKey::TenantInstanceCount(tenant_admin.clone()),
+        }
+    }
+
+    fn page_key(&self, page: u32) -> DataKey {
+        match self {
+            Self::All => DataKey::StreamInstancePage(page),
+            Self::Tenant(tenant_admin) => DataKey::TenantInstancePage(tenant_admin.clone(), page),
+        }
+    }
+}
+
+/// Number of instances in an instance index.
+fn instance_count(env: &Env, index: &InstanceIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&index.count_key())
+        .unwrap_or(0)
+}
+
+/// Append `instance` to an instance index.
+///
+/// Entry `i` lives on page `i / INSTANCE_PAGE_SIZE`, so only the last page is
+/// read and written.
+fn append_instance(env: &Env, index: &InstanceIndex, instance: &Address) {
+    let count = instance_count(env, index);
+    let page_key = index.page_key(count / INSTANCE_PAGE_SIZE);
+    let mut page: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&page_key)
+        .unwrap_or_else(|| vec![env]);
+    page.push_back(instance.clone());
+    env.storage().persistent().set(&page_key, &page);
+    extend_persistent(env, &page_key);
+    let count_key = index.count_key();
+    env.storage().persistent().set(&count_key, &(count + 1));
+    extend_persistent(env, &count_key);
+}
+
+/// Load instances `[start_index, start_index + limit)` of an instance index,
+/// with `limit` capped at [`MAX_PAGE_SIZE`]. Only the pages spanning that range
+/// are read.
+fn load_instance_page(
+    env: &Env,
+    index: &InstanceIndex,
+    start_index: u32,
+    limit: u32,
+) -> Vec<Address> {
+    let mut instances = Vec::new(env);
+    let end = start_index
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(instance_count(env, index));
+
+    let mut next = start_index;
+    while next < end {
+        let page: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&index.page_key(next / INSTANCE_PAGE_SIZE))
+            .unwrap_or_else(|| vec![env]);
+        let from = next % INSTANCE_PAGE_SIZE;
+        let to = (from + (end - next)).min(page.len());
+        if to <= from {
+            break;
+        }
+        instances.append(&page.slice(from..to));
+        next += to - from;
+    }
+    instances
+}
+
+/// Return `items[start_index..start_index + limit]`, with `limit` capped at
+/// [`MAX_PAGE_SIZE`]. Empty when `start_index` is past the end.
+fn paginate<T>(env: &Env, items: &Vec<T>, start_index: u32, limit: u32) -> Vec<T>
+where
+    T: soroban_sdk::IntoVal<Env, soroban_sdk::Val> + soroban_sdk::TryFromVal<Env, soroban_sdk::Val>,
+{
+    let total = items.len();
+    if start_index >= total {
+        return vec![env];
+    }
+
+    let end = start_index
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(total);
+    items.slice(start_index..end)
+}
+
+/// Deployer salt for a tenant instance: `sha256(tenant_admin XDR || salt)`.
+///
+/// Binding the tenant into the salt means nobody can squat another tenant's
+/// instance address by deploying first with the same caller-chosen `salt`.
+fn instance_salt(env: &Env, tenant_admin: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = tenant_admin.clone().to_xdr(env);
+    preimage.append(&salt.clone().into());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Whether `recipient` has an allowlist entry that has not expired.
+fn has_allowlist_entry(env: &Env, recipient: &Address) -> bool {
+    let allowed: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Allowlist(recipient.clone()))
+        .unwrap_or(false);
+    if !allowed {
+        return false;
+    }
+    match env
+        .storage()
+        .persistent()
+        .get::<_, u64>(&DataKey::AllowlistExpiry(recipient.clone()))
+    {
+        Some(expires_at) => env.ledger().timestamp() < expires_at,
+        None => true,
+    }
+}
+
+/// Merkle leaf for `recipient`: `sha256(recipient XDR)`.
+///
+/// The XDR of an address is never 64 bytes, so a leaf preimage cannot be
+/// confused with an inner node preimage.
+pub fn allowlist_leaf(env: &Env, recipient: &Address) -> BytesN<32> {
+    env.crypto().sha256(&recipient.clone().to_xdr(env)).into()
+}
+
+/// Verify `proof` for `recipient` against the stored allowlist root.
+///
+/// Inner nodes hash the sorted pair, `sha256(min(a, b) || max(a, b))`, so the
+/// proof carries no left/right flags. Returns `false` when no root is set.
+fn verify_allowlist_proof(env: &Env, recipient: &Address, proof: &Vec<BytesN<32>>) -> bool {
+    let root: Option<BytesN<32>> = env.storage().instance().get(&DataKey::AllowlistRoot);
+    let Some(root) = root else {
+        return false;
+    };
+    let mut node = allowlist_leaf(env, recipient);
+    for sibling in proof.iter() {
+        let (first, second) = if node <= sibling {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        let mut preimage = Bytes::from(first);
+        preimage.append(&Bytes::from(second));
+        node = env.crypto().sha256(&preimage).into();
+    }
+    node == root
+}
+
+/// Apply the per-stream factory policy to `params`.
+///
+/// Shared by `create_stream`, `create_streams` and `create_stream_on_instance`
+/// so every creation path enforces the same checks in the same order:
+///
+/// 1. Allowlist check: an unexpired entry, or a valid `proof` against the
+///    allowlist root
+/// 2. Positive deposit and deposit cap
+/// 3. Time-range invariants
+/// 4. Minimum-duration check
+/// 5. Rate-per-second bounds check
+/// 6. Memo length check (`fluxora_stream::MAX_MEMO_BYTES`)
+///
+/// The creation pause is checked by the callers before this helper runs.
+fn enforce_stream_policy(
+    env: &Env,
+    policy: &FactoryPolicy,
+    params: &CreateStreamParams,
+    proof: Option<&Vec<BytesN<32>>>,
+) -> Result<(), FactoryError> {
+    let is_allowed = has_allowlist_entry(env, &params.recipient)
+        || proof.is_some_and(|proof| verify_allowlist_proof(env, &params.recipient, proof));
+    if !is_allowed {
+        return Err(FactoryError::RecipientNotAllowlisted);
+    }
+
+    if params.deposit_amount <= 0 {
+        return Err(FactoryError::InvalidCap);
+    }
+    if params.deposit_amount > policy.max_deposit {
+        return Err(FactoryError::DepositExceedsCap);
+    }
+
+    // Mirror FluxoraStream time invariants before the cross-contract call so
+    // invalid schedules return typed factory errors instead of downstream panics.
+    if params.start_time >= params.end_time {
+        return Err(FactoryError::InvalidTimeRange);
+    }
+    if params.cliff_time < params.start_time || params.cliff_time > params.end_time {
+        return Err(FactoryError::InvalidCliff);
+    }
+
+    let duration = params.end_time - params.start_time;
+    if duration < policy.min_duration {
+        return Err(FactoryError::DurationTooShort);
+    }
+
+    // Unset bounds are permissive. Bounds are inclusive.
+    validate_rate_bounds(
+        params.rate_per_second,
+        &policy.min_rate_per_second,
+        &policy.max_rate_per_second,
+    )?;
+
+    if let Some(ref m) = params.memo {
+        if m.len() as usize > fluxora_stream::MAX_MEMO_BYTES {
+            return Err(FactoryError::InvalidMemo);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a sender quota and return it in stored form.
+///
+/// `window_secs == 0` disables the quota; the limits are then stored as zero
+/// so the stored value has one canonical "unlimited" form.
+fn validate_sender_quota(
+    window_secs: u64,
+    max_deposit: i128,
+    max_streams: u32,
+) -> Result<SenderQuota, FactoryError> {
+    if window_secs == 0 {
+        return Ok(SenderQuota::unlimited());
+    }
+    if max_deposit <= 0 || max_streams == 0 {
+        return Err(FactoryError::InvalidSenderQuota);
+    }
+    Ok(SenderQuota {
+        window_secs,
+        max_deposit,
+        max_streams,
+    })
+}
+
+/// Quota that applies to `sender`: its override if one is set, otherwise the
+/// policy default.
+fn effective_sender_quota(env: &Env, policy: &FactoryPolicy, sender: &Address) -> SenderQuota {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderQuotaOverride(sender.clone()))
+        .unwrap_or_else(|| policy.sender_quota.clone())
+}
+
+/// Width in seconds of one usage bucket under `quota`.
+fn sender_quota_bucket_secs(quota: &SenderQuota) -> u64 {
+    quota.window_secs.div_ceil(SENDER_QUOTA_BUCKETS)
+}
+
+/// Timestamp at which `bucket` stops counting against `quota`.
+///
+/// A bucket is dropped only once its whole sub-window is more than
+/// `window_secs` in the past, so every creation counts for at least
+/// `window_secs` and no `window_secs`-long span can exceed the limits.
+fn sender_bucket_expiry(quota: &SenderQuota, bucket: &SenderUsageBucket) -> u64 {
+    bucket
+        .start
+        .saturating_add(sender_quota_bucket_secs(quota))
+        .saturating_add(quota.window_secs)
+}
+
+/// Load `sender`'s usage, dropping the buckets that have left the rolling
+/// window under `quota`.
+fn current_sender_usage(env: &Env, quota: &SenderQuota, sender: &Address) -> SenderUsage {
+    let now = env.ledger().timestamp();
+    let mut buckets = Vec::new(env);
+    if let Some(usage) = env
+        .storage()
+        .persistent()
+        .get::<_, SenderUsage>(&DataKey::SenderUsage(sender.clone()))
+    {
+        for bucket in usage.buckets.iter() {
+            if sender_bucket_expiry(quota, &bucket) > now {
+                buckets.push_back(bucket);
+            }
+        }
+    }
+    SenderUsage { buckets }
+}
+
+/// Charge `deposit` and `streams` against `sender`'s quota.
+///
+/// Shared by every creation path after the per-stream policy has passed. A
+/// sender with an unlimited quota is not tracked at all. The usage write is
+/// reverted with the rest of the transaction if the downstream call fails.
+fn consume_sender_quota(
+    env: &Env,
+    policy: &FactoryPolicy,
+    sender: &Address,
+    deposit: i128,
+    streams: u32,
+) -> Result<(), FactoryError> {
+    let quota = effective_sender_quota(env, policy, sender);
+    if quota.is_unlimited() {
+        return Ok(());
+    }
+
+    let mut usage = current_sender_usage(env, &quota, sender);
+    usage
+        .deposited()
+        .checked_add(deposit)
+        .filter(|total| *total <= quota.max_deposit)
+        .ok_or(FactoryError::SenderDepositQuotaExceeded)?;
+    usage
+        .streams()
+        .checked_add(streams)
+        .filter(|total| *total <= quota.max_streams)
+        .ok_or(FactoryError::SenderStreamQuotaExceeded)?;
+
+    // Both totals fit the quota, so neither bucket field can overflow.
+    let now = env.ledger().timestamp();
+    let start = now - now % sender_quota_bucket_secs(&quota);
+    let last = usage.buckets.len().checked_sub(1);
+    match last.and_then(|index| usage.buckets.get(index).map(|bucket| (index, bucket))) {
+        Some((index, mut bucket)) if bucket.start == start => {
+            bucket.deposited += deposit;
+            bucket.streams += streams;
+            usage.buckets.set(index, bucket);
+        }
+        _ => usage.buckets.push_back(SenderUsageBucket {
+            start,
+            deposited: deposit,
+            streams,
+        }),
+    }
+
+    let key = DataKey::SenderUsage(sender.clone());
+    env.storage().persistent().set(&key, &usage);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    Ok(())
+}
+
+/// Cap of `account`'s `role` grant, or `None` when the role is not granted.
+fn role_cap(env: &Env, account: &Address, role: CreatorRole) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleGrant(account.clone(), role))
+}
+
+/// Require `account` to hold `role` with a cap of at least `amount`.
+fn require_role(
+    env: &Env,
+    account: &Address,
+    role: CreatorRole,
+    amount: i128,
+) -> Result<(), FactoryError> {
+    let cap = role_cap(env, account, role).ok_or(FactoryError::CreatorRoleRequired)?;
+    if amount > cap {
+        return Err(FactoryError::RoleCapExceeded);
+    }
+    Ok(())
+}
+
+/// [`require_role`] for the creation paths, which only check roles while
+/// sender roles are enforced.
+///
+/// The role belongs to the `operator` that authorizes the creation, which may
+/// differ from the `sender` whose funds are streamed.
+fn require_operator_role(
+    env: &Env,
+    policy: &FactoryPolicy,
+    operator: &Address,
+    role: CreatorRole,
+    deposit: i128,
+) -> Result<(), FactoryError> {
+    if !policy.sender_roles_enforced {
+        return Ok(());
+    }
+    require_role(env, operator, role, deposit)
+}
+
+/// Number of live factory templates registered by `manager`.
+fn manager_template_count(env: &Env, manager: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ManagerTemplateCount(manager.clone()))
+        .unwrap_or(0)
+}
+
+fn set_manager_template_count(env: &Env, manager: &Address, count: u32) {
+    let key = DataKey::ManagerTemplateCount(manager.clone());
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+        extend_persistent(env, &key);
+    }
+}
+
 /// Validate a factory deposit cap before storing it.
 ///
 /// The cap must be strictly positive. A non-positive cap would make every
@@ -301,6 +1027,93 @@ pub struct FactoryConfig {
     pub min_rate_per_second: Option<i128>,
     /// Optional inclusive upper bound on `rate_per_second`. `None` is permissive.
     pub max_rate_per_second: Option<i128>,
+    /// Default per-sender quota. A zero `window_secs` means unlimited.
+    pub sender_quota: SenderQuota,
+    /// Whether creation paths require the sender to hold a [`CreatorRole`].
+    pub sender_roles_enforced: bool,
+}
+
+/// Per-sender creation limits over a rolling time window.
+///
+/// Over any `window_secs`-long span the sender may create at most
+/// `max_streams` streams totalling at most `max_deposit`. Usage is tracked in
+/// [`SENDER_QUOTA_BUCKETS`] sub-windows, so a creation frees its capacity
+/// between `window_secs` and `window_secs` plus one sub-window after it was
+/// made. `window_secs == 0` means the sender is unlimited, which lets an
+/// override exempt a single sender from the default quota.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderQuota {
+    pub window_secs: u64,
+    pub max_deposit: i128,
+    pub max_streams: u32,
+}
+
+impl SenderQuota {
+    /// The canonical "no quota" value.
+    pub fn unlimited() -> Self {
+        SenderQuota {
+            window_secs: 0,
+            max_deposit: 0,
+            max_streams: 0,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.window_secs == 0
+    }
+}
+
+/// A sender's consumption in one quota sub-window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderUsageBucket {
+    /// Ledger timestamp at which the sub-window opened, a multiple of the
+    /// sub-window width.
+    pub start: u64,
+    /// Sum of deposits created in the sub-window.
+    pub deposited: i128,
+    /// Number of streams created in the sub-window.
+    pub streams: u32,
+}
+
+/// A sender's consumption over its rolling quota window, oldest bucket first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderUsage {
+    pub buckets: Vec<SenderUsageBucket>,
+}
+
+impl SenderUsage {
+    /// Sum of deposits across the live buckets.
+    pub fn deposited(&self) -> i128 {
+        self.buckets.iter().fold(0i128, |total, bucket| {
+            total.saturating_add(bucket.deposited)
+        })
+    }
+
+    /// Number of streams across the live buckets.
+    pub fn streams(&self) -> u32 {
+        self.buckets
+            .iter()
+            .fold(0u32, |total, bucket| total.saturating_add(bucket.streams))
+    }
+}
+
+/// Result of [`FluxoraFactory::get_sender_quota_remaining`].
+///
+/// For an unlimited sender `quota` is [`SenderQuota::unlimited`],
+/// `next_release_at` is `0` and both remaining fields are at their type maximum.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SenderQuotaStatus {
+    /// The quota that applies to the sender (override or default).
+    pub quota: SenderQuota,
+    /// Timestamp at which the oldest recorded usage leaves the rolling window
+    /// and frees capacity, or `0` when nothing is recorded.
+    pub next_release_at: u64,
+    pub remaining_deposit: i128,
+    pub remaining_streams: u32,
 }
 
 /// Full snapshot of the factory policy required by both creation paths.
@@ -335,6 +1148,13 @@ pub struct FactoryPolicy {
     pub min_rate_per_second: Option<i128>,
     /// Optional inclusive upper bound on `rate_per_second`. `None` is permissive.
     pub max_rate_per_second: Option<i128>,
+    /// Default per-sender quota; individual senders may be overridden with
+    /// [`DataKey::SenderQuotaOverride`]. A zero `window_secs` means unlimited.
+    pub sender_quota: SenderQuota,
+    /// Whether creation paths require the sender to hold the matching
+    /// [`CreatorRole`] (`Creator` for single streams, `BatchCreator` for
+    /// batches).
+    pub sender_roles_enforced: bool,
 }
 
 /// Read the complete factory policy from instance storage in a single pass.
@@ -352,6 +1172,8 @@ pub struct FactoryPolicy {
 /// - `creation_paused`      → `false`
 /// - `min_rate_per_second`  → `None`
 /// - `max_rate_per_second`  → `None`
+/// - `sender_quota`         → [`SenderQuota::unlimited`]
+/// - `sender_roles_enforced` → `false`
 ///
 /// # Required fields
 /// - `stream_contract`
@@ -391,6 +1213,16 @@ pub fn load_policy(env: &Env) -> Result<FactoryPolicy, FactoryError> {
         env.storage().instance().get(&DataKey::MinRatePerSecond);
     let max_rate_per_second: Option<i128> =
         env.storage().instance().get(&DataKey::MaxRatePerSecond);
+    let sender_quota: SenderQuota = env
+        .storage()
+        .instance()
+        .get(&DataKey::SenderQuota)
+        .unwrap_or_else(SenderQuota::unlimited);
+    let sender_roles_enforced: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::SenderRolesEnforced)
+        .unwrap_or(false);
 
     Ok(FactoryPolicy {
         stream_contract,
@@ -400,6 +1232,8 @@ pub fn load_policy(env: &Env) -> Result<FactoryPolicy, FactoryError> {
         creation_paused,
         min_rate_per_second,
         max_rate_per_second,
+        sender_quota,
+        sender_roles_enforced,
     })
 }
 
@@ -444,6 +1278,22 @@ pub struct AllowlistUpdated {
     pub allowed: bool,
 }
 
+/// Emitted when a recipient is allowlisted with an expiry (`allow_exp`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AllowlistExpiryUpdated {
+    pub recipient: Address,
+    pub expires_at: u64,
+}
+
+/// Emitted when the allowlist Merkle root is set or cleared (`allow_rt`).
+/// `root` is all zeroes when the root was cleared.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AllowlistRootUpdated {
+    pub root: BytesN<32>,
+}
+
 /// Emitted when the factory deposit cap is changed (`cap_upd`).
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -487,11 +1337,89 @@ pub struct FactoryStreamCreated {
     pub rate_per_second: i128,
 }
 
-#[contract]
-pub struct FluxoraFactory;
+/// Emitted when the default sender quota is set or cleared (`snd_quota`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SenderQuotaUpdated {
+    pub window_secs: u64,
+    pub max_deposit: i128,
+    pub max_streams: u32,
+}
 
-#[contractimpl]
-#[allow(clippy::too_many_arguments)]
+/// Emitted when a per-sender quota override is set or removed (`quota_ovr`).
+/// `active` is `false` when the override was removed.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SenderQuotaOverrideUpdated {
+    pub sender: Address,
+    pub window_secs: u64,
+    pub max_deposit: i128,
+    pub max_streams: u32,
+    pub active: bool,
+}
+
+/// Emitted when a [`CreatorRole`] is granted or revoked (`role_upd`).
+/// `cap` is `0` when `granted` is `false`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CreatorRoleUpdated {
+    pub account: Address,
+    pub role: CreatorRole,
+    pub cap: i128,
+    pub granted: bool,
+}
+
+/// Emitted when sender role enforcement is toggled (`role_enf`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SenderRolesEnforcementUpdated {
+    pub enabled: bool,
+}
+
+/// Emitted when a factory template is registered or deleted (`fct_tmpl`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FactoryTemplateUpdated {
+    pub template_id: u64,
+    pub manager: Address,
+    pub registered: bool,
+}
+
+/// Emitted when governance pins the stream WASM hash (`wasm_pin`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamWasmHashUpdated {
+    pub wasm_hash: BytesN<32>,
+}
+
+/// Emitted when a tenant stream instance is deployed and initialised (`inst_dep`).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamInstanceDeployed {
+    pub tenant_admin: Address,
+    pub instance: Address,
+    pub token: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// Emitted when a stream is created on a tenant instance through the factory (`inst_strm`).
+/// Stream IDs are per-instance, so `instance` is part of the stream's identity.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InstanceStreamCreated {
+    pub instance: Address,
+    pub stream_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+}
+
+#[contract]
+pub struct FluxoraFactory;
+
+#[contractimpl]
+#[allow(clippy::too_many_arguments)]
 impl FluxoraFactory {
     /// Initialize the factory with admin, stream contract, and policies.
     ///
@@ -654,6 +1582,10 @@ impl FluxoraFactory {
         } else {
             env.storage().persistent().remove(&key);
         }
+        // A plain grant or revoke replaces any earlier expiry.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AllowlistExpiry(recipient.clone()));
 
         env.events().publish(
             (symbol_short!("allow_upd"),),
@@ -662,6 +1594,86 @@ impl FluxoraFactory {
         Ok(())
     }
 
+    /// Admin allowlists `recipient` until `expires_at` (exclusive).
+    ///
+    /// From `expires_at` on the entry no longer counts and `is_allowlisted`
+    /// returns `false`, without another admin transaction. A later
+    /// `set_allowlist` call replaces the expiry.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::InvalidAllowlistExpiry`] — `expires_at` is not in the future.
+    pub fn set_allowlist_until(
+        env: Env,
+        recipient: Address,
+        expires_at: u64,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+        if expires_at <= env.ledger().timestamp() {
+            return Err(FactoryError::InvalidAllowlistExpiry);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowlist(recipient.clone()), &true);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AllowlistExpiry(recipient.clone()), &expires_at);
+
+        env.events().publish(
+            (symbol_short!("allow_exp"),),
+            AllowlistExpiryUpdated {
+                recipient,
+                expires_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin sets the Merkle root of bulk-allowlisted recipients.
+    ///
+    /// Recipients under the root are accepted by `create_stream_with_proof`
+    /// and `create_streams_with_proofs`. Setting a new root replaces the old
+    /// one, which drops every recipient that is not in the new tree. Leaves
+    /// are [`allowlist_leaf`] hashes and inner nodes hash the sorted pair.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn set_allowlist_root(env: Env, root: BytesN<32>) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage().instance().set(&DataKey::AllowlistRoot, &root);
+
+        // Bump instance TTL after successful update.
+        bump_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("allow_rt"),), AllowlistRootUpdated { root });
+        Ok(())
+    }
+
+    /// Admin removes the allowlist Merkle root; only individual entries count
+    /// afterwards.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn clear_allowlist_root(env: Env) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage().instance().remove(&DataKey::AllowlistRoot);
+
+        // Bump instance TTL after successful update.
+        bump_instance(&env);
+
+        env.events().publish(
+            (symbol_short!("allow_rt"),),
+            AllowlistRootUpdated {
+                root: BytesN::from_array(&env, &[0; 32]),
+            },
+        );
+        Ok(())
+    }
+
     /// Admin updates the max deposit cap.
     ///
     /// The cap must be strictly positive; a non-positive value returns
@@ -833,6 +1845,239 @@ impl FluxoraFactory {
         Ok(())
     }
 
+    /// Admin sets the default per-sender quota.
+    ///
+    /// Within each window a sender may create at most `max_streams` streams
+    /// totalling at most `max_deposit` through any factory creation path.
+    /// `window_secs == 0` clears the default so senders without an override
+    /// are unlimited. Usage already recorded is kept and measured against the
+    /// new limits.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::InvalidSenderQuota`] — non-zero window with a
+    ///   non-positive `max_deposit` or zero `max_streams`.
+    pub fn set_sender_quota(
+        env: Env,
+        window_secs: u64,
+        max_deposit: i128,
+        max_streams: u32,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+        let quota = validate_sender_quota(window_secs, max_deposit, max_streams)?;
+
+        if quota.is_unlimited() {
+            env.storage().instance().remove(&DataKey::SenderQuota);
+        } else {
+            env.storage().instance().set(&DataKey::SenderQuota, &quota);
+        }
+
+        // Bump instance TTL after successful update.
+        bump_instance(&env);
+
+        env.events().publish(
+            (symbol_short!("snd_quota"),),
+            SenderQuotaUpdated {
+                window_secs: quota.window_secs,
+                max_deposit: quota.max_deposit,
+                max_streams: quota.max_streams,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin replaces the default quota for one `sender`.
+    ///
+    /// Takes the same arguments as [`set_sender_quota`](Self::set_sender_quota);
+    /// `window_secs == 0` exempts `sender` from any quota.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::InvalidSenderQuota`] — see `set_sender_quota`.
+    pub fn set_sender_quota_override(
+        env: Env,
+        sender: Address,
+        window_secs: u64,
+        max_deposit: i128,
+        max_streams: u32,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+        let quota = validate_sender_quota(window_secs, max_deposit, max_streams)?;
+
+        let key = DataKey::SenderQuotaOverride(sender.clone());
+        env.storage().persistent().set(&key, &quota);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("quota_ovr"),),
+            SenderQuotaOverrideUpdated {
+                sender,
+                window_secs: quota.window_secs,
+                max_deposit: quota.max_deposit,
+                max_streams: quota.max_streams,
+                active: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin removes `sender`'s quota override so the default applies again.
+    ///
+    /// Removing an override that does not exist is a no-op apart from the event.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn remove_sender_quota_override(env: Env, sender: Address) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::SenderQuotaOverride(sender.clone()));
+
+        env.events().publish(
+            (symbol_short!("quota_ovr"),),
+            SenderQuotaOverrideUpdated {
+                sender,
+                window_secs: 0,
+                max_deposit: 0,
+                max_streams: 0,
+                active: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin grants `role` to `account` with the given cap, replacing any
+    /// existing grant of that role.
+    ///
+    /// See [`CreatorRole`] for what each role unlocks and what its cap limits.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::InvalidRoleCap`] — `cap <= 0`.
+    pub fn grant_creator_role(
+        env: Env,
+        account: Address,
+        role: CreatorRole,
+        cap: i128,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+        if cap <= 0 {
+            return Err(FactoryError::InvalidRoleCap);
+        }
+
+        let key = DataKey::RoleGrant(account.clone(), role);
+        env.storage().persistent().set(&key, &cap);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("role_upd"),),
+            CreatorRoleUpdated {
+                account,
+                role,
+                cap,
+                granted: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin revokes `account`'s `role`. Revoking a role that is not granted
+    /// is a no-op apart from the event. Templates registered by a revoked
+    /// template manager stay in place.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn revoke_creator_role(
+        env: Env,
+        account: Address,
+        role: CreatorRole,
+    ) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RoleGrant(account.clone(), role));
+
+        env.events().publish(
+            (symbol_short!("role_upd"),),
+            CreatorRoleUpdated {
+                account,
+                role,
+                cap: 0,
+                granted: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin turns sender role checks on the creation paths on or off.
+    ///
+    /// While enabled, `create_stream`, `create_stream_on_instance` and
+    /// `create_stream_from_template` require the sender to hold
+    /// [`CreatorRole::Creator`], and `create_streams` requires
+    /// [`CreatorRole::BatchCreator`]. Off by default, so any sender may create
+    /// streams to allowlisted recipients.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn set_sender_roles_enforced(env: Env, enabled: bool) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SenderRolesEnforced, &enabled);
+
+        // Bump instance TTL after successful update.
+        bump_instance(&env);
+
+        env.events().publish(
+            (symbol_short!("role_enf"),),
+            SenderRolesEnforcementUpdated { enabled },
+        );
+        Ok(())
+    }
+
+    /// Pin the `FluxoraStream` WASM hash used by `deploy_stream_instance`.
+    ///
+    /// Admin-only. In production the factory admin is the governance contract,
+    /// so the hash only changes through a quorum-approved, timelocked
+    /// `CallData::FactorySetStreamWasmHash` proposal. The WASM must already be
+    /// uploaded to the network; the hash is not checked until the next deploy.
+    /// Instances that are already deployed keep their code.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn set_stream_wasm_hash(env: Env, wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        require_admin(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamWasmHash, &wasm_hash);
+
+        // Bump instance TTL after successful update.
+        bump_instance(&env);
+
+        env.events().publish(
+            (symbol_short!("wasm_pin"),),
+            StreamWasmHashUpdated { wasm_hash },
+        );
+        Ok(())
+    }
+
+    /// Return the pinned stream WASM hash, or `None` before governance sets it.
+    pub fn get_stream_wasm_hash(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::StreamWasmHash)
+    }
+
     /// Return whether factory stream creation is currently paused.
     ///
     /// This is a permissionless view — anyone may call it to check the current
@@ -865,15 +2110,78 @@ impl FluxoraFactory {
             creation_paused: policy.creation_paused,
             min_rate_per_second: policy.min_rate_per_second,
             max_rate_per_second: policy.max_rate_per_second,
+            sender_quota: policy.sender_quota,
+            sender_roles_enforced: policy.sender_roles_enforced,
+        })
+    }
+
+    /// Return the quota that applies to `sender` and what is left of it in the
+    /// rolling window.
+    ///
+    /// Usage that has left the window is not counted, matching what the next
+    /// creation would see.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn get_sender_quota_remaining(
+        env: Env,
+        sender: Address,
+    ) -> Result<SenderQuotaStatus, FactoryError> {
+        let policy = load_policy(&env)?;
+        let quota = effective_sender_quota(&env, &policy, &sender);
+        if quota.is_unlimited() {
+            return Ok(SenderQuotaStatus {
+                quota,
+                next_release_at: 0,
+                remaining_deposit: i128::MAX,
+                remaining_streams: u32::MAX,
+            });
+        }
+
+        let usage = current_sender_usage(&env, &quota, &sender);
+        Ok(SenderQuotaStatus {
+            next_release_at: usage
+                .buckets
+                .first()
+                .map_or(0, |bucket| sender_bucket_expiry(&quota, &bucket)),
+            remaining_deposit: quota.max_deposit.saturating_sub(usage.deposited()).max(0),
+            remaining_streams: quota.max_streams.saturating_sub(usage.streams()),
+            quota,
         })
     }
 
+    /// Return the cap of `account`'s `role` grant, or `None` when the role is
+    /// not granted.
+    pub fn get_creator_role_cap(env: Env, account: Address, role: CreatorRole) -> Option<i128> {
+        role_cap(&env, &account, role)
+    }
+
     /// Return whether `recipient` is currently allowlisted for factory-created streams.
+    ///
+    /// Only individual entries count; an expired entry returns `false`. Use
+    /// [`verify_allowlist_proof`](Self::verify_allowlist_proof) for recipients
+    /// under the Merkle root.
     pub fn is_allowlisted(env: Env, recipient: Address) -> bool {
+        has_allowlist_entry(&env, &recipient)
+    }
+
+    /// Return the expiry of `recipient`'s allowlist entry, or `None` when the
+    /// entry does not expire or does not exist.
+    pub fn get_allowlist_expiry(env: Env, recipient: Address) -> Option<u64> {
         env.storage()
             .persistent()
-            .get(&DataKey::Allowlist(recipient))
-            .unwrap_or(false)
+            .get(&DataKey::AllowlistExpiry(recipient))
+    }
+
+    /// Return the allowlist Merkle root, or `None` when none is set.
+    pub fn get_allowlist_root(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::AllowlistRoot)
+    }
+
+    /// Return whether `proof` places `recipient` under the current allowlist
+    /// root. Lets clients check a proof before submitting a creation.
+    pub fn verify_allowlist_proof(env: Env, recipient: Address, proof: Vec<BytesN<32>>) -> bool {
+        verify_allowlist_proof(&env, &recipient, &proof)
     }
 
     /// Return the total number of streams created through this factory.
@@ -888,20 +2196,102 @@ impl FluxoraFactory {
     ///
     /// Returns an empty list when `start_index` is beyond the end of the registry.
     pub fn get_factory_streams_paginated(env: Env, start_index: u32, limit: u32) -> Vec<u64> {
-        let ids = load_stream_ids(&env);
-        let total = ids.len();
+        paginate(&env, &load_stream_ids(&env), start_index, limit)
+    }
 
-        if start_index >= total {
-            return vec![&env];
-        }
+    /// Return the [`FactoryStreamRecord`] of a factory-created stream, or
+    /// `None` when `stream_id` on `stream_contract` was not created through
+    /// `create_stream` / `create_streams`.
+    pub fn get_stream_record(
+        env: Env,
+        stream_contract: Address,
+        stream_id: u64,
+    ) -> Option<FactoryStreamRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StreamRecord(stream_contract, stream_id))
+    }
 
-        let capped_limit = limit.min(MAX_PAGE_SIZE);
-        let end = (start_index + capped_limit).min(total);
-        let mut page = vec![&env];
-        for i in start_index..end {
-            page.push_back(ids.get(i).unwrap());
-        }
-        page
+    /// Return the number of factory streams created by `sender`.
+    pub fn get_sender_record_count(env: Env, sender: Address) -> u32 {
+        record_count(&env, RecordIndex::Sender, &sender)
+    }
+
+    /// Return a page of the records of streams created by `sender`, oldest
+    /// first. Pagination follows
+    /// [`get_factory_streams_paginated`](Self::get_factory_streams_paginated).
+    pub fn get_sender_records(
+        env: Env,
+        sender: Address,
+        start_index: u32,
+        limit: u32,
+    ) -> Vec<FactoryStreamRecord> {
+        load_record_page(&env, RecordIndex::Sender, &sender, start_index, limit)
+    }
+
+    /// Return the number of factory streams created for `recipient`.
+    pub fn get_recipient_record_count(env: Env, recipient: Address) -> u32 {
+        record_count(&env, RecordIndex::Recipient, &recipient)
+    }
+
+    /// Return a page of the records of factory streams paying `recipient`,
+    /// oldest first. Pagination follows
+    /// [`get_factory_streams_paginated`](Self::get_factory_streams_paginated).
+    pub fn get_recipient_records(
+        env: Env,
+        recipient: Address,
+        start_index: u32,
+        limit: u32,
+    ) -> Vec<FactoryStreamRecord> {
+        load_record_page(&env, RecordIndex::Recipient, &recipient, start_index, limit)
+    }
+
+    /// Return the hash that a stream created now would carry as
+    /// [`FactoryStreamRecord::policy_hash`].
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    pub fn get_policy_hash(env: Env) -> Result<BytesN<32>, FactoryError> {
+        Ok(policy_hash(&env, &load_policy(&env)?))
+    }
+
+    /// Return the total number of stream instances deployed by this factory.
+    pub fn get_stream_instance_count(env: Env) -> u32 {
+        instance_count(&env, &InstanceIndex::All)
+    }
+
+    /// Return a page of deployed stream instances in deployment order.
+    ///
+    /// Pagination follows [`get_factory_streams_paginated`](Self::get_factory_streams_paginated):
+    /// `limit` is capped at [`MAX_PAGE_SIZE`] and an out-of-range `start_index`
+    /// returns an empty list.
+    pub fn get_stream_instances_paginated(env: Env, start_index: u32, limit: u32) -> Vec<Address> {
+        load_instance_page(&env, &InstanceIndex::All, start_index, limit)
+    }
+
+    /// Return a page of the instances deployed for `tenant_admin`, in
+    /// deployment order. Same pagination rules as
+    /// [`get_stream_instances_paginated`](Self::get_stream_instances_paginated).
+    pub fn get_tenant_instances(
+        env: Env,
+        tenant_admin: Address,
+        start_index: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        load_instance_page(
+            &env,
+            &InstanceIndex::Tenant(tenant_admin),
+            start_index,
+            limit,
+        )
+    }
+
+    /// Return the tenant admin an instance was deployed for, or `None` when
+    /// `instance` was not deployed by this factory.
+    pub fn get_instance_tenant(env: Env, instance: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::InstanceTenant(instance))
     }
 
     /// Creates a new stream via the FluxoraStream contract after enforcing treasury policies.
@@ -924,7 +2314,10 @@ impl FluxoraFactory {
     /// 5. Minimum-duration check
     /// 6. Rate-per-second bounds check
     /// 7. Memo length check (`fluxora_stream::MAX_MEMO_BYTES`)
-    /// 8. Cross-contract stream creation
+    /// 8. Operator role check, when enforced ([`CreatorRole::Creator`]); the
+    ///    sender is its own operator here
+    /// 9. Per-sender quota check (see [`set_sender_quota`](Self::set_sender_quota))
+    /// 10. Cross-contract stream creation
     ///
     /// On success the returned stream ID is appended to the factory's [`DataKey::FactoryStreamIds`]
     /// registry. The registry is only written **after** the cross-contract call succeeds, so a
@@ -933,6 +2326,51 @@ impl FluxoraFactory {
         env: Env,
         sender: Address,
         params: fluxora_stream::CreateStreamParams,
+    ) -> Result<u64, FactoryError> {
+        Self::create_stream_checked(env, sender.clone(), sender, params, None)
+    }
+
+    /// [`create_stream`](Self::create_stream) submitted by an `operator` on
+    /// behalf of a funding `sender`.
+    ///
+    /// The operator authorizes this call and is the account whose
+    /// [`CreatorRole::Creator`] grant is checked while sender roles are
+    /// enforced. The sender does not sign the factory call: it pre-approves
+    /// the spend by authorizing the nested `FluxoraStream::create_stream`
+    /// invocation (and the token allowance it draws on). The sender quota is
+    /// charged to the sender. Every other guard of `create_stream` applies
+    /// unchanged.
+    pub fn create_stream_as_operator(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        params: fluxora_stream::CreateStreamParams,
+    ) -> Result<u64, FactoryError> {
+        Self::create_stream_checked(env, operator, sender, params, None)
+    }
+
+    /// [`create_stream`](Self::create_stream) for a recipient allowlisted
+    /// through the Merkle root set by [`set_allowlist_root`](Self::set_allowlist_root).
+    ///
+    /// `proof` is the list of sibling hashes from the recipient's leaf
+    /// ([`allowlist_leaf`]) up to the root. A recipient with an unexpired
+    /// individual entry passes without a valid proof. Every other guard of
+    /// `create_stream` applies unchanged.
+    pub fn create_stream_with_proof(
+        env: Env,
+        sender: Address,
+        params: fluxora_stream::CreateStreamParams,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<u64, FactoryError> {
+        Self::create_stream_checked(env, sender.clone(), sender, params, Some(proof))
+    }
+
+    fn create_stream_checked(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        params: CreateStreamParams,
+        proof: Option<Vec<BytesN<32>>>,
     ) -> Result<u64, FactoryError> {
         // ── Guard 1: load the full policy in one pass ────────────────────────
         // Single chokepoint guarantees the single-path policy set is identical
@@ -947,64 +2385,28 @@ impl FluxoraFactory {
             return Err(FactoryError::CreationPaused);
         }
 
-        // ── Guard 3: allowlist ───────────────────────────────────────────────
-        let is_allowed: bool = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Allowlist(params.recipient.clone()))
-            .unwrap_or(false);
-        if !is_allowed {
-            return Err(FactoryError::RecipientNotAllowlisted);
-        }
-
-        // ── Guard 4: deposit must be positive ───────────────────────────────
-        if params.deposit_amount <= 0 {
-            return Err(FactoryError::InvalidCap);
-        }
-
-        // ── Guard 5: deposit cap ─────────────────────────────────────────────
-        if params.deposit_amount > policy.max_deposit {
-            return Err(FactoryError::DepositExceedsCap);
-        }
-
-        // ── Guard 5: time invariants ─────────────────────────────────────────
-        // Mirror FluxoraStream time invariants before the cross-contract call so
-        // invalid schedules return typed factory errors instead of downstream panics.
-        if params.start_time >= params.end_time {
-            return Err(FactoryError::InvalidTimeRange);
-        }
-        if params.cliff_time < params.start_time || params.cliff_time > params.end_time {
-            return Err(FactoryError::InvalidCliff);
-        }
-
-        // ── Guard 6: minimum duration ────────────────────────────────────────
-        let duration = params.end_time - params.start_time;
-        if duration < policy.min_duration {
-            return Err(FactoryError::DurationTooShort);
-        }
+        // ── Guards 3-8: allowlist, deposit, times, duration, rate, memo ─────
+        enforce_stream_policy(&env, &policy, &params, proof.as_ref())?;
 
-        // ── Guard 7: rate bounds ─────────────────────────────────────────────
-        // Unset bounds are permissive. Bounds are inclusive.
-        validate_rate_bounds(
-            params.rate_per_second,
-            &policy.min_rate_per_second,
-            &policy.max_rate_per_second,
+        // ── Guard 9: operator role ───────────────────────────────────────────
+        require_operator_role(
+            &env,
+            &policy,
+            &operator,
+            CreatorRole::Creator,
+            params.deposit_amount,
         )?;
 
-        // ── Guard 8: memo length ─────────────────────────────────────────────
-        if let Some(ref m) = params.memo {
-            if m.len() as usize > fluxora_stream::MAX_MEMO_BYTES {
-                return Err(FactoryError::InvalidMemo);
-            }
-        }
+        // ── Guard 10: per-sender quota ───────────────────────────────────────
+        consume_sender_quota(&env, &policy, &sender, params.deposit_amount, 1)?;
 
-        // Must authenticate the sender because the factory calls FluxoraStream with this sender.
-        // The sender needs to authorize both this wrapper invocation and the cross-contract invocation.
-        sender.require_auth();
+        // The operator authorizes this wrapper invocation. FluxoraStream
+        // separately requires the sender's authorization for the nested
+        // creation; when the sender is its own operator one signature covers both.
+        operator.require_auth();
 
         // ── Interaction ──────────────────────────────────────────────────────
-        let stream_contract = policy.stream_contract;
-        let stream_client = FluxoraStreamClient::new(&env, &stream_contract);
+        let stream_client = FluxoraStreamClient::new(&env, &policy.stream_contract);
 
         match stream_client.try_create_stream(&sender, &params) {
             Ok(Ok(stream_id)) => {
@@ -1012,6 +2414,13 @@ impl FluxoraFactory {
                 // The registry is written only after the cross-contract call succeeds,
                 // so a downstream failure leaves no orphan index entry.
                 append_stream_id(&env, stream_id);
+                record_factory_streams(
+                    &env,
+                    &policy,
+                    &sender,
+                    &vec![&env, params.clone()],
+                    &vec![&env, stream_id],
+                );
                 env.events().publish(
                     (symbol_short!("fct_strm"),),
                     FactoryStreamCreated {
@@ -1038,13 +2447,17 @@ impl FluxoraFactory {
     /// 1. **Policy load** — every required config field is read in one pass via
     ///    [`load_policy`]. Returns [`FactoryError::NotInitialized`] if the
     ///    factory has not been initialized.
-    /// 2. **Sender authentication** (`sender.require_auth()`).
+    /// 2. **Operator authentication** (`sender.require_auth()`; the sender is
+    ///    its own operator here).
     /// 3. **CreationPaused** — checked immediately after the policy load,
     ///    before any loop work, so that no per-stream policy configuration is
     ///    observable when the factory is in emergency-pause mode.
     /// 4. Iterative validation of each stream: allowlist, cap, times, duration,
     ///    rate, memo, and (when enabled) the cumulative batch-cap.
-    /// 5. Cross-contract batch stream creation.
+    /// 5. Operator role check, when enforced ([`CreatorRole::BatchCreator`],
+    ///    capping the batch total).
+    /// 6. Per-sender quota check, charged once for the whole batch.
+    /// 7. Cross-contract batch stream creation.
     ///
     /// # Event Emission Ordering
     /// Appends all created stream IDs to the persistent registry first, then emits a
@@ -1055,13 +2468,59 @@ impl FluxoraFactory {
         env: Env,
         sender: Address,
         streams: Vec<fluxora_stream::CreateStreamParams>,
+    ) -> Result<Vec<u64>, FactoryError> {
+        Self::create_streams_checked(env, sender.clone(), sender, streams, None)
+    }
+
+    /// [`create_streams`](Self::create_streams) submitted by an `operator` on
+    /// behalf of a funding `sender`.
+    ///
+    /// As in [`create_stream_as_operator`](Self::create_stream_as_operator),
+    /// the operator authorizes the call and holds the
+    /// [`CreatorRole::BatchCreator`] grant, while the sender authorizes the
+    /// nested `FluxoraStream::create_streams` invocation and is charged the
+    /// quota.
+    pub fn create_streams_as_operator(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        streams: Vec<fluxora_stream::CreateStreamParams>,
+    ) -> Result<Vec<u64>, FactoryError> {
+        Self::create_streams_checked(env, operator, sender, streams, None)
+    }
+
+    /// [`create_streams`](Self::create_streams) with one Merkle allowlist
+    /// proof per stream, in the same order as `streams`.
+    ///
+    /// Each proof is checked as in
+    /// [`create_stream_with_proof`](Self::create_stream_with_proof); pass an
+    /// empty proof for recipients with an individual entry.
+    ///
+    /// # Errors
+    /// - [`FactoryError::ProofCountMismatch`] — `proofs.len() != streams.len()`.
+    /// - Every error of `create_streams`.
+    pub fn create_streams_with_proofs(
+        env: Env,
+        sender: Address,
+        streams: Vec<fluxora_stream::CreateStreamParams>,
+        proofs: Vec<Vec<BytesN<32>>>,
+    ) -> Result<Vec<u64>, FactoryError> {
+        Self::create_streams_checked(env, sender.clone(), sender, streams, Some(proofs))
+    }
+
+    fn create_streams_checked(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        streams: Vec<CreateStreamParams>,
+        proofs: Option<Vec<Vec<BytesN<32>>>>,
     ) -> Result<Vec<u64>, FactoryError> {
         // ── Guard 1: load the full policy in one pass ────────────────────────
         // Same chokepoint as `create_stream` — guarantees identical policy set.
         let policy = load_policy(&env)?;
 
-        // ── Guard 2: sender authentication (checked before expensive loop validation) ─
-        sender.require_auth();
+        // ── Guard 2: operator authentication (checked before expensive loop validation) ─
+        operator.require_auth();
 
         // ── Guard 3: pause check ─────────────────────────────────────────────
         if policy.creation_paused {
@@ -1073,50 +2532,15 @@ impl FluxoraFactory {
         bump_instance(&env);
 
         let max_deposit = policy.max_deposit;
-        let min_duration = policy.min_duration;
         let enforce_batch_cap = policy.batch_cap_enforced;
-        let min_rate = policy.min_rate_per_second;
-        let max_rate = policy.max_rate_per_second;
-        let stream_contract = policy.stream_contract;
 
         let mut total_deposit: i128 = 0;
-        for params in streams.iter() {
-            let is_allowed: bool = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Allowlist(params.recipient.clone()))
-                .unwrap_or(false);
-            if !is_allowed {
-                return Err(FactoryError::RecipientNotAllowlisted);
-            }
-
-            if params.deposit_amount <= 0 {
-                return Err(FactoryError::InvalidCap);
-            }
-
-            if params.deposit_amount > max_deposit {
-                return Err(FactoryError::DepositExceedsCap);
-            }
-
-            if params.start_time >= params.end_time {
-                return Err(FactoryError::InvalidTimeRange);
-            }
-            if params.cliff_time < params.start_time || params.cliff_time > params.end_time {
-                return Err(FactoryError::InvalidCliff);
-            }
-
-            let duration = params.end_time - params.start_time;
-            if duration < min_duration {
-                return Err(FactoryError::DurationTooShort);
-            }
-
-            validate_rate_bounds(params.rate_per_second, &min_rate, &max_rate)?;
-
-            if let Some(ref m) = params.memo {
-                if m.len() as usize > fluxora_stream::MAX_MEMO_BYTES {
-                    return Err(FactoryError::InvalidMemo);
-                }
-            }
+        if proofs.as_ref().is_some_and(|p| p.len() != streams.len()) {
+            return Err(FactoryError::ProofCountMismatch);
+        }
+        for (i, params) in streams.iter().enumerate() {
+            let proof = proofs.as_ref().and_then(|p| p.get(i as u32));
+            enforce_stream_policy(&env, &policy, &params, proof.as_ref())?;
 
             if enforce_batch_cap {
                 total_deposit = total_deposit
@@ -1132,7 +2556,22 @@ impl FluxoraFactory {
             return Ok(Vec::new(&env));
         }
 
-        let stream_client = FluxoraStreamClient::new(&env, &stream_contract);
+        // The whole batch counts against the quota, so a batch that does not
+        // fit is rejected as a unit rather than partially created.
+        let mut batch_deposit: i128 = 0;
+        for params in streams.iter() {
+            batch_deposit = batch_deposit.saturating_add(params.deposit_amount);
+        }
+        require_operator_role(
+            &env,
+            &policy,
+            &operator,
+            CreatorRole::BatchCreator,
+            batch_deposit,
+        )?;
+        consume_sender_quota(&env, &policy, &sender, batch_deposit, streams.len())?;
+
+        let stream_client = FluxoraStreamClient::new(&env, &policy.stream_contract);
         let mut wrapped_streams = Vec::new(&env);
         for params in streams.iter() {
             wrapped_streams.push_back(params.clone());
@@ -1153,9 +2592,384 @@ impl FluxoraFactory {
         // Written only after the cross-contract call succeeds; a downstream failure
         // leaves no orphan index entries. TTL is bumped once for the whole batch.
         append_stream_ids_batch(&env, &created_ids);
+        record_factory_streams(&env, &policy, &sender, &streams, &created_ids);
 
         Ok(created_ids)
     }
+
+    /// Deploy an isolated `FluxoraStream` instance for `tenant_admin`.
+    ///
+    /// The instance runs the governance-pinned WASM (see
+    /// [`set_stream_wasm_hash`](Self::set_stream_wasm_hash)), is initialised
+    /// with `FluxoraStream::init_with_creator(token, tenant_admin, factory)` in
+    /// the same transaction, and is recorded in the tenant registry. Each
+    /// tenant's deposits therefore sit in their own contract, with their own
+    /// liabilities and admin.
+    ///
+    /// Stream creation on the instance is locked to this factory, so every
+    /// new stream goes through
+    /// [`create_stream_on_instance`](Self::create_stream_on_instance) and the
+    /// factory policy. Direct creation calls to the instance are rejected.
+    ///
+    /// The deployer salt is `sha256(tenant_admin XDR || salt)`, so the instance
+    /// address is deterministic per tenant and cannot be squatted by another
+    /// caller reusing the same `salt`.
+    ///
+    /// # Authorization
+    /// `tenant_admin` must authorize this call, including the nested
+    /// `FluxoraStream::init_with_creator`, which requires the admin's
+    /// signature.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::CreationPaused`] — the factory creation pause is on.
+    /// - [`FactoryError::StreamWasmNotSet`] — no WASM hash has been pinned.
+    /// - [`FactoryError::InstanceAlreadyDeployed`] — `salt` was already used
+    ///   by this tenant.
+    /// - [`FactoryError::StreamContractError`] — the new instance rejected
+    ///   initialisation (for example an unsupported token); the deployment is
+    ///   reverted.
+    pub fn deploy_stream_instance(
+        env: Env,
+        tenant_admin: Address,
+        token: Address,
+        salt: BytesN<32>,
+    ) -> Result<Address, FactoryError> {
+        let policy = load_policy(&env)?;
+        if policy.creation_paused {
+            return Err(FactoryError::CreationPaused);
+        }
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::StreamWasmHash)
+            .ok_or(FactoryError::StreamWasmNotSet)?;
+
+        tenant_admin.require_auth();
+
+        let deployer =
+            env.deployer()
+                .with_current_contract(instance_salt(&env, &tenant_admin, &salt));
+        let predicted = deployer.deployed_address();
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::InstanceTenant(predicted))
+        {
+            return Err(FactoryError::InstanceAlreadyDeployed);
+        }
+
+        // ── Interaction ──────────────────────────────────────────────────────
+        let instance = deployer.deploy(wasm_hash.clone());
+        match FluxoraStreamClient::new(&env, &instance).try_init_with_creator(
+            &token,
+            &tenant_admin,
+            &env.current_contract_address(),
+        ) {
+            Ok(Ok(())) => {}
+            _ => return Err(FactoryError::StreamContractError),
+        }
+
+        // --- Effect: record only after the instance is initialised ---
+        env.storage()
+            .persistent()
+            .set(&DataKey::InstanceTenant(instance.clone()), &tenant_admin);
+        env.storage().persistent().extend_ttl(
+            &DataKey::InstanceTenant(instance.clone()),
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        append_instance(&env, &InstanceIndex::All, &instance);
+        append_instance(
+            &env,
+            &InstanceIndex::Tenant(tenant_admin.clone()),
+            &instance,
+        );
+        bump_instance(&env);
+
+        env.events().publish(
+            (symbol_short!("inst_dep"),),
+            StreamInstanceDeployed {
+                tenant_admin,
+                instance: instance.clone(),
+                token,
+                wasm_hash,
+            },
+        );
+        Ok(instance)
+    }
+
+    /// Create a stream on a tenant instance after enforcing the factory policy.
+    ///
+    /// Applies exactly the checks of [`create_stream`](Self::create_stream)
+    /// (pause, allowlist, deposit cap, times, minimum duration, rate bounds,
+    /// memo) and forwards to `instance` instead of the factory's
+    /// `stream_contract`. The sender approves the instance, not the factory,
+    /// for the deposit.
+    ///
+    /// Stream IDs are per-instance, so they are not added to the
+    /// [`DataKey::FactoryStreamIds`] registry; indexers pair the ID with the
+    /// instance from the `inst_strm` event.
+    ///
+    /// This is the only way to create streams on an instance (see
+    /// [`deploy_stream_instance`](Self::deploy_stream_instance)).
+    ///
+    /// # Errors
+    /// - [`FactoryError::InstanceNotRegistered`] — `instance` was not deployed
+    ///   by this factory.
+    /// - Every error of [`create_stream`](Self::create_stream).
+    pub fn create_stream_on_instance(
+        env: Env,
+        instance: Address,
+        sender: Address,
+        params: fluxora_stream::CreateStreamParams,
+    ) -> Result<u64, FactoryError> {
+        let policy = load_policy(&env)?;
+        if policy.creation_paused {
+            return Err(FactoryError::CreationPaused);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::InstanceTenant(instance.clone()))
+        {
+            return Err(FactoryError::InstanceNotRegistered);
+        }
+        enforce_stream_policy(&env, &policy, &params, None)?;
+        require_operator_role(
+            &env,
+            &policy,
+            &sender,
+            CreatorRole::Creator,
+            params.deposit_amount,
+        )?;
+        consume_sender_quota(&env, &policy, &sender, params.deposit_amount, 1)?;
+
+        sender.require_auth();
+
+        // ── Interaction ──────────────────────────────────────────────────────
+        let stream_client = FluxoraStreamClient::new(&env, &instance);
+        let stream_id = match stream_client.try_create_stream(&sender, &params) {
+            Ok(Ok(stream_id)) => stream_id,
+            Err(Ok(StreamContractErr::ContractPaused)) => {
+                return Err(FactoryError::StreamContractPaused)
+            }
+            _ => return Err(FactoryError::StreamContractError),
+        };
+
+        env.events().publish(
+            (symbol_short!("inst_strm"),),
+            InstanceStreamCreated {
+                instance,
+                stream_id,
+                sender,
+                recipient: params.recipient,
+                deposit_amount: params.deposit_amount,
+                rate_per_second: params.rate_per_second,
+            },
+        );
+        Ok(stream_id)
+    }
+
+    /// Register a schedule template owned by the factory on the current
+    /// stream contract.
+    ///
+    /// The template is a `FluxoraStream` schedule template whose owner is the
+    /// factory itself, so only the factory can delete it. Factory templates
+    /// are used by [`create_stream_from_template`](Self::create_stream_from_template).
+    /// The stream contract's per-owner template limit applies to the factory
+    /// as a whole.
+    ///
+    /// # Authorization
+    /// `manager` must authorize the call and hold
+    /// [`CreatorRole::TemplateManager`]; its cap limits how many live templates
+    /// it may have registered.
+    ///
+    /// # Errors
+    /// - [`FactoryError::NotInitialized`] — factory has not been initialized.
+    /// - [`FactoryError::CreatorRoleRequired`] — `manager` lacks the role.
+    /// - [`FactoryError::RoleCapExceeded`] — `manager` is at its template cap.
+    /// - [`FactoryError::StreamContractError`] — the stream contract rejected
+    ///   the template (invalid delays or template limit reached).
+    pub fn register_factory_template(
+        env: Env,
+        manager: Address,
+        start_delay: u64,
+        cliff_delay: u64,
+        duration: u64,
+    ) -> Result<u64, FactoryError> {
+        let policy = load_policy(&env)?;
+        manager.require_auth();
+        let count = manager_template_count(&env, &manager);
+        require_role(
+            &env,
+            &manager,
+            CreatorRole::TemplateManager,
+            i128::from(count) + 1,
+        )?;
+
+        let stream_client = FluxoraStreamClient::new(&env, &policy.stream_contract);
+        let template_id = match stream_client.try_register_stream_template(
+            &env.current_contract_address(),
+            &start_delay,
+            &cliff_delay,
+            &duration,
+        ) {
+            Ok(Ok(template_id)) => template_id,
+            _ => return Err(FactoryError::StreamContractError),
+        };
+
+        let key = DataKey::FactoryTemplate(template_id);
+        env.storage().persistent().set(&key, &manager);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        set_manager_template_count(&env, &manager, count + 1);
+
+        env.events().publish(
+            (symbol_short!("fct_tmpl"),),
+            FactoryTemplateUpdated {
+                template_id,
+                manager,
+                registered: true,
+            },
+        );
+        Ok(template_id)
+    }
+
+    /// Delete a factory template and return its slot to the manager that
+    /// registered it.
+    ///
+    /// `manager` must be either that registrar, still holding
+    /// [`CreatorRole::TemplateManager`], or the factory admin. One manager
+    /// cannot delete another's templates.
+    ///
+    /// # Errors
+    /// - [`FactoryError::TemplateNotRegistered`] — `template_id` is not a
+    ///   factory template.
+    /// - [`FactoryError::Unauthorized`] — `manager` is neither the registrar
+    ///   nor the admin.
+    /// - [`FactoryError::CreatorRoleRequired`] — the registrar no longer holds
+    ///   [`CreatorRole::TemplateManager`].
+    /// - [`FactoryError::StreamContractError`] — the stream contract rejected
+    ///   the deletion.
+    pub fn delete_factory_template(
+        env: Env,
+        manager: Address,
+        template_id: u64,
+    ) -> Result<(), FactoryError> {
+        let policy = load_policy(&env)?;
+        manager.require_auth();
+        let key = DataKey::FactoryTemplate(template_id);
+        let registrar: Address = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(FactoryError::TemplateNotRegistered)?;
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)?;
+        if manager != admin {
+            if manager != registrar {
+                return Err(FactoryError::Unauthorized);
+            }
+            require_role(&env, &manager, CreatorRole::TemplateManager, 0)?;
+        }
+
+        let stream_client = FluxoraStreamClient::new(&env, &policy.stream_contract);
+        match stream_client
+            .try_delete_stream_template(&env.current_contract_address(), &template_id)
+        {
+            Ok(Ok(())) => {}
+            _ => return Err(FactoryError::StreamContractError),
+        }
+
+        env.storage().persistent().remove(&key);
+        let count = manager_template_count(&env, &registrar);
+        set_manager_template_count(&env, &registrar, count.saturating_sub(1));
+
+        env.events().publish(
+            (symbol_short!("fct_tmpl"),),
+            FactoryTemplateUpdated {
+                template_id,
+                manager,
+                registered: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Create a linear stream whose schedule comes from a factory template.
+    ///
+    /// Times are taken relative to the current ledger timestamp as in
+    /// `FluxoraStream::create_stream_from_template`: `start = now +
+    /// start_delay`, `cliff = now + cliff_delay`, `end = start + duration`.
+    /// The stream is then created through [`create_stream`](Self::create_stream),
+    /// so the same policy, role and quota checks apply.
+    ///
+    /// # Errors
+    /// - [`FactoryError::TemplateNotRegistered`] — `template_id` is not a
+    ///   factory template on the current stream contract.
+    /// - [`FactoryError::InvalidTimeRange`] — the schedule overflows `u64`.
+    /// - Every error of [`create_stream`](Self::create_stream).
+    pub fn create_stream_from_template(
+        env: Env,
+        sender: Address,
+        template_id: u64,
+        recipient: Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+    ) -> Result<u64, FactoryError> {
+        let policy = load_policy(&env)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::FactoryTemplate(template_id))
+        {
+            return Err(FactoryError::TemplateNotRegistered);
+        }
+        // After a stream contract migration the same ID may name someone
+        // else's template on the new contract.
+        let stream_client = FluxoraStreamClient::new(&env, &policy.stream_contract);
+        let template = match stream_client.try_get_stream_template(&template_id) {
+            Ok(Ok(template)) if template.owner == env.current_contract_address() => template,
+            _ => return Err(FactoryError::TemplateNotRegistered),
+        };
+
+        let now = env.ledger().timestamp();
+        let start_time = now
+            .checked_add(template.start_delay)
+            .ok_or(FactoryError::InvalidTimeRange)?;
+        let cliff_time = now
+            .checked_add(template.cliff_delay)
+            .ok_or(FactoryError::InvalidTimeRange)?;
+        let end_time = start_time
+            .checked_add(template.duration)
+            .ok_or(FactoryError::InvalidTimeRange)?;
+
+        Self::create_stream(
+            env,
+            sender,
+            CreateStreamParams {
+                recipient,
+                deposit_amount,
+                rate_per_second,
+                start_time,
+                cliff_time,
+                end_time,
+                withdraw_dust_threshold: None,
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,232 @@
+//! Integration tests for expiring allowlist entries and the Merkle allowlist.
+//!
+//! Confirms:
+//! - `set_allowlist_until` entries stop counting at `expires_at`, and a plain
+//!   `set_allowlist` replaces the expiry.
+//! - Recipients under the Merkle root can be streamed to with a proof through
+//!   `create_stream_with_proof` and `create_streams_with_proofs`; bad proofs,
+//!   a replaced or cleared root, and miscounted proof lists are rejected.
+
+extern crate std;
+
+use fluxora_factory::{allowlist_leaf, FactoryError, FluxoraFactory, FluxoraFactoryClient};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Bytes, BytesN, Env, Vec,
+};
+
+const MAX_DEPOSIT: i128 = 10_000_000;
+const MIN_DURATION: u64 = 86_400;
+const DEPOSIT_AMOUNT: i128 = 200_000;
+const LEDGER_TIMESTAMP: u64 = 1_000_000_000;
+
+struct Ctx {
+    env: Env,
+    factory: FluxoraFactoryClient<'static>,
+    sender: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(LEDGER_TIMESTAMP);
+
+        let stream_contract_id = env.register_contract(None, FluxoraStream);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        let factory = FluxoraFactoryClient::new(&env, &factory_id);
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_000);
+        TokenClient::new(&env, &token_id).approve(
+            &sender,
+            &stream_contract_id,
+            &1_000_000_000,
+            &200_000,
+        );
+
+        FluxoraStreamClient::new(&env, &stream_contract_id).init(&token_id, &admin);
+        factory.init(&admin, &stream_contract_id, &MAX_DEPOSIT, &MIN_DURATION);
+
+        Self {
+            env,
+            factory,
+            sender,
+        }
+    }
+
+    fn params(&self, recipient: &Address) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: recipient.clone(),
+            deposit_amount: DEPOSIT_AMOUNT,
+            rate_per_second: 1,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 200_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn hash_pair(&self, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        let mut preimage = Bytes::from(first.clone());
+        preimage.append(&Bytes::from(second.clone()));
+        self.env.crypto().sha256(&preimage).into()
+    }
+}
+
+/// Four-leaf tree over `recipients`; returns the root and each leaf's proof.
+fn build_tree(
+    ctx: &Ctx,
+    recipients: &[Address; 4],
+) -> (BytesN<32>, std::vec::Vec<Vec<BytesN<32>>>) {
+    let leaves: std::vec::Vec<BytesN<32>> = recipients
+        .iter()
+        .map(|r| allowlist_leaf(&ctx.env, r))
+        .collect();
+    let left = ctx.hash_pair(&leaves[0], &leaves[1]);
+    let right = ctx.hash_pair(&leaves[2], &leaves[3]);
+    let root = ctx.hash_pair(&left, &right);
+    let proofs = (0..4)
+        .map(|i| {
+            let uncle = if i < 2 { right.clone() } else { left.clone() };
+            vec![&ctx.env, leaves[i ^ 1].clone(), uncle]
+        })
+        .collect();
+    (root, proofs)
+}
+
+#[test]
+fn expiring_entries_drop_off() {
+    let ctx = Ctx::setup();
+    let contractor = Address::generate(&ctx.env);
+    let expires_at = LEDGER_TIMESTAMP + 1_000;
+
+    assert_eq!(
+        ctx.factory
+            .try_set_allowlist_until(&contractor, &LEDGER_TIMESTAMP),
+        Err(Ok(FactoryError::InvalidAllowlistExpiry))
+    );
+    ctx.factory.set_allowlist_until(&contractor, &expires_at);
+    assert!(ctx.factory.is_allowlisted(&contractor));
+    assert_eq!(
+        ctx.factory.get_allowlist_expiry(&contractor),
+        Some(expires_at)
+    );
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(&contractor));
+
+    ctx.env.ledger().set_timestamp(expires_at);
+    assert!(!ctx.factory.is_allowlisted(&contractor));
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(&contractor)),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+
+    // A permanent grant replaces the expiry.
+    ctx.factory.set_allowlist(&contractor, &true);
+    assert_eq!(ctx.factory.get_allowlist_expiry(&contractor), None);
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(&contractor));
+}
+
+#[test]
+fn merkle_proofs_admit_bulk_recipients() {
+    let ctx = Ctx::setup();
+    let recipients = [
+        Address::generate(&ctx.env),
+        Address::generate(&ctx.env),
+        Address::generate(&ctx.env),
+        Address::generate(&ctx.env),
+    ];
+    let (root, proofs) = build_tree(&ctx, &recipients);
+    let outsider = Address::generate(&ctx.env);
+
+    // No root yet: proofs do not help.
+    assert!(!ctx
+        .factory
+        .verify_allowlist_proof(&recipients[0], &proofs[0]));
+
+    ctx.factory.set_allowlist_root(&root);
+    assert_eq!(ctx.factory.get_allowlist_root(), Some(root));
+    assert!(ctx
+        .factory
+        .verify_allowlist_proof(&recipients[2], &proofs[2]));
+    assert!(!ctx.factory.is_allowlisted(&recipients[2]));
+
+    ctx.factory
+        .create_stream_with_proof(&ctx.sender, &ctx.params(&recipients[2]), &proofs[2]);
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(&recipients[2])),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+    assert_eq!(
+        ctx.factory
+            .try_create_stream_with_proof(&ctx.sender, &ctx.params(&outsider), &proofs[2]),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+    assert_eq!(
+        ctx.factory.try_create_stream_with_proof(
+            &ctx.sender,
+            &ctx.params(&recipients[1]),
+            &proofs[2]
+        ),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+
+    // Batches take one proof per stream; individually listed recipients
+    // need only an empty proof.
+    ctx.factory.set_allowlist(&outsider, &true);
+    let batch = vec![
+        &ctx.env,
+        ctx.params(&recipients[0]),
+        ctx.params(&outsider),
+        ctx.params(&recipients[3]),
+    ];
+    assert_eq!(
+        ctx.factory.try_create_streams_with_proofs(
+            &ctx.sender,
+            &batch,
+            &vec![&ctx.env, proofs[0].clone()]
+        ),
+        Err(Ok(FactoryError::ProofCountMismatch))
+    );
+    let batch_proofs = vec![
+        &ctx.env,
+        proofs[0].clone(),
+        Vec::new(&ctx.env),
+        proofs[3].clone(),
+    ];
+    let ids = ctx
+        .factory
+        .create_streams_with_proofs(&ctx.sender, &batch, &batch_proofs);
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ctx.factory.get_factory_stream_count(), 4);
+
+    ctx.factory.clear_allowlist_root();
+    assert_eq!(ctx.factory.get_allowlist_root(), None);
+    assert_eq!(
+        ctx.factory.try_create_stream_with_proof(
+            &ctx.sender,
+            &ctx.params(&recipients[0]),
+            &proofs[0]
+        ),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+}
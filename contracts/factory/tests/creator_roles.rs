@@ -0,0 +1,410 @@
+//! Integration tests for creator roles.
+//!
+//! Confirms:
+//! - Sender role checks are off by default and, once enforced, require
+//!   `Creator` for single streams and `BatchCreator` for batches, each capped
+//!   by its grant.
+//! - The `_as_operator` paths check and require auth from the operator, while
+//!   the funding sender only authorizes the nested stream creation.
+//! - Grants and revocations emit `role_upd` and accept the `u32` role
+//!   encoding used by governance `CallData`.
+//! - `TemplateManager` gates factory templates and caps how many a manager
+//!   keeps live, with the count kept alive like other persistent entries;
+//!   creators can create streams from those templates.
+//! - Only the manager that registered a factory template, or the admin, can
+//!   delete it.
+
+extern crate std;
+
+use fluxora_factory::{
+    CreatorRole, CreatorRoleUpdated, DataKey, FactoryError, FluxoraFactory, FluxoraFactoryClient,
+    PERSISTENT_BUMP_AMOUNT,
+};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, AuthorizedFunction, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal,
+};
+
+const MAX_DEPOSIT: i128 = 10_000_000;
+const MIN_DURATION: u64 = 86_400;
+const DEPOSIT_AMOUNT: i128 = 200_000;
+const LEDGER_TIMESTAMP: u64 = 1_000_000_000;
+
+struct Ctx {
+    env: Env,
+    factory: FluxoraFactoryClient<'static>,
+    stream: FluxoraStreamClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+    operator: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(LEDGER_TIMESTAMP);
+
+        let stream_contract_id = env.register_contract(None, FluxoraStream);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        let factory = FluxoraFactoryClient::new(&env, &factory_id);
+        let stream = FluxoraStreamClient::new(&env, &stream_contract_id);
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        StellarAssetClient::new(&env, &token_id).mint(&operator, &1_000_000_000);
+        TokenClient::new(&env, &token_id).approve(
+            &operator,
+            &stream_contract_id,
+            &1_000_000_000,
+            &200_000,
+        );
+
+        stream.init(&token_id, &admin);
+        factory.init(&admin, &stream_contract_id, &MAX_DEPOSIT, &MIN_DURATION);
+        factory.set_allowlist(&recipient, &true);
+
+        Self {
+            token: TokenClient::new(&env, &token_id),
+            env,
+            factory,
+            stream,
+            admin,
+            operator,
+            recipient,
+        }
+    }
+
+    fn params(&self, deposit_amount: i128) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount,
+            rate_per_second: 1,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 200_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+}
+
+#[test]
+fn enforced_roles_gate_and_cap_creation() {
+    let ctx = Ctx::setup();
+
+    // Not enforced: any sender may create.
+    ctx.factory
+        .create_stream(&ctx.operator, &ctx.params(DEPOSIT_AMOUNT));
+
+    ctx.factory.set_sender_roles_enforced(&true);
+    assert!(ctx.factory.get_factory_config().sender_roles_enforced);
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.operator, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+
+    ctx.factory
+        .grant_creator_role(&ctx.operator, &CreatorRole::Creator, &DEPOSIT_AMOUNT);
+    ctx.factory
+        .create_stream(&ctx.operator, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.operator, &ctx.params(DEPOSIT_AMOUNT + 1)),
+        Err(Ok(FactoryError::RoleCapExceeded))
+    );
+
+    // Creator does not unlock batches; BatchCreator caps the batch total.
+    let batch = vec![
+        &ctx.env,
+        ctx.params(DEPOSIT_AMOUNT),
+        ctx.params(DEPOSIT_AMOUNT),
+    ];
+    assert_eq!(
+        ctx.factory.try_create_streams(&ctx.operator, &batch),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+    ctx.factory.grant_creator_role(
+        &ctx.operator,
+        &CreatorRole::BatchCreator,
+        &(2 * DEPOSIT_AMOUNT - 1),
+    );
+    assert_eq!(
+        ctx.factory.try_create_streams(&ctx.operator, &batch),
+        Err(Ok(FactoryError::RoleCapExceeded))
+    );
+    ctx.factory.grant_creator_role(
+        &ctx.operator,
+        &CreatorRole::BatchCreator,
+        &(2 * DEPOSIT_AMOUNT),
+    );
+    ctx.factory.create_streams(&ctx.operator, &batch);
+    assert_eq!(ctx.factory.get_factory_stream_count(), 4);
+
+    ctx.factory
+        .revoke_creator_role(&ctx.operator, &CreatorRole::Creator);
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.operator, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+}
+
+#[test]
+fn operators_create_from_a_funding_sender() {
+    let ctx = Ctx::setup();
+    let hr = Address::generate(&ctx.env);
+    let treasury = ctx.operator.clone();
+    ctx.factory.set_sender_roles_enforced(&true);
+    ctx.factory
+        .grant_creator_role(&hr, &CreatorRole::Creator, &DEPOSIT_AMOUNT);
+
+    // The treasury holds no role, so it cannot operate for itself.
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&treasury, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+    assert_eq!(
+        ctx.factory.try_create_stream_as_operator(
+            &treasury,
+            &treasury,
+            &ctx.params(DEPOSIT_AMOUNT)
+        ),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+    assert_eq!(
+        ctx.factory
+            .try_create_stream_as_operator(&hr, &treasury, &ctx.params(DEPOSIT_AMOUNT + 1)),
+        Err(Ok(FactoryError::RoleCapExceeded))
+    );
+
+    // The treasury's authorization is not rooted at the factory call.
+    ctx.env.mock_all_auths_allowing_non_root_auth();
+    let before = ctx.token.balance(&treasury);
+    let stream_id =
+        ctx.factory
+            .create_stream_as_operator(&hr, &treasury, &ctx.params(DEPOSIT_AMOUNT));
+
+    // The operator signs the factory call; the treasury only the nested
+    // stream creation.
+    let auths = ctx.env.auths();
+    let root_of = |who: &Address| {
+        auths
+            .iter()
+            .find(|(addr, _)| addr == who)
+            .map(|(_, invocation)| invocation.function.clone())
+            .unwrap()
+    };
+    match root_of(&hr) {
+        AuthorizedFunction::Contract((contract, function, _)) => {
+            assert_eq!(contract, ctx.factory.address);
+            assert_eq!(function, Symbol::new(&ctx.env, "create_stream_as_operator"));
+        }
+        _ => panic!("unexpected operator auth"),
+    }
+    match root_of(&treasury) {
+        AuthorizedFunction::Contract((contract, function, _)) => {
+            assert_eq!(contract, ctx.stream.address);
+            assert_eq!(function, Symbol::new(&ctx.env, "create_stream"));
+        }
+        _ => panic!("unexpected sender auth"),
+    }
+    assert_eq!(ctx.stream.get_stream_state(&stream_id).sender, treasury);
+    assert_eq!(ctx.token.balance(&treasury), before - DEPOSIT_AMOUNT);
+
+    // Batches need the operator's BatchCreator grant.
+    let batch = vec![&ctx.env, ctx.params(DEPOSIT_AMOUNT)];
+    assert_eq!(
+        ctx.factory
+            .try_create_streams_as_operator(&hr, &treasury, &batch),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+    ctx.factory
+        .grant_creator_role(&hr, &CreatorRole::BatchCreator, &DEPOSIT_AMOUNT);
+    let ids = ctx
+        .factory
+        .create_streams_as_operator(&hr, &treasury, &batch);
+    assert_eq!(
+        ctx.stream.get_stream_state(&ids.get(0).unwrap()).sender,
+        treasury
+    );
+}
+
+#[test]
+fn grants_emit_events_and_accept_governance_encoding() {
+    let ctx = Ctx::setup();
+    assert_eq!(
+        ctx.factory
+            .try_grant_creator_role(&ctx.operator, &CreatorRole::Creator, &0),
+        Err(Ok(FactoryError::InvalidRoleCap))
+    );
+
+    // Governance dispatches the role as its u32 discriminant.
+    let _: () = ctx.env.invoke_contract(
+        &ctx.factory.address,
+        &Symbol::new(&ctx.env, "grant_creator_role"),
+        (ctx.operator.clone(), 2u32, 5i128).into_val(&ctx.env),
+    );
+    let (_, topics, data) = ctx.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&ctx.env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("role_upd"));
+    let updated = CreatorRoleUpdated::try_from_val(&ctx.env, &data).unwrap();
+    assert_eq!(updated.role, CreatorRole::TemplateManager);
+    assert_eq!((updated.cap, updated.granted), (5, true));
+    assert_eq!(
+        ctx.factory
+            .get_creator_role_cap(&ctx.operator, &CreatorRole::TemplateManager),
+        Some(5)
+    );
+
+    ctx.factory
+        .revoke_creator_role(&ctx.operator, &CreatorRole::TemplateManager);
+    let (_, _, data) = ctx.env.events().all().last().unwrap();
+    assert!(
+        !CreatorRoleUpdated::try_from_val(&ctx.env, &data)
+            .unwrap()
+            .granted
+    );
+    assert_eq!(
+        ctx.factory
+            .get_creator_role_cap(&ctx.operator, &CreatorRole::TemplateManager),
+        None
+    );
+}
+
+#[test]
+fn template_managers_curate_factory_templates() {
+    let ctx = Ctx::setup();
+    let manager = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.factory
+            .try_register_factory_template(&manager, &0, &0, &MIN_DURATION),
+        Err(Ok(FactoryError::CreatorRoleRequired))
+    );
+
+    ctx.factory
+        .grant_creator_role(&manager, &CreatorRole::TemplateManager, &1);
+    let template_id = ctx
+        .factory
+        .register_factory_template(&manager, &100, &100, &MIN_DURATION);
+    let count_ttl = ctx.env.as_contract(&ctx.factory.address, || {
+        ctx.env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::ManagerTemplateCount(manager.clone()))
+    });
+    assert_eq!(count_ttl, PERSISTENT_BUMP_AMOUNT);
+    assert_eq!(
+        ctx.stream.get_stream_template(&template_id).owner,
+        ctx.factory.address
+    );
+    assert_eq!(
+        ctx.factory
+            .try_register_factory_template(&manager, &0, &0, &MIN_DURATION),
+        Err(Ok(FactoryError::RoleCapExceeded))
+    );
+
+    ctx.factory.set_sender_roles_enforced(&true);
+    ctx.factory
+        .grant_creator_role(&ctx.operator, &CreatorRole::Creator, &DEPOSIT_AMOUNT);
+    let stream_id = ctx.factory.create_stream_from_template(
+        &ctx.operator,
+        &template_id,
+        &ctx.recipient,
+        &DEPOSIT_AMOUNT,
+        &1,
+    );
+    let state = ctx.stream.get_stream_state(&stream_id);
+    assert_eq!(state.start_time, LEDGER_TIMESTAMP + 100);
+    assert_eq!(state.end_time, LEDGER_TIMESTAMP + 100 + MIN_DURATION);
+    assert_eq!(
+        ctx.factory.try_create_stream_from_template(
+            &ctx.operator,
+            &template_id,
+            &ctx.recipient,
+            &(DEPOSIT_AMOUNT + 1),
+            &1,
+        ),
+        Err(Ok(FactoryError::RoleCapExceeded))
+    );
+
+    // Templates registered directly on the stream contract are not usable.
+    let direct = ctx
+        .stream
+        .register_stream_template(&ctx.operator, &0, &0, &MIN_DURATION);
+    assert_eq!(
+        ctx.factory.try_create_stream_from_template(
+            &ctx.operator,
+            &direct,
+            &ctx.recipient,
+            &DEPOSIT_AMOUNT,
+            &1,
+        ),
+        Err(Ok(FactoryError::TemplateNotRegistered))
+    );
+
+    // Deleting frees the manager's slot.
+    ctx.factory.delete_factory_template(&manager, &template_id);
+    assert_eq!(
+        ctx.factory.try_create_stream_from_template(
+            &ctx.operator,
+            &template_id,
+            &ctx.recipient,
+            &DEPOSIT_AMOUNT,
+            &1,
+        ),
+        Err(Ok(FactoryError::TemplateNotRegistered))
+    );
+    ctx.factory
+        .register_factory_template(&manager, &0, &0, &MIN_DURATION);
+}
+
+#[test]
+fn only_registrar_or_admin_deletes_factory_templates() {
+    let ctx = Ctx::setup();
+    let owner = Address::generate(&ctx.env);
+    let other = Address::generate(&ctx.env);
+    ctx.factory
+        .grant_creator_role(&owner, &CreatorRole::TemplateManager, &2);
+    ctx.factory
+        .grant_creator_role(&other, &CreatorRole::TemplateManager, &2);
+    let first = ctx
+        .factory
+        .register_factory_template(&owner, &0, &0, &MIN_DURATION);
+    let second = ctx
+        .factory
+        .register_factory_template(&owner, &0, &0, &MIN_DURATION);
+
+    // Another manager cannot delete the owner's template.
+    assert_eq!(
+        ctx.factory.try_delete_factory_template(&other, &first),
+        Err(Ok(FactoryError::Unauthorized))
+    );
+
+    // The registrar deletes its own template.
+    ctx.factory.delete_factory_template(&owner, &first);
+
+    // The admin deletes any template, and the slot returns to the registrar.
+    ctx.factory.delete_factory_template(&ctx.admin, &second);
+    ctx.factory
+        .register_factory_template(&owner, &0, &0, &MIN_DURATION);
+    ctx.factory
+        .register_factory_template(&owner, &0, &0, &MIN_DURATION);
+}
@@ -60,6 +60,26 @@ fn test_factory_error_discriminants_are_stable() {
 
     // ── Memo ─────────────────────────────────────────────────────────────
     assert_eq!(FactoryError::InvalidMemo as u32, 16);
+
+    // ── Stream instances ─────────────────────────────────────────────────
+    assert_eq!(FactoryError::StreamWasmNotSet as u32, 19);
+    assert_eq!(FactoryError::InstanceAlreadyDeployed as u32, 20);
+    assert_eq!(FactoryError::InstanceNotRegistered as u32, 21);
+
+    // ── Sender quotas ────────────────────────────────────────────────────
+    assert_eq!(FactoryError::SenderDepositQuotaExceeded as u32, 22);
+    assert_eq!(FactoryError::SenderStreamQuotaExceeded as u32, 23);
+    assert_eq!(FactoryError::InvalidSenderQuota as u32, 24);
+
+    // ── Creator roles ────────────────────────────────────────────────────
+    assert_eq!(FactoryError::CreatorRoleRequired as u32, 25);
+    assert_eq!(FactoryError::RoleCapExceeded as u32, 26);
+    assert_eq!(FactoryError::InvalidRoleCap as u32, 27);
+    assert_eq!(FactoryError::TemplateNotRegistered as u32, 28);
+
+    // ── Allowlist expiry and Merkle proofs ───────────────────────────────
+    assert_eq!(FactoryError::InvalidAllowlistExpiry as u32, 29);
+    assert_eq!(FactoryError::ProofCountMismatch as u32, 30);
 }
 
 /// Each discriminant in the table must be unique. Catches accidental duplicate
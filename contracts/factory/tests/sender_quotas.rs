@@ -0,0 +1,295 @@
+//! Integration tests for per-sender creation quotas.
+//!
+//! Confirms:
+//! - The default quota limits both stream count and aggregate deposit per
+//!   window, and usage is released once it leaves the window.
+//! - The window rolls: each creation is released on its own schedule rather
+//!   than all at once.
+//! - `create_streams` charges the whole batch, so an oversized batch is
+//!   rejected without creating any stream.
+//! - A per-sender override replaces the default, a zero-window override
+//!   exempts the sender, and removing it restores the default.
+//! - `get_sender_quota_remaining` reports what the next creation would see.
+
+extern crate std;
+
+use fluxora_factory::{
+    FactoryError, FluxoraFactory, FluxoraFactoryClient, SenderQuota, SENDER_QUOTA_BUCKETS,
+};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env,
+};
+
+const MAX_DEPOSIT: i128 = 10_000_000;
+const MIN_DURATION: u64 = 86_400;
+const DEPOSIT_AMOUNT: i128 = 200_000;
+const LEDGER_TIMESTAMP: u64 = 1_000_000_000;
+const WINDOW: u64 = 3_600;
+const BUCKET: u64 = WINDOW / SENDER_QUOTA_BUCKETS;
+
+struct Ctx {
+    env: Env,
+    factory: FluxoraFactoryClient<'static>,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(LEDGER_TIMESTAMP);
+
+        let stream_contract_id = env.register_contract(None, FluxoraStream);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        let factory = FluxoraFactoryClient::new(&env, &factory_id);
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_000);
+        TokenClient::new(&env, &token_id).approve(
+            &sender,
+            &stream_contract_id,
+            &1_000_000_000,
+            &200_000,
+        );
+
+        FluxoraStreamClient::new(&env, &stream_contract_id).init(&token_id, &admin);
+        factory.init(&admin, &stream_contract_id, &MAX_DEPOSIT, &MIN_DURATION);
+        factory.set_allowlist(&recipient, &true);
+
+        Self {
+            env,
+            factory,
+            sender,
+            recipient,
+        }
+    }
+
+    fn params(&self, deposit_amount: i128) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount,
+            rate_per_second: 1,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 200_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn advance(&self, secs: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + secs);
+    }
+
+    /// When usage recorded at `at` leaves the rolling window.
+    fn release_of(at: u64) -> u64 {
+        at - at % BUCKET + BUCKET + WINDOW
+    }
+}
+
+#[test]
+fn default_quota_limits_count_and_deposit_per_window() {
+    let ctx = Ctx::setup();
+    ctx.factory
+        .set_sender_quota(&WINDOW, &(3 * DEPOSIT_AMOUNT), &2);
+    assert_eq!(
+        ctx.factory.get_factory_config().sender_quota,
+        SenderQuota {
+            window_secs: WINDOW,
+            max_deposit: 3 * DEPOSIT_AMOUNT,
+            max_streams: 2,
+        }
+    );
+
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(2 * DEPOSIT_AMOUNT + 1)),
+        Err(Ok(FactoryError::SenderDepositQuotaExceeded))
+    );
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(
+        ctx.factory.try_create_stream(&ctx.sender, &ctx.params(1)),
+        Err(Ok(FactoryError::SenderStreamQuotaExceeded))
+    );
+
+    let status = ctx.factory.get_sender_quota_remaining(&ctx.sender);
+    assert_eq!(status.next_release_at, Ctx::release_of(LEDGER_TIMESTAMP));
+    assert_eq!(status.remaining_deposit, DEPOSIT_AMOUNT);
+    assert_eq!(status.remaining_streams, 0);
+
+    // Other senders have their own window.
+    let other = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.factory
+            .get_sender_quota_remaining(&other)
+            .remaining_streams,
+        2
+    );
+
+    // Usage still counts for the full window ...
+    ctx.advance(WINDOW);
+    assert_eq!(
+        ctx.factory
+            .get_sender_quota_remaining(&ctx.sender)
+            .remaining_streams,
+        0
+    );
+
+    // ... and is released once its sub-window has left it.
+    ctx.env
+        .ledger()
+        .set_timestamp(Ctx::release_of(LEDGER_TIMESTAMP));
+    let status = ctx.factory.get_sender_quota_remaining(&ctx.sender);
+    assert_eq!(status.remaining_streams, 2);
+    assert_eq!(status.remaining_deposit, 3 * DEPOSIT_AMOUNT);
+    assert_eq!(status.next_release_at, 0);
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(ctx.factory.get_factory_stream_count(), 3);
+}
+
+#[test]
+fn window_rolls_per_creation() {
+    let ctx = Ctx::setup();
+    ctx.factory.set_sender_quota(&WINDOW, &MAX_DEPOSIT, &2);
+
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    ctx.advance(WINDOW / 2);
+    let second = ctx.env.ledger().timestamp();
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::SenderStreamQuotaExceeded))
+    );
+
+    // Only the first creation has left the window; a fixed window would have
+    // reset both.
+    ctx.env
+        .ledger()
+        .set_timestamp(Ctx::release_of(LEDGER_TIMESTAMP));
+    let status = ctx.factory.get_sender_quota_remaining(&ctx.sender);
+    assert_eq!(status.remaining_streams, 1);
+    assert_eq!(status.remaining_deposit, MAX_DEPOSIT - DEPOSIT_AMOUNT);
+    assert_eq!(status.next_release_at, Ctx::release_of(second));
+
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::SenderStreamQuotaExceeded))
+    );
+    assert_eq!(ctx.factory.get_factory_stream_count(), 3);
+}
+
+#[test]
+fn batch_is_charged_as_a_unit() {
+    let ctx = Ctx::setup();
+    ctx.factory.set_sender_quota(&WINDOW, &MAX_DEPOSIT, &2);
+
+    let batch = vec![
+        &ctx.env,
+        ctx.params(DEPOSIT_AMOUNT),
+        ctx.params(DEPOSIT_AMOUNT),
+        ctx.params(DEPOSIT_AMOUNT),
+    ];
+    assert_eq!(
+        ctx.factory.try_create_streams(&ctx.sender, &batch),
+        Err(Ok(FactoryError::SenderStreamQuotaExceeded))
+    );
+    assert_eq!(ctx.factory.get_factory_stream_count(), 0);
+    assert_eq!(
+        ctx.factory
+            .get_sender_quota_remaining(&ctx.sender)
+            .remaining_streams,
+        2
+    );
+
+    ctx.factory.create_streams(&ctx.sender, &batch.slice(0..2));
+    assert_eq!(
+        ctx.factory
+            .get_sender_quota_remaining(&ctx.sender)
+            .remaining_deposit,
+        MAX_DEPOSIT - 2 * DEPOSIT_AMOUNT
+    );
+}
+
+#[test]
+fn overrides_replace_the_default() {
+    let ctx = Ctx::setup();
+    ctx.factory.set_sender_quota(&WINDOW, &MAX_DEPOSIT, &1);
+    let whale = Address::generate(&ctx.env);
+
+    ctx.factory
+        .set_sender_quota_override(&ctx.sender, &WINDOW, &MAX_DEPOSIT, &3);
+    ctx.factory.set_sender_quota_override(&whale, &0, &0, &0);
+    for _ in 0..3 {
+        ctx.factory
+            .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    }
+    let unlimited = ctx.factory.get_sender_quota_remaining(&whale);
+    assert_eq!(unlimited.quota, SenderQuota::unlimited());
+    assert_eq!(unlimited.remaining_deposit, i128::MAX);
+
+    // Back on the default, the three streams already created count.
+    ctx.factory.remove_sender_quota_override(&ctx.sender);
+    assert_eq!(
+        ctx.factory
+            .try_create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT)),
+        Err(Ok(FactoryError::SenderStreamQuotaExceeded))
+    );
+
+    // Clearing the default leaves senders unlimited.
+    ctx.factory.set_sender_quota(&0, &0, &0);
+    ctx.factory
+        .create_stream(&ctx.sender, &ctx.params(DEPOSIT_AMOUNT));
+    assert_eq!(ctx.factory.get_factory_stream_count(), 4);
+}
+
+#[test]
+fn invalid_quotas_are_rejected() {
+    let ctx = Ctx::setup();
+    for (max_deposit, max_streams) in [(0, 1), (-1, 1), (1, 0)] {
+        assert_eq!(
+            ctx.factory
+                .try_set_sender_quota(&WINDOW, &max_deposit, &max_streams),
+            Err(Ok(FactoryError::InvalidSenderQuota))
+        );
+        assert_eq!(
+            ctx.factory.try_set_sender_quota_override(
+                &ctx.sender,
+                &WINDOW,
+                &max_deposit,
+                &max_streams
+            ),
+            Err(Ok(FactoryError::InvalidSenderQuota))
+        );
+    }
+    assert_eq!(
+        ctx.factory.get_factory_config().sender_quota,
+        SenderQuota::unlimited()
+    );
+}
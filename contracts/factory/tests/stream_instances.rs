@@ -0,0 +1,392 @@
+//! Integration tests for per-tenant stream instance deployment.
+//!
+//! Confirms:
+//! - `deploy_stream_instance` requires a governance-pinned WASM hash and
+//!   respects the factory creation pause.
+//! - Deployed instances are recorded per tenant and globally, with paginated
+//!   listing and a reverse tenant lookup; a reused `(tenant, salt)` is rejected.
+//! - Both instance indexes are stored in pages of `INSTANCE_PAGE_SIZE`
+//!   addresses and list correctly across page boundaries.
+//! - `create_stream_on_instance` applies the factory policy and creates the
+//!   stream on the tenant's own contract, not on the shared `stream_contract`.
+//! - Stream creation on an instance is locked to the factory: direct
+//!   `create_stream` calls on the instance are rejected, so the policy cannot
+//!   be skipped.
+//!
+//! The deploy path runs a minimal stub WASM whose `init_with_creator` is a
+//! no-op. Tests that need a working instance then register the native
+//! `FluxoraStream` at the deployed address (the host keeps the instance entry)
+//! and initialise it as the factory would.
+
+extern crate std;
+
+use fluxora_factory::{
+    DataKey, FactoryError, FluxoraFactory, FluxoraFactoryClient, StreamInstanceDeployed,
+    INSTANCE_PAGE_SIZE,
+};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal,
+};
+
+const MAX_DEPOSIT: i128 = 10_000_000;
+const MIN_DURATION: u64 = 86_400;
+const LEDGER_TIMESTAMP: u64 = 1_000_000_000;
+
+/// Minimal Soroban module exporting `memory` and
+/// `init_with_creator(i64, i64, i64) -> i64`, which returns `Void` without
+/// touching storage.
+fn stub_stream_wasm(env: &Env) -> Bytes {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    // type section: (i64, i64, i64) -> i64
+    wasm.extend_from_slice(&[1, 8, 1, 0x60, 3, 0x7e, 0x7e, 0x7e, 1, 0x7e]);
+    // function section: one function of type 0
+    wasm.extend_from_slice(&[3, 2, 1, 0]);
+    // memory section: one page
+    wasm.extend_from_slice(&[5, 3, 1, 0, 1]);
+    // export section: "memory" and "init_with_creator"
+    wasm.extend_from_slice(&[7, 30, 2, 6]);
+    wasm.extend_from_slice(b"memory");
+    wasm.extend_from_slice(&[2, 0, 17]);
+    wasm.extend_from_slice(b"init_with_creator");
+    wasm.extend_from_slice(&[0, 0]);
+    // code section: i64.const 2 (Void)
+    wasm.extend_from_slice(&[10, 6, 1, 4, 0, 0x42, 2, 0x0b]);
+    let name = b"contractenvmetav0";
+    let mut payload = std::vec![name.len() as u8];
+    payload.extend_from_slice(name);
+    payload.extend_from_slice(&0u32.to_be_bytes());
+    payload.extend_from_slice(&(21u64 << 32).to_be_bytes());
+    wasm.push(0);
+    wasm.push(payload.len() as u8);
+    wasm.extend_from_slice(&payload);
+    Bytes::from_slice(env, &wasm)
+}
+
+struct Ctx {
+    env: Env,
+    factory: FluxoraFactoryClient<'static>,
+    default_stream: FluxoraStreamClient<'static>,
+    token_id: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(LEDGER_TIMESTAMP);
+
+        let stream_contract_id = env.register_contract(None, FluxoraStream);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        let factory = FluxoraFactoryClient::new(&env, &factory_id);
+        let default_stream = FluxoraStreamClient::new(&env, &stream_contract_id);
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        default_stream.init(&token_id, &admin);
+        factory.init(&admin, &stream_contract_id, &MAX_DEPOSIT, &MIN_DURATION);
+        factory.set_allowlist(&recipient, &true);
+
+        Self {
+            env,
+            factory,
+            default_stream,
+            token_id,
+            recipient,
+        }
+    }
+
+    fn pin_stub(&self) -> BytesN<32> {
+        let hash = self
+            .env
+            .deployer()
+            .upload_contract_wasm(stub_stream_wasm(&self.env));
+        self.factory.set_stream_wasm_hash(&hash);
+        hash
+    }
+
+    fn salt(&self, tag: u8) -> BytesN<32> {
+        BytesN::from_array(&self.env, &[tag; 32])
+    }
+
+    fn params(&self, recipient: &Address) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: recipient.clone(),
+            deposit_amount: 200_000,
+            rate_per_second: 1,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 200_000,
+            withdraw_dust_threshold: None,
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+}
+
+#[test]
+fn deploy_requires_pinned_hash_and_open_factory() {
+    let ctx = Ctx::setup();
+    let tenant = Address::generate(&ctx.env);
+
+    assert_eq!(ctx.factory.get_stream_wasm_hash(), None);
+    assert_eq!(
+        ctx.factory
+            .try_deploy_stream_instance(&tenant, &ctx.token_id, &ctx.salt(1)),
+        Err(Ok(FactoryError::StreamWasmNotSet))
+    );
+
+    let hash = ctx.pin_stub();
+    assert_eq!(ctx.factory.get_stream_wasm_hash(), Some(hash));
+    let (_, topics, _) = ctx.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&ctx.env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("wasm_pin"));
+
+    ctx.factory.set_factory_paused(&true);
+    assert_eq!(
+        ctx.factory
+            .try_deploy_stream_instance(&tenant, &ctx.token_id, &ctx.salt(1)),
+        Err(Ok(FactoryError::CreationPaused))
+    );
+    assert_eq!(ctx.factory.get_stream_instance_count(), 0);
+}
+
+#[test]
+fn deployed_instances_are_registered_per_tenant() {
+    let ctx = Ctx::setup();
+    let hash = ctx.pin_stub();
+    let alice = Address::generate(&ctx.env);
+    let bob = Address::generate(&ctx.env);
+
+    let a1 = ctx
+        .factory
+        .deploy_stream_instance(&alice, &ctx.token_id, &ctx.salt(1));
+    let (_, _, data) = ctx.env.events().all().last().unwrap();
+    let deployed = StreamInstanceDeployed::try_from_val(&ctx.env, &data).unwrap();
+    assert_eq!(deployed.tenant_admin, alice);
+    assert_eq!(deployed.instance, a1);
+    assert_eq!(deployed.token, ctx.token_id);
+    assert_eq!(deployed.wasm_hash, hash);
+
+    let a2 = ctx
+        .factory
+        .deploy_stream_instance(&alice, &ctx.token_id, &ctx.salt(2));
+    // The same caller salt gives Bob a different address than Alice.
+    let b1 = ctx
+        .factory
+        .deploy_stream_instance(&bob, &ctx.token_id, &ctx.salt(1));
+    assert_ne!(a1, b1);
+    assert_ne!(a1, a2);
+
+    assert_eq!(
+        ctx.factory
+            .try_deploy_stream_instance(&alice, &ctx.token_id, &ctx.salt(1)),
+        Err(Ok(FactoryError::InstanceAlreadyDeployed))
+    );
+
+    assert_eq!(ctx.factory.get_stream_instance_count(), 3);
+    let page = ctx.factory.get_stream_instances_paginated(&1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap(), a2);
+    assert_eq!(page.get(1).unwrap(), b1);
+    assert_eq!(ctx.factory.get_stream_instances_paginated(&3, &10).len(), 0);
+
+    let alice_page = ctx.factory.get_tenant_instances(&alice, &0, &1);
+    assert_eq!(alice_page.len(), 1);
+    assert_eq!(alice_page.get(0).unwrap(), a1);
+    assert_eq!(ctx.factory.get_tenant_instances(&alice, &0, &10).len(), 2);
+    assert_eq!(ctx.factory.get_instance_tenant(&b1), Some(bob));
+    assert_eq!(
+        ctx.factory
+            .get_instance_tenant(&Address::generate(&ctx.env)),
+        None
+    );
+}
+
+#[test]
+fn instance_indexes_span_pages() {
+    let ctx = Ctx::setup();
+    ctx.env.budget().reset_unlimited();
+    ctx.pin_stub();
+    let tenant = Address::generate(&ctx.env);
+
+    let total = INSTANCE_PAGE_SIZE + 3;
+    let mut deployed = std::vec::Vec::new();
+    for tag in 0..total {
+        deployed.push(ctx.factory.deploy_stream_instance(
+            &tenant,
+            &ctx.token_id,
+            &ctx.salt(tag as u8),
+        ));
+    }
+    assert_eq!(ctx.factory.get_stream_instance_count(), total);
+
+    // Each page holds at most `INSTANCE_PAGE_SIZE` addresses.
+    let (first, second) = ctx.env.as_contract(&ctx.factory.address, || {
+        let page = |n: u32| -> soroban_sdk::Vec<Address> {
+            ctx.env
+                .storage()
+                .persistent()
+                .get(&DataKey::StreamInstancePage(n))
+                .unwrap()
+        };
+        (page(0).len(), page(1).len())
+    });
+    assert_eq!((first, second), (INSTANCE_PAGE_SIZE, 3));
+
+    let across = ctx
+        .factory
+        .get_stream_instances_paginated(&(INSTANCE_PAGE_SIZE - 2), &4);
+    let start = (INSTANCE_PAGE_SIZE - 2) as usize;
+    assert_eq!(
+        across.iter().collect::<std::vec::Vec<_>>(),
+        deployed[start..start + 4]
+    );
+
+    let all = ctx.factory.get_tenant_instances(&tenant, &0, &total);
+    assert_eq!(all.iter().collect::<std::vec::Vec<_>>(), deployed);
+    assert_eq!(
+        ctx.factory
+            .get_tenant_instances(&tenant, &(total - 1), &10)
+            .len(),
+        1
+    );
+    assert_eq!(
+        ctx.factory.get_tenant_instances(&tenant, &total, &10).len(),
+        0
+    );
+}
+
+#[test]
+fn instance_streams_follow_factory_policy() {
+    let ctx = Ctx::setup();
+    ctx.pin_stub();
+    let tenant = Address::generate(&ctx.env);
+    let instance = ctx
+        .factory
+        .deploy_stream_instance(&tenant, &ctx.token_id, &ctx.salt(1));
+
+    // Swap in the native contract and initialise it as the real WASM would.
+    ctx.env.register_contract(Some(&instance), FluxoraStream);
+    let tenant_stream = FluxoraStreamClient::new(&ctx.env, &instance);
+    tenant_stream.init_with_creator(&ctx.token_id, &tenant, &ctx.factory.address);
+
+    let sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&sender, &1_000_000);
+    TokenClient::new(&ctx.env, &ctx.token_id).approve(&sender, &instance, &1_000_000, &100_000);
+
+    let outsider = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.factory
+            .try_create_stream_on_instance(&instance, &sender, &ctx.params(&outsider)),
+        Err(Ok(FactoryError::RecipientNotAllowlisted))
+    );
+    let mut too_large = ctx.params(&ctx.recipient);
+    too_large.deposit_amount = MAX_DEPOSIT + 1;
+    assert_eq!(
+        ctx.factory
+            .try_create_stream_on_instance(&instance, &sender, &too_large),
+        Err(Ok(FactoryError::DepositExceedsCap))
+    );
+    assert_eq!(
+        ctx.factory.try_create_stream_on_instance(
+            &Address::generate(&ctx.env),
+            &sender,
+            &ctx.params(&ctx.recipient)
+        ),
+        Err(Ok(FactoryError::InstanceNotRegistered))
+    );
+
+    let stream_id =
+        ctx.factory
+            .create_stream_on_instance(&instance, &sender, &ctx.params(&ctx.recipient));
+    let (_, topics, _) = ctx.env.events().all().last().unwrap();
+    let topic = Symbol::try_from_val(&ctx.env, &topics.get(0).unwrap()).unwrap();
+    assert_eq!(topic, symbol_short!("inst_strm"));
+
+    // Funds sit in the tenant's instance; the shared contract is untouched.
+    assert_eq!(tenant_stream.get_stream_state(&stream_id).sender, sender);
+    assert_eq!(tenant_stream.get_total_liabilities(), 200_000);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.token_id).balance(&instance),
+        200_000
+    );
+    assert_eq!(ctx.default_stream.get_total_liabilities(), 0);
+    assert_eq!(ctx.factory.get_factory_stream_count(), 0);
+    assert_eq!(ctx.factory.get_instance_tenant(&instance), Some(tenant));
+}
+
+#[test]
+fn direct_instance_calls_are_rejected() {
+    let ctx = Ctx::setup();
+    ctx.pin_stub();
+    let tenant = Address::generate(&ctx.env);
+    let instance = ctx
+        .factory
+        .deploy_stream_instance(&tenant, &ctx.token_id, &ctx.salt(1));
+    ctx.env.register_contract(Some(&instance), FluxoraStream);
+    let tenant_stream = FluxoraStreamClient::new(&ctx.env, &instance);
+    tenant_stream.init_with_creator(&ctx.token_id, &tenant, &ctx.factory.address);
+    assert_eq!(
+        tenant_stream.get_stream_creator(),
+        Some(ctx.factory.address.clone())
+    );
+
+    let sender = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&sender, &100_000_000);
+    TokenClient::new(&ctx.env, &ctx.token_id).approve(&sender, &instance, &100_000_000, &100_000);
+
+    // The sender alone cannot create on the instance: the factory did not
+    // authorize the call, so the policy cannot be skipped.
+    let outsider = Address::generate(&ctx.env);
+    let params = ctx.params(&outsider);
+    ctx.env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &instance,
+            fn_name: "create_stream",
+            args: (&sender, params.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(tenant_stream.try_create_stream(&sender, &params).is_err());
+    assert_eq!(tenant_stream.get_stream_count(), 0);
+
+    // Through the factory, the same sender signature suffices: the factory
+    // authorizes its own call to the instance.
+    let params = ctx.params(&ctx.recipient);
+    ctx.env.mock_auths(&[MockAuth {
+        address: &sender,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.factory.address,
+            fn_name: "create_stream_on_instance",
+            args: (&instance, &sender, params.clone()).into_val(&ctx.env),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &instance,
+                fn_name: "create_stream",
+                args: (&sender, params.clone()).into_val(&ctx.env),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+    let stream_id = ctx
+        .factory
+        .create_stream_on_instance(&instance, &sender, &params);
+    assert_eq!(
+        tenant_stream.get_stream_state(&stream_id).recipient,
+        ctx.recipient
+    );
+}
@@ -0,0 +1,252 @@
+//! Integration tests for the factory stream records.
+//!
+//! Confirms:
+//! - `create_stream` and `create_streams` write a `FactoryStreamRecord` with
+//!   the creation time and the hash of the policy in force.
+//! - Records are listed per sender and per recipient, oldest first, with
+//!   pagination and counts.
+//! - The sender and recipient indexes are stored in fixed-size pages, and
+//!   appends and lookups that cross a page boundary keep creation order.
+//! - Records outlive a `set_stream_contract` migration and stay keyed by the
+//!   contract that holds the stream, even when stream IDs restart.
+
+use fluxora_factory::{FluxoraFactory, FluxoraFactoryClient, RECORD_PAGE_SIZE};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env,
+};
+
+const MAX_DEPOSIT: i128 = 10_000_000;
+const MIN_DURATION: u64 = 86_400;
+const LEDGER_TIMESTAMP: u64 = 1_000_000_000;
+
+struct Ctx {
+    env: Env,
+    factory: FluxoraFactoryClient<'static>,
+    admin: Address,
+    token_id: Address,
+    stream_contract: Address,
+    sender: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(LEDGER_TIMESTAMP);
+
+        let stream_contract = env.register_contract(None, FluxoraStream);
+        let factory_id = env.register_contract(None, FluxoraFactory);
+        let factory = FluxoraFactoryClient::new(&env, &factory_id);
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_000);
+
+        let ctx = Self {
+            env,
+            factory,
+            admin,
+            token_id,
+            stream_contract,
+            sender,
+        };
+        ctx.init_stream_contract(&ctx.stream_contract);
+        ctx.factory.init(
+            &ctx.admin,
+            &ctx.stream_contract,
+            &MAX_DEPOSIT,
+            &MIN_DURATION,
+        );
+        ctx
+    }
+
+    fn init_stream_contract(&self, stream_contract: &Address) {
+        FluxoraStreamClient::new(&self.env, stream_contract).init(&self.token_id, &self.admin);
+        TokenClient::new(&self.env, &self.token_id).approve(
+            &self.sender,
+            stream_contract,
+            &1_000_000_000,
+            &200_000,
+        );
+    }
+
+    fn recipient(&self) -> Address {
+        let recipient = Address::generate(&self.env);
+        self.factory.set_allowlist(&recipient, &true);
+        recipient
+    }
+
+    fn params(&self, recipient: &Address) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: recipient.clone(),
+            deposit_amount: 200_000,
+            rate_per_second: 1,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 200_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+}
+
+#[test]
+fn creation_writes_record_with_policy_hash() {
+    let ctx = Ctx::setup();
+    let recipient = ctx.recipient();
+    let hash_at_creation = ctx.factory.get_policy_hash();
+
+    let stream_id = ctx
+        .factory
+        .create_stream(&ctx.sender, &ctx.params(&recipient));
+    let record = ctx
+        .factory
+        .get_stream_record(&ctx.stream_contract, &stream_id)
+        .unwrap();
+    assert_eq!(record.stream_id, stream_id);
+    assert_eq!(record.stream_contract, ctx.stream_contract);
+    assert_eq!(record.sender, ctx.sender);
+    assert_eq!(record.recipient, recipient);
+    assert_eq!(record.created_at, LEDGER_TIMESTAMP);
+    assert_eq!(record.policy_hash, hash_at_creation);
+
+    // A policy change moves the current hash but not the stored one.
+    ctx.factory.set_cap(&(MAX_DEPOSIT / 2));
+    assert_ne!(ctx.factory.get_policy_hash(), hash_at_creation);
+    assert_eq!(
+        ctx.factory
+            .get_stream_record(&ctx.stream_contract, &stream_id)
+            .unwrap()
+            .policy_hash,
+        hash_at_creation
+    );
+    assert_eq!(
+        ctx.factory
+            .get_stream_record(&ctx.stream_contract, &(stream_id + 1)),
+        None
+    );
+}
+
+#[test]
+fn records_are_paginated_per_sender_and_recipient() {
+    let ctx = Ctx::setup();
+    let alice = ctx.recipient();
+    let bob = ctx.recipient();
+
+    let first = ctx.factory.create_stream(&ctx.sender, &ctx.params(&alice));
+    ctx.env.ledger().set_timestamp(LEDGER_TIMESTAMP + 10);
+    let batch = ctx.factory.create_streams(
+        &ctx.sender,
+        &vec![&ctx.env, ctx.params(&bob), ctx.params(&alice)],
+    );
+
+    assert_eq!(ctx.factory.get_sender_record_count(&ctx.sender), 3);
+    let page = ctx.factory.get_sender_records(&ctx.sender, &1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().stream_id, batch.get(0).unwrap());
+    assert_eq!(page.get(0).unwrap().created_at, LEDGER_TIMESTAMP + 10);
+    assert_eq!(page.get(1).unwrap().stream_id, batch.get(1).unwrap());
+    assert_eq!(
+        ctx.factory.get_sender_records(&ctx.sender, &3, &10).len(),
+        0
+    );
+
+    assert_eq!(ctx.factory.get_recipient_record_count(&alice), 2);
+    assert_eq!(ctx.factory.get_recipient_record_count(&bob), 1);
+    let alice_page = ctx.factory.get_recipient_records(&alice, &0, &1);
+    assert_eq!(alice_page.len(), 1);
+    assert_eq!(alice_page.get(0).unwrap().stream_id, first);
+    let bob_page = ctx.factory.get_recipient_records(&bob, &0, &10);
+    assert_eq!(bob_page.get(0).unwrap().recipient, bob);
+
+    let stranger = Address::generate(&ctx.env);
+    assert_eq!(ctx.factory.get_sender_record_count(&stranger), 0);
+    assert_eq!(
+        ctx.factory.get_recipient_records(&stranger, &0, &10).len(),
+        0
+    );
+}
+
+#[test]
+fn record_indexes_span_pages() {
+    let ctx = Ctx::setup();
+    ctx.env.budget().reset_unlimited();
+    let recipient = ctx.recipient();
+
+    // Leave the first page three short, then let the next batch spill over.
+    let mut ids = std::vec::Vec::new();
+    for batch_len in [RECORD_PAGE_SIZE - 3, 8] {
+        let mut batch = vec![&ctx.env];
+        for _ in 0..batch_len {
+            batch.push_back(ctx.params(&recipient));
+        }
+        ids.extend(ctx.factory.create_streams(&ctx.sender, &batch).iter());
+    }
+    let total = RECORD_PAGE_SIZE + 5;
+    assert_eq!(ctx.factory.get_sender_record_count(&ctx.sender), total);
+    assert_eq!(ctx.factory.get_recipient_record_count(&recipient), total);
+
+    let across = ctx
+        .factory
+        .get_sender_records(&ctx.sender, &(RECORD_PAGE_SIZE - 2), &4);
+    let across_ids: std::vec::Vec<u64> = across.iter().map(|r| r.stream_id).collect();
+    let start = (RECORD_PAGE_SIZE - 2) as usize;
+    assert_eq!(across_ids, ids[start..start + 4]);
+
+    let all = ctx.factory.get_recipient_records(&recipient, &0, &total);
+    let all_ids: std::vec::Vec<u64> = all.iter().map(|r| r.stream_id).collect();
+    assert_eq!(all_ids, ids);
+
+    let tail = ctx
+        .factory
+        .get_recipient_records(&recipient, &(total - 2), &10);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail.get(1).unwrap().stream_id, *ids.last().unwrap());
+}
+
+#[test]
+fn records_survive_stream_contract_migration() {
+    let ctx = Ctx::setup();
+    let recipient = ctx.recipient();
+    let old_id = ctx
+        .factory
+        .create_stream(&ctx.sender, &ctx.params(&recipient));
+
+    let new_contract = ctx.env.register_contract(None, FluxoraStream);
+    ctx.init_stream_contract(&new_contract);
+    ctx.factory.set_stream_contract(&new_contract);
+    let new_id = ctx
+        .factory
+        .create_stream(&ctx.sender, &ctx.params(&recipient));
+    // The new contract numbers its streams from scratch.
+    assert_eq!(new_id, old_id);
+
+    let old = ctx
+        .factory
+        .get_stream_record(&ctx.stream_contract, &old_id)
+        .unwrap();
+    let new = ctx
+        .factory
+        .get_stream_record(&new_contract, &new_id)
+        .unwrap();
+    assert_eq!(old.stream_contract, ctx.stream_contract);
+    assert_eq!(new.stream_contract, new_contract);
+    assert_ne!(old.policy_hash, new.policy_hash);
+
+    let records = ctx.factory.get_recipient_records(&recipient, &0, &10);
+    assert_eq!(records, vec![&ctx.env, old, new]);
+    assert_eq!(ctx.factory.get_sender_record_count(&ctx.sender), 2);
+}
@@ -3,7 +3,7 @@
 
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, xdr::FromXdr, Address,
-    Bytes, Env, IntoVal, Map, Symbol, Vec,
+    Bytes, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -24,61 +24,61 @@ const MAX_CALLDATA_BYTES: u32 = 4_096;
 /// non-executable. Default: 30 days.
 const MAX_PROPOSAL_AGE_SECONDS: u64 = 2_592_000;
 
-/// # Registry Migration Edge Cases
-///
-/// The governance contract assumes a stable registry of contract addresses.  It supports
-/// triggering a **registry migration** on a `FluxoraFactory` target via the
-/// [`FactorySetStreamContract`](CallData::FactorySetStreamContract) `CallData` variant.
-/// This causes the factory to point new stream creations at a different
-/// `FluxoraStream` instance while existing streams on the old instance continue
-/// running independently.
-///
-/// ## Governance-specific migration behaviour
-///
-/// - **Dispatch mechanism**: `FactorySetStreamContract(new_stream_contract)` encodes to XDR
-///   and is dispatched by `dispatch_call` → `env.invoke_contract(target, "set_stream_contract", ...)`.
-///   The target factory runs its full `set_stream_contract` logic including
-///   `validate_stream_contract`, registry TTL bump, and `stm_upd` event emission.
-/// - **Same-address no-op (E1)**: If `new_stream_contract == current_address`, the factory
-///   returns `Ok(())` with no side effects (no storage write, no event, no TTL bump).
-///   The governance proposal is still marked as executed — it succeeds without
-///   producing a `stm_upd` event.  Indexers must not assume every executed
-///   `FactorySetStreamContract` proposal emits `stm_upd`.
-/// - **Non-factory target (E6)**: If the proposal's `target` is not a `FluxoraFactory`
-///   (or does not expose `set_stream_contract`), the cross-contract call traps and the
-///   entire governance `execute()` transaction reverts.  The proposal remains
-///   unexecuted and can be re-submitted with the correct target address.
-/// - **Admin continuity**: The factory's admin stays unchanged by the migration.
-///   The governance contract must remain the factory admin (or be replaced via a
-///   separate `FactorySetAdmin` proposal) for post-migration governance operations.
-/// - **Registry TTL bump**: The factory bumps its stream-ID registry TTL during
-///   `set_stream_contract` via `bump_registry_ttl`, giving indexers a ~7 day window
-///   post-migration without requiring active writes.
-///
-/// ## Fresh-instance migration (new governance deployment)
-///
-/// When deploying a **new** governance contract instance alongside a registry migration:
-/// - The admin must call `set_admin` on the new instance before executing proposals.
-/// - Signer index is rebuilt from scratch on `init`; no state migrates automatically.
-/// - Proposal IDs restart at 0 on a fresh instance; off-chain tooling must handle
-///   ID discontinuity across contract versions.
-///
-/// ## Backward compatibility
-///
-/// All changes in this area are additive.  Existing `CallData` variants keep their
-/// discriminant values; existing proposals remain executable; existing event schemas
-/// are unchanged.  The same-address no-op (E1) was hardened in a previous release to
-/// suppress misleading `stm_upd` events — indexers should handle both cases.
-///
-/// ## See also
-///
-/// - [`docs/registry-migration.md`](../docs/registry-migration.md) — full edge-case
-///   catalogue, gas profile, and regression surface.
-/// - [`test_factory_set_stream_contract_dispatches_via_governance`] — governance
-///   dispatch integration test.
-/// - [`test_governance_registry_migration_same_address`] — same-address no-op test.
-/// - [`test_governance_registry_migration_non_factory_target_reverts`] — non-factory
-///   target revert test.
+// # Registry Migration Edge Cases
+//
+// The governance contract assumes a stable registry of contract addresses.  It supports
+// triggering a **registry migration** on a `FluxoraFactory` target via the
+// [`FactorySetStreamContract`](CallData::FactorySetStreamContract) `CallData` variant.
+// This causes the factory to point new stream creations at a different
+// `FluxoraStream` instance while existing streams on the old instance continue
+// running independently.
+//
+// ## Governance-specific migration behaviour
+//
+// - **Dispatch mechanism**: `FactorySetStreamContract(new_stream_contract)` encodes to XDR
+//   and is dispatched by `dispatch_call` → `env.invoke_contract(target, "set_stream_contract", ...)`.
+//   The target factory runs its full `set_stream_contract` logic including
+//   `validate_stream_contract`, registry TTL bump, and `stm_upd` event emission.
+// - **Same-address no-op (E1)**: If `new_stream_contract == current_address`, the factory
+//   returns `Ok(())` with no side effects (no storage write, no event, no TTL bump).
+//   The governance proposal is still marked as executed — it succeeds without
+//   producing a `stm_upd` event.  Indexers must not assume every executed
+//   `FactorySetStreamContract` proposal emits `stm_upd`.
+// - **Non-factory target (E6)**: If the proposal's `target` is not a `FluxoraFactory`
+//   (or does not expose `set_stream_contract`), the cross-contract call traps and the
+//   entire governance `execute()` transaction reverts.  The proposal remains
+//   unexecuted and can be re-submitted with the correct target address.
+// - **Admin continuity**: The factory's admin stays unchanged by the migration.
+//   The governance contract must remain the factory admin (or be replaced via a
+//   separate `FactorySetAdmin` proposal) for post-migration governance operations.
+// - **Registry TTL bump**: The factory bumps its stream-ID registry TTL during
+//   `set_stream_contract` via `bump_registry_ttl`, giving indexers a ~7 day window
+//   post-migration without requiring active writes.
+//
+// ## Fresh-instance migration (new governance deployment)
+//
+// When deploying a **new** governance contract instance alongside a registry migration:
+// - The admin must call `set_admin` on the new instance before executing proposals.
+// - Signer index is rebuilt from scratch on `init`; no state migrates automatically.
+// - Proposal IDs restart at 0 on a fresh instance; off-chain tooling must handle
+//   ID discontinuity across contract versions.
+//
+// ## Backward compatibility
+//
+// All changes in this area are additive.  Existing `CallData` variants keep their
+// discriminant values; existing proposals remain executable; existing event schemas
+// are unchanged.  The same-address no-op (E1) was hardened in a previous release to
+// suppress misleading `stm_upd` events — indexers should handle both cases.
+//
+// ## See also
+//
+// - [`docs/registry-migration.md`](../docs/registry-migration.md) — full edge-case
+//   catalogue, gas profile, and regression surface.
+// - [`test_factory_set_stream_contract_dispatches_via_governance`] — governance
+//   dispatch integration test.
+// - [`test_governance_registry_migration_same_address`] — same-address no-op test.
+// - [`test_governance_registry_migration_non_factory_target_reverts`] — non-factory
+//   target revert test.
 
 /// Maximum number of proposals that `get_proposals_by_id_range` will return in
 /// a single call.
@@ -169,6 +169,9 @@ pub enum GovernanceError {
     CalldataEmpty = 19,
     /// Proposal calldata failed to decode into a known `CallData` variant.
     InvalidCalldata = 20,
+    /// `GenericInvoke` targets a `(target, function)` pair that is not on the
+    /// governance call allowlist.
+    CallNotAllowed = 21,
 }
 
 /// Storage keys for the governance contract.
@@ -190,6 +193,9 @@ pub enum DataKey {
     SignerIndex,
     /// Per-proposal Map<Address, bool> for O(1) duplicate-approval detection (persistent).
     ProposalApprovalIdx(u32),
+    /// `(target, function)` pair callable through `CallData::GenericInvoke`
+    /// (persistent `bool`; absent means not allowed).
+    AllowedCall(Address, Symbol),
 }
 
 // ---------------------------------------------------------------------------
@@ -259,6 +265,68 @@ pub enum CallData {
     /// the `remove_signer_internal` early-return contract). QuorumWouldBreak
     /// is re-applied inside.
     GovRemoveSigner(Address),
+
+    // ---- stream contract protocol fee operations ----
+    // Appended after the governance-self variants to keep existing variants
+    // in place. Both require the governance contract to be the stream admin.
+    /// `set_protocol_fee(deposit_fee_bps, withdraw_fee_bps)`
+    StreamSetProtocolFee(u32, u32),
+    /// `set_treasury(treasury)`
+    StreamSetTreasury(Address),
+
+    // ---- generic governed calls ----
+    // Appended last. `GenericInvoke` reaches any entrypoint on the on-chain
+    // allowlist without a new variant; the allowlist itself only changes via
+    // `GovSetCallAllowed`, i.e. through quorum + timelock.
+    /// `function(args...)` on `target`. `target` must equal the proposal's
+    /// `target`, and `(target, function)` must be allowlisted, otherwise
+    /// dispatch fails with `InvalidCalldata` / `CallNotAllowed`.
+    GenericInvoke(Address, Symbol, Vec<Val>),
+    /// Allow (`true`) or disallow (`false`) `(target, function)` for
+    /// `GenericInvoke`. The proposal `target` is ignored, as for the other
+    /// governance-self operations.
+    GovSetCallAllowed(Address, Symbol, bool),
+
+    // ---- stream contract upgrade ----
+    /// `upgrade(new_wasm_hash)` — the proposal commits to the exact WASM hash,
+    /// so signers approve a specific build (compare it against
+    /// `wasm/checksums.sha256`) and the swap waits out the timelock.
+    /// Requires the governance contract to be the stream admin.
+    StreamUpgrade(BytesN<32>),
+
+    // ---- factory instance deployment ----
+    /// `set_stream_wasm_hash(wasm_hash)` — pins the `FluxoraStream` build that
+    /// `deploy_stream_instance` deploys for new tenants. Requires the governance
+    /// contract to be the factory admin.
+    FactorySetStreamWasmHash(BytesN<32>),
+
+    // ---- factory sender quotas ----
+    /// `set_sender_quota(window_secs, max_deposit, max_streams)` — a zero
+    /// window clears the default quota.
+    FactorySetSenderQuota(u64, i128, u32),
+    /// `set_sender_quota_override(sender, window_secs, max_deposit, max_streams)`
+    /// — a zero window exempts `sender`.
+    FactorySetSenderQuotaOverride(Address, u64, i128, u32),
+    /// `remove_sender_quota_override(sender)`
+    FactoryRemoveSenderQuotaOverride(Address),
+
+    // ---- factory creator roles ----
+    // The role is the `fluxora_factory::CreatorRole` discriminant:
+    // 0 = Creator, 1 = BatchCreator, 2 = TemplateManager.
+    /// `grant_creator_role(account, role, cap)`
+    FactoryGrantCreatorRole(Address, u32, i128),
+    /// `revoke_creator_role(account, role)`
+    FactoryRevokeCreatorRole(Address, u32),
+    /// `set_sender_roles_enforced(enabled)`
+    FactorySetSenderRolesEnforced(bool),
+
+    // ---- factory bulk and expiring allowlist ----
+    /// `set_allowlist_until(recipient, expires_at)`
+    FactorySetAllowlistUntil(Address, u64),
+    /// `set_allowlist_root(root)`
+    FactorySetAllowlistRoot(BytesN<32>),
+    /// `clear_allowlist_root()`
+    FactoryClearAllowlistRoot,
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
@@ -367,6 +435,55 @@ fn remove_signer_internal(env: &Env, signer: Address) -> Result<(), GovernanceEr
     Ok(())
 }
 
+/// Add or remove a `(target, function)` pair on the `GenericInvoke` allowlist.
+/// Reachable ONLY via `execute()` -> `dispatch_call` — see
+/// `set_threshold_internal` doc comment. The governance contract itself cannot
+/// be allowlisted, so its own configuration stays behind the `Gov*` variants.
+fn set_call_allowed_internal(
+    env: &Env,
+    target: Address,
+    function: Symbol,
+    allowed: bool,
+) -> Result<(), GovernanceError> {
+    if target == env.current_contract_address() {
+        return Err(GovernanceError::InvalidCalldata);
+    }
+    let key = DataKey::AllowedCall(target.clone(), function.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    env.events().publish(
+        (symbol_short!("call_alw"),),
+        CallAllowlistUpdated {
+            target,
+            function,
+            allowed,
+        },
+    );
+    Ok(())
+}
+
+fn is_call_allowed(env: &Env, target: &Address, function: &Symbol) -> bool {
+    let key = DataKey::AllowedCall(target.clone(), function.clone());
+    let allowed = env.storage().persistent().has(&key);
+    if allowed {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    allowed
+}
+
 /// Decode `calldata` bytes into a `CallData` variant and invoke the target.
 /// Called inside `execute` *after* the proposal has been marked executed (CEI).
 fn dispatch_call(env: &Env, target: &Address, calldata: &Bytes) -> Result<(), GovernanceError> {
@@ -458,6 +575,111 @@ fn dispatch_call(env: &Env, target: &Address, calldata: &Bytes) -> Result<(), Go
         CallData::GovRemoveSigner(signer) => {
             remove_signer_internal(env, signer)?;
         }
+        CallData::StreamSetProtocolFee(deposit_fee_bps, withdraw_fee_bps) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_protocol_fee"),
+                (deposit_fee_bps, withdraw_fee_bps).into_val(env),
+            );
+        }
+        CallData::StreamSetTreasury(treasury) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_treasury"),
+                (treasury,).into_val(env),
+            );
+        }
+        CallData::GenericInvoke(call_target, function, args) => {
+            // Signers approve the proposal's `target`; a payload pointing
+            // elsewhere is rejected rather than silently redirected.
+            if &call_target != target {
+                return Err(GovernanceError::InvalidCalldata);
+            }
+            if !is_call_allowed(env, &call_target, &function) {
+                return Err(GovernanceError::CallNotAllowed);
+            }
+            env.invoke_contract::<Val>(&call_target, &function, args);
+        }
+        CallData::GovSetCallAllowed(call_target, function, allowed) => {
+            set_call_allowed_internal(env, call_target, function, allowed)?;
+        }
+        CallData::StreamUpgrade(new_wasm_hash) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "upgrade"),
+                (new_wasm_hash,).into_val(env),
+            );
+        }
+        CallData::FactorySetStreamWasmHash(wasm_hash) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_stream_wasm_hash"),
+                (wasm_hash,).into_val(env),
+            );
+        }
+        CallData::FactorySetSenderQuota(window_secs, max_deposit, max_streams) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_sender_quota"),
+                (window_secs, max_deposit, max_streams).into_val(env),
+            );
+        }
+        CallData::FactorySetSenderQuotaOverride(sender, window_secs, max_deposit, max_streams) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_sender_quota_override"),
+                (sender, window_secs, max_deposit, max_streams).into_val(env),
+            );
+        }
+        CallData::FactoryRemoveSenderQuotaOverride(sender) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "remove_sender_quota_override"),
+                (sender,).into_val(env),
+            );
+        }
+        CallData::FactoryGrantCreatorRole(account, role, cap) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "grant_creator_role"),
+                (account, role, cap).into_val(env),
+            );
+        }
+        CallData::FactoryRevokeCreatorRole(account, role) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "revoke_creator_role"),
+                (account, role).into_val(env),
+            );
+        }
+        CallData::FactorySetSenderRolesEnforced(enabled) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_sender_roles_enforced"),
+                (enabled,).into_val(env),
+            );
+        }
+        CallData::FactorySetAllowlistUntil(recipient, expires_at) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_allowlist_until"),
+                (recipient, expires_at).into_val(env),
+            );
+        }
+        CallData::FactorySetAllowlistRoot(root) => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "set_allowlist_root"),
+                (root,).into_val(env),
+            );
+        }
+        CallData::FactoryClearAllowlistRoot => {
+            env.invoke_contract::<()>(
+                target,
+                &Symbol::new(env, "clear_allowlist_root"),
+                Vec::new(env),
+            );
+        }
     }
     Ok(())
 }
@@ -591,6 +813,16 @@ pub struct AdminChanged {
     pub new: Address,
 }
 
+/// Emitted when a `GovSetCallAllowed` proposal changes the `GenericInvoke`
+/// allowlist.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CallAllowlistUpdated {
+    pub target: Address,
+    pub function: Symbol,
+    pub allowed: bool,
+}
+
 // ---------------------------------------------------------------------------
 // Storage helpers
 // ---------------------------------------------------------------------------
@@ -1356,6 +1588,12 @@ impl FluxoraGovernance {
             .unwrap_or(false)
     }
 
+    /// Return whether `(target, function)` may be called through
+    /// `CallData::GenericInvoke`.
+    pub fn is_call_allowed(env: Env, target: Address, function: Symbol) -> bool {
+        is_call_allowed(&env, &target, &function)
+    }
+
     /// Return a bounded page of proposals whose IDs fall in `[start_id, start_id + limit)`.
     ///
     /// This mirrors `FluxoraStream::get_streams_by_id_range` and is the primary
@@ -1537,6 +1775,142 @@ mod tests {
         pub fn get_stream_contract(env: Env) -> Option<Address> {
             env.storage().instance().get(&symbol_short!("strm_ctr"))
         }
+
+        pub fn set_stream_wasm_hash(env: Env, wasm_hash: BytesN<32>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("wasm"), &wasm_hash);
+        }
+
+        pub fn stream_wasm_hash(env: Env) -> Option<BytesN<32>> {
+            env.storage().instance().get(&symbol_short!("wasm"))
+        }
+
+        pub fn set_sender_quota(env: Env, window_secs: u64, max_deposit: i128, max_streams: u32) {
+            env.storage().instance().set(
+                &symbol_short!("quota"),
+                &(window_secs, max_deposit, max_streams),
+            );
+        }
+
+        pub fn set_sender_quota_override(
+            env: Env,
+            sender: Address,
+            window_secs: u64,
+            max_deposit: i128,
+            max_streams: u32,
+        ) {
+            env.storage()
+                .instance()
+                .set(&sender, &(window_secs, max_deposit, max_streams));
+        }
+
+        pub fn remove_sender_quota_override(env: Env, sender: Address) {
+            env.storage().instance().remove(&sender);
+        }
+
+        pub fn grant_creator_role(env: Env, account: Address, role: u32, cap: i128) {
+            env.storage().instance().set(&(account, role), &cap);
+        }
+
+        pub fn revoke_creator_role(env: Env, account: Address, role: u32) {
+            env.storage().instance().remove(&(account, role));
+        }
+
+        pub fn set_sender_roles_enforced(env: Env, enabled: bool) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("roles"), &enabled);
+        }
+
+        pub fn creator_role_cap(env: Env, account: Address, role: u32) -> Option<i128> {
+            env.storage().instance().get(&(account, role))
+        }
+
+        pub fn sender_roles_enforced(env: Env) -> bool {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("roles"))
+                .unwrap_or(false)
+        }
+
+        pub fn set_allowlist_until(env: Env, recipient: Address, expires_at: u64) {
+            env.storage().instance().set(&recipient, &expires_at);
+        }
+
+        pub fn set_allowlist_root(env: Env, root: BytesN<32>) {
+            env.storage().instance().set(&symbol_short!("root"), &root);
+        }
+
+        pub fn clear_allowlist_root(env: Env) {
+            env.storage().instance().remove(&symbol_short!("root"));
+        }
+
+        pub fn allowlist_expiry(env: Env, recipient: Address) -> Option<u64> {
+            env.storage().instance().get(&recipient)
+        }
+
+        pub fn allowlist_root(env: Env) -> Option<BytesN<32>> {
+            env.storage().instance().get(&symbol_short!("root"))
+        }
+
+        pub fn sender_quota(env: Env) -> Option<(u64, i128, u32)> {
+            env.storage().instance().get(&symbol_short!("quota"))
+        }
+
+        pub fn sender_quota_override(env: Env, sender: Address) -> Option<(u64, i128, u32)> {
+            env.storage().instance().get(&sender)
+        }
+    }
+
+    #[contract]
+    pub struct MockStreamTarget;
+
+    #[contractimpl]
+    impl MockStreamTarget {
+        pub fn set_protocol_fee(env: Env, deposit_fee_bps: u32, withdraw_fee_bps: u32) {
+            env.storage().instance().set(
+                &symbol_short!("fee_bps"),
+                &(deposit_fee_bps, withdraw_fee_bps),
+            );
+        }
+
+        pub fn set_treasury(env: Env, treasury: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("treasury"), &treasury);
+        }
+
+        pub fn fee_bps(env: Env) -> Option<(u32, u32)> {
+            env.storage().instance().get(&symbol_short!("fee_bps"))
+        }
+
+        pub fn treasury(env: Env) -> Option<Address> {
+            env.storage().instance().get(&symbol_short!("treasury"))
+        }
+
+        pub fn set_contract_paused(env: Env, paused: bool) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ct_pause"), &paused);
+        }
+
+        pub fn contract_paused(env: Env) -> bool {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("ct_pause"))
+                .unwrap_or(false)
+        }
+
+        pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("wasm"), &new_wasm_hash);
+        }
+
+        pub fn wasm_hash(env: Env) -> Option<BytesN<32>> {
+            env.storage().instance().get(&symbol_short!("wasm"))
+        }
     }
 
     const TIMELOCK: u64 = 172_800;
@@ -1724,6 +2098,178 @@ mod tests {
         assert_eq!(target.max_rate(), Some(1_000));
     }
 
+    #[test]
+    fn test_stream_protocol_fee_and_treasury_calldata_dispatch() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockStreamTarget);
+        let target = MockStreamTargetClient::new(&ctx.env, &target_id);
+        let treasury = Address::generate(&ctx.env);
+
+        let fee = CallData::StreamSetProtocolFee(25, 10).to_xdr(&ctx.env);
+        let fee_id = ctx.client.propose(&ctx.signer_a, &target_id, &fee);
+        let set_treasury = CallData::StreamSetTreasury(treasury.clone()).to_xdr(&ctx.env);
+        let treasury_id = ctx.client.propose(&ctx.signer_a, &target_id, &set_treasury);
+        for id in [fee_id, treasury_id] {
+            ctx.client.approve(&ctx.signer_a, &id);
+            ctx.client.approve(&ctx.signer_b, &id);
+        }
+        ctx.env.ledger().set_timestamp(1_000_000 + TIMELOCK + 1);
+
+        let executor = Address::generate(&ctx.env);
+        ctx.client.execute(&executor, &fee_id);
+        ctx.client.execute(&executor, &treasury_id);
+
+        assert_eq!(target.fee_bps(), Some((25, 10)));
+        assert_eq!(target.treasury(), Some(treasury));
+    }
+
+    /// Propose `calldata` against `target`, reach quorum and wait out the timelock.
+    fn pass_proposal(ctx: &Ctx, target: &Address, calldata: &Bytes) -> u32 {
+        let id = ctx.client.propose(&ctx.signer_a, target, calldata);
+        ctx.client.approve(&ctx.signer_a, &id);
+        ctx.client.approve(&ctx.signer_b, &id);
+        ctx.env
+            .ledger()
+            .set_timestamp(ctx.env.ledger().timestamp() + TIMELOCK + 1);
+        id
+    }
+
+    #[test]
+    fn test_stream_upgrade_waits_for_timelock_and_pins_hash() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockStreamTarget);
+        let target = MockStreamTargetClient::new(&ctx.env, &target_id);
+        let hash = BytesN::from_array(&ctx.env, &[7u8; 32]);
+        let calldata = CallData::StreamUpgrade(hash.clone()).to_xdr(&ctx.env);
+
+        let id = ctx.client.propose(&ctx.signer_a, &target_id, &calldata);
+        ctx.client.approve(&ctx.signer_a, &id);
+        ctx.client.approve(&ctx.signer_b, &id);
+        let executor = Address::generate(&ctx.env);
+        ctx.env.ledger().set_timestamp(1_000_000 + TIMELOCK - 1);
+        assert_eq!(
+            ctx.client.try_execute(&executor, &id),
+            Err(Ok(GovernanceError::TimelockNotElapsed))
+        );
+        assert_eq!(target.wasm_hash(), None);
+
+        ctx.env.ledger().set_timestamp(1_000_000 + TIMELOCK);
+        ctx.client.execute(&executor, &id);
+        assert_eq!(target.wasm_hash(), Some(hash.clone()));
+        match CallData::from_xdr(&ctx.env, &ctx.client.get_proposal(&id).calldata) {
+            Ok(CallData::StreamUpgrade(stored)) => assert_eq!(stored, hash),
+            _ => panic!("proposal must store the committed StreamUpgrade hash"),
+        }
+    }
+
+    #[test]
+    fn test_generic_invoke_requires_allowlisted_call() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockStreamTarget);
+        let target = MockStreamTargetClient::new(&ctx.env, &target_id);
+        let function = Symbol::new(&ctx.env, "set_contract_paused");
+        let invoke = CallData::GenericInvoke(
+            target_id.clone(),
+            function.clone(),
+            vec![&ctx.env, true.into_val(&ctx.env)],
+        )
+        .to_xdr(&ctx.env);
+        let executor = Address::generate(&ctx.env);
+
+        let blocked = pass_proposal(&ctx, &target_id, &invoke);
+        assert_eq!(
+            ctx.client.try_execute(&executor, &blocked),
+            Err(Ok(GovernanceError::CallNotAllowed))
+        );
+        assert!(!ctx.client.get_proposal(&blocked).executed);
+
+        let allow =
+            CallData::GovSetCallAllowed(target_id.clone(), function.clone(), true).to_xdr(&ctx.env);
+        let allow_id = pass_proposal(&ctx, &ctx.dummy_target(), &allow);
+        ctx.client.execute(&executor, &allow_id);
+        let (topic, data) = nth_last_contract_event_with_topic(
+            &ctx.env,
+            &ctx.contract_id,
+            symbol_short!("call_alw"),
+        );
+        assert_eq!(topic, symbol_short!("call_alw"));
+        let update = CallAllowlistUpdated::try_from_val(&ctx.env, &data).unwrap();
+        assert_eq!(update.target, target_id);
+        assert!(update.allowed);
+        assert!(ctx.client.is_call_allowed(&target_id, &function));
+
+        // The earlier proposal now dispatches; its approvals are still valid.
+        ctx.client.execute(&executor, &blocked);
+        assert!(target.contract_paused());
+    }
+
+    #[test]
+    fn test_generic_invoke_rejects_target_mismatch_and_revoked_calls() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockStreamTarget);
+        let function = Symbol::new(&ctx.env, "set_contract_paused");
+        let executor = Address::generate(&ctx.env);
+
+        let allow =
+            CallData::GovSetCallAllowed(target_id.clone(), function.clone(), true).to_xdr(&ctx.env);
+        let allow_id = pass_proposal(&ctx, &ctx.dummy_target(), &allow);
+        ctx.client.execute(&executor, &allow_id);
+
+        // Payload target differs from the approved proposal target.
+        let invoke = CallData::GenericInvoke(
+            target_id.clone(),
+            function.clone(),
+            vec![&ctx.env, true.into_val(&ctx.env)],
+        )
+        .to_xdr(&ctx.env);
+        let mismatch = pass_proposal(&ctx, &ctx.dummy_target(), &invoke);
+        assert_eq!(
+            ctx.client.try_execute(&executor, &mismatch),
+            Err(Ok(GovernanceError::InvalidCalldata))
+        );
+
+        let revoke = CallData::GovSetCallAllowed(target_id.clone(), function.clone(), false)
+            .to_xdr(&ctx.env);
+        let revoke_id = pass_proposal(&ctx, &ctx.dummy_target(), &revoke);
+        ctx.client.execute(&executor, &revoke_id);
+        assert!(!ctx.client.is_call_allowed(&target_id, &function));
+
+        let revoked = pass_proposal(&ctx, &target_id, &invoke);
+        assert_eq!(
+            ctx.client.try_execute(&executor, &revoked),
+            Err(Ok(GovernanceError::CallNotAllowed))
+        );
+    }
+
+    #[test]
+    fn test_call_allowlist_rejects_governance_itself() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let allow_self = CallData::GovSetCallAllowed(
+            ctx.contract_id.clone(),
+            Symbol::new(&ctx.env, "set_admin"),
+            true,
+        )
+        .to_xdr(&ctx.env);
+        let id = pass_proposal(&ctx, &ctx.dummy_target(), &allow_self);
+        assert_eq!(
+            ctx.client.try_execute(&Address::generate(&ctx.env), &id),
+            Err(Ok(GovernanceError::InvalidCalldata))
+        );
+        assert!(!ctx
+            .client
+            .is_call_allowed(&ctx.contract_id, &Symbol::new(&ctx.env, "set_admin")));
+    }
+
     #[test]
     fn test_factory_paused_calldata_dispatches() {
         use soroban_sdk::xdr::ToXdr;
@@ -1744,6 +2290,107 @@ mod tests {
         assert!(target.paused());
     }
 
+    #[test]
+    fn test_factory_stream_wasm_hash_calldata_dispatches() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockFactoryTarget);
+        let target = MockFactoryTargetClient::new(&ctx.env, &target_id);
+        let hash = BytesN::from_array(&ctx.env, &[7u8; 32]);
+        let calldata = CallData::FactorySetStreamWasmHash(hash.clone()).to_xdr(&ctx.env);
+
+        let id = ctx.client.propose(&ctx.signer_a, &target_id, &calldata);
+        ctx.client.approve(&ctx.signer_a, &id);
+        ctx.client.approve(&ctx.signer_b, &id);
+        ctx.env.ledger().set_timestamp(1_000_000 + TIMELOCK + 1);
+
+        ctx.client.execute(&Address::generate(&ctx.env), &id);
+
+        assert_eq!(target.stream_wasm_hash(), Some(hash));
+    }
+
+    #[test]
+    fn test_factory_sender_quota_calldata_dispatches() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockFactoryTarget);
+        let target = MockFactoryTargetClient::new(&ctx.env, &target_id);
+        let sender = Address::generate(&ctx.env);
+        let executor = Address::generate(&ctx.env);
+
+        let quota = CallData::FactorySetSenderQuota(86_400, 5_000, 3).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &quota));
+        let quota_override =
+            CallData::FactorySetSenderQuotaOverride(sender.clone(), 3_600, 100, 1).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &quota_override));
+        assert_eq!(target.sender_quota(), Some((86_400, 5_000, 3)));
+        assert_eq!(target.sender_quota_override(&sender), Some((3_600, 100, 1)));
+
+        let remove_override =
+            CallData::FactoryRemoveSenderQuotaOverride(sender.clone()).to_xdr(&ctx.env);
+        ctx.client.execute(
+            &executor,
+            &pass_proposal(&ctx, &target_id, &remove_override),
+        );
+        assert_eq!(target.sender_quota_override(&sender), None);
+    }
+
+    #[test]
+    fn test_factory_creator_role_calldata_dispatches() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockFactoryTarget);
+        let target = MockFactoryTargetClient::new(&ctx.env, &target_id);
+        let operator = Address::generate(&ctx.env);
+        let executor = Address::generate(&ctx.env);
+
+        let grant = CallData::FactoryGrantCreatorRole(operator.clone(), 0, 50_000).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &grant));
+        let enforce = CallData::FactorySetSenderRolesEnforced(true).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &enforce));
+        assert_eq!(target.creator_role_cap(&operator, &0), Some(50_000));
+        assert!(target.sender_roles_enforced());
+
+        let revoke = CallData::FactoryRevokeCreatorRole(operator.clone(), 0).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &revoke));
+        assert_eq!(target.creator_role_cap(&operator, &0), None);
+    }
+
+    #[test]
+    fn test_factory_allowlist_calldata_dispatches() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let ctx = Ctx::setup();
+        let target_id = ctx.env.register_contract(None, MockFactoryTarget);
+        let target = MockFactoryTargetClient::new(&ctx.env, &target_id);
+        let contractor = Address::generate(&ctx.env);
+        let root = BytesN::from_array(&ctx.env, &[9u8; 32]);
+        let executor = Address::generate(&ctx.env);
+
+        let allow_until =
+            CallData::FactorySetAllowlistUntil(contractor.clone(), 5_000_000).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &allow_until));
+        let set_root = CallData::FactorySetAllowlistRoot(root.clone()).to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &set_root));
+        assert_eq!(target.allowlist_expiry(&contractor), Some(5_000_000));
+        assert_eq!(target.allowlist_root(), Some(root));
+
+        let clear_root = CallData::FactoryClearAllowlistRoot.to_xdr(&ctx.env);
+        ctx.client
+            .execute(&executor, &pass_proposal(&ctx, &target_id, &clear_root));
+        assert_eq!(target.allowlist_root(), None);
+    }
+
     #[test]
     fn test_factory_set_stream_contract_dispatches_via_governance() {
         use soroban_sdk::xdr::ToXdr;
@@ -2041,6 +2688,19 @@ mod tests {
             CallData::FactorySetMinDuration(86400),
             CallData::FactorySetAllowlist(Address::generate(&ctx.env), true),
             CallData::FactorySetStreamContract(Address::generate(&ctx.env)),
+            CallData::StreamSetProtocolFee(25, 10),
+            CallData::StreamSetTreasury(Address::generate(&ctx.env)),
+            CallData::GenericInvoke(
+                Address::generate(&ctx.env),
+                Symbol::new(&ctx.env, "sweep_excess"),
+                vec![&ctx.env, Address::generate(&ctx.env).into_val(&ctx.env)],
+            ),
+            CallData::GovSetCallAllowed(
+                Address::generate(&ctx.env),
+                Symbol::new(&ctx.env, "upgrade"),
+                true,
+            ),
+            CallData::StreamUpgrade(BytesN::from_array(&ctx.env, &[9u8; 32])),
         ];
 
         for var in variants.iter() {
@@ -2079,6 +2739,32 @@ mod tests {
                 ) => {
                     assert_eq!(a1, a2);
                 }
+                (
+                    CallData::StreamSetProtocolFee(d1, w1),
+                    CallData::StreamSetProtocolFee(d2, w2),
+                ) => {
+                    assert_eq!(d1, d2);
+                    assert_eq!(w1, w2);
+                }
+                (CallData::StreamSetTreasury(t1), CallData::StreamSetTreasury(t2)) => {
+                    assert_eq!(t1, t2)
+                }
+                (CallData::GenericInvoke(t1, f1, a1), CallData::GenericInvoke(t2, f2, a2)) => {
+                    assert_eq!(t1, t2);
+                    assert_eq!(f1, f2);
+                    assert_eq!(a1.len(), a2.len());
+                }
+                (
+                    CallData::GovSetCallAllowed(t1, f1, b1),
+                    CallData::GovSetCallAllowed(t2, f2, b2),
+                ) => {
+                    assert_eq!(t1, t2);
+                    assert_eq!(f1, f2);
+                    assert_eq!(b1, b2);
+                }
+                (CallData::StreamUpgrade(h1), CallData::StreamUpgrade(h2)) => {
+                    assert_eq!(h1, h2)
+                }
                 _ => panic!("Variant mismatch during CallData round-trip test"),
             }
         }
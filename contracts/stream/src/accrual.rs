@@ -28,6 +28,10 @@ pub const MAX_RATE_SEGMENTS: u32 = 256;
 /// - `duration_secs > 0`
 /// - The cumulative sum `∑(rate × duration)` across all segments fits in
 ///   `i128` without wrapping.
+///
+/// Segments are accepted at the ABI boundary by `create_stream_with_schedule`
+/// and persisted in packed form (see [`pack_rate_segment`]).
+#[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RateSegment {
     /// Streaming rate for this segment, in base token units per second.
@@ -72,6 +76,20 @@ pub struct RateSegment {
 ///   but a multi-segment schedule with negative rates could produce
 ///   incorrect cumulative totals).
 pub fn validate_rate_schedule(segments: &[RateSegment]) -> Result<(), ContractError> {
+    validate_rate_segments(segments.iter().copied())
+}
+
+/// Iterator form of [`validate_rate_schedule`].
+///
+/// Lets the contract validate a `soroban_sdk::Vec<RateSegment>` received at
+/// the ABI boundary without first copying it into a slice. Performs exactly
+/// the same checks, in the same order, and returns the same errors.
+pub fn validate_rate_segments<I>(segments: I) -> Result<(), ContractError>
+where
+    I: IntoIterator<Item = RateSegment>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let segments = segments.into_iter();
     if segments.len() > MAX_RATE_SEGMENTS as usize {
         return Err(ContractError::RateScheduleTooManySegments);
     }
@@ -445,6 +463,44 @@ pub fn assert_ledger_time_monotonic(prev_ts: u64, current_ts: u64) -> Result<(),
     Ok(())
 }
 
+/// Worst-case expected drift, in seconds, between a cliff timestamp and the
+/// first ledger whose close time reaches it.
+///
+/// Stellar ledger close times average 5-6 seconds but are not fixed to an
+/// exact cadence. This constant is observability only: it never relaxes the
+/// strict `now >= cliff_time` gate used by accrual and withdrawal math.
+pub const MAX_LEDGER_CLOSE_SKEW_SECS: u64 = 10;
+
+/// Classification of the current time against a stream's `cliff_time`.
+///
+/// Returned by `get_cliff_status`; see [`cliff_status`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CliffStatus {
+    /// `now < cliff_time - MAX_LEDGER_CLOSE_SKEW_SECS`.
+    Pending,
+    /// `cliff_time - MAX_LEDGER_CLOSE_SKEW_SECS <= now < cliff_time`.
+    /// Withdrawal is still blocked.
+    WithinSkewWindow,
+    /// `now >= cliff_time` — the same gate used by accrual math.
+    Unlocked,
+}
+
+/// Pure classifier behind `get_cliff_status`.
+///
+/// Uses the exact `now >= cliff_time` comparison of
+/// [`calculate_accrued_amount_checkpointed`] for `Unlocked`, so this view can
+/// never disagree with withdrawable amounts.
+pub fn cliff_status(now: u64, cliff_time: u64) -> CliffStatus {
+    if now >= cliff_time {
+        CliffStatus::Unlocked
+    } else if now >= cliff_time.saturating_sub(MAX_LEDGER_CLOSE_SKEW_SECS) {
+        CliffStatus::WithinSkewWindow
+    } else {
+        CliffStatus::Pending
+    }
+}
+
 /// Computes accrued stream amount without relying on Soroban environment state.
 ///
 /// This helper is intentionally pure to make the core vesting math easy to unit test.
@@ -650,6 +706,13 @@ pub struct CheckpointState {
 ///
 /// See `docs/streaming.md#cliffonly-accrual` for worked `CliffOnly` examples
 /// showing the pre-cliff zero result and the full-deposit lump-sum unlock.
+///
+/// `Piecewise` streams carry their rate in a segment schedule rather than in
+/// `rate_per_second`; the contract evaluates them with
+/// [`calculate_piecewise_accrued_amount`]. Called directly for a `Piecewise`
+/// state, this function conservatively returns the clamped checkpoint; the same
+/// holds for `Tranched` streams ([`calculate_tranched_accrued_amount`]) and
+/// the curve kinds ([`calculate_curve_accrued_amount`]).
 pub fn calculate_accrued_amount_checkpointed(
     state: CheckpointState,
     rate_per_second: i128,
@@ -673,6 +736,20 @@ pub fn calculate_accrued_amount_checkpointed(
         return accrued.min(state.deposit_amount).max(0);
     }
 
+    if matches!(
+        state.kind,
+        StreamKind::Piecewise
+            | StreamKind::Tranched
+            | StreamKind::ExponentialDecay
+            | StreamKind::Quadratic
+    ) {
+        // The schedule or curve inputs live outside `CheckpointState`; the
+        // contract routes these kinds through `calculate_piecewise_accrued_amount`,
+        // `calculate_tranched_accrued_amount` and `calculate_curve_accrued_amount`.
+        // Without the schedule, only the locked-in checkpoint is provably owed.
+        return state.checkpointed_amount.min(state.deposit_amount).max(0);
+    }
+
     if rate_per_second < 0 {
         return 0;
     }
@@ -713,6 +790,423 @@ pub fn calculate_accrued_amount_checkpointed(
         .max(0)
 }
 
+/// Accrual for a `Piecewise` stream driven by a rate-segment schedule.
+///
+/// Segments are laid end-to-end starting at `start_time`; segment `i` streams
+/// at `segments[i].rate` for `segments[i].duration_secs` seconds. The accrued
+/// amount at `now` is the sum of `rate × overlap` over every segment, where
+/// `overlap` is the part of the segment that lies before `now`.
+///
+/// # Behaviour
+/// - Returns `0` before `cliff_time` (the cliff gates visibility exactly as for
+///   `Linear` streams; accrual still counts from `start_time`).
+/// - Time after the last segment contributes nothing, so the result is constant
+///   once the schedule is exhausted.
+/// - The result is clamped to `[0, deposit_amount]`.
+///
+/// # Safety invariants
+/// 1. Monotonically non-decreasing in `now` (every segment rate is `>= 0` after
+///    [`validate_rate_schedule`]; negative rates are ignored defensively).
+/// 2. `accrued <= deposit_amount` for all `now`.
+/// 3. Checked arithmetic: a multiplication or addition overflow clamps to
+///    `deposit_amount` instead of wrapping.
+pub fn calculate_piecewise_accrued_amount<I>(
+    start_time: u64,
+    cliff_time: u64,
+    deposit_amount: i128,
+    segments: I,
+    now: u64,
+) -> i128
+where
+    I: IntoIterator<Item = RateSegment>,
+{
+    if now < cliff_time || deposit_amount <= 0 {
+        return 0;
+    }
+
+    let mut accrued: i128 = 0;
+    let mut segment_start = start_time;
+
+    for segment in segments {
+        if now <= segment_start {
+            break;
+        }
+        let segment_end = segment_start.saturating_add(segment.duration_secs);
+        let overlap = (now.min(segment_end) - segment_start) as i128;
+        let rate = segment.rate.max(0);
+
+        accrued = match overlap
+            .checked_mul(rate)
+            .and_then(|added| accrued.checked_add(added))
+        {
+            Some(total) => total,
+            None => return deposit_amount,
+        };
+        if accrued >= deposit_amount {
+            return deposit_amount;
+        }
+        segment_start = segment_end;
+    }
+
+    accrued.min(deposit_amount).max(0)
+}
+
+/// Maximum number of tranches in a `Tranched` unlock schedule.
+///
+/// Same rationale as [`MAX_RATE_SEGMENTS`]: bounds storage and the linear
+/// scans in validation and accrual. Monthly unlocks over four years fit
+/// comfortably.
+pub const MAX_TRANCHES: u32 = 64;
+
+/// One unlock point of a `Tranched` stream.
+///
+/// At `unlock_time` the recipient's vested total becomes `cumulative_amount`
+/// (a running total, not the size of this tranche). Between unlock points the
+/// vested total is flat.
+///
+/// # Invariants (enforced by [`validate_tranches`])
+/// - `unlock_time` strictly increasing across the schedule
+/// - `cumulative_amount` strictly increasing and `> 0`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Tranche {
+    /// Ledger timestamp at which this tranche unlocks.
+    pub unlock_time: u64,
+    /// Total amount vested once this tranche has unlocked.
+    pub cumulative_amount: i128,
+}
+
+/// Validates a tranche schedule, analogous to [`validate_rate_segments`].
+///
+/// # Checks performed
+/// 1. **Tranche count** – non-empty and at most [`MAX_TRANCHES`].
+/// 2. **Ordering** – `unlock_time` strictly increasing.
+/// 3. **Amounts** – `cumulative_amount` positive and strictly increasing, so
+///    every tranche unlocks something.
+///
+/// # Returns
+/// - `Err(ContractError::RateScheduleTooManySegments)` above [`MAX_TRANCHES`].
+/// - `Err(ContractError::RateScheduleInvalid)` for an empty schedule or any
+///   other violation.
+pub fn validate_tranches<I>(tranches: I) -> Result<(), ContractError>
+where
+    I: IntoIterator<Item = Tranche>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let tranches = tranches.into_iter();
+    if tranches.len() > MAX_TRANCHES as usize {
+        return Err(ContractError::RateScheduleTooManySegments);
+    }
+    if tranches.len() == 0 {
+        return Err(ContractError::RateScheduleInvalid);
+    }
+
+    let mut previous: Option<Tranche> = None;
+    for tranche in tranches {
+        let floor = previous.map_or(0, |p| p.cumulative_amount);
+        if tranche.cumulative_amount <= floor {
+            return Err(ContractError::RateScheduleInvalid);
+        }
+        if previous.is_some_and(|p| tranche.unlock_time <= p.unlock_time) {
+            return Err(ContractError::RateScheduleInvalid);
+        }
+        previous = Some(tranche);
+    }
+
+    Ok(())
+}
+
+/// Accrual for a `Tranched` stream.
+///
+/// Returns the `cumulative_amount` of the last tranche whose `unlock_time` is
+/// `<= now` (the same inclusive gate as the cliff), or `0` before the first
+/// unlock or before `cliff_time`. The result is clamped to
+/// `[0, deposit_amount]` and is monotonically non-decreasing in `now` for a
+/// schedule that passed [`validate_tranches`].
+pub fn calculate_tranched_accrued_amount<I>(
+    cliff_time: u64,
+    deposit_amount: i128,
+    tranches: I,
+    now: u64,
+) -> i128
+where
+    I: IntoIterator<Item = Tranche>,
+{
+    if now < cliff_time || deposit_amount <= 0 {
+        return 0;
+    }
+
+    let mut accrued: i128 = 0;
+    for tranche in tranches {
+        if tranche.unlock_time > now {
+            break;
+        }
+        accrued = accrued.max(tranche.cumulative_amount);
+    }
+
+    accrued.min(deposit_amount).max(0)
+}
+
+/// First tranche that has not unlocked at `now`, if any.
+pub fn next_tranche<I>(tranches: I, now: u64) -> Option<Tranche>
+where
+    I: IntoIterator<Item = Tranche>,
+{
+    tranches.into_iter().find(|t| t.unlock_time > now)
+}
+
+/// Fixed-point `1.0` for curve accrual.
+///
+/// Curve kinds compute the vested share of the deposit as a fraction in
+/// `[0, CURVE_ONE]` before applying it, so the deposit itself is never
+/// multiplied by a time value.
+pub const CURVE_ONE: u128 = 1 << CURVE_FRACTION_BITS;
+
+/// Binary precision of [`CURVE_ONE`].
+pub const CURVE_FRACTION_BITS: u32 = 60;
+
+/// Longest accepted half-life of an `ExponentialDecay` stream (~136 years).
+///
+/// Keeps the decay integral below `2^97`, well inside `u128`.
+pub const MAX_DECAY_HALF_LIFE_SECS: u64 = u32::MAX as u64;
+
+/// Number of half-lives after which an `ExponentialDecay` rate is treated as
+/// zero. At that point the rate has fallen to `2^-64` of its starting value.
+const MAX_DECAY_HALVINGS: u64 = 64;
+
+/// `floor(num / den)` as a [`CURVE_ONE`] fraction, for `num <= den`.
+///
+/// Both operands are shifted right until `den` fits in 64 bits, so the scaled
+/// product stays below `2^124`. The result is non-decreasing in `num` and is
+/// exactly `CURVE_ONE` when `num == den`.
+fn curve_ratio(num: u128, den: u128) -> u128 {
+    if den == 0 || num >= den {
+        return CURVE_ONE;
+    }
+    let shift = (u128::BITS - den.leading_zeros()).saturating_sub(64);
+    let (num, den) = (num >> shift, den >> shift);
+    num.checked_mul(CURVE_ONE)
+        .map_or(CURVE_ONE, |scaled| scaled / den)
+        .min(CURVE_ONE)
+}
+
+/// `floor(deposit × fraction / CURVE_ONE)` without overflow.
+///
+/// Splits `deposit` into `q × CURVE_ONE + r` so neither partial product can
+/// exceed `i128`: `q × fraction <= deposit` and `r × fraction < 2^120`.
+fn apply_curve_fraction(deposit_amount: i128, fraction: u128) -> i128 {
+    if deposit_amount <= 0 {
+        return 0;
+    }
+    let fraction = fraction.min(CURVE_ONE);
+    let deposit = deposit_amount as u128;
+    let (q, r) = (deposit >> CURVE_FRACTION_BITS, deposit & (CURVE_ONE - 1));
+    q.checked_mul(fraction)
+        .and_then(|whole| whole.checked_add((r * fraction) >> CURVE_FRACTION_BITS))
+        .map_or(deposit_amount, |amount| amount as i128)
+        .min(deposit_amount)
+}
+
+/// Area under a halving rate curve from `0` to `elapsed`.
+///
+/// The rate is `2^(64 - k)` during the `k`-th half-life and `0` after
+/// [`MAX_DECAY_HALVINGS`] of them, so the integral is continuous, piecewise
+/// linear and non-decreasing. Bounded by `half_life × 2^65 < 2^97`.
+fn decay_integral(elapsed: u64, half_life_secs: u64) -> u128 {
+    let half_life_secs = half_life_secs.clamp(1, MAX_DECAY_HALF_LIFE_SECS);
+    let half_life = half_life_secs as u128;
+    let periods = elapsed / half_life_secs;
+    if periods > MAX_DECAY_HALVINGS {
+        return half_life * ((1u128 << 65) - 1);
+    }
+    let k = periods as u32;
+    let partial = (elapsed % half_life_secs) as u128;
+    half_life * ((1u128 << 65) - (1u128 << (65 - k))) + (1u128 << (64 - k)) * partial
+}
+
+/// Accrual for the curve kinds `Quadratic` and `ExponentialDecay`.
+///
+/// With `elapsed = min(now, end_time) - start_time` and
+/// `duration = end_time - start_time`, the vested fraction of the deposit is:
+/// - `Quadratic`: `(elapsed / duration)²` — back-loaded vesting.
+/// - `ExponentialDecay`: the share of a rate that halves every
+///   `half_life_secs`, normalised so the full deposit vests at `end_time` —
+///   front-loaded emissions.
+///
+/// Any other kind is evaluated as the linear fraction `elapsed / duration`.
+///
+/// # Behaviour
+/// - Returns `0` before `cliff_time`; the curve still counts from `start_time`.
+/// - Returns exactly `deposit_amount` at and after `end_time`.
+///
+/// # Safety invariants
+/// 1. Monotonically non-decreasing in `now`: each step (ratio, square, decay
+///    integral, fraction application) is a floor of a non-decreasing function.
+/// 2. `0 <= accrued <= deposit_amount` for all inputs.
+/// 3. Fixed-point integer math only. Intermediate values are bounded below
+///    `u128::MAX` by construction; checked operations clamp to the deposit
+///    rather than wrap should a bound ever be violated.
+pub fn calculate_curve_accrued_amount(
+    kind: StreamKind,
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+    deposit_amount: i128,
+    half_life_secs: u64,
+    now: u64,
+) -> i128 {
+    if now < cliff_time || deposit_amount <= 0 {
+        return 0;
+    }
+
+    let duration = end_time.saturating_sub(start_time);
+    let elapsed = now.min(end_time).saturating_sub(start_time);
+    if elapsed >= duration {
+        return deposit_amount;
+    }
+
+    let fraction = match kind {
+        StreamKind::Quadratic => {
+            let linear = curve_ratio(elapsed as u128, duration as u128);
+            (linear * linear) >> CURVE_FRACTION_BITS
+        }
+        StreamKind::ExponentialDecay => curve_ratio(
+            decay_integral(elapsed, half_life_secs),
+            decay_integral(duration, half_life_secs),
+        ),
+        _ => curve_ratio(elapsed as u128, duration as u128),
+    };
+
+    apply_curve_fraction(deposit_amount, fraction)
+}
+
+/// Balances carried by one side of a stream split.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitTerms {
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub checkpointed_amount: i128,
+    pub withdrawn_amount: i128,
+}
+
+/// Divide a stream's deposit, rate, checkpoint and withdrawn amount into two
+/// streams that keep the original `start_time`, `cliff_time`, `end_time`,
+/// `checkpointed_at` and kind. Returns `(kept, split)` where `split` receives
+/// roughly `share_num / share_den` of every balance.
+///
+/// # Exactness
+/// Every field of `kept` and `split` sums back to the original, so the total
+/// deposit, total withdrawn and therefore outstanding liabilities are
+/// unchanged. Rounding is resolved so that:
+/// 1. each side's deposit still covers its own rate to `end_time`
+///    (`deposit >= checkpoint + rate × remaining`), so neither side strands
+///    funds it can never accrue;
+/// 2. each side's `withdrawn_amount` never exceeds what it has accrued at
+///    `now`, so neither side starts with a negative claimable balance.
+///
+/// # Errors
+/// - `InvalidParams` if the share is not strictly between 0 and 1.
+/// - `UnsupportedStreamKind` for `Piecewise`, `Tranched` and curve streams
+///   (their accrual is not a rate).
+/// - `InvalidState` if the stream itself violates the coverage invariant.
+/// - `ArithmeticOverflow` on checked-arithmetic overflow.
+pub fn split_stream_terms(
+    state: CheckpointState,
+    rate_per_second: i128,
+    withdrawn_amount: i128,
+    share_num: i128,
+    share_den: i128,
+    now: u64,
+) -> Result<(SplitTerms, SplitTerms), ContractError> {
+    if share_num <= 0 || share_den <= 0 || share_num >= share_den {
+        return Err(ContractError::InvalidParams);
+    }
+    let portion = |amount: i128| -> Result<i128, ContractError> {
+        amount
+            .checked_mul(share_num)
+            .map(|scaled| scaled / share_den)
+            .ok_or(ContractError::ArithmeticOverflow)
+    };
+
+    // Accrual is `base + rate × elapsed` measured from `anchor`, over at most
+    // `horizon` seconds. Only Linear streams carry their checkpoint forward.
+    let (uses_checkpoint, anchor, horizon) = match state.kind {
+        StreamKind::Linear => (true, state.checkpointed_at, state.end_time),
+        StreamKind::CliffSlope => (false, state.cliff_time, state.end_time),
+        StreamKind::CliffOnly => (false, 0, 0),
+        StreamKind::Piecewise
+        | StreamKind::Tranched
+        | StreamKind::ExponentialDecay
+        | StreamKind::Quadratic => return Err(ContractError::UnsupportedStreamKind),
+    };
+    let horizon = horizon.saturating_sub(anchor) as i128;
+    let elapsed = (now.min(state.end_time).saturating_sub(anchor) as i128).min(horizon);
+
+    let split_rate = portion(rate_per_second)?;
+    let split_checkpoint = portion(state.checkpointed_amount)?;
+    let (base, split_base) = if uses_checkpoint {
+        (state.checkpointed_amount, split_checkpoint)
+    } else {
+        (0, 0)
+    };
+
+    // Clamp the proportional deposit into the range that keeps both sides covered.
+    let needed = |base: i128, rate: i128| -> Result<i128, ContractError> {
+        rate.checked_mul(horizon)
+            .and_then(|streamed| streamed.checked_add(base))
+            .ok_or(ContractError::ArithmeticOverflow)
+    };
+    let min_split = needed(split_base, split_rate)?;
+    let max_split = state
+        .deposit_amount
+        .checked_sub(needed(base - split_base, rate_per_second - split_rate)?)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    if min_split > max_split {
+        return Err(ContractError::InvalidState);
+    }
+    let split_deposit = portion(state.deposit_amount)?.clamp(min_split, max_split);
+    let kept_deposit = state.deposit_amount - split_deposit;
+
+    let accrued = |base: i128, rate: i128, deposit: i128| -> i128 {
+        if now < state.cliff_time {
+            0
+        } else if state.kind == StreamKind::CliffOnly {
+            deposit
+        } else {
+            rate.saturating_mul(elapsed)
+                .saturating_add(base)
+                .min(deposit)
+        }
+    };
+    let split_accrued = accrued(split_base, split_rate, split_deposit);
+    let kept_accrued = accrued(
+        base - split_base,
+        rate_per_second - split_rate,
+        kept_deposit,
+    );
+    let split_withdrawn = portion(withdrawn_amount)?
+        .min(split_accrued)
+        .max(withdrawn_amount - kept_accrued);
+    if split_withdrawn < 0 || split_withdrawn > split_accrued {
+        return Err(ContractError::InvalidState);
+    }
+
+    Ok((
+        SplitTerms {
+            deposit_amount: kept_deposit,
+            rate_per_second: rate_per_second - split_rate,
+            checkpointed_amount: state.checkpointed_amount - split_checkpoint,
+            withdrawn_amount: withdrawn_amount - split_withdrawn,
+        },
+        SplitTerms {
+            deposit_amount: split_deposit,
+            rate_per_second: split_rate,
+            checkpointed_amount: split_checkpoint,
+            withdrawn_amount: split_withdrawn,
+        },
+    ))
+}
+
 // Kani formal proofs (bounded model checking harnesses).
 // These are compiled only when the `kani` cfg is active and are intended
 // to provide machine-checked guarantees about arithmetic and clamping.
@@ -2207,3 +2701,426 @@ mod accrual_bounds_and_monotonicity {
         );
     }
 }
+
+#[cfg(test)]
+mod piecewise_accrual {
+    use super::*;
+
+    fn ramp() -> [RateSegment; 3] {
+        [
+            RateSegment {
+                rate: 1,
+                duration_secs: 100,
+            },
+            RateSegment {
+                rate: 3,
+                duration_secs: 100,
+            },
+            RateSegment {
+                rate: 2,
+                duration_secs: 50,
+            },
+        ]
+    }
+
+    #[test]
+    fn accrues_across_segment_boundaries() {
+        let segments = ramp();
+        let accrued = |now| calculate_piecewise_accrued_amount(0, 0, 500, segments, now);
+        assert_eq!(accrued(0), 0);
+        assert_eq!(accrued(50), 50);
+        assert_eq!(accrued(100), 100);
+        assert_eq!(accrued(150), 250);
+        assert_eq!(accrued(200), 400);
+        assert_eq!(accrued(225), 450);
+        assert_eq!(accrued(250), 500);
+        assert_eq!(
+            accrued(10_000),
+            500,
+            "nothing accrues past the last segment"
+        );
+    }
+
+    #[test]
+    fn cliff_hides_accrual_until_reached() {
+        let segments = ramp();
+        assert_eq!(
+            calculate_piecewise_accrued_amount(0, 120, 500, segments, 119),
+            0
+        );
+        assert_eq!(
+            calculate_piecewise_accrued_amount(0, 120, 500, segments, 120),
+            160
+        );
+    }
+
+    #[test]
+    fn clamps_to_deposit_and_on_overflow() {
+        let segments = ramp();
+        assert_eq!(
+            calculate_piecewise_accrued_amount(0, 0, 300, segments, 250),
+            300
+        );
+
+        let huge = [RateSegment {
+            rate: i128::MAX,
+            duration_secs: 10,
+        }];
+        assert_eq!(
+            calculate_piecewise_accrued_amount(0, 0, 1_000, huge, 5),
+            1_000
+        );
+    }
+
+    #[test]
+    fn monotonic_in_now() {
+        let segments = ramp();
+        let mut prev = 0;
+        for now in 0..=300u64 {
+            let accrued = calculate_piecewise_accrued_amount(10, 10, 600, segments, now);
+            assert!(accrued >= prev);
+            assert!(accrued <= 600);
+            prev = accrued;
+        }
+    }
+
+    #[test]
+    fn checkpointed_fallback_returns_checkpoint_for_piecewise() {
+        let state = CheckpointState {
+            checkpointed_amount: 40,
+            checkpointed_at: 0,
+            cliff_time: 0,
+            end_time: 250,
+            deposit_amount: 500,
+            kind: StreamKind::Piecewise,
+        };
+        assert_eq!(calculate_accrued_amount_checkpointed(state, 0, 200), 40);
+    }
+
+    #[test]
+    fn iterator_validation_matches_slice_validation() {
+        let segments = ramp();
+        assert_eq!(
+            validate_rate_segments(segments.iter().copied()),
+            validate_rate_schedule(&segments)
+        );
+        let bad = [RateSegment {
+            rate: 1,
+            duration_secs: 0,
+        }];
+        assert_eq!(
+            validate_rate_segments(bad.iter().copied()),
+            Err(ContractError::RateScheduleInvalid)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tranched_accrual {
+    use super::*;
+
+    /// 25% at t=400, then 25% every 200s: fully vested at t=1_000.
+    fn grant() -> [Tranche; 4] {
+        [
+            Tranche {
+                unlock_time: 400,
+                cumulative_amount: 250,
+            },
+            Tranche {
+                unlock_time: 600,
+                cumulative_amount: 500,
+            },
+            Tranche {
+                unlock_time: 800,
+                cumulative_amount: 750,
+            },
+            Tranche {
+                unlock_time: 1_000,
+                cumulative_amount: 1_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn steps_at_each_unlock() {
+        let accrued = |now| calculate_tranched_accrued_amount(0, 1_000, grant(), now);
+        assert_eq!(accrued(399), 0);
+        assert_eq!(accrued(400), 250);
+        assert_eq!(accrued(599), 250);
+        assert_eq!(accrued(600), 500);
+        assert_eq!(accrued(1_000), 1_000);
+        assert_eq!(accrued(u64::MAX), 1_000);
+    }
+
+    #[test]
+    fn cliff_and_deposit_bound_the_result() {
+        assert_eq!(
+            calculate_tranched_accrued_amount(700, 1_000, grant(), 650),
+            0
+        );
+        assert_eq!(
+            calculate_tranched_accrued_amount(700, 1_000, grant(), 700),
+            500
+        );
+        assert_eq!(calculate_tranched_accrued_amount(0, 600, grant(), 900), 600);
+        assert_eq!(calculate_tranched_accrued_amount(0, 0, grant(), 900), 0);
+    }
+
+    #[test]
+    fn next_tranche_is_first_still_locked() {
+        assert_eq!(next_tranche(grant(), 0), Some(grant()[0]));
+        assert_eq!(next_tranche(grant(), 400), Some(grant()[1]));
+        assert_eq!(next_tranche(grant(), 1_000), None);
+    }
+
+    #[test]
+    fn validation_rejects_bad_schedules() {
+        assert_eq!(validate_tranches(grant()), Ok(()));
+        assert_eq!(
+            validate_tranches(core::iter::empty::<Tranche>()),
+            Err(ContractError::RateScheduleInvalid)
+        );
+
+        let mut unordered = grant();
+        unordered[2].unlock_time = 600;
+        assert_eq!(
+            validate_tranches(unordered),
+            Err(ContractError::RateScheduleInvalid)
+        );
+
+        let mut flat = grant();
+        flat[1].cumulative_amount = 250;
+        assert_eq!(
+            validate_tranches(flat),
+            Err(ContractError::RateScheduleInvalid)
+        );
+
+        let mut zero = grant();
+        zero[0].cumulative_amount = 0;
+        assert_eq!(
+            validate_tranches(zero),
+            Err(ContractError::RateScheduleInvalid)
+        );
+
+        let many = (0..MAX_TRANCHES + 1).map(|i| Tranche {
+            unlock_time: i as u64,
+            cumulative_amount: i as i128 + 1,
+        });
+        assert_eq!(
+            validate_tranches(many),
+            Err(ContractError::RateScheduleTooManySegments)
+        );
+    }
+
+    #[test]
+    fn split_rejects_tranched() {
+        let state = CheckpointState {
+            checkpointed_amount: 0,
+            checkpointed_at: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            deposit_amount: 1_000,
+            kind: StreamKind::Tranched,
+        };
+        assert_eq!(calculate_accrued_amount_checkpointed(state, 0, 500), 0);
+        assert_eq!(
+            split_stream_terms(state, 0, 0, 1, 2, 0),
+            Err(ContractError::UnsupportedStreamKind)
+        );
+    }
+}
+
+#[cfg(test)]
+mod curve_accrual {
+    use super::*;
+
+    fn quadratic(deposit: i128, now: u64) -> i128 {
+        calculate_curve_accrued_amount(StreamKind::Quadratic, 0, 0, 1_000, deposit, 0, now)
+    }
+
+    fn decay(deposit: i128, half_life: u64, now: u64) -> i128 {
+        calculate_curve_accrued_amount(
+            StreamKind::ExponentialDecay,
+            0,
+            0,
+            1_000,
+            deposit,
+            half_life,
+            now,
+        )
+    }
+
+    #[test]
+    fn quadratic_is_back_loaded() {
+        assert_eq!(quadratic(1_000_000, 0), 0);
+        // Fixed-point floors may land one unit below the exact value.
+        assert!((9_999..=10_000).contains(&quadratic(1_000_000, 100)));
+        assert_eq!(quadratic(1_000_000, 500), 250_000);
+        assert_eq!(quadratic(1_000_000, 1_000), 1_000_000);
+        assert_eq!(quadratic(1_000_000, u64::MAX), 1_000_000);
+    }
+
+    #[test]
+    fn decay_is_front_loaded() {
+        // Two half-lives: 2/3 of the deposit vests in the first one.
+        assert!((1_999..=2_000).contains(&decay(3_000, 500, 500)));
+        assert!((999..=1_000).contains(&decay(3_000, 500, 250)));
+        assert_eq!(decay(3_000, 500, 1_000), 3_000);
+        // The rate is constant within a half-life, so a half-life longer than
+        // the stream degenerates to linear.
+        assert_eq!(decay(1_000_000, 1_000_000, 500), 500_000);
+        // Beyond MAX_DECAY_HALVINGS the curve is flat until end_time.
+        assert_eq!(decay(1_000, 1, 100), decay(1_000, 1, 999));
+    }
+
+    #[test]
+    fn cliff_gates_curves() {
+        let gated =
+            |kind, now| calculate_curve_accrued_amount(kind, 0, 400, 1_000, 1_000, 250, now);
+        for kind in [StreamKind::Quadratic, StreamKind::ExponentialDecay] {
+            assert_eq!(gated(kind, 399), 0);
+            assert!(gated(kind, 400) > 0);
+        }
+    }
+
+    #[test]
+    fn extreme_inputs_stay_bounded() {
+        for kind in [StreamKind::Quadratic, StreamKind::ExponentialDecay] {
+            let mut prev = 0;
+            for now in [0, 1, 2, u64::MAX / 4, u64::MAX / 2, u64::MAX - 1, u64::MAX] {
+                let accrued = calculate_curve_accrued_amount(
+                    kind,
+                    0,
+                    0,
+                    u64::MAX,
+                    i128::MAX,
+                    MAX_DECAY_HALF_LIFE_SECS,
+                    now,
+                );
+                assert!(accrued >= prev && accrued <= i128::MAX);
+                prev = accrued;
+            }
+            assert_eq!(prev, i128::MAX);
+            assert_eq!(
+                calculate_curve_accrued_amount(kind, 0, 0, 1_000, -5, 10, 500),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn checkpoint_fallback_and_split_reject_curves() {
+        for kind in [StreamKind::Quadratic, StreamKind::ExponentialDecay] {
+            let state = CheckpointState {
+                checkpointed_amount: 0,
+                checkpointed_at: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                deposit_amount: 1_000,
+                kind,
+            };
+            assert_eq!(calculate_accrued_amount_checkpointed(state, 0, 500), 0);
+            assert_eq!(
+                split_stream_terms(state, 0, 0, 1, 2, 0),
+                Err(ContractError::UnsupportedStreamKind)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod split_terms {
+    use super::*;
+
+    fn linear(deposit: i128, checkpointed_amount: i128, checkpointed_at: u64) -> CheckpointState {
+        CheckpointState {
+            checkpointed_amount,
+            checkpointed_at,
+            cliff_time: 0,
+            end_time: 1_000,
+            deposit_amount: deposit,
+            kind: StreamKind::Linear,
+        }
+    }
+
+    fn assert_conserved(
+        state: CheckpointState,
+        rate: i128,
+        withdrawn: i128,
+        kept: SplitTerms,
+        split: SplitTerms,
+    ) {
+        assert_eq!(
+            kept.deposit_amount + split.deposit_amount,
+            state.deposit_amount
+        );
+        assert_eq!(kept.rate_per_second + split.rate_per_second, rate);
+        assert_eq!(
+            kept.checkpointed_amount + split.checkpointed_amount,
+            state.checkpointed_amount
+        );
+        assert_eq!(kept.withdrawn_amount + split.withdrawn_amount, withdrawn);
+    }
+
+    #[test]
+    fn even_split_halves_every_balance() {
+        let state = linear(10_000, 0, 0);
+        let (kept, split) = split_stream_terms(state, 10, 3_000, 5_000, 10_000, 400).unwrap();
+        assert_conserved(state, 10, 3_000, kept, split);
+        assert_eq!(split.deposit_amount, 5_000);
+        assert_eq!(split.rate_per_second, 5);
+        assert_eq!(split.withdrawn_amount, 1_500);
+    }
+
+    #[test]
+    fn rounding_keeps_both_sides_covered() {
+        // 7 / 3 does not divide evenly; each side must still fully accrue its deposit.
+        let state = linear(7_000, 0, 0);
+        let (kept, split) = split_stream_terms(state, 7, 0, 1, 3, 0).unwrap();
+        assert_conserved(state, 7, 0, kept, split);
+        for terms in [kept, split] {
+            assert!(terms.deposit_amount >= terms.rate_per_second * 1_000);
+        }
+    }
+
+    #[test]
+    fn withdrawn_never_exceeds_accrued() {
+        // Rate 3 split 1/3 floors to 1; at t=100 the split side has accrued 100
+        // and must not be charged for more than that.
+        let state = linear(3_000, 0, 0);
+        let (kept, split) = split_stream_terms(state, 3, 300, 1, 2, 100).unwrap();
+        assert_conserved(state, 3, 300, kept, split);
+        assert!(split.withdrawn_amount <= split.rate_per_second * 100);
+        assert!(kept.withdrawn_amount <= kept.rate_per_second * 100);
+    }
+
+    #[test]
+    fn checkpoint_is_divided_for_linear() {
+        let state = linear(6_000, 2_000, 500);
+        let (kept, split) = split_stream_terms(state, 8, 1_000, 1, 4, 600).unwrap();
+        assert_conserved(state, 8, 1_000, kept, split);
+        assert_eq!(split.checkpointed_amount, 500);
+        assert!(split.deposit_amount >= split.checkpointed_amount + split.rate_per_second * 500);
+    }
+
+    #[test]
+    fn rejects_out_of_range_share_and_piecewise() {
+        let state = linear(1_000, 0, 0);
+        assert_eq!(
+            split_stream_terms(state, 1, 0, 0, 10_000, 0),
+            Err(ContractError::InvalidParams)
+        );
+        assert_eq!(
+            split_stream_terms(state, 1, 0, 10_000, 10_000, 0),
+            Err(ContractError::InvalidParams)
+        );
+        let piecewise = CheckpointState {
+            kind: StreamKind::Piecewise,
+            ..state
+        };
+        assert_eq!(
+            split_stream_terms(piecewise, 0, 0, 1, 2, 0),
+            Err(ContractError::UnsupportedStreamKind)
+        );
+    }
+}
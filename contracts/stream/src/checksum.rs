@@ -37,6 +37,11 @@
 //!   matches `wasm/checksums.sha256`; it only enforces admin auth and then delegates
 //!   hash existence/validity checks to Soroban's
 //!   `env.deployer().update_current_contract_wasm(new_wasm_hash)` host function.
+//! - When governance is the admin, `CallData::StreamUpgrade(hash)` pins the hash in
+//!   the approved proposal; signers compare it against `wasm/checksums.sha256`
+//!   before approving. Each successful upgrade is appended to the on-chain
+//!   upgrade history (`get_upgrade_history`), so the applied hash can be audited
+//!   against the reference file afterwards.
 //! - In the Soroban Rust test environment, arbitrary WASM blobs are not preloaded, so
 //!   an otherwise-authorized upgrade using `[0u8; 32]` reaches the host and traps with
 //!   `Error(Storage, MissingValue)` rather than returning a contract-defined error.
@@ -126,7 +131,7 @@
 //! | 28           | `TotalKeeperFeesPaid`           | Instance  | `i128`       |
 //!
 //! Total live `DataKey` variant count in V7 (before post-V7 additions): **29** (discriminants 0–28).
-//! Current live `DataKey` variant count: **37** (discriminants 0–36) — see post-V7 additions below.
+//! Current live `DataKey` variant count: **50** (discriminants 0–49) — see post-V7 additions below.
 //!
 //! V6 `Stream` struct adds one field at the end:
 //!
@@ -191,7 +196,7 @@
 //!
 //! Total `DataKey` variants in V7 (before post-V7 additions): **29** (discriminants 0 through 28).
 //!
-//! ## V8/V9 additions (discriminants 29–36)
+//! ## V8–V10 additions (discriminants 29–49)
 //!
 //! | Discriminant | Variant                              | Storage    | Value type           |
 //! |:------------:|:-------------------------------------|:-----------|:---------------------|
@@ -203,26 +208,39 @@
 //! | 34           | `PooledStreamShares(u64)`            | Persistent | `Vec<(Address,u32)>` |
 //! | 35           | `PooledStreamWithdrawn(u64,Address)` | Persistent | `i128`               |
 //! | 36           | `DelegatedCancelNonce(Address)`      | Persistent | `u64`                |
+//! | 37           | `RateSchedule(u64)`                  | Persistent | `Vec<u128>`          |
+//! | 38           | `StreamToken(u64)`                   | Persistent | `Address`            |
+//! | 39           | `AllowedToken(Address)`              | Persistent | `bool`               |
+//! | 40           | `TokenLiabilities(Address)`          | Instance   | `i128`               |
+//! | 41           | `ProtocolFee`                        | Instance   | `ProtocolFeeConfig`  |
+//! | 42           | `Treasury`                           | Instance   | `Address`            |
+//! | 43           | `ProtocolFeeLiabilities(Address)`    | Instance   | `i128`               |
+//! | 44           | `OwnerPositions(Address)`            | Persistent | `Vec<u64>`           |
+//! | 45           | `PositionApproval(u64)`              | Persistent | `Address`            |
+//! | 46           | `RecipientHook(u64)`                 | Persistent | `Address`            |
+//! | 47           | `RefundHook(u64)`                    | Persistent | `RefundHookConfig`   |
+//! | 48           | `UpgradeCount`                       | Instance   | `u32`                |
+//! | 49           | `UpgradeRecord(u32)`                 | Persistent | `UpgradeRecord`      |
 //!
 //! These variants are strictly append-only: no existing discriminant 0–28 was
-//! changed. The live v9 total is **37** variants (0–36), and the next safe append
-//! position is 37. Additive keys may remain under the current contract version,
+//! changed. The live v10 total is **50** variants (0–49), and the next safe append
+//! position is 50. Additive keys may remain under the current contract version,
 //! but their absent-key behavior and the exhaustive compatibility map must be
 //! updated in the same change.
 //!
 //! See [`docs/storage.md`](../../../docs/storage.md) and
 //! [`docs/upgrade.md`](../../../docs/upgrade.md) for policy and runbooks.
 //!
-//! ## Invariant: discriminants 0–36 are frozen
+//! ## Invariant: discriminants 0–49 are frozen
 //!
-//! No variant at position 0–36 may ever be reordered, renamed, or removed on
+//! No variant at position 0–49 may ever be reordered, renamed, or removed on
 //! an instance that may contain that key. Violations cause storage corruption.
 //!
 //! ## Security assumptions
 //!
 //! - **Append-only extension**: New `DataKey` variants must always be appended.
-//!   Inserting at any position ≤ 36 shifts subsequent discriminants. The next
-//!   variant must receive discriminant 37.
+//!   Inserting at any position ≤ 49 shifts subsequent discriminants. The next
+//!   variant must receive discriminant 50.
 //! - **Struct field ordering**: `Stream` fields must never be reordered. Soroban
 //!   XDR encodes structs positionally; a field swap is a silent type mismatch.
 //! - **Option-tail compatibility**: The V5→V6 `memo: Option<Bytes>` addition is
@@ -301,11 +319,11 @@ mod tests {
         assert_eq!(V6_INITIAL_VARIANT_COUNT, 21);
     }
 
-    /// Live DataKey contains 37 variants (discriminants 0–36).
+    /// Live DataKey contains 50 variants (discriminants 0–49).
     #[test]
-    fn live_datakey_variant_count_is_37() {
-        const LIVE_VARIANT_COUNT: usize = 37;
-        assert_eq!(LIVE_VARIANT_COUNT, 37);
+    fn live_datakey_variant_count_is_50() {
+        const LIVE_VARIANT_COUNT: usize = 50;
+        assert_eq!(LIVE_VARIANT_COUNT, 50);
     }
 
     /// Eight post-V7 additive variants occupy discriminants 29–36.
@@ -378,6 +396,18 @@ mod tests {
         assert_eq!(*v9_only_range.end(), 36);
     }
 
+    /// V10 appends `RateSchedule` at 37 and the multi-token keys at 38–40;
+    /// next append is 41.
+    #[test]
+    fn v10_additions_occupy_discriminants_37_to_40() {
+        const V10_VARIANT_COUNT: usize = 41;
+        const RATE_SCHEDULE_DISCRIMINANT: usize = 37;
+        let multi_token_range = 38usize..=40;
+        assert_eq!(multi_token_range.clone().count(), 3);
+        assert_eq!(*multi_token_range.start(), RATE_SCHEDULE_DISCRIMINANT + 1);
+        assert_eq!(*multi_token_range.end(), V10_VARIANT_COUNT - 1);
+    }
+
     /// The eight V7-only DataKey variants occupy discriminants 21–28.
     ///
     /// This test documents the exact discriminant range so that any future
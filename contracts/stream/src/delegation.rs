@@ -1,6 +1,6 @@
 //! Delegation parameter validation for delegated-withdraw operations.
 
-use soroban_sdk::Env;
+use soroban_sdk::{Address, Env};
 
 use crate::{load_delegated_cancel_nonce, load_delegated_nonce, load_stream, ContractError};
 
@@ -45,7 +45,8 @@ pub(crate) fn build_witnessed_cancel_message(
 /// Checks, in order:
 /// 1. `relayer_fee >= 0` — rejects negative fee parameters.
 /// 2. `deadline >= env.ledger().timestamp()` — rejects expired signatures.
-/// 3. `nonce == current_nonce(stream.recipient)` — rejects replays.
+/// 3. `nonce == current_nonce(owner)` — rejects replays, where `owner` is the
+///    claim owner, or the recipient if the position was never transferred.
 pub(crate) fn validate_delegation_params(
     env: &Env,
     stream_id: u64,
@@ -62,7 +63,8 @@ pub(crate) fn validate_delegation_params(
     }
 
     let stream = load_stream(env, stream_id)?;
-    let current_nonce = load_delegated_nonce(env, &stream.recipient);
+    let owner = stream.claim_owner.unwrap_or(stream.recipient);
+    let current_nonce = load_delegated_nonce(env, &owner);
     if nonce != current_nonce {
         return Err(ContractError::InvalidSignature);
     }
@@ -70,6 +72,32 @@ pub(crate) fn validate_delegation_params(
     Ok(())
 }
 
+/// Validate the delegation parameters for a pooled delegated withdrawal.
+///
+/// Same checks as [`validate_delegation_params`], with the nonce keyed by the
+/// signing pool `member` instead of the stream's aggregate recipient.
+pub(crate) fn validate_pool_delegation_params(
+    env: &Env,
+    member: &Address,
+    nonce: u64,
+    deadline: u64,
+    relayer_fee: i128,
+) -> Result<(), ContractError> {
+    if relayer_fee < 0 {
+        return Err(ContractError::InvalidParams);
+    }
+
+    if env.ledger().timestamp() > deadline {
+        return Err(ContractError::SignatureDeadlineExpired);
+    }
+
+    if nonce != load_delegated_nonce(env, member) {
+        return Err(ContractError::InvalidSignature);
+    }
+
+    Ok(())
+}
+
 /// Domain-separation tag for delegated cancellation signatures.
 pub(crate) const DELEGATED_CANCEL_DOMAIN: &[u8; 24] = b"fluxora_delegated_cancel";
 
@@ -76,6 +76,35 @@
 //! | `"offr_acc"` | 2           | V8              |
 //! | `"offr_cxl"` | 2           | V8              |
 //! | `"upgraded"` | 1           | V8              |
+//! | `"tok_allow"`| 2           | V10             |
+//! | `"fee_cfg"`  | 1           | V10             |
+//! | `"treasury"` | 1           | V10             |
+//! | `"fee_dep"`  | 2           | V10             |
+//! | `"fee_wdr"`  | 2           | V10             |
+//! | `"fee_claim"`| 2           | V10             |
+//! | `"pos_appr"` | 2           | V10             |
+//! | `"pos_xfer"` | 2           | V10             |
+//! | `"split"`    | 2           | V10             |
+//! | `"merged"`   | 2           | V10             |
+//! | `"rcpt_hook"`| 2           | V10             |
+//! | `"rfnd_hook"`| 2           | V10             |
+//! | `"sub_set"`  | 2           | V10             |
+//! | `"sub_renew"`| 2           | V10             |
+//! | `"sub_stop"` | 2           | V10             |
+//! | `"vault_dep"`| 2           | V10             |
+//! | `"vault_wdr"`| 2           | V10             |
+//! | `"vault_dbt"`| 2           | V10             |
+//! | `"vault_crd"`| 2           | V10             |
+//! | `"vault_set"`| 2           | V10             |
+//! | `"bal_crd"`  | 2           | V10             |
+//! | `"bal_wdr"`  | 2           | V10             |
+//! | `"bal_mode"` | 2           | V10             |
+//! | `"term_set"` | 2           | V10             |
+//! | `"term_appl"`| 2           | V10             |
+//! | `"pool_mbr"` | 2           | V10             |
+//! | `"pool_rot"` | 2           | V10             |
+//! | `"pool_own"` | 2           | V10             |
+//! | `"pool_ac"`  | 2           | V10             |
 //! | `"AdminUpd"` | 1           | V1              |
 //! | `"migrated"` | 1           | Reserved        |
 //!
@@ -240,6 +269,198 @@ pub(crate) fn emit_excess_swept(env: &Env, recipient: Address, payload: ExcessSw
         .publish((symbol_short!("ex_swept"), recipient), payload);
 }
 
+/// Emit `tok_allow` when the admin changes the stream-token allowlist.
+pub(crate) fn emit_token_allowlist_updated(env: &Env, payload: TokenAllowlistUpdated) {
+    env.events()
+        .publish((symbol_short!("tok_allow"), payload.token.clone()), payload);
+}
+
+/// Emit `fee_cfg` when the protocol fee rates change.
+pub(crate) fn emit_protocol_fee_updated(env: &Env, payload: ProtocolFeeUpdated) {
+    env.events().publish((symbol_short!("fee_cfg"),), payload);
+}
+
+/// Emit `treasury` when the protocol fee treasury changes.
+pub(crate) fn emit_treasury_updated(env: &Env, payload: TreasuryUpdated) {
+    env.events().publish((symbol_short!("treasury"),), payload);
+}
+
+/// Emit `fee_dep` when a protocol fee is charged on a stream deposit.
+pub(crate) fn emit_deposit_fee_charged(env: &Env, stream_id: u64, payload: ProtocolFeeCharged) {
+    env.events()
+        .publish((symbol_short!("fee_dep"), stream_id), payload);
+}
+
+/// Emit `fee_wdr` when a protocol fee is deducted from a withdrawal.
+pub(crate) fn emit_withdraw_fee_charged(env: &Env, stream_id: u64, payload: ProtocolFeeCharged) {
+    env.events()
+        .publish((symbol_short!("fee_wdr"), stream_id), payload);
+}
+
+/// Emit `fee_claim` when collected protocol fees are paid to the treasury.
+pub(crate) fn emit_protocol_fees_claimed(env: &Env, payload: ProtocolFeesClaimed) {
+    env.events()
+        .publish((symbol_short!("fee_claim"), payload.token.clone()), payload);
+}
+
+/// Emit `pos_appr` when a stream position approval is set or revoked.
+pub(crate) fn emit_position_approved(env: &Env, stream_id: u64, payload: PositionApproved) {
+    env.events()
+        .publish((symbol_short!("pos_appr"), stream_id), payload);
+}
+
+/// Emit `pos_xfer` when a stream position changes owner.
+pub(crate) fn emit_position_transferred(env: &Env, stream_id: u64, payload: PositionTransferred) {
+    env.events()
+        .publish((symbol_short!("pos_xfer"), stream_id), payload);
+}
+
+/// Emit `split` when `split_stream` creates a new stream from an existing one.
+pub(crate) fn emit_stream_split(env: &Env, stream_id: u64, payload: StreamSplit) {
+    env.events()
+        .publish((symbol_short!("split"), stream_id), payload);
+}
+
+/// Emit `merged` when `merge_streams` folds streams into a surviving stream.
+pub(crate) fn emit_streams_merged(env: &Env, stream_id: u64, payload: StreamsMerged) {
+    env.events()
+        .publish((symbol_short!("merged"), stream_id), payload);
+}
+
+/// Emit `rcpt_hook` when a stream's recipient payout hook is set or cleared.
+pub(crate) fn emit_recipient_hook_set(env: &Env, stream_id: u64, payload: RecipientHookSet) {
+    env.events()
+        .publish((symbol_short!("rcpt_hook"), stream_id), payload);
+}
+
+/// Emit `rfnd_hook` when a stream's sender refund hook is set or cleared.
+pub(crate) fn emit_refund_hook_set(env: &Env, stream_id: u64, payload: RefundHookSet) {
+    env.events()
+        .publish((symbol_short!("rfnd_hook"), stream_id), payload);
+}
+
+/// Emit `sub_set` when a stream's subscription period is set or cleared.
+pub(crate) fn emit_subscription_set(env: &Env, stream_id: u64, payload: SubscriptionSet) {
+    env.events()
+        .publish((symbol_short!("sub_set"), stream_id), payload);
+}
+
+/// Emit `sub_renew` when a subscription period is funded in place.
+pub(crate) fn emit_subscription_renewed(env: &Env, stream_id: u64, payload: SubscriptionRenewed) {
+    env.events()
+        .publish((symbol_short!("sub_renew"), stream_id), payload);
+}
+
+/// Emit `sub_stop` when a subscription is cleared for lack of funding.
+pub(crate) fn emit_subscription_stopped(env: &Env, stream_id: u64, payload: SubscriptionStopped) {
+    env.events()
+        .publish((symbol_short!("sub_stop"), stream_id), payload);
+}
+
+/// Emit `vault_dep` when a sender deposits into its vault.
+pub(crate) fn emit_vault_deposited(env: &Env, payload: VaultBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("vault_dep"), payload.sender.clone()),
+        payload,
+    );
+}
+
+/// Emit `vault_wdr` when a sender withdraws from its vault.
+pub(crate) fn emit_vault_withdrawn(env: &Env, payload: VaultBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("vault_wdr"), payload.sender.clone()),
+        payload,
+    );
+}
+
+/// Emit `vault_dbt` when a creation, top-up or fee is funded from a vault.
+pub(crate) fn emit_vault_debited(env: &Env, payload: VaultBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("vault_dbt"), payload.sender.clone()),
+        payload,
+    );
+}
+
+/// Emit `vault_crd` when a refund is credited to a vault.
+pub(crate) fn emit_vault_credited(env: &Env, payload: VaultBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("vault_crd"), payload.sender.clone()),
+        payload,
+    );
+}
+
+/// Emit `vault_set` when a sender switches vault funding on or off.
+pub(crate) fn emit_vault_funding_set(env: &Env, payload: VaultFundingSet) {
+    env.events().publish(
+        (symbol_short!("vault_set"), payload.sender.clone()),
+        payload,
+    );
+}
+
+/// Emit `bal_crd` when a withdrawal is credited to a recipient balance.
+pub(crate) fn emit_recipient_balance_credited(env: &Env, payload: RecipientBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("bal_crd"), payload.recipient.clone()),
+        payload,
+    );
+}
+
+/// Emit `bal_wdr` when a recipient withdraws its internal balance.
+pub(crate) fn emit_recipient_balance_withdrawn(env: &Env, payload: RecipientBalanceChanged) {
+    env.events().publish(
+        (symbol_short!("bal_wdr"), payload.recipient.clone()),
+        payload,
+    );
+}
+
+/// Emit `bal_mode` when a recipient switches claim-to-balance mode on or off.
+pub(crate) fn emit_claim_to_balance_set(env: &Env, payload: ClaimToBalanceSet) {
+    env.events().publish(
+        (symbol_short!("bal_mode"), payload.recipient.clone()),
+        payload,
+    );
+}
+
+/// Emit `term_set` when a stream is created with a termination policy.
+pub(crate) fn emit_termination_policy_set(
+    env: &Env,
+    stream_id: u64,
+    payload: TerminationPolicySet,
+) {
+    env.events()
+        .publish((symbol_short!("term_set"), stream_id), payload);
+}
+
+/// Emit `term_appl` when a termination policy changes what a cancellation vests.
+pub(crate) fn emit_termination_applied(env: &Env, stream_id: u64, payload: TerminationApplied) {
+    env.events()
+        .publish((symbol_short!("term_appl"), stream_id), payload);
+}
+
+/// Emit `pool_mbr` when a pooled stream's member list or share weights change.
+pub(crate) fn emit_pool_member_updated(env: &Env, payload: PoolMemberUpdated) {
+    env.events()
+        .publish((symbol_short!("pool_mbr"), payload.stream_id), payload);
+}
+
+/// Emit `pool_rot` when a pool member rotates to a new address.
+pub(crate) fn emit_pool_member_rotated(env: &Env, payload: PoolMemberRotated) {
+    env.events()
+        .publish((symbol_short!("pool_rot"), payload.stream_id), payload);
+}
+
+/// Emit `pool_own` when a pool member's withdrawal right changes hands.
+pub(crate) fn emit_pool_claim_owner_transferred(env: &Env, payload: PoolClaimOwnerTransferred) {
+    env.events()
+        .publish((symbol_short!("pool_own"), payload.stream_id), payload);
+}
+
+/// Emit `pool_ac` when a pool member's auto-claim destination changes.
+pub(crate) fn emit_pool_auto_claim_set(env: &Env, payload: PoolAutoClaimSet) {
+    env.events()
+        .publish((symbol_short!("pool_ac"), payload.stream_id), payload);
+}
+
 /// Emit the `cloned` event when a stream is cloned.
 pub(crate) fn emit_stream_cloned(env: &Env, stream_id: u64, payload: StreamCloned) {
     env.events()
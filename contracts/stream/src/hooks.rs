@@ -0,0 +1,136 @@
+//! Payout and refund hook interfaces.
+//!
+//! # Recipient payout hooks
+//!
+//! The position owner (claim owner, else recipient) may register a hook
+//! contract for a stream with `set_recipient_hook`. The stream contract then calls
+//! [`RecipientHook::on_stream_payout`] on that contract so it can account for
+//! funds it did not pull itself (e.g. a savings vault or AMM deposit contract
+//! receiving streamed salary).
+//!
+//! | Entry point | Reason | `amount` | Hook failure |
+//! |---|---|---|---|
+//! | `withdraw` | [`PayoutReason::Withdraw`] | Net amount sent to the recipient | Reverts the call |
+//! | `withdraw` (claim-to-balance) | [`PayoutReason::Credited`] | Net amount credited to the recipient balance | Reverts the call |
+//! | `trigger_auto_claim` | [`PayoutReason::AutoClaim`] | Amount sent to the auto-claim destination | Reverts the call |
+//! | `cancel_stream` | [`PayoutReason::Cancel`] | Accrued balance still claimable | Ignored |
+//!
+//! Only `Withdraw` and `AutoClaim` mean tokens left the contract. `Credited`
+//! means the amount now sits in the recipient's in-contract balance (see
+//! `set_claim_to_balance`), and `Cancel` moves nothing at all.
+//!
+//! Hooks run **after** the payout and while the contract's
+//! `ReentrancyLock` is still held, so a hook that calls back into any
+//! transfer path of this contract fails with `InvalidState`. Cancel ignores
+//! hook failures so a recipient cannot block the sender's cancellation.
+//!
+//! # Sender refund hooks
+//!
+//! A sender may register a [`RefundHookConfig`] for a stream with
+//! `set_refund_hook`. After any cancellation, `shorten_stream_end_time` or
+//! `decrease_rate_per_second` pushes a non-zero refund to the sender, the
+//! stream contract calls [`RefundHook::on_stream_refund`] under the same
+//! `ReentrancyLock`. [`HookFailurePolicy`] decides whether a failing hook
+//! reverts the call (`RefundHookFailed`) or is ignored. `Revert` only applies
+//! to calls the sender started: admin, witness and keeper cancellations
+//! always ignore hook failures so the sender cannot block them.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// Why the stream contract is notifying a recipient hook.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayoutReason {
+    /// Recipient withdrawal via `withdraw`; `amount` was transferred.
+    Withdraw = 0,
+    /// Permissionless final withdrawal via `trigger_auto_claim`; `amount` was
+    /// transferred to the auto-claim destination.
+    AutoClaim = 1,
+    /// The sender (or admin) cancelled the stream. No tokens moved; `amount`
+    /// is what the recipient can still claim.
+    Cancel = 2,
+    /// Recipient withdrawal via `withdraw` in claim-to-balance mode. No tokens
+    /// left the contract; `amount` was credited to the recipient balance.
+    Credited = 3,
+}
+
+/// Interface a recipient hook contract must implement.
+#[contractclient(name = "RecipientHookClient")]
+pub trait RecipientHook {
+    /// Called once per payout notification; `reason` says whether `amount` of
+    /// `token` was transferred, credited to a balance, or is only claimable.
+    fn on_stream_payout(
+        env: Env,
+        stream_id: u64,
+        token: Address,
+        amount: i128,
+        reason: PayoutReason,
+    );
+}
+
+/// Invoke `hook`, returning `false` if the call failed for any reason.
+pub(crate) fn call_recipient_hook(
+    env: &Env,
+    hook: &Address,
+    stream_id: u64,
+    token: &Address,
+    amount: i128,
+    reason: PayoutReason,
+) -> bool {
+    matches!(
+        RecipientHookClient::new(env, hook)
+            .try_on_stream_payout(&stream_id, token, &amount, &reason),
+        Ok(Ok(()))
+    )
+}
+
+/// Why the stream contract is refunding the sender.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefundReason {
+    /// The stream was cancelled; the unstreamed deposit is returned.
+    Cancel = 0,
+    /// `shorten_stream_end_time` released the deposit past the new end.
+    ShortenEndTime = 1,
+    /// `decrease_rate_per_second` released the deposit above the new rate.
+    DecreaseRate = 2,
+}
+
+/// What a refunding entry point does when the sender's refund hook fails.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookFailurePolicy {
+    /// Complete the refund and ignore the failure.
+    Ignore = 0,
+    /// Revert the whole call with `RefundHookFailed`.
+    Revert = 1,
+}
+
+/// Sender refund hook registered for a stream.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundHookConfig {
+    pub hook: Address,
+    pub on_failure: HookFailurePolicy,
+}
+
+/// Interface a sender refund hook contract must implement.
+#[contractclient(name = "RefundHookClient")]
+pub trait RefundHook {
+    /// Called once per refund, after `amount` has been transferred to the sender.
+    fn on_stream_refund(env: Env, stream_id: u64, amount: i128, reason: RefundReason);
+}
+
+/// Invoke a refund `hook`, returning `false` if the call failed for any reason.
+pub(crate) fn call_refund_hook(
+    env: &Env,
+    hook: &Address,
+    stream_id: u64,
+    amount: i128,
+    reason: RefundReason,
+) -> bool {
+    matches!(
+        RefundHookClient::new(env, hook).try_on_stream_refund(&stream_id, &amount, &reason),
+        Ok(Ok(()))
+    )
+}
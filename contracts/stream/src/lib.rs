@@ -9,6 +9,7 @@ pub mod accrual;
 mod checksum;
 mod delegation;
 pub(crate) mod events;
+pub mod hooks;
 #[cfg(any(test, feature = "testutils"))]
 pub mod storage;
 #[cfg(not(any(test, feature = "testutils")))]
@@ -55,9 +56,10 @@ pub mod types;
 ///   new keys must be appended at the end of the `DataKey` enum.
 pub mod versioning;
 
+use hooks::{HookFailurePolicy, PayoutReason, RefundHookConfig, RefundReason};
 #[cfg(not(all(target_arch = "wasm32", feature = "import_only")))]
 use soroban_sdk::contractimpl;
-use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::xdr::{FromXdr, ToXdr};
 use soroban_sdk::{contract, contracttype, symbol_short, token, Address, Env, Map};
 pub use storage::*;
 use token_check::verify_token_behavior;
@@ -225,6 +227,10 @@ pub const MAX_DELEGATION_DEPTH: u32 = 3;
 /// malformed parent chain.
 pub const MAX_LINEAGE_DEPTH: u32 = MAX_DELEGATION_DEPTH + 1;
 
+/// Maximum number of streams `merge_streams` consolidates in one call
+/// (including the surviving stream).
+pub const MAX_MERGE_STREAMS: u32 = 20;
+
 /// Maximum byte length for pause-reason strings passed to `pause_stream`,
 /// `pause_stream_as_admin`, and `pause_protocol`.
 ///
@@ -433,10 +439,24 @@ const MIN_RATE_INTERVAL_LEDGERS: u32 = 17;
 /// existing indexers, dashboards, and accounting pipelines built against
 /// pre-v9 snapshots will under-report by the relayer fee.
 ///
+/// Bumped to 10: `StreamKind::Piecewise` (discriminant 3) and the
+/// `create_stream_with_schedule` / `get_rate_schedule` entrypoints were added,
+/// with the packed segment schedule stored under the append-only
+/// `DataKey::RateSchedule`. Indexers decoding `StreamKind` must accept the new
+/// discriminant; the persisted `Stream` shape is unchanged. The same version
+/// adds per-stream tokens (`create_stream_with_token`, admin token allowlist)
+/// with `DataKey::StreamToken`, `DataKey::AllowedToken` and
+/// `DataKey::TokenLiabilities`; streams without a `StreamToken` entry keep
+/// using `Config.token`. `StreamKind::Tranched` (discriminant 4, created by
+/// `create_stream_with_tranches`) also lands in this version, with its
+/// schedule under `DataKeyExt::TrancheSchedule`, as do the curve kinds
+/// `ExponentialDecay` (5, half-life under `DataKeyExt::DecayHalfLife`) and
+/// `Quadratic` (6).
+///
 /// The current live storage layout remains append-only and backward-compatible
 /// for existing deployments: `Stream` fields are only appended at the end, and
 /// `DataKey` variants are appended at the end of the enum. The current live
-/// `DataKey` surface is 37 variants (discriminants 0..=36), so any future
+/// `DataKey` surface is 50 variants (discriminants 0..=49), so any future
 /// storage-key change must preserve the existing discriminants and update the
 /// versioning tests in `contracts/stream/tests/storage_key_compat.rs`.
 ///
@@ -450,7 +470,7 @@ const MIN_RATE_INTERVAL_LEDGERS: u32 = 17;
 /// `OfferWrongSender` errors, optional `witness: Option<Address>` supporting
 /// `witnessed_cancel_stream`, and an irrevocable stream mode blocking all
 /// cancel/shorten paths.
-pub const CONTRACT_VERSION: u32 = 9;
+pub const CONTRACT_VERSION: u32 = 10;
 
 // ---------------------------------------------------------------------------
 // Data types
@@ -464,6 +484,29 @@ pub struct Config {
     pub admin: Address,
 }
 
+/// How much of a stream `split_stream` moves into the new stream.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SplitShare {
+    /// Fraction of every balance in basis points (exclusive range 1–9999).
+    Bps(u32),
+    /// Target unwithdrawn balance for the new stream; converted to the fraction
+    /// `amount / (deposit_amount - withdrawn_amount)`.
+    Amount(i128),
+}
+
+/// Protocol fee rates in basis points (`DataKey::ProtocolFee`).
+///
+/// `deposit_fee_bps` is charged on top of the deposit when a stream is created
+/// through `create_stream` and its variants; `withdraw_fee_bps` is deducted
+/// from every `withdraw` / `withdraw_to` payout. Both default to 0.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProtocolFeeConfig {
+    pub deposit_fee_bps: u32,
+    pub withdraw_fee_bps: u32,
+}
+
 /// An active ID reservation held by a caller after `reserve_stream_ids`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -600,7 +643,8 @@ pub enum StreamStatus {
     Cancelled = 3,
 }
 
-/// The architectural style of the stream (Linear, CliffOnly, or CliffSlope).
+/// The architectural style of the stream (Linear, CliffOnly, CliffSlope,
+/// Piecewise, Tranched or one of the accrual curves).
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum StreamKind {
@@ -610,6 +654,30 @@ pub enum StreamKind {
     CliffOnly = 1,
     /// Stream with a cliff period followed by linear accrual.
     CliffSlope = 2,
+    /// Stream whose rate follows a schedule of `accrual::RateSegment`s laid
+    /// end-to-end from `start_time` (e.g. probation rate, full rate, bonus tail).
+    ///
+    /// Created only through `create_stream_with_schedule`; the schedule is stored
+    /// under `DataKey::RateSchedule` and `rate_per_second` is always `0`.
+    Piecewise = 3,
+    /// Stream that unlocks in steps at the `accrual::Tranche` points of its
+    /// schedule (e.g. 25% at 12 months, then quarterly), flat in between.
+    ///
+    /// Created only through `create_stream_with_tranches`; the schedule is
+    /// stored under `DataKeyExt::TrancheSchedule` and `rate_per_second` is
+    /// always `0`.
+    Tranched = 4,
+    /// Front-loaded emissions: the rate halves every half-life, normalised so
+    /// the full deposit vests at `end_time` (see
+    /// `accrual::calculate_curve_accrued_amount`).
+    ///
+    /// Created only through `create_stream_with_decay`; the half-life is stored
+    /// under `DataKeyExt::DecayHalfLife` and `rate_per_second` is always `0`.
+    ExponentialDecay = 5,
+    /// Back-loaded vesting: the vested share is `(elapsed / duration)²`.
+    ///
+    /// Created through `create_stream` with `rate_per_second = 0`.
+    Quadratic = 6,
 }
 
 #[soroban_sdk::contracterror]
@@ -674,7 +742,9 @@ pub enum ContractError {
     WithdrawalTooFrequent = 31,
     /// Metadata payload exceeds the allowed size.
     MetadataTooLarge = 32,
-    /// Keeper attempted to close a stream before the grace period elapsed.
+    /// Keeper attempted to act before its window opened: `keeper_cancel`
+    /// before the grace period elapsed, or `renew_subscription` before the
+    /// stream's final period.
     KeeperGracePeriodNotElapsed = 33,
     /// ID reservation is active; 34 is distinct from keeper grace errors.
     ReservationAlreadyActive = 34,
@@ -701,6 +771,14 @@ pub enum ContractError {
     /// Rate schedule validation failed: zero-length segment, negative rate,
     /// non-increasing boundaries, or cumulative-sum overflow.
     RateScheduleInvalid = 46,
+    /// The token is not on the admin-managed allowlist for new streams.
+    TokenNotAllowed = 47,
+    /// Protocol fees cannot be claimed because no treasury is configured.
+    TreasuryNotSet = 48,
+    /// The stream's recipient hook contract failed during a payout.
+    RecipientHookFailed = 49,
+    /// The stream's sender refund hook failed and its policy is `Revert`.
+    RefundHookFailed = 50,
     /// The token contract did not expose the expected SEP-41 interface during init.
     TokenVerificationFailed = 88,
 }
@@ -748,6 +826,65 @@ pub struct CreateStreamResult {
     pub error: Option<u32>,
 }
 
+/// One sender-side mutation for [`FluxoraStream::execute_batch`].
+///
+/// Each variant runs the same checks as the standalone entrypoint it names;
+/// only the per-op `require_auth` is replaced by the batch's single check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamOp {
+    /// `pause_stream(stream_id, reason)`
+    Pause(u64, PauseReason),
+    /// `resume_stream(stream_id)`
+    Resume(u64),
+    /// `cancel_stream(stream_id)`
+    Cancel(u64),
+    /// `update_rate_per_second(stream_id, new_rate_per_second)`
+    UpdateRate(u64, i128),
+    /// `decrease_rate_per_second(stream_id, new_rate_per_second)`
+    DecreaseRate(u64, i128),
+    /// `top_up_stream(stream_id, caller, amount)`
+    TopUp(u64, i128),
+    /// `extend_stream_end_time(stream_id, new_end_time)`
+    ExtendEndTime(u64, u64),
+    /// `shorten_stream_end_time(stream_id, new_end_time)`
+    ShortenEndTime(u64, u64),
+}
+
+/// Result of a single op in `execute_batch_partial`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamOpResult {
+    /// True if the op was applied.
+    pub success: bool,
+    /// The error code if the op failed (None if success is true).
+    pub error: Option<u32>,
+}
+
+/// How a cancellation splits a stream between recipient and sender.
+///
+/// Fixed at creation by
+/// [`create_stream_with_termination`](FluxoraStream::create_stream_with_termination)
+/// and applied by every cancel path. The vested amount stays with the
+/// recipient and the rest of the deposit returns to the sender. It never
+/// drops below `withdrawn_amount` or exceeds `deposit_amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TerminationPolicy {
+    /// Vested is what has accrued at cancellation.
+    Standard,
+    /// Good leaver: vesting continues on the stream's schedule for this many
+    /// seconds after cancellation. The sender refund is settled at
+    /// cancellation; the recipient claims the extra amount as it vests.
+    GoodLeaver(u64),
+    /// Bad leaver: accrual above this floor is forfeited to the sender, unless
+    /// already withdrawn.
+    BadLeaver(i128),
+    /// Acceleration: this many basis points of the unvested remainder also
+    /// vest on cancellation (`1..=10_000`).
+    Accelerate(u32),
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Withdrawal {
@@ -922,6 +1059,256 @@ pub struct ExcessSwept {
     pub amount: i128,
 }
 
+/// Emitted when the admin adds or removes a token from the stream-token allowlist.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TokenAllowlistUpdated {
+    pub token: Address,
+    pub allowed: bool,
+}
+
+/// Emitted when the protocol fee rates change.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProtocolFeeUpdated {
+    pub deposit_fee_bps: u32,
+    pub withdraw_fee_bps: u32,
+}
+
+/// Emitted when the protocol fee treasury changes.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TreasuryUpdated {
+    pub old_treasury: Option<Address>,
+    pub new_treasury: Address,
+}
+
+/// Emitted when a protocol fee is charged on a stream deposit or withdrawal.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProtocolFeeCharged {
+    pub stream_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted when collected protocol fees are paid out to the treasury.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProtocolFeesClaimed {
+    pub token: Address,
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `split_stream` carves a new independent stream out of an
+/// existing one. Amounts describe the new stream.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamSplit {
+    pub stream_id: u64,
+    pub new_stream_id: u64,
+    pub new_recipient: Address,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub withdrawn_amount: i128,
+}
+
+/// Emitted when `merge_streams` folds `merged_stream_ids` into `stream_id`.
+/// Amounts describe the surviving stream after the merge.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StreamsMerged {
+    pub stream_id: u64,
+    pub merged_stream_ids: soroban_sdk::Vec<u64>,
+    pub deposit_amount: i128,
+    pub rate_per_second: i128,
+    pub withdrawn_amount: i128,
+}
+
+/// Emitted when an owner approves (or revokes, with `approved = None`) an
+/// address to transfer a stream position.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionApproved {
+    pub stream_id: u64,
+    pub owner: Address,
+    pub approved: Option<Address>,
+}
+
+/// Emitted whenever a stream position changes hands, via either
+/// `transfer_claim_ownership` or `transfer_from`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionTransferred {
+    pub stream_id: u64,
+    pub from: Address,
+    pub to: Address,
+}
+
+/// Emitted when the position owner registers (or clears, with `hook = None`)
+/// the payout hook for a stream. `recipient` is the owner that authorized it.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecipientHookSet {
+    pub stream_id: u64,
+    pub recipient: Address,
+    pub hook: Option<Address>,
+}
+
+/// Emitted when a sender registers (or clears, with `hook = None`) the
+/// refund hook for a stream. `on_failure` is `Ignore` when cleared.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefundHookSet {
+    pub stream_id: u64,
+    pub sender: Address,
+    pub hook: Option<Address>,
+    pub on_failure: HookFailurePolicy,
+}
+
+/// Emitted when a sender enables (or disables, with `period = None`)
+/// subscription renewal for a stream.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionSet {
+    pub stream_id: u64,
+    pub sender: Address,
+    pub period: Option<u64>,
+}
+
+/// Emitted when `renew_subscription` funds another period in place.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionRenewed {
+    pub stream_id: u64,
+    pub keeper: Address,
+    pub amount: i128,
+    pub new_deposit_amount: i128,
+    pub new_end_time: u64,
+}
+
+/// Why `renew_subscription` stopped a subscription.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStopReason {
+    /// The sender's token balance is below one period's deposit.
+    InsufficientBalance = 0,
+    /// The sender's allowance for this contract is below one period's deposit.
+    InsufficientAllowance = 1,
+}
+
+/// Emitted when `renew_subscription` cannot fund the next period and clears
+/// the subscription. The stream itself keeps running to its current `end_time`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SubscriptionStopped {
+    pub stream_id: u64,
+    pub required: i128,
+    pub reason: SubscriptionStopReason,
+}
+
+/// Emitted when a stream is created with a non-standard termination policy.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TerminationPolicySet {
+    pub stream_id: u64,
+    pub policy: TerminationPolicy,
+}
+
+/// Emitted when a cancellation vests a different amount than plain accrual
+/// because of the stream's termination policy.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TerminationApplied {
+    pub stream_id: u64,
+    pub policy: TerminationPolicy,
+    pub accrued: i128,
+    pub vested: i128,
+}
+
+/// Emitted when a pooled stream's sender adds a member (`old_share = 0`),
+/// removes one (`new_share = 0`) or changes a member's share weight.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolMemberUpdated {
+    pub stream_id: u64,
+    pub member: Address,
+    pub old_share: u32,
+    pub new_share: u32,
+}
+
+/// Emitted when a pool member moves its share and balances to a new address.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolMemberRotated {
+    pub stream_id: u64,
+    pub old_member: Address,
+    pub new_member: Address,
+}
+
+/// Emitted when a pool member's withdrawal right moves to `new_owner`.
+/// `old_owner` is the member itself before the first transfer.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolClaimOwnerTransferred {
+    pub stream_id: u64,
+    pub member: Address,
+    pub old_owner: Address,
+    pub new_owner: Address,
+}
+
+/// Emitted when a pool member's auto-claim destination is set or, with
+/// `destination = None`, revoked.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolAutoClaimSet {
+    pub stream_id: u64,
+    pub member: Address,
+    pub destination: Option<Address>,
+}
+
+/// Emitted whenever a sender's vault balance changes: on `deposit_to_vault`
+/// (`vault_dep`), `withdraw_from_vault` (`vault_wdr`), a creation or top-up
+/// funded from the vault (`vault_dbt`) and a refund credited to it (`vault_crd`).
+/// `balance` is the vault balance after the change.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VaultBalanceChanged {
+    pub sender: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Emitted when a recipient balance changes: a withdrawal credited to it
+/// (`bal_crd`) or a payout of the whole balance by `withdraw_recipient_balance`
+/// (`bal_wdr`). `balance` is the balance after the change.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecipientBalanceChanged {
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+/// Emitted when a recipient switches claim-to-balance mode on or off.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ClaimToBalanceSet {
+    pub recipient: Address,
+    pub enabled: bool,
+}
+
+/// Emitted when a sender switches vault funding on or off.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct VaultFundingSet {
+    pub sender: Address,
+    pub enabled: bool,
+}
+
 /// Emitted when a stream is cancelled by a keeper via `keeper_cancel`.
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -969,6 +1356,9 @@ pub struct StreamOffer {
     pub recipient: Address,
     /// Total deposit amount escrowed for this offer.
     pub deposit_amount: i128,
+    /// Deposit fee escrowed alongside the deposit at offer creation. Booked as
+    /// a protocol fee on acceptance and refunded with the deposit otherwise.
+    pub deposit_fee: i128,
     /// Streaming rate in tokens per second (0 for `CliffOnly` streams).
     pub rate_per_second: i128,
     /// Requested stream start time (absolute ledger timestamp).
@@ -1447,6 +1837,101 @@ pub enum DataKey {
     ///
     /// Appended at the end to preserve all existing discriminants.
     DelegatedCancelNonce(Address),
+    /// Packed rate-segment schedule of a `StreamKind::Piecewise` stream
+    /// (`Vec<u128>`, one word per segment via `accrual::pack_rate_segment`).
+    ///
+    /// Written once by `create_stream_with_schedule` and removed when the
+    /// stream is closed. Absent for every other stream kind.
+    RateSchedule(u64),
+    /// Token a stream is denominated in (`Address`). Absent for streams in the
+    /// default `Config.token`, including every stream created before
+    /// multi-token support.
+    StreamToken(u64),
+    /// Admin-managed allowlist entry for a non-default stream token (`bool`).
+    AllowedToken(Address),
+    /// Outstanding liabilities for a non-default token (`i128`, instance).
+    /// The default token keeps using `TotalLiabilities`.
+    TokenLiabilities(Address),
+    /// Protocol fee rates (`ProtocolFeeConfig`, instance). Absent means no fee.
+    ProtocolFee,
+    /// Address that receives claimed protocol fees (`Address`, instance).
+    Treasury,
+    /// Protocol fees collected in a token and not yet claimed by the treasury
+    /// (`i128`, instance). Tracked apart from stream liabilities and excluded
+    /// from `sweep_excess`.
+    ProtocolFeeLiabilities(Address),
+    /// Stream positions held by an address (`Vec<u64>`, sorted): streams it
+    /// receives and has not transferred away, plus streams transferred to it.
+    /// Pooled streams are not positions.
+    OwnerPositions(Address),
+    /// Address approved to `transfer_from` a single stream position (`Address`).
+    PositionApproval(u64),
+    /// Recipient hook contract notified on payouts for a stream (`Address`).
+    /// See [`hooks`].
+    RecipientHook(u64),
+    /// Sender refund hook and failure policy for a stream (`RefundHookConfig`).
+    /// See [`hooks`].
+    RefundHook(u64),
+    /// Number of recorded WASM upgrades (`u32`, instance). Absent means none.
+    UpgradeCount,
+    /// One entry of the upgrade history, keyed by 0-based index
+    /// (`UpgradeRecord`, persistent).
+    UpgradeRecord(u32),
+}
+
+/// Storage keys added after [`DataKey`] reached the 50-variant limit that the
+/// Soroban contract spec places on a union type.
+///
+/// Same rules as `DataKey`: append only, never reorder or remove. Variant
+/// names must also differ from every `DataKey` name, because both enums
+/// encode a key as its variant name followed by its fields.
+#[contracttype]
+pub enum DataKeyExt {
+    /// Subscription period in seconds for a stream renewed in place by
+    /// `renew_subscription` (`u64`, persistent). Absent means not a subscription.
+    Subscription(u64),
+    /// Internal vault balance of a sender in a token (`i128`, persistent),
+    /// keyed by `(sender, token)`. Absent means zero.
+    VaultBalance(Address, Address),
+    /// Present when the sender funds creations and top-ups from its vault and
+    /// takes refunds into it (`bool`, persistent). Absent means tokens move.
+    VaultFunding(Address),
+    /// Internal balance of a recipient in a token (`i128`, persistent), keyed
+    /// by `(recipient, token)`. Absent means zero.
+    RecipientBalance(Address, Address),
+    /// Present when withdrawals paid to the address are credited to its
+    /// recipient balance instead of transferred (`bool`, persistent).
+    ClaimToBalance(Address),
+    /// Termination policy fixed at creation (`TerminationPolicy`, persistent).
+    /// Absent means `Standard`.
+    TerminationPolicy(u64),
+    /// Vested amount fixed by a cancellation whose policy moved it off plain
+    /// accrual (`i128`, persistent). Read back as the stream's accrual.
+    TerminationVested(u64),
+    /// Unlock schedule of a `StreamKind::Tranched` stream
+    /// (`Vec<accrual::Tranche>`, persistent).
+    TrancheSchedule(u64),
+    /// Half-life in seconds of a `StreamKind::ExponentialDecay` stream
+    /// (`u64`, persistent).
+    DecayHalfLife(u64),
+    /// Pool-wide accrual at the last share-table change of a pooled stream
+    /// (`i128`, persistent). Absent means `0`.
+    PoolCheckpoint(u64),
+    /// Accrual settled to a pool member at the pool checkpoint (`i128`,
+    /// persistent), keyed by `(stream_id, member)`. Absent means `0`.
+    PoolMemberCredit(u64, Address),
+    /// Address holding a pool member's withdrawal right (`Address`,
+    /// persistent), keyed by `(stream_id, member)`. Absent means the member.
+    PoolClaimOwner(u64, Address),
+    /// Auto-claim destination of a pool member (`Address`, persistent), keyed
+    /// by `(stream_id, member)`. Absent means auto-claim is off.
+    PoolAutoClaim(u64, Address),
+    /// Number of stream positions held by an address (`u32`, persistent);
+    /// the length of its `OwnerPositions` index. Absent means `0`.
+    OwnerPositionCount(Address),
+    /// Contract that must authorize every new stream (`Address`, instance),
+    /// set once by `init_with_creator`. Absent means creation is open.
+    StreamCreator,
 }
 
 // ---------------------------------------------------------------------------
@@ -1465,6 +1950,165 @@ fn validate_lookback_window(max_lookback_ledgers: Option<u32>) -> Result<(), Con
     Ok(())
 }
 
+/// Accrued amount of `stream` at `now`, honoring its kind.
+///
+/// `Piecewise` and `Tranched` streams are evaluated against their stored
+/// schedule and the curve kinds through `accrual::calculate_curve_accrued_amount`;
+/// every other kind goes through `accrual::calculate_accrued_amount_checkpointed`.
+/// Status handling (pause freeze, cancellation freeze) is the caller's job, as
+/// is choosing `now`; see [`cancelled_accrual_time`] for cancelled streams.
+///
+/// A cancelled stream whose termination policy fixed a vested amount returns
+/// that amount, except during a `GoodLeaver` window, where the schedule keeps
+/// accruing up to it until `cancelled_at + seconds`.
+fn stream_accrued_at(env: &Env, stream: &Stream, now: u64) -> i128 {
+    if stream.status == StreamStatus::Cancelled {
+        if let Some(vested) = get_termination_vested(env, stream.stream_id) {
+            let Some(cancelled_at) = stream.cancelled_at else {
+                return vested;
+            };
+            let vesting_end = cancelled_vesting_end(env, stream, cancelled_at);
+            if vesting_end == cancelled_at {
+                return vested;
+            }
+            return scheduled_accrued_at(env, stream, now.min(vesting_end))
+                .max(stream.withdrawn_amount)
+                .min(vested);
+        }
+    }
+
+    scheduled_accrued_at(env, stream, now)
+}
+
+/// End of a cancelled stream's vesting: `cancelled_at + seconds` under a
+/// `GoodLeaver(seconds)` policy, `cancelled_at` otherwise.
+fn cancelled_vesting_end(env: &Env, stream: &Stream, cancelled_at: u64) -> u64 {
+    match get_termination_policy(env, stream.stream_id) {
+        Some(TerminationPolicy::GoodLeaver(seconds)) => cancelled_at.saturating_add(seconds),
+        _ => cancelled_at,
+    }
+}
+
+/// Time at which a cancelled stream's accrual is read: `cancelled_at`, or
+/// the current time while a `GoodLeaver` vesting window is still open.
+fn cancelled_accrual_time(env: &Env, stream: &Stream) -> Result<u64, ContractError> {
+    let cancelled_at = stream.cancelled_at.ok_or(ContractError::InvalidState)?;
+    let vesting_end = cancelled_vesting_end(env, stream, cancelled_at);
+    Ok(env.ledger().timestamp().clamp(cancelled_at, vesting_end))
+}
+
+/// Schedule-only part of [`stream_accrued_at`]: what the stream's kind and
+/// parameters yield at `now`, ignoring any settled termination.
+fn scheduled_accrued_at(env: &Env, stream: &Stream, now: u64) -> i128 {
+    if stream.kind == StreamKind::Piecewise {
+        return accrual::calculate_piecewise_accrued_amount(
+            stream.start_time,
+            stream.cliff_time,
+            stream.deposit_amount,
+            load_rate_schedule(env, stream.stream_id).iter(),
+            now,
+        );
+    }
+
+    if stream.kind == StreamKind::Tranched {
+        return accrual::calculate_tranched_accrued_amount(
+            stream.cliff_time,
+            stream.deposit_amount,
+            load_tranche_schedule(env, stream.stream_id).iter(),
+            now,
+        );
+    }
+
+    if matches!(
+        stream.kind,
+        StreamKind::ExponentialDecay | StreamKind::Quadratic
+    ) {
+        return accrual::calculate_curve_accrued_amount(
+            stream.kind,
+            stream.start_time,
+            stream.cliff_time,
+            stream.end_time,
+            stream.deposit_amount,
+            get_decay_half_life(env, stream.stream_id),
+            now,
+        );
+    }
+
+    accrual::calculate_accrued_amount_checkpointed(
+        accrual::CheckpointState {
+            checkpointed_amount: stream.checkpointed_amount,
+            checkpointed_at: stream.checkpointed_at,
+            cliff_time: stream.cliff_time,
+            end_time: stream.end_time,
+            deposit_amount: stream.deposit_amount,
+            kind: stream.kind,
+        },
+        stream.rate_per_second,
+        now,
+    )
+}
+
+/// Plain accrual of `stream` at `now` and the amount vested if it were
+/// cancelled then under its [`TerminationPolicy`], as `(accrued, vested)`.
+fn termination_split(env: &Env, stream: &Stream, now: u64) -> Result<(i128, i128), ContractError> {
+    let accrued = stream_accrued_at(env, stream, now);
+    let vested = match get_termination_policy(env, stream.stream_id) {
+        None | Some(TerminationPolicy::Standard) => return Ok((accrued, accrued)),
+        Some(TerminationPolicy::GoodLeaver(seconds)) => {
+            stream_accrued_at(env, stream, now.saturating_add(seconds))
+        }
+        Some(TerminationPolicy::BadLeaver(floor)) => accrued.min(floor),
+        Some(TerminationPolicy::Accelerate(bps)) => {
+            let extra = stream
+                .deposit_amount
+                .saturating_sub(accrued)
+                .checked_mul(bps as i128)
+                .ok_or(ContractError::ArithmeticOverflow)?
+                / 10_000;
+            accrued.saturating_add(extra)
+        }
+    };
+    Ok((
+        accrued,
+        vested
+            .max(stream.withdrawn_amount)
+            .min(stream.deposit_amount),
+    ))
+}
+
+/// Vested amount for `stream` being cancelled at `now`. When its policy moves
+/// the result off plain accrual, the amount is stored so later accrual reads
+/// return it, and `term_appl` is emitted. Call before marking it `Cancelled`.
+fn settle_termination(env: &Env, stream: &Stream, now: u64) -> Result<i128, ContractError> {
+    let (accrued, vested) = termination_split(env, stream, now)?;
+    if vested != accrued {
+        set_termination_vested(env, stream.stream_id, Some(vested));
+        if let Some(policy) = get_termination_policy(env, stream.stream_id) {
+            events::emit_termination_applied(
+                env,
+                stream.stream_id,
+                TerminationApplied {
+                    stream_id: stream.stream_id,
+                    policy,
+                    accrued,
+                    vested,
+                },
+            );
+        }
+    }
+    Ok(vested)
+}
+
+fn validate_termination_policy(policy: &TerminationPolicy) -> Result<(), ContractError> {
+    match policy {
+        TerminationPolicy::BadLeaver(floor) if *floor < 0 => Err(ContractError::InvalidParams),
+        TerminationPolicy::Accelerate(bps) if *bps == 0 || *bps > 10_000 => {
+            Err(ContractError::InvalidParams)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Cap a withdrawal without changing lifetime accrual or withdrawn accounting.
 /// `calculate_accrued` remains the total entitlement; this helper only limits
 /// the amount payable in the current claim to one recent ledger window.
@@ -1482,30 +2126,8 @@ fn apply_lookback_cap(
     let window_seconds = u64::from(ledgers).saturating_mul(SECONDS_PER_LEDGER);
     let endpoint = effective_time.min(stream.end_time);
     let window_start = endpoint.saturating_sub(window_seconds);
-    let recent_accrual = accrual::calculate_accrued_amount_checkpointed(
-        accrual::CheckpointState {
-            checkpointed_amount: stream.checkpointed_amount,
-            checkpointed_at: stream.checkpointed_at,
-            cliff_time: stream.cliff_time,
-            end_time: stream.end_time,
-            deposit_amount: stream.deposit_amount,
-            kind: stream.kind,
-        },
-        stream.rate_per_second,
-        endpoint,
-    )
-    .saturating_sub(accrual::calculate_accrued_amount_checkpointed(
-        accrual::CheckpointState {
-            checkpointed_amount: stream.checkpointed_amount,
-            checkpointed_at: stream.checkpointed_at,
-            cliff_time: stream.cliff_time,
-            end_time: stream.end_time,
-            deposit_amount: stream.deposit_amount,
-            kind: stream.kind,
-        },
-        stream.rate_per_second,
-        window_start,
-    ));
+    let recent_accrual = stream_accrued_at(env, stream, endpoint)
+        .saturating_sub(stream_accrued_at(env, stream, window_start));
 
     // CliffOnly accrual is a one-shot event. Once unlocked, its full entitlement
     // is claimable even if the caller first observes it after the lookback window.
@@ -1535,10 +2157,9 @@ fn apply_lookback_cap(
 // ---------------------------------------------------------------------------
 // IdReservation storage helpers — delegated to storage.rs
 // ---------------------------------------------------------------------------
-
-use storage::{
-    load_id_reservation, next_stream_id_for, remove_id_reservation, save_id_reservation,
-};
+//
+// `load_id_reservation`, `next_stream_id_for`, `remove_id_reservation` and
+// `save_id_reservation` come from storage.rs via the glob re-export above.
 
 /// Enforce the rate-change cooldown and record the current ledger as the last change.
 ///
@@ -1559,8 +2180,231 @@ fn check_and_bump_rate_cooldown(env: &Env, stream: &mut Stream) -> Result<(), Co
     Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// Protocol constants
+/// Protocol fee owed on `amount` at `fee_bps`, rounded down.
+fn protocol_fee_for(amount: i128, fee_bps: u32) -> Result<i128, ContractError> {
+    amount
+        .checked_mul(fee_bps as i128)
+        .map(|scaled| scaled / 10_000)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+/// Fund a sender-side payment of `amount` in `token`.
+///
+/// With vault funding on, `sender`'s vault is debited and the token's
+/// liabilities drop by `amount`; the caller books the payment as a liability
+/// again, so funds that never leave the contract stay counted exactly once.
+/// Otherwise the tokens are pulled with `transfer_from`.
+///
+/// # Errors
+/// - `ContractError::InsufficientBalance`: vault balance below `amount`
+fn pull_sender_funds(
+    env: &Env,
+    token: &Address,
+    sender: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if !get_vault_funding(env, sender) {
+        return pull_token_of(env, token, sender, amount);
+    }
+    let balance = read_vault_balance(env, sender, token);
+    if balance < amount {
+        return Err(ContractError::InsufficientBalance);
+    }
+    let balance = balance - amount;
+    write_vault_balance(env, sender, token, balance);
+    let liabilities = read_token_liabilities(env, token)?
+        .checked_sub(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_token_liabilities(env, token, liabilities)?;
+    events::emit_vault_debited(
+        env,
+        VaultBalanceChanged {
+            sender: sender.clone(),
+            token: token.clone(),
+            amount,
+            balance,
+        },
+    );
+    Ok(())
+}
+
+/// Return `amount` of `token` to `sender`: credited to its vault (and booked
+/// back as a liability) with vault funding on, transferred otherwise.
+/// Callers have already released the refunded amount from the liabilities.
+fn push_sender_funds(
+    env: &Env,
+    token: &Address,
+    sender: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if !get_vault_funding(env, sender) {
+        return push_token_of(env, token, sender, amount);
+    }
+    let balance = credit_vault(env, sender, token, amount)?;
+    events::emit_vault_credited(
+        env,
+        VaultBalanceChanged {
+            sender: sender.clone(),
+            token: token.clone(),
+            amount,
+            balance,
+        },
+    );
+    Ok(())
+}
+
+/// Add `amount` to a vault and to the token's liabilities. Returns the new balance.
+fn credit_vault(
+    env: &Env,
+    sender: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, ContractError> {
+    let balance = read_vault_balance(env, sender, token)
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    let liabilities = read_token_liabilities(env, token)?
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_vault_balance(env, sender, token, balance);
+    write_token_liabilities(env, token, liabilities)?;
+    Ok(balance)
+}
+
+/// Pay a withdrawal of `amount` in `token` to `to`: credited to its recipient
+/// balance (and booked back as a liability) in claim-to-balance mode,
+/// transferred otherwise. Callers have already released `amount` from the
+/// liabilities.
+fn push_payout(
+    env: &Env,
+    token: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if !get_claim_to_balance(env, to) {
+        return push_token_of(env, token, to, amount);
+    }
+    let liabilities = read_token_liabilities(env, token)?
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_token_liabilities(env, token, liabilities)?;
+    credit_recipient_balance(env, to, token, amount)
+}
+
+/// Add `amount` to a recipient balance and emit `bal_crd`. Liabilities are the
+/// caller's concern.
+fn credit_recipient_balance(
+    env: &Env,
+    recipient: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let balance = read_recipient_balance(env, recipient, token)
+        .checked_add(amount)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_recipient_balance(env, recipient, token, balance);
+    events::emit_recipient_balance_credited(
+        env,
+        RecipientBalanceChanged {
+            recipient: recipient.clone(),
+            token: token.clone(),
+            amount,
+            balance,
+        },
+    );
+    Ok(())
+}
+
+/// Deposit fee owed on a deposit of `amount` at the current fee config.
+fn deposit_fee_for(env: &Env, amount: i128) -> Result<i128, ContractError> {
+    protocol_fee_for(amount, get_protocol_fee(env).deposit_fee_bps)
+}
+
+/// Pull the deposit fee for a newly created stream from `sender` on top of
+/// its deposit and book it to the protocol fee liabilities of `token`.
+///
+/// No-op while `deposit_fee_bps == 0`, so fee-free deployments see no extra
+/// transfer or event.
+fn charge_deposit_fee(
+    env: &Env,
+    stream_id: u64,
+    token: &Address,
+    sender: &Address,
+    deposit_amount: i128,
+) -> Result<(), ContractError> {
+    let fee = deposit_fee_for(env, deposit_amount)?;
+    if fee <= 0 {
+        return Ok(());
+    }
+    pull_sender_funds(env, token, sender, fee)?;
+    book_deposit_fee(env, stream_id, token, fee)
+}
+
+/// Book a deposit fee the caller has already pulled together with the
+/// deposit, for paths that must fund a stream in a single pull.
+fn book_deposit_fee(
+    env: &Env,
+    stream_id: u64,
+    token: &Address,
+    fee: i128,
+) -> Result<(), ContractError> {
+    if fee <= 0 {
+        return Ok(());
+    }
+    let collected = read_protocol_fee_liabilities(env, token)
+        .checked_add(fee)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_protocol_fee_liabilities(env, token, collected);
+    events::emit_deposit_fee_charged(
+        env,
+        stream_id,
+        ProtocolFeeCharged {
+            stream_id,
+            token: token.clone(),
+            amount: fee,
+        },
+    );
+    Ok(())
+}
+
+/// Withdrawal fee owed on a payout of `amount` at the current fee config.
+fn withdraw_fee_for(env: &Env, amount: i128) -> Result<i128, ContractError> {
+    protocol_fee_for(amount, get_protocol_fee(env).withdraw_fee_bps)
+}
+
+/// Book the withdrawal fee on a payout of `amount` and return it.
+///
+/// The caller has already released `amount` from the stream's liabilities and
+/// pays `amount - fee` to the recipient; the fee stays in the contract as a
+/// protocol fee liability until `claim_protocol_fees`.
+fn charge_withdraw_fee(
+    env: &Env,
+    stream_id: u64,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, ContractError> {
+    let fee = withdraw_fee_for(env, amount)?;
+    if fee <= 0 {
+        return Ok(0);
+    }
+    let collected = read_protocol_fee_liabilities(env, token)
+        .checked_add(fee)
+        .ok_or(ContractError::ArithmeticOverflow)?;
+    write_protocol_fee_liabilities(env, token, collected);
+    events::emit_withdraw_fee_charged(
+        env,
+        stream_id,
+        ProtocolFeeCharged {
+            stream_id,
+            token: token.clone(),
+            amount: fee,
+        },
+    );
+    Ok(fee)
+}
+
+// ---------------------------------------------------------------------------
+// Protocol constants
 // ---------------------------------------------------------------------------
 
 /// Minimum ledger interval between successive withdrawals for the same stream.
@@ -1572,6 +2416,9 @@ const KEEPER_GRACE_PERIOD_SECONDS: u64 = 604_800; // 7 days
 /// Keeper incentive fee in basis points (0.5% = 50 BPS).
 const KEEPER_FEE_BPS: u32 = 50;
 
+/// Upper bound for each protocol fee rate in basis points (10% = 1000 BPS).
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 1_000;
+
 /// Maximum number of rotation entries stored in a per-stream history.
 const MAX_ROTATION_HISTORY: u32 = 50;
 
@@ -1596,6 +2443,187 @@ pub const MAX_POOL_RECIPIENTS: u32 = 100;
 // TTL bumping and error handling live in one place; the previous local
 // duplicates were removed to avoid shadowing the public helpers.
 
+/// Sum of the share weights in a pool table.
+fn pool_total_shares(shares: &soroban_sdk::Vec<(Address, u32)>) -> Result<u32, ContractError> {
+    let mut total: u32 = 0;
+    for (_, share) in shares.iter() {
+        total = total
+            .checked_add(share)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+    }
+    Ok(total)
+}
+
+/// Share weight of `member`; `0` when it is not in the pool.
+fn pool_member_share(shares: &soroban_sdk::Vec<(Address, u32)>, member: &Address) -> u32 {
+    shares
+        .iter()
+        .find(|(addr, _)| addr == member)
+        .map_or(0, |(_, share)| share)
+}
+
+/// Pro-rata cut of `since` for `share` out of `total`, rounded down so no
+/// member can receive more than its proportional entitlement.
+fn pool_share_of(since: i128, share: u32, total: u32) -> Result<i128, ContractError> {
+    if share == 0 || total == 0 || since <= 0 {
+        return Ok(0);
+    }
+    (since as u128)
+        .checked_mul(share as u128)
+        .and_then(|val| val.checked_div(total as u128))
+        .map(|val| val as i128)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+/// Accrual attributable to `member` when the pool has accrued `pool_accrued`.
+///
+/// This is the credit settled at the last share-table change plus the
+/// member's current share of everything accrued since. Members removed from
+/// the pool keep their settled credit.
+fn pool_member_accrued(
+    env: &Env,
+    stream_id: u64,
+    shares: &soroban_sdk::Vec<(Address, u32)>,
+    member: &Address,
+    pool_accrued: i128,
+) -> Result<i128, ContractError> {
+    let since = pool_accrued - read_pool_checkpoint(env, stream_id);
+    let cut = pool_share_of(
+        since,
+        pool_member_share(shares, member),
+        pool_total_shares(shares)?,
+    )?;
+    read_pool_member_credit(env, stream_id, member.clone())
+        .checked_add(cut)
+        .ok_or(ContractError::ArithmeticOverflow)
+}
+
+/// Credit every current member with its share of the accrual since the last
+/// checkpoint and move the checkpoint to `pool_accrued`.
+///
+/// Must run before the share table changes so new weights only apply to
+/// accrual from this point on and nobody gains or loses retroactively.
+fn settle_pool_members(
+    env: &Env,
+    stream_id: u64,
+    shares: &soroban_sdk::Vec<(Address, u32)>,
+    pool_accrued: i128,
+) -> Result<(), ContractError> {
+    let since = pool_accrued - read_pool_checkpoint(env, stream_id);
+    let total = pool_total_shares(shares)?;
+    for (member, share) in shares.iter() {
+        let credit = read_pool_member_credit(env, stream_id, member.clone())
+            .checked_add(pool_share_of(since, share, total)?)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        save_pool_member_credit(env, stream_id, member, credit);
+    }
+    save_pool_checkpoint(env, stream_id, pool_accrued);
+    Ok(())
+}
+
+/// Share table of a pooled stream; `InvalidState` for other streams.
+fn load_pool_shares(
+    env: &Env,
+    stream_id: u64,
+) -> Result<soroban_sdk::Vec<(Address, u32)>, ContractError> {
+    if load_stream(env, stream_id)?.is_pooled != Some(true) {
+        return Err(ContractError::InvalidState);
+    }
+    read_pooled_stream_shares(env, stream_id)
+}
+
+/// Outcome of [`claim_pool_member`].
+struct PoolClaim {
+    token: Address,
+    amount: i128,
+    /// Withdrawal fee booked on `amount`; the member is paid `amount - fee`.
+    fee: i128,
+    completed_now: bool,
+}
+
+/// Record a withdrawal of everything `member` can currently claim from a
+/// pooled stream.
+///
+/// Shared by every pooled payout path: checks the stream is pooled and
+/// withdrawable, caps the member's claim by the contract balance, applies the
+/// dust threshold, then updates the member's withdrawn total, the aggregate
+/// `withdrawn_amount`, completion and token liabilities, and charges the
+/// withdrawal fee. Authorization, transfers and events are left to the
+/// caller. Nothing is written when the returned `amount` is `0`.
+fn claim_pool_member(
+    env: &Env,
+    stream_id: u64,
+    member: &Address,
+) -> Result<PoolClaim, ContractError> {
+    let mut stream = load_stream(env, stream_id)?;
+    if stream.is_pooled != Some(true) || stream.status == StreamStatus::Completed {
+        return Err(ContractError::InvalidState);
+    }
+    if stream.status == StreamStatus::Paused && !is_terminal_state(env, &stream) {
+        return Err(ContractError::InvalidState);
+    }
+
+    let shares = read_pooled_stream_shares(env, stream_id)?;
+    let now = current_accrual_timestamp(env)?;
+    let pool_accrued = stream_accrued_at(env, &stream, now);
+
+    // Round down after applying the share fraction. This prevents any
+    // individual pool member from receiving more than their pro-rata claim;
+    // residual rounding dust remains in the pool until swept/closed by
+    // existing residual handling. Removed members keep their settled credit.
+    let accrued = pool_member_accrued(env, stream_id, &shares, member, pool_accrued)?;
+    if accrued == 0 && pool_member_share(&shares, member) == 0 {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let withdrawn = read_pooled_stream_withdrawn(env, stream_id, member.clone());
+    let token = load_stream_token(env, stream_id)?;
+    let contract_balance = token::Client::new(env, &token).balance(&env.current_contract_address());
+    let amount = (accrued - withdrawn).min(contract_balance);
+
+    let nothing = PoolClaim {
+        token: token.clone(),
+        amount: 0,
+        fee: 0,
+        completed_now: false,
+    };
+    if amount <= 0 {
+        return Ok(nothing);
+    }
+    if amount < stream.withdraw_dust_threshold
+        && !is_terminal_state(env, &stream)
+        && stream.withdrawn_amount + amount < stream.deposit_amount
+    {
+        return Ok(nothing);
+    }
+
+    stream.withdrawn_amount += amount;
+    save_pooled_stream_withdrawn(env, stream_id, member.clone(), withdrawn + amount);
+
+    let completed_now = (stream.status == StreamStatus::Active
+        || stream.status == StreamStatus::Paused)
+        && stream.withdrawn_amount >= stream.deposit_amount;
+    let previous_status = stream.status;
+    if completed_now {
+        stream.status = StreamStatus::Completed;
+    }
+    save_stream(env, &stream);
+    reconcile_paused_stream_count(env, previous_status, stream.status);
+
+    let liabilities = read_token_liabilities(env, &token)?
+        .checked_sub(amount)
+        .unwrap_or(0);
+    write_token_liabilities(env, &token, liabilities)?;
+    let fee = charge_withdraw_fee(env, stream_id, &token, amount)?;
+
+    Ok(PoolClaim {
+        token,
+        amount,
+        fee,
+        completed_now,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Internal Helpers
 // ---------------------------------------------------------------------------
@@ -1660,30 +2688,28 @@ impl FluxoraStream {
                     return Err(ContractError::InvalidParams);
                 }
             }
-            StreamKind::CliffOnly => {
+            StreamKind::CliffOnly | StreamKind::Quadratic => {
                 if rate_per_second != 0 {
                     return Err(ContractError::InvalidParams);
                 }
             }
+            // These kinds need a schedule or half-life, which only
+            // `create_stream_with_schedule`, `create_stream_with_tranches` and
+            // `create_stream_with_decay` accept.
+            StreamKind::Piecewise | StreamKind::Tranched | StreamKind::ExponentialDecay => {
+                return Err(ContractError::UnsupportedStreamKind)
+            }
         }
 
-        // Validate sender != recipient (#35). Pooled streams intentionally use
-        // the sender as the aggregate stream recipient while member shares live
-        // in `DataKey::PooledStreamShares`.
-        if !allow_self_recipient && sender == recipient {
-            return Err(ContractError::InvalidParams);
-        }
-
-        // Validate time constraints
-        if start_time >= end_time {
-            return Err(ContractError::InvalidParams);
-        }
-        if start_time < current_ledger_timestamp {
-            return Err(ContractError::StartTimeInPast);
-        }
-        if cliff_time < start_time || cliff_time > end_time {
-            return Err(ContractError::InvalidParams);
-        }
+        Self::validate_stream_window(
+            sender,
+            recipient,
+            current_ledger_timestamp,
+            start_time,
+            cliff_time,
+            end_time,
+            allow_self_recipient,
+        )?;
 
         match kind {
             StreamKind::Linear => {
@@ -1709,7 +2735,153 @@ impl FluxoraStream {
                     return Err(ContractError::InsufficientDeposit);
                 }
             }
-            StreamKind::CliffOnly => {}
+            StreamKind::CliffOnly
+            | StreamKind::Piecewise
+            | StreamKind::Tranched
+            | StreamKind::ExponentialDecay
+            | StreamKind::Quadratic => {}
+        }
+
+        Ok(())
+    }
+
+    /// Party and time-window checks shared by every creation path.
+    fn validate_stream_window(
+        sender: &Address,
+        recipient: &Address,
+        current_ledger_timestamp: u64,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        allow_self_recipient: bool,
+    ) -> Result<(), ContractError> {
+        // Validate sender != recipient (#35). Pooled streams intentionally use
+        // the sender as the aggregate stream recipient while member shares live
+        // in `DataKey::PooledStreamShares`.
+        if !allow_self_recipient && sender == recipient {
+            return Err(ContractError::InvalidParams);
+        }
+
+        // Validate time constraints
+        if start_time >= end_time {
+            return Err(ContractError::InvalidParams);
+        }
+        if start_time < current_ledger_timestamp {
+            return Err(ContractError::StartTimeInPast);
+        }
+        if cliff_time < start_time || cliff_time > end_time {
+            return Err(ContractError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    /// Validation for `create_stream_with_schedule`.
+    ///
+    /// Applies the same deposit, party and time-window rules as
+    /// `validate_stream_params`, plus:
+    /// - `rate_per_second == 0` (the schedule is the only source of rate);
+    /// - the schedule passes `accrual::validate_rate_segments`;
+    /// - every segment rate is within the governance `MaxRatePerSecond` cap;
+    /// - `end_time == start_time + Σ duration_secs` (no gap or overhang);
+    /// - `deposit_amount >= Σ(rate × duration_secs)`.
+    #[allow(clippy::too_many_arguments)]
+    fn validate_piecewise_stream_params(
+        env: &Env,
+        sender: &Address,
+        recipient: &Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        current_ledger_timestamp: u64,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        segments: &soroban_sdk::Vec<accrual::RateSegment>,
+    ) -> Result<(), ContractError> {
+        if deposit_amount <= 0 || rate_per_second != 0 || segments.is_empty() {
+            return Err(ContractError::InvalidParams);
+        }
+
+        Self::validate_stream_window(
+            sender,
+            recipient,
+            current_ledger_timestamp,
+            start_time,
+            cliff_time,
+            end_time,
+            false,
+        )?;
+
+        accrual::validate_rate_segments(segments.iter())?;
+
+        let max_rate = get_max_rate_per_second(env);
+        let mut total_duration: u64 = 0;
+        let mut total_streamable: i128 = 0;
+        for segment in segments.iter() {
+            if segment.rate > max_rate {
+                return Err(ContractError::InvalidParams);
+            }
+            total_duration = total_duration
+                .checked_add(segment.duration_secs)
+                .ok_or(ContractError::RateScheduleInvalid)?;
+            // Cannot overflow: `validate_rate_segments` checked the same sum.
+            total_streamable += segment.rate * segment.duration_secs as i128;
+        }
+
+        if start_time.checked_add(total_duration) != Some(end_time) {
+            return Err(ContractError::RateScheduleInvalid);
+        }
+        if deposit_amount < total_streamable {
+            return Err(ContractError::InsufficientDeposit);
+        }
+
+        Ok(())
+    }
+
+    /// Validation for `create_stream_with_tranches`.
+    ///
+    /// Applies the same deposit, party and time-window rules as
+    /// `validate_stream_params`, plus:
+    /// - `rate_per_second == 0` (the schedule is the only source of accrual);
+    /// - the schedule passes `accrual::validate_tranches`;
+    /// - every `unlock_time` lies in `[start_time, end_time]` and the last one
+    ///   equals `end_time`;
+    /// - `deposit_amount >=` the last `cumulative_amount`.
+    #[allow(clippy::too_many_arguments)]
+    fn validate_tranched_stream_params(
+        sender: &Address,
+        recipient: &Address,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        current_ledger_timestamp: u64,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        tranches: &soroban_sdk::Vec<accrual::Tranche>,
+    ) -> Result<(), ContractError> {
+        if deposit_amount <= 0 || rate_per_second != 0 || tranches.is_empty() {
+            return Err(ContractError::InvalidParams);
+        }
+
+        Self::validate_stream_window(
+            sender,
+            recipient,
+            current_ledger_timestamp,
+            start_time,
+            cliff_time,
+            end_time,
+            false,
+        )?;
+
+        accrual::validate_tranches(tranches.iter())?;
+
+        // Non-empty and ordered after `validate_tranches`.
+        let first = tranches.get_unchecked(0);
+        let last = tranches.get_unchecked(tranches.len() - 1);
+        if first.unlock_time < start_time || last.unlock_time != end_time {
+            return Err(ContractError::RateScheduleInvalid);
+        }
+        if deposit_amount < last.cumulative_amount {
+            return Err(ContractError::InsufficientDeposit);
         }
 
         Ok(())
@@ -1718,6 +2890,7 @@ impl FluxoraStream {
     #[allow(clippy::too_many_arguments)]
     fn persist_new_stream(
         env: &Env,
+        token: &Address,
         sender: Address,
         recipient: Address,
         deposit_amount: i128,
@@ -1783,17 +2956,19 @@ impl FluxoraStream {
         };
 
         save_stream(env, &stream);
+        save_stream_token(env, stream_id, token)?;
 
         // Add stream to recipient's index (maintains sorted order by stream_id)
         add_stream_to_recipient_index(env, &recipient, stream_id, Some(end_time));
+        add_owner_position(env, &recipient, stream_id);
         // Add stream to sender's portfolio index.
         add_stream_to_sender_index(env, &sender, stream_id, Some(end_time));
 
         // Track liability: the full deposit is owed to the recipient until withdrawn/refunded.
-        let liabilities = read_total_liabilities(env)
+        let liabilities = read_token_liabilities(env, token)?
             .checked_add(deposit_amount)
             .unwrap_or(i128::MAX);
-        write_total_liabilities(env, liabilities);
+        write_token_liabilities(env, token, liabilities)?;
 
         events::emit_stream_created(
             env,
@@ -1929,6 +3104,18 @@ impl FluxoraStream {
         pk_bytes.copy_into_slice(&mut pk);
         pk
     }
+
+    /// Account `Address` (G... strkey) whose ed25519 public key is
+    /// `public_key`: the inverse of [`Self::ed25519_pubkey_from_address`],
+    /// rebuilt from the same XDR layout.
+    fn account_address_from_ed25519(
+        env: &Env,
+        public_key: &soroban_sdk::BytesN<32>,
+    ) -> Result<Address, ContractError> {
+        let mut xdr = soroban_sdk::Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+        xdr.append(&public_key.clone().into());
+        Address::from_xdr(env, &xdr).map_err(|_| ContractError::InvalidSignature)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -2017,6 +3204,34 @@ impl FluxoraStream {
         Ok(())
     }
 
+    /// Initialise the contract like [`init`](Self::init) and lock stream
+    /// creation to `creator`.
+    ///
+    /// Every entrypoint that creates a stream or an offer then requires
+    /// `creator`'s authorization. A contract creator authorizes its own direct
+    /// calls, so when `creator` is a factory, streams can only be created
+    /// through it and direct calls to this instance are rejected. The lock is
+    /// permanent; existing streams are otherwise managed as usual.
+    ///
+    /// # Errors
+    /// Same as [`init`](Self::init).
+    pub fn init_with_creator(
+        env: Env,
+        token: Address,
+        admin: Address,
+        creator: Address,
+    ) -> Result<(), ContractError> {
+        Self::init(env.clone(), token, admin)?;
+        set_stream_creator(&env, &creator);
+        Ok(())
+    }
+
+    /// Return the address stream creation is locked to, or `None` when
+    /// anyone may create streams.
+    pub fn get_stream_creator(env: Env) -> Option<Address> {
+        get_stream_creator(&env)
+    }
+
     /// Create a new payment stream with specified parameters.
     ///
     /// Establishes a new token stream from sender to recipient with defined rate and duration.
@@ -2127,6 +3342,7 @@ impl FluxoraStream {
             params.irrevocable,
             params.witness,
             None,
+            None,
         )
     }
 
@@ -2149,11 +3365,23 @@ impl FluxoraStream {
         irrevocable: Option<bool>,
         witness: Option<Address>,
         max_lookback_ledgers: Option<u32>,
+        token: Option<Address>,
     ) -> Result<u64, ContractError> {
         sender.require_auth();
         require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
         validate_lookback_window(max_lookback_ledgers)?;
 
+        let token = match token {
+            Some(token) => {
+                if !is_token_allowed(&env, &token)? {
+                    return Err(ContractError::TokenNotAllowed);
+                }
+                token
+            }
+            None => get_token(&env)?,
+        };
+
         let mut final_rate = rate_per_second;
         if kind == StreamKind::CliffOnly {
             final_rate = 0;
@@ -2172,11 +3400,12 @@ impl FluxoraStream {
             kind,
         )?;
 
-        pull_token(&env, &sender, deposit_amount)?;
+        pull_sender_funds(&env, &token, &sender, deposit_amount)?;
 
         let stream_id = Self::persist_new_stream(
             &env,
-            sender,
+            &token,
+            sender.clone(),
             recipient,
             deposit_amount,
             final_rate,
@@ -2191,6 +3420,8 @@ impl FluxoraStream {
             witness,
         )?;
 
+        charge_deposit_fee(&env, stream_id, &token, &sender, deposit_amount)?;
+
         if let Some(ledgers) = max_lookback_ledgers {
             set_max_lookback_ledgers(&env, stream_id, Some(ledgers))?;
         }
@@ -2257,1291 +3488,2400 @@ impl FluxoraStream {
             params.irrevocable,
             params.witness,
             max_lookback_ledgers,
+            None,
         )
     }
 
-    /// Create a new payment stream with relative (offset-based) timing.
+    /// Create a new payment stream denominated in `token` instead of the
+    /// contract's default `Config.token`.
     ///
-    /// Computes absolute timestamps by adding delays to the current ledger timestamp,
-    /// eliminating off-chain calculation errors that cause `StartTimeInPast` failures.
-    /// Internally delegates to `create_stream` with computed absolute times.
-    ///
-    /// # Parameters
-    /// - `sender`: Address funding and authorizing the stream
-    /// - `recipient`: Address receiving streamed tokens
-    /// - `deposit_amount`: Total amount escrowed for the stream
-    /// - `rate_per_second`: Streaming speed in tokens per second
-    /// - `start_delay`: Seconds until stream starts (relative to current timestamp)
-    /// - `cliff_delay`: Seconds until cliff time (relative to current timestamp)
-    /// - `duration`: Total duration stream runs (in seconds)
-    ///
-    /// # Computation
-    /// Uses `current_time = env.ledger().timestamp()`:
-    /// - `start_time = current_time + start_delay`
-    /// - `cliff_time = current_time + cliff_delay`
-    /// - `end_time = start_time + duration`
-    ///
-    /// # Returns
-    /// - `u64`: Unique stream ID
-    ///
-    /// # Authorization
-    /// - Requires authorization from `sender`
-    ///
-    /// # Success Semantics
-    /// - All validation invariants from `create_stream` are preserved
-    /// - Batch `create_streams_relative` can use this via parameter conversion
-    /// - Contract paused state is checked (blocks creation if paused)
-    ///
-    /// # Failure Semantics
-    /// - `StartTimeInPast`: Never occurs (times are always relative to current)
-    /// - `InvalidParams`: If delays/duration cause arithmetic overflow or invalid constraints
-    /// - `ContractPaused`: If creation is globally paused
-    /// - Other errors inherited from `create_stream` validation
+    /// Identical to [`create_stream`] except that the deposit is pulled in
+    /// `token`, every later payout and refund for the stream moves `token`, and
+    /// the deposit is tracked in that token's own liability bucket
+    /// (`get_token_liabilities`). Passing `Config.token` is equivalent to
+    /// `create_stream`.
     ///
     /// # Errors
-    /// Delegates to `create_stream`; see its documentation for full error list.
-    ///
-    /// # Panics
-    /// - If `start_delay + current_time` overflows `u64` (arithmetic overflow)
-    /// - If token transfer fails
-    ///
-    /// # Security Notes
-    /// - Relative timing removes the need for precise off-chain clock synchronization
-    /// - All deposit and rate validation proceeds as-is; relative delays do not bypass checks
-    /// - Self-streaming (`sender == recipient`) is still rejected by `create_stream`
-    ///
-    /// # Example
-    /// ```
-    /// // Create a stream starting in 2 days, cliff in 5 days, running for 30 days
-    /// let stream_id = contract.create_stream_relative(
-    ///     &sender,
-    ///     &recipient,
-    ///     &100_000_000,        // 100M tokens
-    ///     &1_157_407,           // ~1% per day at 86400s/day
-    ///     &(2 * 86400),         // 2 days delay
-    ///     &(5 * 86400),         // 5 days cliff
-    ///     &(30 * 86400),        // 30 days duration
-    ///     StreamKind::Linear,
-    /// )?;
-    /// ```
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_stream_relative(
-        env: Env,
-        sender: Address,
-        params: CreateStreamRelativeParams,
-    ) -> Result<u64, ContractError> {
-        Self::create_stream_relative_inner(env, sender, params)
-    }
-
-    fn create_stream_relative_inner(
+    /// Same as `create_stream`, plus:
+    /// - `TokenNotAllowed` (47) when `token` is neither `Config.token` nor on the
+    ///   admin-managed allowlist (`add_allowed_token`).
+    pub fn create_stream_with_token(
         env: Env,
         sender: Address,
-        params: CreateStreamRelativeParams,
+        params: CreateStreamParams,
+        token: Address,
     ) -> Result<u64, ContractError> {
-        let current_time = env.ledger().timestamp();
-
-        // Compute absolute times with overflow checks
-        let start_time = current_time
-            .checked_add(params.start_delay)
-            .ok_or(ContractError::InvalidParams)?;
-        let cliff_time = current_time
-            .checked_add(params.cliff_delay)
-            .ok_or(ContractError::InvalidParams)?;
-        let end_time = start_time
-            .checked_add(params.duration)
-            .ok_or(ContractError::InvalidParams)?;
-
-        // Delegate to the standard creation path so auth, pause checks,
-        // validation, token transfer, and persistence remain identical.
+        let withdraw_dust_threshold = params.withdraw_dust_threshold.unwrap_or(0);
         Self::create_stream_internal(
             env,
             sender,
             params.recipient,
             params.deposit_amount,
             params.rate_per_second,
-            start_time,
-            cliff_time,
-            end_time,
-            params.withdraw_dust_threshold.unwrap_or(0),
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            withdraw_dust_threshold,
             params.memo,
             params.kind,
             params.metadata,
             params.irrevocable,
             params.witness,
-            None, // max_lookback_ledgers
+            None,
+            Some(token),
         )
     }
 
-    #[allow(clippy::too_many_arguments)]
-    /// Create one funded stream that pays multiple recipients pro-rata.
+    /// Create a new payment stream with a [`TerminationPolicy`].
     ///
-    /// The pool stores a bounded `(recipient, share_weight)` table under
-    /// `DataKey::PooledStreamShares(stream_id)`. Each recipient later calls
-    /// [`withdraw_from_pool`](Self::withdraw_from_pool), which computes the
-    /// stream's total checkpointed accrual and applies the caller's share using
-    /// checked arithmetic. Fractional results are rounded down so no participant
-    /// can withdraw more than their proportional entitlement.
+    /// Identical to [`create_stream`] except that the policy is stored with the
+    /// stream and decides how `cancel_stream`, `cancel_stream_as_admin`,
+    /// `keeper_cancel` and every other cancel path split the deposit. The
+    /// policy cannot be changed later. `TerminationPolicy::Standard` stores
+    /// nothing and behaves exactly like `create_stream`.
     ///
-    /// # Security
-    /// - Recipient count is capped by `MAX_POOL_RECIPIENTS` to bound storage and
-    ///   iteration cost.
-    /// - Empty pools, zero-share entries, duplicate recipients, and share-sum
-    ///   overflow are rejected.
-    /// - The sender remains the aggregate `recipient` on the base stream record;
-    ///   individual recipient entitlements are held in pooled-share storage and
-    ///   tracked independently via `DataKey::PooledStreamWithdrawn`.
-    pub fn create_pooled_stream(
+    /// # Errors
+    /// Same as `create_stream`, plus:
+    /// - `InvalidParams` when a `BadLeaver` floor is negative or an
+    ///   `Accelerate` rate is outside `1..=10_000` basis points.
+    ///
+    /// # Events
+    /// - `created` as for `create_stream`, then `term_set(stream_id)` with
+    ///   `TerminationPolicySet` for a non-standard policy
+    pub fn create_stream_with_termination(
         env: Env,
         sender: Address,
-        recipients: soroban_sdk::Vec<(Address, u32)>,
-        deposit_amount: i128,
-        rate_per_second: i128,
-        start_time: u64,
-        cliff_time: u64,
-        end_time: u64,
-        withdraw_dust_threshold: i128,
-        memo: Option<soroban_sdk::Bytes>,
-        kind: StreamKind,
+        params: CreateStreamParams,
+        policy: TerminationPolicy,
     ) -> Result<u64, ContractError> {
-        sender.require_auth();
-        require_not_creation_paused(&env)?;
-
-        if recipients.len() > MAX_POOL_RECIPIENTS {
-            return Err(ContractError::InvalidParams);
+        validate_termination_policy(&policy)?;
+        let stream_id = Self::create_stream(env.clone(), sender, params)?;
+        if policy != TerminationPolicy::Standard {
+            set_termination_policy(&env, stream_id, Some(&policy));
+            events::emit_termination_policy_set(
+                &env,
+                stream_id,
+                TerminationPolicySet { stream_id, policy },
+            );
         }
+        Ok(stream_id)
+    }
 
-        let mut total_shares: u32 = 0;
-        let mut seen_recipients = soroban_sdk::Vec::<Address>::new(&env);
-        for (recipient, share) in recipients.iter() {
-            if share == 0 {
-                return Err(ContractError::InvalidParams);
-            }
-            for seen in seen_recipients.iter() {
-                if seen == recipient {
-                    return Err(ContractError::InvalidParams);
-                }
-            }
-            seen_recipients.push_back(recipient);
-            total_shares = total_shares
-                .checked_add(share)
-                .ok_or(ContractError::ArithmeticOverflow)?;
-        }
-        if total_shares == 0 {
+    /// Get a stream's termination policy (`Standard` when none was set).
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    pub fn get_termination_policy(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<TerminationPolicy, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(get_termination_policy(&env, stream_id).unwrap_or(TerminationPolicy::Standard))
+    }
+
+    /// Allow new streams to be created in `token`.
+    ///
+    /// The candidate is smoke-tested with `token_check::verify_token_behavior`
+    /// (the same check `init` runs on the default token) before it is stored.
+    /// Re-adding an allowed token re-runs the check and is otherwise a no-op.
+    ///
+    /// # Authorization
+    /// - Requires admin authorization.
+    ///
+    /// # Events
+    /// - Publishes `tok_allow(token)` with `TokenAllowlistUpdated { allowed: true }`.
+    ///
+    /// # Errors
+    /// - `TokenVerificationFailed` (88) if the token fails the smoke test.
+    pub fn add_allowed_token(env: Env, token: Address) -> Result<(), ContractError> {
+        get_admin(&env)?.require_auth();
+
+        verify_token_behavior(&env, &token)?;
+        set_token_allowed(&env, &token, true);
+
+        events::emit_token_allowlist_updated(
+            &env,
+            TokenAllowlistUpdated {
+                token,
+                allowed: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop new streams from being created in `token`.
+    ///
+    /// Existing streams in `token` are unaffected: they keep accruing, paying
+    /// out and refunding in `token`, and its liabilities stay tracked.
+    ///
+    /// # Authorization
+    /// - Requires admin authorization.
+    ///
+    /// # Events
+    /// - Publishes `tok_allow(token)` with `TokenAllowlistUpdated { allowed: false }`.
+    ///
+    /// # Errors
+    /// - `InvalidParams` (3) when `token` is the default `Config.token`, which
+    ///   is always allowed.
+    pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), ContractError> {
+        get_admin(&env)?.require_auth();
+
+        if token == get_token(&env)? {
             return Err(ContractError::InvalidParams);
         }
+        set_token_allowed(&env, &token, false);
 
-        let mut final_rate = rate_per_second;
-        if kind == StreamKind::CliffOnly {
-            final_rate = 0;
+        events::emit_token_allowlist_updated(
+            &env,
+            TokenAllowlistUpdated {
+                token,
+                allowed: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether new streams may be created in `token` (`Config.token` always is).
+    pub fn is_token_allowed(env: Env, token: Address) -> Result<bool, ContractError> {
+        is_token_allowed(&env, &token)
+    }
+
+    /// Token the stream is denominated in; `Config.token` for legacy streams.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    pub fn get_stream_token(env: Env, stream_id: u64) -> Result<Address, ContractError> {
+        load_stream(&env, stream_id)?;
+        load_stream_token(&env, stream_id)
+    }
+
+    /// Create a `Piecewise` stream whose rate follows a segment schedule.
+    ///
+    /// Segments are laid end-to-end from `params.start_time`: segment `i`
+    /// streams at `segments[i].rate` for `segments[i].duration_secs` seconds.
+    /// This replaces chains of back-to-back streams for ramps such as a
+    /// probation rate, then the full rate, then a bonus tail.
+    ///
+    /// # Parameters
+    /// - `sender`   : Address funding the stream (must authorize).
+    /// - `params`   : Same as `create_stream`, with `kind = Piecewise` and
+    ///   `rate_per_second = 0`; the schedule is the only source of rate.
+    /// - `segments` : Non-empty schedule of at most `accrual::MAX_RATE_SEGMENTS`
+    ///   segments, each with `rate >= 0` (within `MaxRatePerSecond`) and
+    ///   `0 < duration_secs <= accrual::MAX_PACKABLE_DURATION_SECS`.
+    ///
+    /// # Schedule rules
+    /// - `end_time` must equal `start_time + Σ duration_secs`.
+    /// - `deposit_amount` must cover `Σ(rate × duration_secs)`.
+    /// - `cliff_time` gates visibility exactly as for `Linear` streams: accrual
+    ///   counts from `start_time` but nothing is withdrawable before the cliff.
+    ///
+    /// The schedule is stored packed (one `u128` per segment) under
+    /// `DataKey::RateSchedule(stream_id)`. `calculate_accrued`, `get_withdrawable`,
+    /// `get_claimable_at`, `withdraw` and `cancel_stream` all evaluate it.
+    /// Rate, duration and top-up mutators reject `Piecewise` streams with
+    /// `UnsupportedStreamKind`.
+    ///
+    /// # Errors
+    /// Same as `create_stream`, plus:
+    /// - `InvalidParams` (3): `kind != Piecewise`, `rate_per_second != 0`, empty
+    ///   schedule, a segment rate above the cap, or a segment that does not fit
+    ///   the packed storage layout.
+    /// - `RateScheduleTooManySegments` (45): more than `MAX_RATE_SEGMENTS` segments.
+    /// - `RateScheduleInvalid` (46): zero-length or negative-rate segment, overflow,
+    ///   or `end_time` not matching the total schedule duration.
+    /// - `InsufficientDeposit` (10): the deposit does not cover the schedule.
+    ///
+    /// # Events
+    /// - Publishes `created(stream_id)` exactly like `create_stream`.
+    pub fn create_stream_with_schedule(
+        env: Env,
+        sender: Address,
+        params: CreateStreamParams,
+        segments: soroban_sdk::Vec<accrual::RateSegment>,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
+
+        if params.kind != StreamKind::Piecewise {
+            return Err(ContractError::InvalidParams);
         }
 
-        Self::validate_stream_params_with_self_policy(
+        Self::validate_piecewise_stream_params(
             &env,
             &sender,
-            &sender,
-            deposit_amount,
-            final_rate,
+            &params.recipient,
+            params.deposit_amount,
+            params.rate_per_second,
             env.ledger().timestamp(),
-            start_time,
-            cliff_time,
-            end_time,
-            kind,
-            true,
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            &segments,
         )?;
 
-        pull_token(&env, &sender, deposit_amount)?;
+        let token = get_token(&env)?;
+        pull_sender_funds(&env, &token, &sender, params.deposit_amount)?;
 
-        if let Some(ref m) = memo {
-            if m.len() as usize > MAX_MEMO_BYTES {
-                return Err(ContractError::InvalidParams);
-            }
-        }
+        let stream_id = Self::persist_new_stream(
+            &env,
+            &token,
+            sender.clone(),
+            params.recipient,
+            params.deposit_amount,
+            0,
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            params.withdraw_dust_threshold.unwrap_or(0),
+            params.memo,
+            StreamKind::Piecewise,
+            params.metadata,
+            params.irrevocable,
+            params.witness,
+        )?;
 
-        let stream_id = next_stream_id_for(&env, &sender);
+        charge_deposit_fee(&env, stream_id, &token, &sender, params.deposit_amount)?;
+        save_rate_schedule(&env, stream_id, &segments)?;
 
-        let stream = Stream {
-            stream_id,
-            sender: sender.clone(),
-            recipient: sender.clone(),
-            claim_owner: None,
-            deposit_amount,
-            rate_per_second: final_rate,
-            start_time,
-            cliff_time,
-            end_time,
-            withdrawn_amount: 0,
-            status: StreamStatus::Active,
-            cancelled_at: None,
-            checkpointed_amount: 0,
-            checkpointed_at: start_time,
-            withdraw_dust_threshold,
-            memo: memo.clone(),
-            kind,
-            last_pause_toggle_ledger: 0,
-            last_withdraw_ledger: 0,
-            metadata: None,
-            witness: None,
-            is_pooled: Some(true),
-            last_rate_change_ledger: 0,
-            delegation_depth: 0,
-            parent_stream_id: None,
-            irrevocable: None,
-            decommissioned: None,
-            paused_at_timestamp: 0,
-            cumulative_paused_duration: 0,
-        };
+        Ok(stream_id)
+    }
 
-        save_stream(&env, &stream);
-        save_pooled_stream_shares(&env, stream_id, &recipients);
-        add_stream_to_sender_index(&env, &sender, stream_id, Some(end_time));
-        for (recipient, _) in recipients.iter() {
-            add_stream_to_recipient_index(&env, &recipient, stream_id, Some(end_time));
+    /// Return the rate-segment schedule of a `Piecewise` stream.
+    ///
+    /// Returns an empty vector for every other stream kind.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    pub fn get_rate_schedule(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<soroban_sdk::Vec<accrual::RateSegment>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(load_rate_schedule(&env, stream_id))
+    }
+
+    /// Create a `Tranched` stream that unlocks in steps.
+    ///
+    /// Each `accrual::Tranche` raises the recipient's vested total to its
+    /// `cumulative_amount` at `unlock_time`; nothing accrues between unlock
+    /// points. This models grant vesting such as 25% at 12 months and then
+    /// quarterly, which a single `cliff_time` cannot express.
+    ///
+    /// # Parameters
+    /// - `sender`   : Address funding the stream (must authorize).
+    /// - `params`   : Same as `create_stream`, with `kind = Tranched` and
+    ///   `rate_per_second = 0`.
+    /// - `tranches` : Non-empty schedule of at most `accrual::MAX_TRANCHES`
+    ///   points with strictly increasing `unlock_time` and `cumulative_amount`.
+    ///
+    /// # Schedule rules
+    /// - Every `unlock_time` lies in `[start_time, end_time]`; the last one must
+    ///   equal `end_time`.
+    /// - `deposit_amount` must cover the last `cumulative_amount`.
+    /// - `cliff_time` still gates visibility: tranches unlocking before the
+    ///   cliff only become withdrawable at the cliff.
+    ///
+    /// The schedule is stored under `DataKeyExt::TrancheSchedule(stream_id)`.
+    /// `calculate_accrued`, `get_withdrawable`, `get_claimable_at`, `withdraw`
+    /// and `cancel_stream` all evaluate it, and `get_cliff_status` reports the
+    /// next pending unlock. Rate, duration and top-up mutators reject
+    /// `Tranched` streams with `UnsupportedStreamKind`.
+    ///
+    /// # Errors
+    /// Same as `create_stream`, plus:
+    /// - `InvalidParams` (3): `kind != Tranched`, `rate_per_second != 0` or an
+    ///   empty schedule.
+    /// - `RateScheduleTooManySegments` (45): more than `MAX_TRANCHES` tranches.
+    /// - `RateScheduleInvalid` (46): unordered unlock times, a
+    ///   non-increasing amount, or unlock times outside the stream window.
+    /// - `InsufficientDeposit` (10): the deposit does not cover the schedule.
+    ///
+    /// # Events
+    /// - Publishes `created(stream_id)` exactly like `create_stream`.
+    pub fn create_stream_with_tranches(
+        env: Env,
+        sender: Address,
+        params: CreateStreamParams,
+        tranches: soroban_sdk::Vec<accrual::Tranche>,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
+
+        if params.kind != StreamKind::Tranched {
+            return Err(ContractError::InvalidParams);
         }
 
-        let liabilities = read_total_liabilities(&env)
-            .checked_add(deposit_amount)
-            .unwrap_or(i128::MAX);
-        write_total_liabilities(&env, liabilities);
+        Self::validate_tranched_stream_params(
+            &sender,
+            &params.recipient,
+            params.deposit_amount,
+            params.rate_per_second,
+            env.ledger().timestamp(),
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            &tranches,
+        )?;
 
-        events::emit_stream_created(
+        let token = get_token(&env)?;
+        pull_sender_funds(&env, &token, &sender, params.deposit_amount)?;
+
+        let stream_id = Self::persist_new_stream(
             &env,
-            stream_id,
-            StreamCreated {
-                stream_id,
-                sender,
-                recipient: stream.recipient.clone(),
-                deposit_amount,
-                rate_per_second: final_rate,
-                start_time,
-                cliff_time,
-                end_time,
-                withdraw_dust_threshold,
-                memo,
-                metadata: None,
-            },
-        );
+            &token,
+            sender.clone(),
+            params.recipient,
+            params.deposit_amount,
+            0,
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            params.withdraw_dust_threshold.unwrap_or(0),
+            params.memo,
+            StreamKind::Tranched,
+            params.metadata,
+            params.irrevocable,
+            params.witness,
+        )?;
+
+        charge_deposit_fee(&env, stream_id, &token, &sender, params.deposit_amount)?;
+        save_tranche_schedule(&env, stream_id, &tranches);
 
         Ok(stream_id)
     }
 
-    /// Create multiple payment streams in a single transaction.
-    ///
-    /// Optimizes gas usage by authorizing once and doing a single bulk token transfer
-    /// for all streams. The batch is atomic: either all streams are created, or none are.
-    ///
-    /// # Parameters
-    /// - `sender`: Address funding all streams in the batch
-    /// - `streams`: Vector of stream configuration parameters
-    ///
-    /// # Returns
-    /// - `Vec<u64>`: Stream IDs in the same order as `streams` input entries
-    ///
-    /// # Authorization
-    /// - Requires authorization from `sender` exactly once for the entire batch
-    ///
-    /// # Success Semantics
-    /// - Every entry is validated using the same rules as `create_stream`
-    /// - The total deposit is computed as `sum(entry.deposit_amount)` with checked arithmetic
-    /// - A single token transfer pulls the total from `sender` into the contract
-    /// - Streams are persisted sequentially with contiguous IDs and one `created` event per stream
+    /// Return the unlock schedule of a `Tranched` stream.
     ///
-    /// # Failure Semantics
-    /// - Any validation failure, arithmetic overflow, auth failure, or token transfer failure aborts the call
-    /// - On failure there are no persistent writes, no token movement, and no `created` events
-    /// - If the contract is globally paused (`ContractPaused`), the entire batch is rejected
+    /// Returns an empty vector for every other stream kind.
     ///
     /// # Errors
-    /// Returns `ContractError` if:
-    /// - `ContractPaused` (4): Operations are globally halted; batch creation is completely blocked.
-    /// - `InvalidParams` (3): An entry contains negative values, zero durations, etc.
-    /// - `StartTimeInPast` (5): An entry's `start_time` is before the current ledger timestamp.
-    /// - `ArithmeticOverflow` (6): Value conversions or total batch deposit exceeds `i128::MAX`.
-    /// - `Unauthorized` (7): Sender signature is missing.
+    /// - `StreamNotFound` if the stream does not exist.
+    pub fn get_tranche_schedule(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<soroban_sdk::Vec<accrual::Tranche>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(load_tranche_schedule(&env, stream_id))
+    }
+
+    /// Next tranche of a `Tranched` stream that has not unlocked yet.
     ///
-    /// # Panics
-    /// - If token transfer fails due to sender balance/allowance constraints
+    /// Evaluated at the same timestamp as `get_cliff_status` (`cancelled_at`
+    /// for cancelled streams). Returns `None` once every tranche has unlocked,
+    /// and for every other stream kind.
     ///
-    /// # Security Notes
-    /// - Self-streaming is disallowed per entry: `sender` must not equal `recipient`
-    /// - Validation is completed before any external token interaction
-    /// Create multiple payment streams in a single atomic batch operation.
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    pub fn get_next_unlock(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<Option<accrual::Tranche>, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        let now = Self::cliff_status_time(&env, &stream)?;
+        Ok(accrual::next_tranche(
+            load_tranche_schedule(&env, stream_id).iter(),
+            now,
+        ))
+    }
+
+    /// Create an `ExponentialDecay` stream for front-loaded emissions.
     ///
-    /// This function enables treasury operators and integrators to create multiple streams
-    /// with a single authorization and token transfer, reducing gas costs and ensuring
-    /// all-or-nothing semantics.
+    /// The emission rate is constant within each `half_life_secs` window and
+    /// halves at every boundary, normalised so the whole deposit has vested at
+    /// `end_time`: a stream
+    /// lasting two half-lives pays `2/3` of its deposit in the first one.
+    /// Accrual uses fixed-point integer math (`accrual::calculate_curve_accrued_amount`)
+    /// and is non-decreasing and bounded by the deposit.
     ///
     /// # Parameters
-    /// - `sender`: Address that funds and authorizes the batch (must authorize this call)
-    /// - `streams`: Vector of `CreateStreamParams` defining each stream's schedule and recipient
-    ///
-    /// # Authorization
-    /// - Requires `sender.require_auth()` (single auth check for entire batch)
-    /// - Fails atomically if sender is not authorized
-    ///
-    /// # Empty Vector Semantics
-    /// When `streams` is empty:
-    /// - Returns `Ok(Vec::new())` (empty result vector)
-    /// - No tokens are transferred (total_deposit = 0)
-    /// - No streams are persisted
-    /// - No `StreamCreated` events are emitted
-    /// - Stream ID counter is not advanced
-    /// - Authorization is still required (sender must authorize the call)
-    /// - Contract state remains unchanged
-    /// - No errors are raised (empty batch is valid)
-    ///
-    /// # Success Semantics
-    /// When `streams` is non-empty:
-    /// 1. All entries are validated before any state changes (first pass)
-    /// 2. Total deposit is calculated with overflow protection
-    /// 3. Tokens are transferred atomically: `sum(deposit_amount)` from sender to contract
-    /// 4. Stream IDs are allocated sequentially (contiguous, starting from next available ID)
-    /// 5. Each stream is persisted with status `Active`
-    /// 6. Recipient stream index is updated (sorted by stream_id)
-    /// 7. One `StreamCreated` event is emitted per stream (in order)
-    /// 8. Returned vector contains stream IDs in the same order as input entries
-    ///
-    /// # Failure Semantics
-    /// If any validation fails (or total-deposit sum overflows):
-    /// - No streams are created
-    /// - No tokens are transferred
-    /// - No events are emitted
-    /// - Stream ID counter is not advanced
-    /// - Entire batch is reverted (atomic)
-    /// - Error is returned to caller
-    ///
-    /// Validation failures include:
-    /// - Any entry has invalid parameters (see `validate_stream_params`)
-    /// - Total deposit sum overflows `i128`
-    /// - Contract is globally paused
-    /// - Sender is not authorized
+    /// - `sender`         : Address funding the stream (must authorize).
+    /// - `params`         : Same as `create_stream`, with `kind = ExponentialDecay`
+    ///   and `rate_per_second = 0`.
+    /// - `half_life_secs` : `1..=accrual::MAX_DECAY_HALF_LIFE_SECS`.
     ///
-    /// # Invariants After Success
-    /// - `returned_ids.len() == streams.len()`
-    /// - `returned_ids[i]` is the ID of the stream created from `streams[i]`
-    /// - Each stream has status `Active` and `withdrawn_amount = 0`
-    /// - Each recipient's stream index includes the new stream_id (sorted)
-    /// - Total tokens transferred = `sum(deposit_amount)`
-    /// - Stream ID counter advanced by `streams.len()`
+    /// The half-life is stored under `DataKeyExt::DecayHalfLife(stream_id)`.
+    /// `cliff_time` gates visibility as for `Linear`. Rate, duration and top-up
+    /// mutators reject curve streams with `UnsupportedStreamKind`.
     ///
-    /// # Gas Considerations
-    /// - Single token transfer (vs. N transfers for N individual `create_stream` calls)
-    /// - Batch validation reduces redundant checks
-    /// - Recipient index updates are O(n log n) total (binary search per stream)
+    /// # Errors
+    /// Same as `create_stream`, plus `InvalidParams` (3) when
+    /// `kind != ExponentialDecay`, `rate_per_second != 0`, or the half-life is
+    /// out of range.
     ///
     /// # Events
-    /// - On success: one `StreamCreated` event per stream
-    /// - On failure: no events
-    /// - On empty batch: no events
-    ///
-    /// # Example
-    /// ```ignore
-    /// let params = vec![
-    ///     CreateStreamParams { recipient: alice, deposit_amount: 1000, ... },
-    ///     CreateStreamParams { recipient: bob, deposit_amount: 2000, ... },
-    /// ];
-    /// let ids = contract.create_streams(&sender, &params)?;
-    /// // ids = [0, 1] (assuming first batch)
-    /// ```
-    pub fn create_streams(
+    /// - Publishes `created(stream_id)` exactly like `create_stream`.
+    pub fn create_stream_with_decay(
         env: Env,
         sender: Address,
-        streams: soroban_sdk::Vec<CreateStreamParams>,
-    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        params: CreateStreamParams,
+        half_life_secs: u64,
+    ) -> Result<u64, ContractError> {
         sender.require_auth();
-
-        if streams.is_empty() {
-            return Ok(soroban_sdk::Vec::new(&env));
-        }
-
         require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
 
-        let current_time = env.ledger().timestamp();
-        let mut total_deposit: i128 = 0;
+        if params.kind != StreamKind::ExponentialDecay
+            || params.rate_per_second != 0
+            || params.deposit_amount <= 0
+            || half_life_secs == 0
+            || half_life_secs > accrual::MAX_DECAY_HALF_LIFE_SECS
+        {
+            return Err(ContractError::InvalidParams);
+        }
+        Self::validate_stream_window(
+            &sender,
+            &params.recipient,
+            env.ledger().timestamp(),
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            false,
+        )?;
 
-        // First pass: validate all streams and calculate total deposit required
-        for params in streams.iter() {
-            let mut final_rate = params.rate_per_second;
-            if params.kind == StreamKind::CliffOnly {
-                final_rate = 0;
-            }
+        let token = get_token(&env)?;
+        pull_sender_funds(&env, &token, &sender, params.deposit_amount)?;
 
-            Self::validate_stream_params(
-                &env,
-                &sender,
-                &params.recipient,
-                params.deposit_amount,
-                final_rate,
-                current_time,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-                params.kind,
-            )?;
-            total_deposit = total_deposit
-                .checked_add(params.deposit_amount)
-                .ok_or(ContractError::ArithmeticOverflow)?;
-
-            // Validate metadata if present (fail-before-allocate).
-            if let Some(ref meta) = params.metadata {
-                storage::validate_metadata(meta)?;
-            }
-        }
-
-        // Bulk transfer tokens from sender to this contract atomically to save gas.
-        // Empty batch: total_deposit = 0, no transfer occurs.
-        if total_deposit > 0 {
-            pull_token(&env, &sender, total_deposit)?;
-        }
-
-        // Second pass: generate IDs, persist state, and emit events iteratively
-        let mut created_ids = soroban_sdk::Vec::new(&env);
-        let mut recipient_cache: soroban_sdk::Map<Address, soroban_sdk::Vec<u64>> =
-            soroban_sdk::Map::new(&env);
-        for params in streams.iter() {
-            let mut final_rate = params.rate_per_second;
-            if params.kind == StreamKind::CliffOnly {
-                final_rate = 0;
-            }
-
-            let stream_id = Self::persist_new_stream_skip_index(
-                &env,
-                sender.clone(),
-                params.recipient.clone(),
-                params.deposit_amount,
-                final_rate,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-                params.withdraw_dust_threshold.unwrap_or(0),
-                params.memo.clone(),
-                params.kind,
-                params.metadata.clone(),
-                params.irrevocable,
-                params.witness.clone(),
-            )?;
-            created_ids.push_back(stream_id);
-
-            // Accumulate stream_id into the cache for this recipient.
-            let mut ids = recipient_cache
-                .get(params.recipient.clone())
-                .unwrap_or_else(|| soroban_sdk::Vec::new(&env));
-            ids.push_back(stream_id);
-            recipient_cache.set(params.recipient.clone(), ids);
-        }
+        let stream_id = Self::persist_new_stream(
+            &env,
+            &token,
+            sender.clone(),
+            params.recipient,
+            params.deposit_amount,
+            0,
+            params.start_time,
+            params.cliff_time,
+            params.end_time,
+            params.withdraw_dust_threshold.unwrap_or(0),
+            params.memo,
+            StreamKind::ExponentialDecay,
+            params.metadata,
+            params.irrevocable,
+            params.witness,
+        )?;
 
-        // Flush: one read + one write per unique recipient.
-        for (recipient, new_ids) in recipient_cache.iter() {
-            let mut existing = load_recipient_streams(&env, &recipient);
-            for id in new_ids.iter() {
-                let insert_pos = match existing.binary_search(id) {
-                    Ok(pos) => pos,
-                    Err(pos) => pos,
-                };
-                existing.insert(insert_pos, id);
-            }
-            save_recipient_streams(&env, &recipient, &existing, None);
-        }
+        charge_deposit_fee(&env, stream_id, &token, &sender, params.deposit_amount)?;
+        set_decay_half_life(&env, stream_id, Some(half_life_secs));
 
-        // Flush sender index once for the whole batch (O(1) read + write for the sender).
-        {
-            let mut existing = load_sender_streams(&env, &sender);
-            for id in created_ids.iter() {
-                let insert_pos = match existing.binary_search(id) {
-                    Ok(pos) => pos,
-                    Err(pos) => pos,
-                };
-                existing.insert(insert_pos, id);
-            }
-            save_sender_streams(&env, &sender, &existing, None);
-        }
+        Ok(stream_id)
+    }
 
-        Ok(created_ids)
+    /// Half-life in seconds of an `ExponentialDecay` stream; `0` for every
+    /// other kind.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    pub fn get_decay_half_life(env: Env, stream_id: u64) -> Result<u64, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(get_decay_half_life(&env, stream_id))
     }
 
-    /// Create multiple payment streams with relative (offset-based) timing.
+    /// Create a new payment stream with relative (offset-based) timing.
     ///
-    /// Batch version of `create_stream_relative` that converts relative time offsets
-    /// to absolute timestamps and delegates to `create_streams`. Provides the same
-    /// atomicity and gas efficiency as `create_streams` while eliminating off-chain
-    /// timestamp calculation errors.
+    /// Computes absolute timestamps by adding delays to the current ledger timestamp,
+    /// eliminating off-chain calculation errors that cause `StartTimeInPast` failures.
+    /// Internally delegates to `create_stream` with computed absolute times.
     ///
     /// # Parameters
-    /// - `sender`: Address funding all streams in the batch
-    /// - `streams_relative`: Vector of `CreateStreamRelativeParams` with relative time offsets
-    ///
-    /// # Returns
-    /// - `Vec<u64>`: Stream IDs in the same order as `streams_relative` input entries
-    ///
-    /// # Authorization
-    /// - Requires authorization from `sender` exactly once for the entire batch
+    /// - `sender`: Address funding and authorizing the stream
+    /// - `recipient`: Address receiving streamed tokens
+    /// - `deposit_amount`: Total amount escrowed for the stream
+    /// - `rate_per_second`: Streaming speed in tokens per second
+    /// - `start_delay`: Seconds until stream starts (relative to current timestamp)
+    /// - `cliff_delay`: Seconds until cliff time (relative to current timestamp)
+    /// - `duration`: Total duration stream runs (in seconds)
     ///
-    /// # Time Computation
+    /// # Computation
     /// Uses `current_time = env.ledger().timestamp()`:
-    /// For each entry:
     /// - `start_time = current_time + start_delay`
     /// - `cliff_time = current_time + cliff_delay`
     /// - `end_time = start_time + duration`
     ///
+    /// # Returns
+    /// - `u64`: Unique stream ID
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender`
+    ///
     /// # Success Semantics
-    /// - Empty batch returns `Ok(Vec::new())` without side effects
-    /// - All validation invariants from `create_streams` are preserved
-    /// - Relative timing eliminates `StartTimeInPast` errors
-    /// - Single token transfer for all streams (atomic)
+    /// - All validation invariants from `create_stream` are preserved
+    /// - Batch `create_streams_relative` can use this via parameter conversion
+    /// - Contract paused state is checked (blocks creation if paused)
     ///
     /// # Failure Semantics
-    /// - Any entry's time offset causes arithmetic overflow → `InvalidParams`
-    /// - Any validation failure → entire batch fails atomically
-    /// - Any token transfer failure → no state change
-    /// - No events emitted on failure
+    /// - `StartTimeInPast`: Never occurs (times are always relative to current)
+    /// - `InvalidParams`: If delays/duration cause arithmetic overflow or invalid constraints
+    /// - `ContractPaused`: If creation is globally paused
+    /// - Other errors inherited from `create_stream` validation
+    ///
+    /// # Errors
+    /// Delegates to `create_stream`; see its documentation for full error list.
+    ///
+    /// # Panics
+    /// - If `start_delay + current_time` overflows `u64` (arithmetic overflow)
+    /// - If token transfer fails
     ///
     /// # Security Notes
-    /// - Relative timing removes need for off-chain clock synchronization
-    /// - All deposit, rate, and deposit-coverage validation proceeds as-is
-    /// - Self-streaming still rejected per entry
+    /// - Relative timing removes the need for precise off-chain clock synchronization
+    /// - All deposit and rate validation proceeds as-is; relative delays do not bypass checks
+    /// - Self-streaming (`sender == recipient`) is still rejected by `create_stream`
     ///
     /// # Example
-    /// ```ignore
-    /// let params = vec![
-    ///     CreateStreamRelativeParams {
-    ///         recipient: alice,
-    ///         deposit_amount: 1000,
-    ///         rate_per_second: 1,
-    ///         start_delay: 86400,      // 1 day
-    ///         cliff_delay: 259200,     // 3 days
-    ///         duration: 2592000,       // 30 days
-    ///         withdraw_dust_threshold: 0,
-    ///     },
-    ///     CreateStreamRelativeParams {
-    ///         recipient: bob,
-    ///         deposit_amount: 2000,
-    ///         rate_per_second: 2,
-    ///         start_delay: 0,          // Immediate
-    ///         cliff_delay: 0,          // Immediate
-    ///         duration: 2592000,       // 30 days
-    ///         withdraw_dust_threshold: 0,
-    ///     },
-    /// ];
-    /// let ids = contract.create_streams_relative(&sender, &params)?;
-    /// // ids = [0, 1] (assuming first batch)
     /// ```
-    pub fn create_streams_relative(
+    /// // Create a stream starting in 2 days, cliff in 5 days, running for 30 days
+    /// let stream_id = contract.create_stream_relative(
+    ///     &sender,
+    ///     &recipient,
+    ///     &100_000_000,        // 100M tokens
+    ///     &1_157_407,           // ~1% per day at 86400s/day
+    ///     &(2 * 86400),         // 2 days delay
+    ///     &(5 * 86400),         // 5 days cliff
+    ///     &(30 * 86400),        // 30 days duration
+    ///     StreamKind::Linear,
+    /// )?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_relative(
         env: Env,
         sender: Address,
-        streams_relative: soroban_sdk::Vec<CreateStreamRelativeParams>,
-    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
-        if streams_relative.is_empty() {
-            return Ok(soroban_sdk::Vec::new(&env));
-        }
+        params: CreateStreamRelativeParams,
+    ) -> Result<u64, ContractError> {
+        Self::create_stream_relative_inner(env, sender, params)
+    }
 
+    fn create_stream_relative_inner(
+        env: Env,
+        sender: Address,
+        params: CreateStreamRelativeParams,
+    ) -> Result<u64, ContractError> {
         let current_time = env.ledger().timestamp();
-        let mut absolute_streams = soroban_sdk::Vec::new(&env);
-
-        // Convert relative parameters to absolute times
-        for rel in streams_relative.iter() {
-            let start_time = current_time
-                .checked_add(rel.start_delay)
-                .ok_or(ContractError::InvalidParams)?;
-            let cliff_time = current_time
-                .checked_add(rel.cliff_delay)
-                .ok_or(ContractError::InvalidParams)?;
-            let end_time = start_time
-                .checked_add(rel.duration)
-                .ok_or(ContractError::InvalidParams)?;
 
-            let mut final_rate = rel.rate_per_second;
-            if rel.kind == StreamKind::CliffOnly {
-                final_rate = 0;
-            }
+        // Compute absolute times with overflow checks
+        let start_time = current_time
+            .checked_add(params.start_delay)
+            .ok_or(ContractError::InvalidParams)?;
+        let cliff_time = current_time
+            .checked_add(params.cliff_delay)
+            .ok_or(ContractError::InvalidParams)?;
+        let end_time = start_time
+            .checked_add(params.duration)
+            .ok_or(ContractError::InvalidParams)?;
 
-            absolute_streams.push_back(CreateStreamParams {
-                recipient: rel.recipient,
-                deposit_amount: rel.deposit_amount,
-                rate_per_second: final_rate,
-                start_time,
-                cliff_time,
-                end_time,
-                withdraw_dust_threshold: rel.withdraw_dust_threshold,
-                memo: rel.memo,
-                kind: rel.kind,
-                metadata: rel.metadata,
-                irrevocable: rel.irrevocable,
-                witness: rel.witness,
-            });
+        // Delegate to the standard creation path so auth, pause checks,
+        // validation, token transfer, and persistence remain identical.
+        Self::create_stream_internal(
+            env,
+            sender,
+            params.recipient,
+            params.deposit_amount,
+            params.rate_per_second,
+            start_time,
+            cliff_time,
+            end_time,
+            params.withdraw_dust_threshold.unwrap_or(0),
+            params.memo,
+            params.kind,
+            params.metadata,
+            params.irrevocable,
+            params.witness,
+            None, // max_lookback_ledgers
+            None, // default token
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Create one funded stream that pays multiple recipients pro-rata.
+    ///
+    /// The pool stores a bounded `(recipient, share_weight)` table under
+    /// `DataKey::PooledStreamShares(stream_id)`. Each recipient later calls
+    /// [`withdraw_from_pool`](Self::withdraw_from_pool), which computes the
+    /// stream's total checkpointed accrual and applies the caller's share using
+    /// checked arithmetic. Fractional results are rounded down so no participant
+    /// can withdraw more than their proportional entitlement.
+    ///
+    /// # Security
+    /// - Recipient count is capped by `MAX_POOL_RECIPIENTS` to bound storage and
+    ///   iteration cost.
+    /// - Empty pools, zero-share entries, duplicate recipients, and share-sum
+    ///   overflow are rejected.
+    /// - The sender remains the aggregate `recipient` on the base stream record;
+    ///   individual recipient entitlements are held in pooled-share storage and
+    ///   tracked independently via `DataKey::PooledStreamWithdrawn`.
+    pub fn create_pooled_stream(
+        env: Env,
+        sender: Address,
+        recipients: soroban_sdk::Vec<(Address, u32)>,
+        deposit_amount: i128,
+        rate_per_second: i128,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        withdraw_dust_threshold: i128,
+        memo: Option<soroban_sdk::Bytes>,
+        kind: StreamKind,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
+
+        if recipients.len() > MAX_POOL_RECIPIENTS {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let mut total_shares: u32 = 0;
+        let mut seen_recipients = soroban_sdk::Vec::<Address>::new(&env);
+        for (recipient, share) in recipients.iter() {
+            if share == 0 {
+                return Err(ContractError::InvalidParams);
+            }
+            for seen in seen_recipients.iter() {
+                if seen == recipient {
+                    return Err(ContractError::InvalidParams);
+                }
+            }
+            seen_recipients.push_back(recipient);
+            total_shares = total_shares
+                .checked_add(share)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+        if total_shares == 0 {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let mut final_rate = rate_per_second;
+        if kind == StreamKind::CliffOnly {
+            final_rate = 0;
+        }
+
+        Self::validate_stream_params_with_self_policy(
+            &env,
+            &sender,
+            &sender,
+            deposit_amount,
+            final_rate,
+            env.ledger().timestamp(),
+            start_time,
+            cliff_time,
+            end_time,
+            kind,
+            true,
+        )?;
+
+        let token = get_token(&env)?;
+        pull_sender_funds(&env, &token, &sender, deposit_amount)?;
+
+        if let Some(ref m) = memo {
+            if m.len() as usize > MAX_MEMO_BYTES {
+                return Err(ContractError::InvalidParams);
+            }
+        }
+
+        let stream_id = next_stream_id_for(&env, &sender);
+
+        let stream = Stream {
+            stream_id,
+            sender: sender.clone(),
+            recipient: sender.clone(),
+            claim_owner: None,
+            deposit_amount,
+            rate_per_second: final_rate,
+            start_time,
+            cliff_time,
+            end_time,
+            withdrawn_amount: 0,
+            status: StreamStatus::Active,
+            cancelled_at: None,
+            checkpointed_amount: 0,
+            checkpointed_at: start_time,
+            withdraw_dust_threshold,
+            memo: memo.clone(),
+            kind,
+            last_pause_toggle_ledger: 0,
+            last_withdraw_ledger: 0,
+            metadata: None,
+            witness: None,
+            is_pooled: Some(true),
+            last_rate_change_ledger: 0,
+            delegation_depth: 0,
+            parent_stream_id: None,
+            irrevocable: None,
+            decommissioned: None,
+            paused_at_timestamp: 0,
+            cumulative_paused_duration: 0,
+        };
+
+        save_stream(&env, &stream);
+        save_pooled_stream_shares(&env, stream_id, &recipients);
+        add_stream_to_sender_index(&env, &sender, stream_id, Some(end_time));
+        for (recipient, _) in recipients.iter() {
+            add_stream_to_recipient_index(&env, &recipient, stream_id, Some(end_time));
+        }
+
+        let liabilities = read_total_liabilities(&env)
+            .checked_add(deposit_amount)
+            .unwrap_or(i128::MAX);
+        write_total_liabilities(&env, liabilities);
+        charge_deposit_fee(&env, stream_id, &token, &sender, deposit_amount)?;
+
+        events::emit_stream_created(
+            &env,
+            stream_id,
+            StreamCreated {
+                stream_id,
+                sender,
+                recipient: stream.recipient.clone(),
+                deposit_amount,
+                rate_per_second: final_rate,
+                start_time,
+                cliff_time,
+                end_time,
+                withdraw_dust_threshold,
+                memo,
+                metadata: None,
+            },
+        );
+
+        Ok(stream_id)
+    }
+
+    /// Create multiple payment streams in a single transaction.
+    ///
+    /// Optimizes gas usage by authorizing once and doing a single bulk token transfer
+    /// for all streams. The batch is atomic: either all streams are created, or none are.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding all streams in the batch
+    /// - `streams`: Vector of stream configuration parameters
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Stream IDs in the same order as `streams` input entries
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender` exactly once for the entire batch
+    ///
+    /// # Success Semantics
+    /// - Every entry is validated using the same rules as `create_stream`
+    /// - The total deposit is computed as `sum(entry.deposit_amount)` with checked arithmetic
+    /// - A single token transfer pulls the total from `sender` into the contract
+    /// - Streams are persisted sequentially with contiguous IDs and one `created` event per stream
+    ///
+    /// # Failure Semantics
+    /// - Any validation failure, arithmetic overflow, auth failure, or token transfer failure aborts the call
+    /// - On failure there are no persistent writes, no token movement, and no `created` events
+    /// - If the contract is globally paused (`ContractPaused`), the entire batch is rejected
+    ///
+    /// # Errors
+    /// Returns `ContractError` if:
+    /// - `ContractPaused` (4): Operations are globally halted; batch creation is completely blocked.
+    /// - `InvalidParams` (3): An entry contains negative values, zero durations, etc.
+    /// - `StartTimeInPast` (5): An entry's `start_time` is before the current ledger timestamp.
+    /// - `ArithmeticOverflow` (6): Value conversions or total batch deposit exceeds `i128::MAX`.
+    /// - `Unauthorized` (7): Sender signature is missing.
+    ///
+    /// # Panics
+    /// - If token transfer fails due to sender balance/allowance constraints
+    ///
+    /// # Security Notes
+    /// - Self-streaming is disallowed per entry: `sender` must not equal `recipient`
+    /// - Validation is completed before any external token interaction
+    /// Create multiple payment streams in a single atomic batch operation.
+    ///
+    /// This function enables treasury operators and integrators to create multiple streams
+    /// with a single authorization and token transfer, reducing gas costs and ensuring
+    /// all-or-nothing semantics.
+    ///
+    /// # Parameters
+    /// - `sender`: Address that funds and authorizes the batch (must authorize this call)
+    /// - `streams`: Vector of `CreateStreamParams` defining each stream's schedule and recipient
+    ///
+    /// # Authorization
+    /// - Requires `sender.require_auth()` (single auth check for entire batch)
+    /// - Fails atomically if sender is not authorized
+    ///
+    /// # Empty Vector Semantics
+    /// When `streams` is empty:
+    /// - Returns `Ok(Vec::new())` (empty result vector)
+    /// - No tokens are transferred (total_deposit = 0)
+    /// - No streams are persisted
+    /// - No `StreamCreated` events are emitted
+    /// - Stream ID counter is not advanced
+    /// - Authorization is still required (sender must authorize the call)
+    /// - Contract state remains unchanged
+    /// - No errors are raised (empty batch is valid)
+    ///
+    /// # Success Semantics
+    /// When `streams` is non-empty:
+    /// 1. All entries are validated before any state changes (first pass)
+    /// 2. Total deposit is calculated with overflow protection
+    /// 3. Tokens are transferred atomically: `sum(deposit_amount)` from sender to contract
+    /// 4. Stream IDs are allocated sequentially (contiguous, starting from next available ID)
+    /// 5. Each stream is persisted with status `Active`
+    /// 6. Recipient stream index is updated (sorted by stream_id)
+    /// 7. One `StreamCreated` event is emitted per stream (in order)
+    /// 8. Returned vector contains stream IDs in the same order as input entries
+    ///
+    /// # Failure Semantics
+    /// If any validation fails (or total-deposit sum overflows):
+    /// - No streams are created
+    /// - No tokens are transferred
+    /// - No events are emitted
+    /// - Stream ID counter is not advanced
+    /// - Entire batch is reverted (atomic)
+    /// - Error is returned to caller
+    ///
+    /// Validation failures include:
+    /// - Any entry has invalid parameters (see `validate_stream_params`)
+    /// - Total deposit sum overflows `i128`
+    /// - Contract is globally paused
+    /// - Sender is not authorized
+    ///
+    /// # Invariants After Success
+    /// - `returned_ids.len() == streams.len()`
+    /// - `returned_ids[i]` is the ID of the stream created from `streams[i]`
+    /// - Each stream has status `Active` and `withdrawn_amount = 0`
+    /// - Each recipient's stream index includes the new stream_id (sorted)
+    /// - Total tokens transferred = `sum(deposit_amount)`
+    /// - Stream ID counter advanced by `streams.len()`
+    ///
+    /// # Gas Considerations
+    /// - Single token transfer (vs. N transfers for N individual `create_stream` calls)
+    /// - Batch validation reduces redundant checks
+    /// - Recipient index updates are O(n log n) total (binary search per stream)
+    ///
+    /// # Events
+    /// - On success: one `StreamCreated` event per stream
+    /// - On failure: no events
+    /// - On empty batch: no events
+    ///
+    /// # Example
+    /// ```ignore
+    /// let params = vec![
+    ///     CreateStreamParams { recipient: alice, deposit_amount: 1000, ... },
+    ///     CreateStreamParams { recipient: bob, deposit_amount: 2000, ... },
+    /// ];
+    /// let ids = contract.create_streams(&sender, &params)?;
+    /// // ids = [0, 1] (assuming first batch)
+    /// ```
+    pub fn create_streams(
+        env: Env,
+        sender: Address,
+        streams: soroban_sdk::Vec<CreateStreamParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        sender.require_auth();
+
+        if streams.is_empty() {
+            return Ok(soroban_sdk::Vec::new(&env));
+        }
+
+        require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut total_deposit: i128 = 0;
+        let mut total_fees: i128 = 0;
+
+        // First pass: validate all streams and calculate total deposit required
+        for params in streams.iter() {
+            let mut final_rate = params.rate_per_second;
+            if params.kind == StreamKind::CliffOnly {
+                final_rate = 0;
+            }
+
+            Self::validate_stream_params(
+                &env,
+                &sender,
+                &params.recipient,
+                params.deposit_amount,
+                final_rate,
+                current_time,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+                params.kind,
+            )?;
+            total_deposit = total_deposit
+                .checked_add(params.deposit_amount)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+            total_fees = total_fees
+                .checked_add(deposit_fee_for(&env, params.deposit_amount)?)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+
+            // Validate metadata if present (fail-before-allocate).
+            if let Some(ref meta) = params.metadata {
+                storage::validate_metadata(meta)?;
+            }
+        }
+
+        // Bulk transfer deposits and deposit fees from sender to this contract
+        // atomically to save gas.
+        // Empty batch: total_deposit = 0, no transfer occurs.
+        let token = get_token(&env)?;
+        let total_pull = total_deposit
+            .checked_add(total_fees)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        if total_pull > 0 {
+            pull_sender_funds(&env, &token, &sender, total_pull)?;
+        }
+
+        // Second pass: generate IDs, persist state, and emit events iteratively
+        let mut created_ids = soroban_sdk::Vec::new(&env);
+        let mut recipient_cache: soroban_sdk::Map<Address, soroban_sdk::Vec<u64>> =
+            soroban_sdk::Map::new(&env);
+        for params in streams.iter() {
+            let mut final_rate = params.rate_per_second;
+            if params.kind == StreamKind::CliffOnly {
+                final_rate = 0;
+            }
+
+            let stream_id = Self::persist_new_stream_skip_index(
+                &env,
+                sender.clone(),
+                params.recipient.clone(),
+                params.deposit_amount,
+                final_rate,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+                params.withdraw_dust_threshold.unwrap_or(0),
+                params.memo.clone(),
+                params.kind,
+                params.metadata.clone(),
+                params.irrevocable,
+                params.witness.clone(),
+            )?;
+            book_deposit_fee(
+                &env,
+                stream_id,
+                &token,
+                deposit_fee_for(&env, params.deposit_amount)?,
+            )?;
+            created_ids.push_back(stream_id);
+
+            // Accumulate stream_id into the cache for this recipient.
+            let mut ids = recipient_cache
+                .get(params.recipient.clone())
+                .unwrap_or_else(|| soroban_sdk::Vec::new(&env));
+            ids.push_back(stream_id);
+            recipient_cache.set(params.recipient.clone(), ids);
+        }
+
+        // Flush: one read + one write per unique recipient.
+        for (recipient, new_ids) in recipient_cache.iter() {
+            let mut existing = load_recipient_streams(&env, &recipient);
+            for id in new_ids.iter() {
+                let insert_pos = match existing.binary_search(id) {
+                    Ok(pos) => pos,
+                    Err(pos) => pos,
+                };
+                existing.insert(insert_pos, id);
+            }
+            save_recipient_streams(&env, &recipient, &existing, None);
+            add_owner_positions(&env, &recipient, &new_ids);
+        }
+
+        // Flush sender index once for the whole batch (O(1) read + write for the sender).
+        {
+            let mut existing = load_sender_streams(&env, &sender);
+            for id in created_ids.iter() {
+                let insert_pos = match existing.binary_search(id) {
+                    Ok(pos) => pos,
+                    Err(pos) => pos,
+                };
+                existing.insert(insert_pos, id);
+            }
+            save_sender_streams(&env, &sender, &existing, None);
+        }
+
+        Ok(created_ids)
+    }
+
+    /// Create multiple payment streams with relative (offset-based) timing.
+    ///
+    /// Batch version of `create_stream_relative` that converts relative time offsets
+    /// to absolute timestamps and delegates to `create_streams`. Provides the same
+    /// atomicity and gas efficiency as `create_streams` while eliminating off-chain
+    /// timestamp calculation errors.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding all streams in the batch
+    /// - `streams_relative`: Vector of `CreateStreamRelativeParams` with relative time offsets
+    ///
+    /// # Returns
+    /// - `Vec<u64>`: Stream IDs in the same order as `streams_relative` input entries
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender` exactly once for the entire batch
+    ///
+    /// # Time Computation
+    /// Uses `current_time = env.ledger().timestamp()`:
+    /// For each entry:
+    /// - `start_time = current_time + start_delay`
+    /// - `cliff_time = current_time + cliff_delay`
+    /// - `end_time = start_time + duration`
+    ///
+    /// # Success Semantics
+    /// - Empty batch returns `Ok(Vec::new())` without side effects
+    /// - All validation invariants from `create_streams` are preserved
+    /// - Relative timing eliminates `StartTimeInPast` errors
+    /// - Single token transfer for all streams (atomic)
+    ///
+    /// # Failure Semantics
+    /// - Any entry's time offset causes arithmetic overflow → `InvalidParams`
+    /// - Any validation failure → entire batch fails atomically
+    /// - Any token transfer failure → no state change
+    /// - No events emitted on failure
+    ///
+    /// # Security Notes
+    /// - Relative timing removes need for off-chain clock synchronization
+    /// - All deposit, rate, and deposit-coverage validation proceeds as-is
+    /// - Self-streaming still rejected per entry
+    ///
+    /// # Example
+    /// ```ignore
+    /// let params = vec![
+    ///     CreateStreamRelativeParams {
+    ///         recipient: alice,
+    ///         deposit_amount: 1000,
+    ///         rate_per_second: 1,
+    ///         start_delay: 86400,      // 1 day
+    ///         cliff_delay: 259200,     // 3 days
+    ///         duration: 2592000,       // 30 days
+    ///         withdraw_dust_threshold: 0,
+    ///     },
+    ///     CreateStreamRelativeParams {
+    ///         recipient: bob,
+    ///         deposit_amount: 2000,
+    ///         rate_per_second: 2,
+    ///         start_delay: 0,          // Immediate
+    ///         cliff_delay: 0,          // Immediate
+    ///         duration: 2592000,       // 30 days
+    ///         withdraw_dust_threshold: 0,
+    ///     },
+    /// ];
+    /// let ids = contract.create_streams_relative(&sender, &params)?;
+    /// // ids = [0, 1] (assuming first batch)
+    /// ```
+    pub fn create_streams_relative(
+        env: Env,
+        sender: Address,
+        streams_relative: soroban_sdk::Vec<CreateStreamRelativeParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, ContractError> {
+        if streams_relative.is_empty() {
+            return Ok(soroban_sdk::Vec::new(&env));
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut absolute_streams = soroban_sdk::Vec::new(&env);
+
+        // Convert relative parameters to absolute times
+        for rel in streams_relative.iter() {
+            let start_time = current_time
+                .checked_add(rel.start_delay)
+                .ok_or(ContractError::InvalidParams)?;
+            let cliff_time = current_time
+                .checked_add(rel.cliff_delay)
+                .ok_or(ContractError::InvalidParams)?;
+            let end_time = start_time
+                .checked_add(rel.duration)
+                .ok_or(ContractError::InvalidParams)?;
+
+            let mut final_rate = rel.rate_per_second;
+            if rel.kind == StreamKind::CliffOnly {
+                final_rate = 0;
+            }
+
+            absolute_streams.push_back(CreateStreamParams {
+                recipient: rel.recipient,
+                deposit_amount: rel.deposit_amount,
+                rate_per_second: final_rate,
+                start_time,
+                cliff_time,
+                end_time,
+                withdraw_dust_threshold: rel.withdraw_dust_threshold,
+                memo: rel.memo,
+                kind: rel.kind,
+                metadata: rel.metadata,
+                irrevocable: rel.irrevocable,
+                witness: rel.witness,
+            });
+        }
+
+        // Delegate to standard create_streams with converted absolute times
+        Self::create_streams(env, sender, absolute_streams)
+    }
+
+    /// Create multiple payment streams in a single transaction with failure isolation.
+    ///
+    /// Unlike `create_streams`, this function is non-atomic: it attempts to create each
+    /// stream independently. If an entry fails validation or token transfer, it is
+    /// recorded as a failure in the results vector, but the rest of the batch continues.
+    ///
+    /// # Parameters
+    /// - `sender`: Address funding the streams (must authorize the call)
+    /// - `streams`: Vector of stream parameters to process
+    ///
+    /// # Returns
+    /// - `Vec<CreateStreamResult>`: Per-entry success/failure results in input order
+    pub fn create_streams_partial(
+        env: Env,
+        sender: Address,
+        streams: soroban_sdk::Vec<CreateStreamParams>,
+    ) -> Result<soroban_sdk::Vec<CreateStreamResult>, ContractError> {
+        sender.require_auth();
+
+        if streams.is_empty() {
+            return Ok(soroban_sdk::Vec::new(&env));
+        }
+
+        require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut results = soroban_sdk::Vec::new(&env);
+
+        for params in streams.iter() {
+            let mut final_rate = params.rate_per_second;
+            if params.kind == StreamKind::CliffOnly {
+                final_rate = 0;
+            }
+
+            // Validation first
+            let validation = Self::validate_stream_params(
+                &env,
+                &sender,
+                &params.recipient,
+                params.deposit_amount,
+                final_rate,
+                current_time,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+                params.kind,
+            );
+
+            if let Err(e) = validation {
+                results.push_back(CreateStreamResult {
+                    success: false,
+                    stream_id: None,
+                    error: Some(e as u32),
+                });
+                continue;
+            }
+
+            // Validate metadata if present (fail-before-transfer).
+            if let Some(ref meta) = params.metadata {
+                if let Err(e) = storage::validate_metadata(meta) {
+                    results.push_back(CreateStreamResult {
+                        success: false,
+                        stream_id: None,
+                        error: Some(e as u32),
+                    });
+                    continue;
+                }
+            }
+
+            // Deposit and deposit fee move in one pull so a failed entry
+            // leaves neither behind.
+            let funding = deposit_fee_for(&env, params.deposit_amount).and_then(|fee| {
+                params
+                    .deposit_amount
+                    .checked_add(fee)
+                    .map(|total| (fee, total))
+                    .ok_or(ContractError::ArithmeticOverflow)
+            });
+            let (fee, total) = match funding {
+                Ok(funding) => funding,
+                Err(e) => {
+                    results.push_back(CreateStreamResult {
+                        success: false,
+                        stream_id: None,
+                        error: Some(e as u32),
+                    });
+                    continue;
+                }
+            };
+
+            // Attempt transfer (per-entry isolation)
+            let token = get_token(&env)?;
+            let transfer = pull_sender_funds(&env, &token, &sender, total);
+            if transfer.is_err() {
+                results.push_back(CreateStreamResult {
+                    success: false,
+                    stream_id: None,
+                    error: Some(ContractError::InsufficientBalance as u32),
+                });
+                continue;
+            }
+
+            // Persist
+            let stream_id = Self::persist_new_stream(
+                &env,
+                &token,
+                sender.clone(),
+                params.recipient,
+                params.deposit_amount,
+                final_rate,
+                params.start_time,
+                params.cliff_time,
+                params.end_time,
+                params.withdraw_dust_threshold.unwrap_or(0),
+                params.memo.clone(),
+                params.kind,
+                params.metadata.clone(),
+                params.irrevocable,
+                params.witness,
+            )
+            .and_then(|id| book_deposit_fee(&env, id, &token, fee).map(|_| id));
+
+            match stream_id {
+                Ok(id) => results.push_back(CreateStreamResult {
+                    success: true,
+                    stream_id: Some(id),
+                    error: None,
+                }),
+                Err(e) => results.push_back(CreateStreamResult {
+                    success: false,
+                    stream_id: None,
+                    error: Some(e as u32),
+                }),
+            }
         }
 
-        // Delegate to standard create_streams with converted absolute times
-        Self::create_streams(env, sender, absolute_streams)
+        Ok(results)
+    }
+
+    /// Pause an active payment stream.
+    ///
+    /// Temporarily halts withdrawals from the stream while preserving accrual calculations.
+    /// The stream can be resumed later by the sender or admin. Accrual continues based on
+    /// time elapsed, but the recipient cannot withdraw while paused.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to pause
+    /// - `reason`: Operational reason code for the pause (see `PauseReason`)
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `pause_stream_as_admin` for administrative override
+    ///
+    /// # Panics
+    /// - If the stream is not in `Active` state (already paused, completed, or cancelled)
+    /// - If the stream does not exist (`stream_id` is invalid)
+    /// - If caller is not authorized (not the sender)
+    ///
+    /// # Events
+    /// - Publishes `("paused", stream_id)` → `StreamPaused { stream_id, reason }` on success
+    ///
+    /// # Usage Notes
+    /// - Pausing does not affect accrual calculations (time-based)
+    /// - Recipient cannot withdraw while stream is paused
+    /// - Stream can be cancelled while paused
+    /// - Use `resume_stream` to reactivate withdrawals
+    pub fn pause_stream(
+        env: Env,
+        stream_id: u64,
+        reason: PauseReason,
+    ) -> Result<(), ContractError> {
+        Self::pause_stream_internal(env, stream_id, reason, None)
+    }
+
+    /// Body of [`pause_stream`](Self::pause_stream).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn pause_stream_internal(
+        env: Env,
+        stream_id: u64,
+        reason: PauseReason,
+        batch_caller: Option<&Address>,
+    ) -> Result<(), ContractError> {
+        let mut stream = load_stream(&env, stream_id)?;
+
+        Self::require_op_auth(&stream.sender, batch_caller)?;
+
+        if stream.status == StreamStatus::Paused {
+            return Err(ContractError::StreamAlreadyPaused);
+        }
+
+        if is_terminal_state(&env, &stream) {
+            return Err(ContractError::StreamTerminalState);
+        }
+
+        if stream.status != StreamStatus::Active {
+            return Err(ContractError::InvalidState);
+        }
+
+        // Check pause/resume cooldown to prevent rapid-toggle DoS
+        let current_ledger = env.ledger().sequence();
+        let ledgers_since_last_toggle =
+            current_ledger.saturating_sub(stream.last_pause_toggle_ledger);
+        if ledgers_since_last_toggle < MIN_PAUSE_INTERVAL_LEDGERS {
+            return Err(ContractError::PauseCooldownActive);
+        }
+
+        let previous_status = stream.status;
+        stream.status = StreamStatus::Paused;
+        stream.last_pause_toggle_ledger = current_ledger;
+        stream.paused_at_timestamp = env.ledger().timestamp();
+        save_stream(&env, &stream);
+        reconcile_paused_stream_count(&env, previous_status, stream.status);
+
+        let reason_str = match reason {
+            PauseReason::Operational => soroban_sdk::String::from_str(&env, "Operational"),
+            PauseReason::Administrative => soroban_sdk::String::from_str(&env, "Administrative"),
+            PauseReason::Emergency => soroban_sdk::String::from_str(&env, "Emergency"),
+            PauseReason::Compliance => soroban_sdk::String::from_str(&env, "Compliance"),
+        };
+        events::emit_stream_paused(
+            &env,
+            stream_id,
+            StreamPaused {
+                stream_id,
+                reason: reason_str,
+            },
+        );
+        Ok(())
+    }
+
+    /// Resume a paused payment stream.
+    ///
+    /// Reactivates a paused stream, allowing the recipient to withdraw accrued funds again.
+    /// Only streams in `Paused` state can be resumed. Terminal states (Completed, Cancelled)
+    /// cannot be resumed.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to resume
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `resume_stream_as_admin` for administrative override
+    ///
+    /// # Panics
+    /// - If the stream is `Active` (not paused, already running)
+    /// - If the stream is `Completed` (terminal state, cannot be resumed)
+    /// - If the stream is `Cancelled` (terminal state, cannot be resumed)
+    /// - If the stream does not exist (`stream_id` is invalid)
+    /// - If caller is not authorized (not the sender)
+    ///
+    /// # Events
+    /// - Publishes `Resumed(stream_id)` event on success
+    ///
+    /// # Usage Notes
+    /// - Only paused streams can be resumed
+    /// - Accrual calculations are time-based and unaffected by pause/resume
+    /// - After resume, recipient can immediately withdraw accrued funds
+    pub fn resume_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        Self::resume_stream_internal(env, stream_id, None)
+    }
+
+    /// Body of [`resume_stream`](Self::resume_stream).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn resume_stream_internal(
+        env: Env,
+        stream_id: u64,
+        batch_caller: Option<&Address>,
+    ) -> Result<(), ContractError> {
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::require_op_auth(&stream.sender, batch_caller)?;
+
+        if stream.status == StreamStatus::Active {
+            return Err(ContractError::StreamNotPaused);
+        }
+        if is_terminal_state(&env, &stream) {
+            return Err(ContractError::StreamTerminalState);
+        }
+        if stream.status != StreamStatus::Paused {
+            return Err(ContractError::StreamNotPaused);
+        }
+
+        // Check pause/resume cooldown to prevent rapid-toggle DoS
+        let current_ledger = env.ledger().sequence();
+        let ledgers_since_last_toggle =
+            current_ledger.saturating_sub(stream.last_pause_toggle_ledger);
+        if ledgers_since_last_toggle < MIN_PAUSE_INTERVAL_LEDGERS {
+            return Err(ContractError::PauseCooldownActive);
+        }
+
+        let previous_status = stream.status;
+        let paused_duration = env.ledger().timestamp().saturating_sub(stream.paused_at_timestamp);
+        stream.cumulative_paused_duration = stream
+            .cumulative_paused_duration
+            .checked_add(paused_duration)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        stream.paused_at_timestamp = 0;
+        stream.status = StreamStatus::Active;
+        stream.last_pause_toggle_ledger = current_ledger;
+        save_stream(&env, &stream);
+        reconcile_paused_stream_count(&env, previous_status, stream.status);
+
+        events::emit_stream_resumed(&env, stream_id);
+        Ok(())
+    }
+
+    /// Cancel a payment stream and refund unstreamed funds to the sender.
+    ///
+    /// Terminates an active or paused stream, immediately refunding any unstreamed tokens
+    /// to the sender. The accrued amount (based on time elapsed) remains in the contract
+    /// for the recipient to withdraw. This is a terminal operation - cancelled streams
+    /// cannot be resumed.
+    ///
+    /// # Parameters
+    /// - `stream_id`: Unique identifier of the stream to cancel
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream's sender (original creator)
+    /// - Admin can use `cancel_stream_as_admin` for administrative override
+    ///
+    /// # Behavior
+    /// 1. Validates stream is in `Active` or `Paused` state
+    /// 2. Captures cancellation timestamp: `now = ledger.timestamp()`
+    /// 3. Calculates accrued amount at `now`: `min((now - start_time) × rate, deposit_amount)`
+    /// 4. Calculates refund: `deposit_amount - accrued_at_now`
+    /// 5. Persists terminal state before transfer:
+    ///    - `status = Cancelled`
+    ///    - `cancelled_at = Some(now)`
+    /// 6. Transfers refund to sender (if > 0)
+    /// 7. Emits `StreamCancelled(stream_id)` event
+    ///
+    /// # Returns
+    /// - Implicitly returns via state change and token transfer
+    ///
+    /// # Panics
+    /// - Returns `ContractError::InvalidState` if stream is not `Active` or `Paused`
+    /// - If the stream does not exist (`stream_id` is invalid)
+    /// - If caller is not authorized (not the sender)
+    /// - If token transfer fails (should not happen with valid contract state)
+    ///
+    /// # Events
+    /// - Publishes `Cancelled(stream_id)` event on success
+    ///
+    /// # Usage Notes
+    /// - Cancellation is irreversible (terminal state)
+    /// - Recipient can still withdraw accrued amount after cancellation
+    /// - If fully accrued (time >= end_time), sender receives no refund
+    /// - Accrual is time-based, not affected by pause state
+    /// - Can be called on paused streams
+    ///
+    /// # Handling of already-accrued amount
+    /// - The accrued portion of the stream (based on time, up to `deposit_amount`)
+    ///   is **never** refunded to the sender.
+    /// - It remains locked in the contract and can only be claimed by the recipient
+    ///   via `withdraw()`.
+    /// - The contract does **not** auto-transfer accrued funds to the recipient when
+    ///   cancelling; the recipient must explicitly withdraw.
+    ///
+    /// # Examples
+    /// - Cancel at 30% completion → sender gets 70% refund, recipient can withdraw 30%
+    /// - Cancel at 100% completion → sender gets 0% refund, recipient can withdraw 100%
+    /// - Cancel before cliff → sender gets 100% refund (no accrual before cliff)
+    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+        require_not_globally_paused(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+        Self::require_stream_sender(&stream.sender);
+        Self::cancel_stream_internal(&env, &mut stream, true)
     }
 
-    /// Create multiple payment streams in a single transaction with failure isolation.
-    ///
-    /// Unlike `create_streams`, this function is non-atomic: it attempts to create each
-    /// stream independently. If an entry fails validation or token transfer, it is
-    /// recorded as a failure in the results vector, but the rest of the batch continues.
-    ///
-    /// # Parameters
-    /// - `sender`: Address funding the streams (must authorize the call)
-    /// - `streams`: Vector of stream parameters to process
-    ///
-    /// # Returns
-    /// - `Vec<CreateStreamResult>`: Per-entry success/failure results in input order
-    pub fn create_streams_partial(
+    pub fn delegated_cancel(
         env: Env,
-        sender: Address,
-        streams: soroban_sdk::Vec<CreateStreamParams>,
-    ) -> Result<soroban_sdk::Vec<CreateStreamResult>, ContractError> {
-        sender.require_auth();
-
-        if streams.is_empty() {
-            return Ok(soroban_sdk::Vec::new(&env));
-        }
-
-        require_not_creation_paused(&env)?;
-
-        let current_time = env.ledger().timestamp();
-        let mut results = soroban_sdk::Vec::new(&env);
-
-        for params in streams.iter() {
-            let mut final_rate = params.rate_per_second;
-            if params.kind == StreamKind::CliffOnly {
-                final_rate = 0;
-            }
+        stream_id: u64,
+        relayer: Address,
+        sender_public_key: soroban_sdk::BytesN<32>,
+        nonce: u64,
+        deadline: u64,
+        signature: soroban_sdk::BytesN<64>,
+    ) -> Result<(), ContractError> {
+        require_not_globally_paused(&env)?;
+        relayer.require_auth();
 
-            // Validation first
-            let validation = Self::validate_stream_params(
-                &env,
-                &sender,
-                &params.recipient,
-                params.deposit_amount,
-                final_rate,
-                current_time,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-                params.kind,
-            );
+        delegation::validate_delegated_cancel_params(&env, stream_id, nonce, deadline)?;
 
-            if let Err(e) = validation {
-                results.push_back(CreateStreamResult {
-                    success: false,
-                    stream_id: None,
-                    error: Some(e as u32),
-                });
-                continue;
-            }
+        let mut stream = load_stream(&env, stream_id)?;
 
-            // Validate metadata if present (fail-before-transfer).
-            if let Some(ref meta) = params.metadata {
-                if let Err(e) = storage::validate_metadata(meta) {
-                    results.push_back(CreateStreamResult {
-                        success: false,
-                        stream_id: None,
-                        error: Some(e as u32),
-                    });
-                    continue;
-                }
-            }
+        if Self::ed25519_pubkey_from_address(&env, &stream.sender) != sender_public_key.to_array() {
+            return Err(ContractError::InvalidSignature);
+        }
 
-            // Attempt transfer (per-entry isolation)
-            let transfer = pull_token(&env, &sender, params.deposit_amount);
-            if transfer.is_err() {
-                results.push_back(CreateStreamResult {
-                    success: false,
-                    stream_id: None,
-                    error: Some(ContractError::InsufficientBalance as u32),
-                });
-                continue;
-            }
+        let mut msg = soroban_sdk::Bytes::new(&env);
+        msg.extend_from_slice(delegation::DELEGATED_CANCEL_DOMAIN);
+        msg.extend_from_array(&stream_id.to_be_bytes());
+        msg.extend_from_array(&nonce.to_be_bytes());
+        msg.extend_from_array(&deadline.to_be_bytes());
 
-            // Persist
-            let stream_id = Self::persist_new_stream(
-                &env,
-                sender.clone(),
-                params.recipient,
-                params.deposit_amount,
-                final_rate,
-                params.start_time,
-                params.cliff_time,
-                params.end_time,
-                params.withdraw_dust_threshold.unwrap_or(0),
-                params.memo.clone(),
-                params.kind,
-                params.metadata.clone(),
-                params.irrevocable,
-                params.witness,
-            );
+        env.crypto()
+            .ed25519_verify(&sender_public_key, &msg, &signature);
 
-            match stream_id {
-                Ok(id) => results.push_back(CreateStreamResult {
-                    success: true,
-                    stream_id: Some(id),
-                    error: None,
-                }),
-                Err(e) => results.push_back(CreateStreamResult {
-                    success: false,
-                    stream_id: None,
-                    error: Some(e as u32),
-                }),
-            }
-        }
+        crate::storage::increment_delegated_cancel_nonce(&env, &stream.sender);
 
-        Ok(results)
+        Self::cancel_stream_internal(&env, &mut stream, true)
     }
 
-    /// Pause an active payment stream.
+    /// Withdraw accrued tokens from a payment stream to the recipient.
     ///
-    /// Temporarily halts withdrawals from the stream while preserving accrual calculations.
-    /// The stream can be resumed later by the sender or admin. Accrual continues based on
-    /// time elapsed, but the recipient cannot withdraw while paused.
+    /// Transfers all accrued-but-not-yet-withdrawn tokens to the stream's recipient.
+    /// The amount withdrawn is calculated as `accrued - withdrawn_amount`, where accrued
+    /// is based on time elapsed since stream start. If this withdrawal completes the
+    /// stream (all deposited tokens withdrawn), the stream status transitions to `Completed`.
     ///
     /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to pause
-    /// - `reason`: Operational reason code for the pause (see `PauseReason`)
+    /// - `stream_id`: Unique identifier of the stream to withdraw from
+    ///
+    /// # Returns
+    /// - `i128`: The amount debited from the stream (0 if nothing to withdraw). With a
+    ///   non-zero `withdraw_fee_bps` the recipient receives this amount minus the
+    ///   protocol fee (see `set_protocol_fee`).
     ///
     /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `pause_stream_as_admin` for administrative override
+    /// - Requires authorization from the stream's recipient (only recipient can withdraw)
+    /// - This prevents anyone from withdrawing on behalf of the recipient
+    ///
+    /// # Zero Withdrawable Behavior
+    /// - If `accrued == withdrawn_amount` (nothing to withdraw), returns 0 immediately.
+    /// - No token transfer occurs, no state is modified or saved, and no events are published.
+    /// - This is idempotent: safe to call continuously without state churn or cost footprint.
+    /// - Occurs before cliff, after a full claim, or when the stream is already drained to its cancellation point.
+    /// - Frontends and indexers can safely poll `withdraw` without pre-checking the balance.
     ///
     /// # Panics
-    /// - If the stream is not in `Active` state (already paused, completed, or cancelled)
+    /// - If the stream is `Completed` (all tokens already withdrawn)
+    /// - If the stream is `Paused` (withdrawals not allowed while paused)
     /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
+    /// - If caller is not authorized (not the recipient)
+    /// - If token transfer fails (insufficient contract balance, should not happen)
+    /// - If the stream is pooled (`InvalidState`; members use `withdraw_from_pool`)
+    ///
+    /// # State Changes
+    /// - Updates `withdrawn_amount` by the amount transferred (only if withdrawable > 0)
+    /// - Sets status to `Completed` only when withdrawing from an `Active` stream and all
+    ///   deposited tokens are withdrawn
+    /// - Extends stream storage TTL to prevent expiration
     ///
     /// # Events
-    /// - Publishes `("paused", stream_id)` → `StreamPaused { stream_id, reason }` on success
+    /// - Publishes `withdrew(stream_id, amount)` event on success (only if amount > 0)
     ///
     /// # Usage Notes
-    /// - Pausing does not affect accrual calculations (time-based)
-    /// - Recipient cannot withdraw while stream is paused
-    /// - Stream can be cancelled while paused
-    /// - Use `resume_stream` to reactivate withdrawals
-    pub fn pause_stream(
+    /// - Can be called multiple times to withdraw incrementally
+    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
+    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
+    /// - After end_time, accrued amount is capped at deposit_amount
+    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
+    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn,
+    ///   and status remains `Cancelled` (no `Completed` transition)
+    ///
+    /// # Cross-Entrypoint Idempotency
+    /// - Reentrancy-protected via `acquire_reentrancy_lock` before calling `push_token`
+    /// - State is persisted BEFORE `push_token` (CEI pattern), ensuring that repeated
+    ///   calls with the same withdrawal amount produce the same result (idempotent)
+    /// - If `push_token` fails, the entire transaction reverts (state is not updated)
+    /// - After a successful withdrawal, subsequent calls with the same stream_id return 0
+    ///   until new tokens accrue
+    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
+    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
+    /// - After end_time, accrued amount is capped at deposit_amount
+    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
+    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn,
+    ///   and status remains `Cancelled` (no `Completed` transition)
+    ///
+    /// # Slippage Guard (`min_expected_amount`)
+    /// Protects the recipient from MEV-style withdrawal-ordering risk where their realized
+    /// payout is silently reduced by a transaction ordered ahead of their `withdraw` call.
+    /// Ordering scenarios this protects against include:
+    /// - `top_up_stream` expanding the deposit (and potentially shifting lookback bounds)
+    /// - `decrease_rate_per_second` lowering the accrual rate before execution
+    /// - Lookback cap logic triggering based on a modified effective time
+    ///
+    /// # Examples
+    /// - Stream: 1000 tokens over 1000 seconds (1 token/sec)
+    /// - At t=0 (before cliff): withdraw() returns 0 (no transfer)
+    /// - At t=300: withdraw() returns 300 tokens
+    /// - At t=300 (again): withdraw() returns 0 (already withdrawn)
+    /// - At t=800: withdraw() returns 500 tokens (800 - 300 already withdrawn)
+    /// - At t=1000: withdraw() returns 200 tokens, status → Completed
+    pub fn withdraw(
         env: Env,
         stream_id: u64,
-        reason: PauseReason,
-    ) -> Result<(), ContractError> {
+        min_expected_amount: Option<i128>,
+    ) -> Result<i128, ContractError> {
+        require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
 
-        Self::require_stream_sender(&stream.sender);
+        // Pooled streams pay per member; see `withdraw_from_pool`.
+        if stream.is_pooled == Some(true) {
+            return Err(ContractError::InvalidState);
+        }
+
+        // Enforce claim owner or recipient authorization; the owner is paid.
+        let owner = stream
+            .claim_owner
+            .clone()
+            .unwrap_or(stream.recipient.clone());
+        owner.require_auth();
+
+        // Enforce withdrawal frequency limit to prevent excessive ledger I/O.
+        // Use saturating_sub to prevent underflow from backward timestamp skew
+        // (if current_ledger < last_withdraw_ledger, elapsed=0, withdrawal blocked).
+        // First withdrawal (last_withdraw_ledger == 0) always succeeds.
+        let current_ledger = env.ledger().sequence();
+        if stream.last_withdraw_ledger != 0
+            && current_ledger.saturating_sub(stream.last_withdraw_ledger)
+                < MIN_WITHDRAW_INTERVAL_LEDGERS
+        {
+            return Err(ContractError::WithdrawalTooFrequent);
+        }
+
+        if stream.status == StreamStatus::Completed {
+            return Err(ContractError::InvalidState);
+        }
+
+        if stream.status == StreamStatus::Paused && !is_terminal_state(&env, &stream) {
+            return Err(ContractError::InvalidState);
+        }
+
+        let accrued = Self::calculate_accrued(env.clone(), stream_id)?;
+        let mut withdrawable = accrued - stream.withdrawn_amount;
+        let effective_time = stream
+            .cancelled_at
+            .unwrap_or_else(|| env.ledger().timestamp());
+        withdrawable = apply_lookback_cap(&env, &stream, effective_time, accrued, withdrawable);
 
-        if stream.status == StreamStatus::Paused {
-            return Err(ContractError::StreamAlreadyPaused);
-        }
+        // Cap by contract balance for safety (#39)
+        let token_address = load_stream_token(&env, stream_id)?;
+        let contract_balance =
+            token::Client::new(&env, &token_address).balance(&env.current_contract_address());
+        withdrawable = withdrawable.min(contract_balance);
 
-        if is_terminal_state(&env, &stream) {
-            return Err(ContractError::StreamTerminalState);
+        if let Some(min) = min_expected_amount {
+            if withdrawable < min {
+                return Err(ContractError::BelowMinimumAmount);
+            }
         }
 
-        if stream.status != StreamStatus::Active {
-            return Err(ContractError::InvalidState);
+        if withdrawable <= 0 {
+            return Ok(0);
         }
 
-        // Check pause/resume cooldown to prevent rapid-toggle DoS
-        let current_ledger = env.ledger().sequence();
-        let ledgers_since_last_toggle =
-            current_ledger.saturating_sub(stream.last_pause_toggle_ledger);
-        if ledgers_since_last_toggle < MIN_PAUSE_INTERVAL_LEDGERS {
-            return Err(ContractError::PauseCooldownActive);
+        // Enforce dust threshold unless terminal state or final drain (#423)
+        if withdrawable < stream.withdraw_dust_threshold
+            && !is_terminal_state(&env, &stream)
+            && stream.withdrawn_amount + withdrawable < stream.deposit_amount
+        {
+            return Ok(0);
         }
 
+        // CEI: update state before external token transfer to reduce reentrancy risk.
+        // Cross-entrypoint idempotency: state is persisted BEFORE push_token so
+        // repeated calls produce the same result (withdrawable will be 0 after
+        // the first successful withdrawal).
+        stream.withdrawn_amount += withdrawable;
+        stream.last_withdraw_ledger = current_ledger; // Update withdrawal timestamp
+        let completed_now = (stream.status == StreamStatus::Active
+            || stream.status == StreamStatus::Paused)
+            && stream.withdrawn_amount == stream.deposit_amount;
         let previous_status = stream.status;
-        stream.status = StreamStatus::Paused;
-        stream.last_pause_toggle_ledger = current_ledger;
-        stream.paused_at_timestamp = env.ledger().timestamp();
+        if completed_now {
+            stream.status = StreamStatus::Completed;
+        }
         save_stream(&env, &stream);
         reconcile_paused_stream_count(&env, previous_status, stream.status);
 
-        let reason_str = match reason {
-            PauseReason::Operational => soroban_sdk::String::from_str(&env, "Operational"),
-            PauseReason::Administrative => soroban_sdk::String::from_str(&env, "Administrative"),
-            PauseReason::Emergency => soroban_sdk::String::from_str(&env, "Emergency"),
-            PauseReason::Compliance => soroban_sdk::String::from_str(&env, "Compliance"),
+        // Reduce liabilities as tokens leave the contract to the recipient.
+        let liabilities = read_token_liabilities(&env, &token_address)?
+            .checked_sub(withdrawable)
+            .unwrap_or(0);
+        write_token_liabilities(&env, &token_address, liabilities)?;
+
+        let fee = charge_withdraw_fee(&env, stream_id, &token_address, withdrawable)?;
+        let reason = if get_claim_to_balance(&env, &owner) {
+            PayoutReason::Credited
+        } else {
+            PayoutReason::Withdraw
         };
-        events::emit_stream_paused(
+
+        acquire_reentrancy_lock(&env)?;
+        let transfer_result = push_payout(&env, &token_address, &owner, withdrawable - fee)
+            .and_then(|()| {
+                Self::notify_recipient_hook(
+                    &env,
+                    stream_id,
+                    &token_address,
+                    withdrawable - fee,
+                    reason,
+                )
+            });
+        release_reentrancy_lock(&env);
+        transfer_result?;
+
+        events::emit_withdrawal(
             &env,
             stream_id,
-            StreamPaused {
+            Withdrawal {
                 stream_id,
-                reason: reason_str,
+                recipient: stream.recipient.clone(),
+                amount: withdrawable,
             },
         );
-        Ok(())
+
+        if completed_now {
+            events::emit_stream_completed(&env, stream_id);
+        }
+
+        Ok(withdrawable)
     }
 
-    /// Resume a paused payment stream.
-    ///
-    /// Reactivates a paused stream, allowing the recipient to withdraw accrued funds again.
-    /// Only streams in `Paused` state can be resumed. Terminal states (Completed, Cancelled)
-    /// cannot be resumed.
-    ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to resume
-    ///
-    /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `resume_stream_as_admin` for administrative override
-    ///
-    /// # Panics
-    /// - If the stream is `Active` (not paused, already running)
-    /// - If the stream is `Completed` (terminal state, cannot be resumed)
-    /// - If the stream is `Cancelled` (terminal state, cannot be resumed)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
+    /// Withdraw `caller`'s pro-rata share of a pooled stream to `caller`.
     ///
-    /// # Events
-    /// - Publishes `Resumed(stream_id)` event on success
+    /// Requires auth from the member's claim owner (the member itself unless
+    /// moved with `transfer_pool_claim_ownership`). Removed members keep
+    /// claiming their settled credit here.
     ///
-    /// # Usage Notes
-    /// - Only paused streams can be resumed
-    /// - Accrual calculations are time-based and unaffected by pause/resume
-    /// - After resume, recipient can immediately withdraw accrued funds
-    pub fn resume_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
-        let mut stream = load_stream(&env, stream_id)?;
-        Self::require_stream_sender(&stream.sender);
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled, is `Completed`, or is
+    ///   paused before its end.
+    /// - `Unauthorized` if `caller` has no stake in the pool.
+    pub fn withdraw_from_pool(
+        env: Env,
+        stream_id: u64,
+        caller: Address,
+    ) -> Result<i128, ContractError> {
+        require_not_globally_paused(&env)?;
+        read_pool_claim_owner(&env, stream_id, &caller).require_auth();
 
-        if stream.status == StreamStatus::Active {
-            return Err(ContractError::StreamNotPaused);
-        }
-        if is_terminal_state(&env, &stream) {
-            return Err(ContractError::StreamTerminalState);
-        }
-        if stream.status != StreamStatus::Paused {
-            return Err(ContractError::StreamNotPaused);
+        let claim = claim_pool_member(&env, stream_id, &caller)?;
+        if claim.amount == 0 {
+            return Ok(0);
         }
 
-        // Check pause/resume cooldown to prevent rapid-toggle DoS
-        let current_ledger = env.ledger().sequence();
-        let ledgers_since_last_toggle =
-            current_ledger.saturating_sub(stream.last_pause_toggle_ledger);
-        if ledgers_since_last_toggle < MIN_PAUSE_INTERVAL_LEDGERS {
-            return Err(ContractError::PauseCooldownActive);
-        }
+        acquire_reentrancy_lock(&env)?;
+        let transfer_result = push_payout(&env, &claim.token, &caller, claim.amount - claim.fee);
+        release_reentrancy_lock(&env);
+        transfer_result?;
 
-        let previous_status = stream.status;
-        let paused_duration = env.ledger().timestamp().saturating_sub(stream.paused_at_timestamp);
-        stream.cumulative_paused_duration = stream
-            .cumulative_paused_duration
-            .checked_add(paused_duration)
-            .ok_or(ContractError::ArithmeticOverflow)?;
-        stream.paused_at_timestamp = 0;
-        stream.status = StreamStatus::Active;
-        stream.last_pause_toggle_ledger = current_ledger;
-        save_stream(&env, &stream);
-        reconcile_paused_stream_count(&env, previous_status, stream.status);
+        events::emit_withdrawal(
+            &env,
+            stream_id,
+            Withdrawal {
+                stream_id,
+                recipient: caller,
+                amount: claim.amount,
+            },
+        );
+        if claim.completed_now {
+            events::emit_stream_completed(&env, stream_id);
+        }
 
-        events::emit_stream_resumed(&env, stream_id);
-        Ok(())
+        Ok(claim.amount)
     }
 
-    /// Cancel a payment stream and refund unstreamed funds to the sender.
-    ///
-    /// Terminates an active or paused stream, immediately refunding any unstreamed tokens
-    /// to the sender. The accrued amount (based on time elapsed) remains in the contract
-    /// for the recipient to withdraw. This is a terminal operation - cancelled streams
-    /// cannot be resumed.
-    ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to cancel
-    ///
-    /// # Authorization
-    /// - Requires authorization from the stream's sender (original creator)
-    /// - Admin can use `cancel_stream_as_admin` for administrative override
-    ///
-    /// # Behavior
-    /// 1. Validates stream is in `Active` or `Paused` state
-    /// 2. Captures cancellation timestamp: `now = ledger.timestamp()`
-    /// 3. Calculates accrued amount at `now`: `min((now - start_time) × rate, deposit_amount)`
-    /// 4. Calculates refund: `deposit_amount - accrued_at_now`
-    /// 5. Persists terminal state before transfer:
-    ///    - `status = Cancelled`
-    ///    - `cancelled_at = Some(now)`
-    /// 6. Transfers refund to sender (if > 0)
-    /// 7. Emits `StreamCancelled(stream_id)` event
+    /// Withdraw `member`'s pro-rata share of a pooled stream to `destination`.
     ///
-    /// # Returns
-    /// - Implicitly returns via state change and token transfer
+    /// The pooled counterpart of `withdraw_to`, with the same accounting as
+    /// `withdraw_from_pool`. Requires auth from the member's claim owner.
     ///
-    /// # Panics
-    /// - Returns `ContractError::InvalidState` if stream is not `Active` or `Paused`
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the sender)
-    /// - If token transfer fails (should not happen with valid contract state)
+    /// # Errors
+    /// - `InvalidParams` if `destination` is this contract.
+    /// - Otherwise the same as `withdraw_from_pool`.
     ///
     /// # Events
-    /// - Publishes `Cancelled(stream_id)` event on success
-    ///
-    /// # Usage Notes
-    /// - Cancellation is irreversible (terminal state)
-    /// - Recipient can still withdraw accrued amount after cancellation
-    /// - If fully accrued (time >= end_time), sender receives no refund
-    /// - Accrual is time-based, not affected by pause state
-    /// - Can be called on paused streams
-    ///
-    /// # Handling of already-accrued amount
-    /// - The accrued portion of the stream (based on time, up to `deposit_amount`)
-    ///   is **never** refunded to the sender.
-    /// - It remains locked in the contract and can only be claimed by the recipient
-    ///   via `withdraw()`.
-    /// - The contract does **not** auto-transfer accrued funds to the recipient when
-    ///   cancelling; the recipient must explicitly withdraw.
-    ///
-    /// # Examples
-    /// - Cancel at 30% completion → sender gets 70% refund, recipient can withdraw 30%
-    /// - Cancel at 100% completion → sender gets 0% refund, recipient can withdraw 100%
-    /// - Cancel before cliff → sender gets 100% refund (no accrual before cliff)
-    pub fn cancel_stream(env: Env, stream_id: u64) -> Result<(), ContractError> {
+    /// - `wdraw_to(stream_id)` with `recipient = member`, then `completed` if
+    ///   the pool is drained.
+    pub fn withdraw_from_pool_to(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+        destination: Address,
+    ) -> Result<i128, ContractError> {
         require_not_globally_paused(&env)?;
-        let mut stream = load_stream(&env, stream_id)?;
-        Self::require_stream_sender(&stream.sender);
-        Self::cancel_stream_internal(&env, &mut stream)
+        read_pool_claim_owner(&env, stream_id, &member).require_auth();
+        if destination == env.current_contract_address() {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let claim = claim_pool_member(&env, stream_id, &member)?;
+        if claim.amount == 0 {
+            return Ok(0);
+        }
+
+        acquire_reentrancy_lock(&env)?;
+        let transfer_result =
+            push_payout(&env, &claim.token, &destination, claim.amount - claim.fee);
+        release_reentrancy_lock(&env);
+        transfer_result?;
+
+        events::emit_withdrawal_to(
+            &env,
+            stream_id,
+            WithdrawalTo {
+                stream_id,
+                recipient: member,
+                destination,
+                amount: claim.amount,
+            },
+        );
+        if claim.completed_now {
+            events::emit_stream_completed(&env, stream_id);
+        }
+
+        Ok(claim.amount)
     }
 
-    pub fn delegated_cancel(
-        env: Env,
+    /// Pooled branch of `delegated_withdraw`: the signing key selects the
+    /// member, whose own delegated nonce protects against replay. Removed
+    /// members that still hold settled credit are accepted.
+    #[allow(clippy::too_many_arguments)]
+    fn delegated_pool_withdraw(
+        env: &Env,
         stream_id: u64,
         relayer: Address,
-        sender_public_key: soroban_sdk::BytesN<32>,
+        member_public_key: soroban_sdk::BytesN<32>,
         nonce: u64,
         deadline: u64,
+        expected_minimum_amount: i128,
+        relayer_fee: i128,
         signature: soroban_sdk::BytesN<64>,
-    ) -> Result<(), ContractError> {
-        require_not_globally_paused(&env)?;
-        relayer.require_auth();
-
-        delegation::validate_delegated_cancel_params(&env, stream_id, nonce, deadline)?;
-
-        let mut stream = load_stream(&env, stream_id)?;
-
-        if Self::ed25519_pubkey_from_address(&env, &stream.sender) != sender_public_key.to_array() {
+    ) -> Result<i128, ContractError> {
+        // Look the member up by key rather than by scanning the share table,
+        // so removed members can still claim their settled credit.
+        let member = Self::account_address_from_ed25519(env, &member_public_key)?;
+        let shares = read_pooled_stream_shares(env, stream_id)?;
+        if pool_member_share(&shares, &member) == 0
+            && read_pool_member_credit(env, stream_id, member.clone()) == 0
+        {
             return Err(ContractError::InvalidSignature);
         }
-
-        let mut msg = soroban_sdk::Bytes::new(&env);
-        msg.extend_from_slice(delegation::DELEGATED_CANCEL_DOMAIN);
+        delegation::validate_pool_delegation_params(env, &member, nonce, deadline, relayer_fee)?;
+
+        let mut msg = soroban_sdk::Bytes::new(env);
         msg.extend_from_array(&stream_id.to_be_bytes());
         msg.extend_from_array(&nonce.to_be_bytes());
         msg.extend_from_array(&deadline.to_be_bytes());
-
+        msg.extend_from_array(&expected_minimum_amount.to_be_bytes());
+        msg.extend_from_array(&relayer_fee.to_be_bytes());
         env.crypto()
-            .ed25519_verify(&sender_public_key, &msg, &signature);
+            .ed25519_verify(&member_public_key, &msg, &signature);
 
-        crate::storage::increment_delegated_cancel_nonce(&env, &stream.sender);
+        let claim = claim_pool_member(env, stream_id, &member)?;
+        if claim.amount - claim.fee < relayer_fee {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let net_amount = claim.amount - claim.fee - relayer_fee;
+        if net_amount < expected_minimum_amount {
+            return Err(ContractError::BelowMinimumAmount);
+        }
+        if claim.amount <= 0 {
+            return Ok(0);
+        }
+
+        increment_delegated_nonce(env, &member);
+
+        acquire_reentrancy_lock(env)?;
+        if net_amount > 0 {
+            push_payout(env, &claim.token, &member, net_amount)?;
+        }
+        if relayer_fee > 0 {
+            push_token_of(env, &claim.token, &relayer, relayer_fee)?;
+        }
+        release_reentrancy_lock(env);
+
+        events::emit_withdrawal(
+            env,
+            stream_id,
+            Withdrawal {
+                stream_id,
+                recipient: member,
+                amount: net_amount,
+            },
+        );
+        if claim.completed_now {
+            events::emit_stream_completed(env, stream_id);
+        }
 
-        Self::cancel_stream_internal(&env, &mut stream)
+        Ok(net_amount)
     }
 
-    /// Withdraw accrued tokens from a payment stream to the recipient.
-    ///
-    /// Transfers all accrued-but-not-yet-withdrawn tokens to the stream's recipient.
-    /// The amount withdrawn is calculated as `accrued - withdrawn_amount`, where accrued
-    /// is based on time elapsed since stream start. If this withdrawal completes the
-    /// stream (all deposited tokens withdrawn), the stream status transitions to `Completed`.
+    /// Move a pool member's withdrawal right to `new_owner`.
     ///
-    /// # Parameters
-    /// - `stream_id`: Unique identifier of the stream to withdraw from
+    /// The pooled counterpart of `transfer_claim_ownership`: `current_owner`
+    /// must be the member's recorded claim owner (or the member when none is
+    /// recorded) and must authorize. The owner authorizes `withdraw_from_pool`,
+    /// `withdraw_from_pool_to` and `set_pool_auto_claim` for the member; the
+    /// member keeps its share, and `delegated_withdraw` still verifies the
+    /// member's own key. Transferring back to the member clears the record.
     ///
-    /// # Returns
-    /// - `i128`: The amount of tokens transferred to the recipient (0 if nothing to withdraw)
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled.
+    /// - `Unauthorized` if `current_owner` does not hold the member's right.
+    /// - `InvalidParams` if `member` has no stake in the pool.
     ///
-    /// # Authorization
-    /// - Requires authorization from the stream's recipient (only recipient can withdraw)
-    /// - This prevents anyone from withdrawing on behalf of the recipient
+    /// # Events
+    /// - `pool_own(stream_id)` with `PoolClaimOwnerTransferred`.
+    pub fn transfer_pool_claim_ownership(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+        current_owner: Address,
+        new_owner: Address,
+    ) -> Result<(), ContractError> {
+        require_not_globally_paused(&env)?;
+        let shares = load_pool_shares(&env, stream_id)?;
+        if read_pool_claim_owner(&env, stream_id, &member) != current_owner {
+            return Err(ContractError::Unauthorized);
+        }
+        current_owner.require_auth();
+        if pool_member_share(&shares, &member) == 0
+            && read_pool_member_credit(&env, stream_id, member.clone()) == 0
+        {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let owner = if new_owner == member {
+            None
+        } else {
+            Some(&new_owner)
+        };
+        set_pool_claim_owner(&env, stream_id, &member, owner);
+
+        events::emit_pool_claim_owner_transferred(
+            &env,
+            PoolClaimOwnerTransferred {
+                stream_id,
+                member,
+                old_owner: current_owner,
+                new_owner,
+            },
+        );
+        Ok(())
+    }
+
+    /// Address holding `member`'s withdrawal right in a pooled stream.
     ///
-    /// # Zero Withdrawable Behavior
-    /// - If `accrued == withdrawn_amount` (nothing to withdraw), returns 0 immediately.
-    /// - No token transfer occurs, no state is modified or saved, and no events are published.
-    /// - This is idempotent: safe to call continuously without state churn or cost footprint.
-    /// - Occurs before cliff, after a full claim, or when the stream is already drained to its cancellation point.
-    /// - Frontends and indexers can safely poll `withdraw` without pre-checking the balance.
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `InvalidState` if the stream is not pooled.
+    pub fn get_pool_claim_owner(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+    ) -> Result<Address, ContractError> {
+        load_pool_shares(&env, stream_id)?;
+        Ok(read_pool_claim_owner(&env, stream_id, &member))
+    }
+
+    /// Set or, with `destination = None`, revoke a pool member's auto-claim
+    /// destination.
     ///
-    /// # Panics
-    /// - If the stream is `Completed` (all tokens already withdrawn)
-    /// - If the stream is `Paused` (withdrawals not allowed while paused)
-    /// - If the stream does not exist (`stream_id` is invalid)
-    /// - If caller is not authorized (not the recipient)
-    /// - If token transfer fails (insufficient contract balance, should not happen)
+    /// The pooled counterpart of `set_auto_claim` / `revoke_auto_claim`.
+    /// Requires auth from the member's claim owner. Once set, anyone may call
+    /// `trigger_pool_auto_claim` after `end_time`.
     ///
-    /// # State Changes
-    /// - Updates `withdrawn_amount` by the amount transferred (only if withdrawable > 0)
-    /// - Sets status to `Completed` only when withdrawing from an `Active` stream and all
-    ///   deposited tokens are withdrawn
-    /// - Extends stream storage TTL to prevent expiration
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled.
+    /// - `InvalidParams` if `destination` is this contract.
     ///
     /// # Events
-    /// - Publishes `withdrew(stream_id, amount)` event on success (only if amount > 0)
+    /// - `pool_ac(stream_id)` with `PoolAutoClaimSet`.
+    pub fn set_pool_auto_claim(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+        destination: Option<Address>,
+    ) -> Result<(), ContractError> {
+        load_pool_shares(&env, stream_id)?;
+        read_pool_claim_owner(&env, stream_id, &member).require_auth();
+        if let Some(destination) = &destination {
+            if !Self::is_valid_destination(&env, destination) {
+                return Err(ContractError::InvalidParams);
+            }
+        }
+
+        set_pool_auto_claim(&env, stream_id, &member, destination.as_ref());
+        events::emit_pool_auto_claim_set(
+            &env,
+            PoolAutoClaimSet {
+                stream_id,
+                member,
+                destination,
+            },
+        );
+        Ok(())
+    }
+
+    /// Auto-claim destination of a pool member, if set.
     ///
-    /// # Usage Notes
-    /// - Can be called multiple times to withdraw incrementally
-    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
-    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
-    /// - After end_time, accrued amount is capped at deposit_amount
-    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
-    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn,
-    ///   and status remains `Cancelled` (no `Completed` transition)
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `InvalidState` if the stream is not pooled.
+    pub fn get_pool_auto_claim(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+    ) -> Result<Option<Address>, ContractError> {
+        load_pool_shares(&env, stream_id)?;
+        Ok(read_pool_auto_claim(&env, stream_id, &member))
+    }
+
+    /// Send a pool member's remaining share to its auto-claim destination
+    /// (permissionless).
     ///
-    /// # Cross-Entrypoint Idempotency
-    /// - Reentrancy-protected via `acquire_reentrancy_lock` before calling `push_token`
-    /// - State is persisted BEFORE `push_token` (CEI pattern), ensuring that repeated
-    ///   calls with the same withdrawal amount produce the same result (idempotent)
-    /// - If `push_token` fails, the entire transaction reverts (state is not updated)
-    /// - After a successful withdrawal, subsequent calls with the same stream_id return 0
-    ///   until new tokens accrue
-    /// - Accrual is time-based: `min((now - start_time) × rate, deposit_amount)`
-    /// - Before cliff time, accrued amount is 0 (returns 0, no transfer)
-    /// - After end_time, accrued amount is capped at deposit_amount
-    /// - Works on `Active` and `Cancelled` streams, not on `Paused` or `Completed`
-    /// - For cancelled streams, only the accrued amount (not refunded) can be withdrawn,
-    ///   and status remains `Cancelled` (no `Completed` transition)
+    /// The pooled counterpart of `trigger_auto_claim`, with the same
+    /// accounting as `withdraw_from_pool`.
     ///
-    /// # Slippage Guard (`min_expected_amount`)
-    /// Protects the recipient from MEV-style withdrawal-ordering risk where their realized
-    /// payout is silently reduced by a transaction ordered ahead of their `withdraw` call.
-    /// Ordering scenarios this protects against include:
-    /// - `top_up_stream` expanding the deposit (and potentially shifting lookback bounds)
-    /// - `decrease_rate_per_second` lowering the accrual rate before execution
-    /// - Lookback cap logic triggering based on a modified effective time
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled, is `Completed` or
+    ///   `Cancelled`, or `end_time` has not been reached.
+    /// - `InvalidParams` if the member has no auto-claim destination.
     ///
-    /// # Examples
-    /// - Stream: 1000 tokens over 1000 seconds (1 token/sec)
-    /// - At t=0 (before cliff): withdraw() returns 0 (no transfer)
-    /// - At t=300: withdraw() returns 300 tokens
-    /// - At t=300 (again): withdraw() returns 0 (already withdrawn)
-    /// - At t=800: withdraw() returns 500 tokens (800 - 300 already withdrawn)
-    /// - At t=1000: withdraw() returns 200 tokens, status → Completed
-    pub fn withdraw(
+    /// # Events
+    /// - `ac_trig(stream_id)`, then `wdraw_to(stream_id)` with
+    ///   `recipient = member`, then `completed` if the pool is drained.
+    pub fn trigger_pool_auto_claim(
         env: Env,
         stream_id: u64,
-        min_expected_amount: Option<i128>,
+        member: Address,
     ) -> Result<i128, ContractError> {
         require_not_globally_paused(&env)?;
-        let mut stream = load_stream(&env, stream_id)?;
-
-        // Enforce claim owner or recipient authorization
-        if let Some(owner) = &stream.claim_owner {
-            owner.require_auth();
-        } else {
-            stream.recipient.require_auth();
-        }
-
-        // Enforce withdrawal frequency limit to prevent excessive ledger I/O.
-        // Use saturating_sub to prevent underflow from backward timestamp skew
-        // (if current_ledger < last_withdraw_ledger, elapsed=0, withdrawal blocked).
-        // First withdrawal (last_withdraw_ledger == 0) always succeeds.
-        let current_ledger = env.ledger().sequence();
-        if stream.last_withdraw_ledger != 0
-            && current_ledger.saturating_sub(stream.last_withdraw_ledger)
-                < MIN_WITHDRAW_INTERVAL_LEDGERS
+        let stream = load_stream(&env, stream_id)?;
+        if stream.is_pooled != Some(true)
+            || stream.status == StreamStatus::Completed
+            || stream.status == StreamStatus::Cancelled
+            || current_accrual_timestamp(&env)? < stream.end_time
         {
-            return Err(ContractError::WithdrawalTooFrequent);
+            return Err(ContractError::InvalidState);
         }
+        let destination =
+            read_pool_auto_claim(&env, stream_id, &member).ok_or(ContractError::InvalidParams)?;
 
-        if stream.status == StreamStatus::Completed {
-            return Err(ContractError::InvalidState);
+        let claim = claim_pool_member(&env, stream_id, &member)?;
+        if claim.amount == 0 {
+            return Ok(0);
         }
 
-        if stream.status == StreamStatus::Paused && !is_terminal_state(&env, &stream) {
-            return Err(ContractError::InvalidState);
+        events::emit_auto_claim_triggered(
+            &env,
+            stream_id,
+            AutoClaimTriggered {
+                stream_id,
+                destination: destination.clone(),
+                amount: claim.amount,
+            },
+        );
+        events::emit_withdrawal_to(
+            &env,
+            stream_id,
+            WithdrawalTo {
+                stream_id,
+                recipient: member,
+                destination: destination.clone(),
+                amount: claim.amount,
+            },
+        );
+        if claim.completed_now {
+            events::emit_stream_completed(&env, stream_id);
         }
 
-        let accrued = Self::calculate_accrued(env.clone(), stream_id)?;
-        let mut withdrawable = accrued - stream.withdrawn_amount;
-        let effective_time = stream
-            .cancelled_at
-            .unwrap_or_else(|| env.ledger().timestamp());
-        withdrawable = apply_lookback_cap(&env, &stream, effective_time, accrued, withdrawable);
+        acquire_reentrancy_lock(&env)?;
+        let transfer_result =
+            push_token_of(&env, &claim.token, &destination, claim.amount - claim.fee);
+        release_reentrancy_lock(&env);
+        transfer_result?;
 
-        // Cap by contract balance for safety (#39)
-        let token_address = get_token(&env)?;
-        let contract_balance =
-            token::Client::new(&env, &token_address).balance(&env.current_contract_address());
-        withdrawable = withdrawable.min(contract_balance);
+        Ok(claim.amount)
+    }
 
-        if let Some(min) = min_expected_amount {
-            if withdrawable < min {
-                return Err(ContractError::BelowMinimumAmount);
-            }
+    /// Load a pooled stream for a sender-side membership change.
+    ///
+    /// Requires sender auth and an `Active` or `Paused` pool, then settles
+    /// every member at the current accrual so the caller can rewrite shares.
+    fn settle_pool_for_update(
+        env: &Env,
+        stream_id: u64,
+    ) -> Result<(Stream, soroban_sdk::Vec<(Address, u32)>), ContractError> {
+        require_not_globally_paused(env)?;
+        let stream = load_stream(env, stream_id)?;
+        if stream.is_pooled != Some(true) {
+            return Err(ContractError::InvalidState);
         }
-
-        if withdrawable <= 0 {
-            return Ok(0);
+        Self::require_stream_sender(&stream.sender);
+        if stream.status != StreamStatus::Active && stream.status != StreamStatus::Paused {
+            return Err(ContractError::InvalidState);
         }
 
-        // Enforce dust threshold unless terminal state or final drain (#423)
-        if withdrawable < stream.withdraw_dust_threshold
-            && !is_terminal_state(&env, &stream)
-            && stream.withdrawn_amount + withdrawable < stream.deposit_amount
-        {
-            return Ok(0);
-        }
+        let shares = read_pooled_stream_shares(env, stream_id)?;
+        let pool_accrued = stream_accrued_at(env, &stream, current_accrual_timestamp(env)?);
+        settle_pool_members(env, stream_id, &shares, pool_accrued)?;
+        Ok((stream, shares))
+    }
 
-        // CEI: update state before external token transfer to reduce reentrancy risk.
-        // Cross-entrypoint idempotency: state is persisted BEFORE push_token so
-        // repeated calls produce the same result (withdrawable will be 0 after
-        // the first successful withdrawal).
-        stream.withdrawn_amount += withdrawable;
-        stream.last_withdraw_ledger = current_ledger; // Update withdrawal timestamp
-        let completed_now = (stream.status == StreamStatus::Active
-            || stream.status == StreamStatus::Paused)
-            && stream.withdrawn_amount == stream.deposit_amount;
-        let previous_status = stream.status;
-        if completed_now {
-            stream.status = StreamStatus::Completed;
-        }
-        save_stream(&env, &stream);
-        reconcile_paused_stream_count(&env, previous_status, stream.status);
+    /// Add a member to a pooled stream (sender only).
+    ///
+    /// Existing members are settled first: accrual up to now stays split by
+    /// the old weights and `member` only shares in accrual from this point on.
+    ///
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled or not `Active`/`Paused`.
+    /// - `InvalidParams` for a zero share, an existing member, or a pool
+    ///   already at `MAX_POOL_RECIPIENTS`.
+    /// - `ArithmeticOverflow` if the share total overflows `u32`.
+    ///
+    /// # Events
+    /// - `pool_mbr(stream_id)` with `PoolMemberUpdated { old_share: 0, .. }`.
+    pub fn add_pool_member(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+        share: u32,
+    ) -> Result<(), ContractError> {
+        let (stream, mut shares) = Self::settle_pool_for_update(&env, stream_id)?;
+        if share == 0
+            || pool_member_share(&shares, &member) != 0
+            || shares.len() >= MAX_POOL_RECIPIENTS
+        {
+            return Err(ContractError::InvalidParams);
+        }
+        pool_total_shares(&shares)?
+            .checked_add(share)
+            .ok_or(ContractError::ArithmeticOverflow)?;
 
-        // Reduce liabilities as tokens leave the contract to the recipient.
-        let liabilities = read_total_liabilities(&env)
-            .checked_sub(withdrawable)
-            .unwrap_or(0);
-        write_total_liabilities(&env, liabilities);
+        shares.push_back((member.clone(), share));
+        save_pooled_stream_shares(&env, stream_id, &shares);
+        add_stream_to_recipient_index(&env, &member, stream_id, Some(stream.end_time));
 
-        acquire_reentrancy_lock(&env)?;
-        let transfer_result = push_token(&env, &stream.recipient, withdrawable);
-        release_reentrancy_lock(&env);
-        transfer_result?;
+        events::emit_pool_member_updated(
+            &env,
+            PoolMemberUpdated {
+                stream_id,
+                member,
+                old_share: 0,
+                new_share: share,
+            },
+        );
+        Ok(())
+    }
 
-        events::emit_withdrawal(
+    /// Remove a member from a pooled stream (sender only).
+    ///
+    /// The member's accrual up to now is settled into its credit: it keeps
+    /// claiming that balance through `withdraw_from_pool` and stays in its
+    /// recipient index, but shares in nothing accrued afterwards.
+    ///
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled or not `Active`/`Paused`.
+    /// - `InvalidParams` if `member` is not in the pool or is its last member.
+    ///
+    /// # Events
+    /// - `pool_mbr(stream_id)` with `PoolMemberUpdated { new_share: 0, .. }`.
+    pub fn remove_pool_member(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        let (_, mut shares) = Self::settle_pool_for_update(&env, stream_id)?;
+        let index = shares
+            .iter()
+            .position(|(addr, _)| addr == member)
+            .ok_or(ContractError::InvalidParams)?;
+        if shares.len() == 1 {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let (_, old_share) = shares.get_unchecked(index as u32);
+        shares.remove(index as u32);
+        save_pooled_stream_shares(&env, stream_id, &shares);
+
+        events::emit_pool_member_updated(
             &env,
-            stream_id,
-            Withdrawal {
+            PoolMemberUpdated {
                 stream_id,
-                recipient: stream.recipient.clone(),
-                amount: withdrawable,
+                member,
+                old_share,
+                new_share: 0,
             },
         );
+        Ok(())
+    }
 
-        if completed_now {
-            events::emit_stream_completed(&env, stream_id);
+    /// Change a pool member's share weight (sender only).
+    ///
+    /// All members are settled at the old weights first, so the new weight
+    /// only applies to accrual from this point on.
+    ///
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled or not `Active`/`Paused`.
+    /// - `InvalidParams` for a zero share (use `remove_pool_member`) or if
+    ///   `member` is not in the pool.
+    /// - `ArithmeticOverflow` if the share total overflows `u32`.
+    ///
+    /// # Events
+    /// - `pool_mbr(stream_id)` with `PoolMemberUpdated`.
+    pub fn update_pool_share(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+        share: u32,
+    ) -> Result<(), ContractError> {
+        let (_, mut shares) = Self::settle_pool_for_update(&env, stream_id)?;
+        let index = shares
+            .iter()
+            .position(|(addr, _)| addr == member)
+            .ok_or(ContractError::InvalidParams)?;
+        if share == 0 {
+            return Err(ContractError::InvalidParams);
         }
 
-        Ok(withdrawable)
+        let (_, old_share) = shares.get_unchecked(index as u32);
+        (pool_total_shares(&shares)? - old_share)
+            .checked_add(share)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        shares.set(index as u32, (member.clone(), share));
+        save_pooled_stream_shares(&env, stream_id, &shares);
+
+        events::emit_pool_member_updated(
+            &env,
+            PoolMemberUpdated {
+                stream_id,
+                member,
+                old_share,
+                new_share: share,
+            },
+        );
+        Ok(())
     }
 
-    pub fn withdraw_from_pool(
+    /// Move a pool member's share, settled credit, withdrawn total, claim
+    /// owner and auto-claim destination to a new address (member only).
+    ///
+    /// The pooled counterpart of `update_recipient`: the member authorizes
+    /// its own rotation, the recipient indexes are updated and the move is
+    /// recorded in the stream's rotation history. Removed members with an
+    /// unclaimed credit may rotate too. Accrual is unaffected, so no
+    /// settlement is needed.
+    ///
+    /// # Errors
+    /// - `InvalidState` if the stream is not pooled.
+    /// - `Unauthorized` if `member` has neither a share nor a credit.
+    /// - `InvalidParams` if `new_member` equals `member` or already has a
+    ///   share, credit or withdrawal in this pool.
+    ///
+    /// # Events
+    /// - `pool_rot(stream_id)` with `PoolMemberRotated`.
+    pub fn rotate_pool_member(
         env: Env,
         stream_id: u64,
-        caller: Address,
-    ) -> Result<i128, ContractError> {
+        member: Address,
+        new_member: Address,
+    ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
-        caller.require_auth();
-
-        let mut stream = load_stream(&env, stream_id)?;
+        member.require_auth();
+        let stream = load_stream(&env, stream_id)?;
         if stream.is_pooled != Some(true) {
             return Err(ContractError::InvalidState);
         }
 
-        if stream.status == StreamStatus::Completed {
-            return Err(ContractError::InvalidState);
-        }
-        if stream.status == StreamStatus::Paused && !is_terminal_state(&env, &stream) {
-            return Err(ContractError::InvalidState);
+        let mut shares = read_pooled_stream_shares(&env, stream_id)?;
+        let credit = read_pool_member_credit(&env, stream_id, member.clone());
+        let index = shares.iter().position(|(addr, _)| addr == member);
+        if index.is_none() && credit == 0 {
+            return Err(ContractError::Unauthorized);
         }
-
-        let shares = read_pooled_stream_shares(&env, stream_id)?;
-        let mut caller_share: u32 = 0;
-        let mut total_shares: u32 = 0;
-        for (addr, share) in shares.iter() {
-            if addr == caller {
-                caller_share = caller_share
-                    .checked_add(share)
-                    .ok_or(ContractError::ArithmeticOverflow)?;
-            }
-            total_shares = total_shares
-                .checked_add(share)
-                .ok_or(ContractError::ArithmeticOverflow)?;
+        if new_member == member
+            || pool_member_share(&shares, &new_member) != 0
+            || read_pool_member_credit(&env, stream_id, new_member.clone()) != 0
+            || read_pooled_stream_withdrawn(&env, stream_id, new_member.clone()) != 0
+        {
+            return Err(ContractError::InvalidParams);
         }
 
-        if caller_share == 0 || total_shares == 0 {
-            return Err(ContractError::Unauthorized);
+        if let Some(index) = index {
+            let (_, share) = shares.get_unchecked(index as u32);
+            shares.set(index as u32, (new_member.clone(), share));
+            save_pooled_stream_shares(&env, stream_id, &shares);
         }
-
-        let now = current_accrual_timestamp(&env)?;
-        let global_accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
-
-        // Round down after applying the share fraction. This prevents any
-        // individual pool member from receiving more than their pro-rata claim;
-        // residual rounding dust remains in the pool until swept/closed by
-        // existing residual handling.
-        let caller_accrued = (global_accrued as u128)
-            .checked_mul(caller_share as u128)
-            .and_then(|val| val.checked_div(total_shares as u128))
-            .ok_or(ContractError::ArithmeticOverflow)? as i128;
-
-        let caller_withdrawn = read_pooled_stream_withdrawn(&env, stream_id, caller.clone());
-        let mut withdrawable = caller_accrued - caller_withdrawn;
-
-        let token_address = get_token(&env)?;
-        let contract_balance =
-            token::Client::new(&env, &token_address).balance(&env.current_contract_address());
-        withdrawable = withdrawable.min(contract_balance);
-
-        if withdrawable <= 0 {
-            return Ok(0);
+        save_pool_member_credit(&env, stream_id, member.clone(), 0);
+        save_pool_member_credit(&env, stream_id, new_member.clone(), credit);
+        let owner = read_pool_claim_owner(&env, stream_id, &member);
+        set_pool_claim_owner(&env, stream_id, &member, None);
+        if owner != member {
+            set_pool_claim_owner(&env, stream_id, &new_member, Some(&owner));
         }
-
-        if withdrawable < stream.withdraw_dust_threshold
-            && !is_terminal_state(&env, &stream)
-            && stream.withdrawn_amount + withdrawable < stream.deposit_amount
-        {
-            return Ok(0);
+        let auto_claim = read_pool_auto_claim(&env, stream_id, &member);
+        set_pool_auto_claim(&env, stream_id, &member, None);
+        set_pool_auto_claim(&env, stream_id, &new_member, auto_claim.as_ref());
+        let withdrawn = read_pooled_stream_withdrawn(&env, stream_id, member.clone());
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PooledStreamWithdrawn(stream_id, member.clone()));
+        if withdrawn != 0 {
+            save_pooled_stream_withdrawn(&env, stream_id, new_member.clone(), withdrawn);
         }
 
-        stream.withdrawn_amount += withdrawable;
-        save_pooled_stream_withdrawn(
+        remove_stream_from_recipient_index(&env, &member, stream_id);
+        add_stream_to_recipient_index(&env, &new_member, stream_id, Some(stream.end_time));
+        append_rotation_entry(
             &env,
             stream_id,
-            caller.clone(),
-            caller_withdrawn + withdrawable,
+            RotationEntry {
+                old_addr: member.clone(),
+                new_addr: new_member.clone(),
+                ledger: env.ledger().sequence(),
+                role: RotationRole::Recipient,
+                authoriser: member.clone(),
+            },
         );
 
-        let completed_now = (stream.status == StreamStatus::Active
-            || stream.status == StreamStatus::Paused)
-            && stream.withdrawn_amount >= stream.deposit_amount;
-
-        let previous_status = stream.status;
-        if completed_now {
-            stream.status = StreamStatus::Completed;
-        }
-        save_stream(&env, &stream);
-        reconcile_paused_stream_count(&env, previous_status, stream.status);
-
-        let liabilities = read_total_liabilities(&env)
-            .checked_sub(withdrawable)
-            .unwrap_or(0);
-        write_total_liabilities(&env, liabilities);
-
-        acquire_reentrancy_lock(&env)?;
-        let transfer_result = push_token(&env, &caller, withdrawable);
-        release_reentrancy_lock(&env);
-        transfer_result?;
-
-        events::emit_withdrawal(
+        events::emit_pool_member_rotated(
             &env,
-            stream_id,
-            Withdrawal {
+            PoolMemberRotated {
                 stream_id,
-                recipient: caller.clone(),
-                amount: withdrawable,
+                old_member: member,
+                new_member,
             },
         );
+        Ok(())
+    }
 
-        if completed_now {
-            events::emit_stream_completed(&env, stream_id);
+    /// Current `(member, share_weight)` table of a pooled stream.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `InvalidState` if the stream is not pooled.
+    pub fn get_pool_members(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<soroban_sdk::Vec<(Address, u32)>, ContractError> {
+        if load_stream(&env, stream_id)?.is_pooled != Some(true) {
+            return Err(ContractError::InvalidState);
         }
+        read_pooled_stream_shares(&env, stream_id)
+    }
 
-        Ok(withdrawable)
+    /// Amount `member` could withdraw from a pooled stream right now: the
+    /// per-member counterpart of `get_withdrawable`.
+    ///
+    /// Includes any credit settled to a removed member and is capped by the
+    /// contract balance like `withdraw_from_pool`. Returns `0` for addresses
+    /// with no stake in the pool and while the pool is `Completed` or paused.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `InvalidState` if the stream is not pooled.
+    pub fn get_pool_member_withdrawable(
+        env: Env,
+        stream_id: u64,
+        member: Address,
+    ) -> Result<i128, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        if stream.is_pooled != Some(true) {
+            return Err(ContractError::InvalidState);
+        }
+        if stream.status == StreamStatus::Completed || stream.status == StreamStatus::Paused {
+            return Ok(0);
+        }
+        let shares = read_pooled_stream_shares(&env, stream_id)?;
+        let pool_accrued = stream_accrued_at(&env, &stream, env.ledger().timestamp());
+        let accrued = pool_member_accrued(&env, stream_id, &shares, &member, pool_accrued)?;
+        let token = load_stream_token(&env, stream_id)?;
+        let contract_balance =
+            token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let withdrawable = accrued - read_pooled_stream_withdrawn(&env, stream_id, member);
+        Ok(withdrawable.min(contract_balance).max(0))
     }
 
     /// Withdraw accrued tokens from a payment stream to a specified destination address.
@@ -3593,6 +5933,7 @@ impl FluxoraStream {
     /// - `"cannot withdraw from paused stream"` — if stream status is `Paused`
     /// - If the stream does not exist (`StreamNotFound`)
     /// - If caller is not the stream's recipient (auth failure)
+    /// - `InvalidState` for pooled streams (use `withdraw_from_pool_to`)
     ///
     /// # Usage Notes
     /// - Works on `Active` and `Cancelled` streams (same as `withdraw`).
@@ -3607,6 +5948,11 @@ impl FluxoraStream {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
 
+        // Pooled streams pay per member; see `withdraw_from_pool`.
+        if stream.is_pooled == Some(true) {
+            return Err(ContractError::InvalidState);
+        }
+
         // Enforce claim owner or recipient authorization for source of funds
         if let Some(owner) = &stream.claim_owner {
             owner.require_auth();
@@ -3634,7 +5980,7 @@ impl FluxoraStream {
         withdrawable = apply_lookback_cap(&env, &stream, effective_time, accrued, withdrawable);
 
         // Cap by contract balance for safety (#39)
-        let token_address = get_token(&env)?;
+        let token_address = load_stream_token(&env, stream_id)?;
         let contract_balance =
             token::Client::new(&env, &token_address).balance(&env.current_contract_address());
         withdrawable = withdrawable.min(contract_balance);
@@ -3663,13 +6009,15 @@ impl FluxoraStream {
         reconcile_paused_stream_count(&env, previous_status, stream.status);
 
         // Reduce liabilities as tokens leave the contract.
-        let liabilities = read_total_liabilities(&env)
+        let liabilities = read_token_liabilities(&env, &token_address)?
             .checked_sub(withdrawable)
             .unwrap_or(0);
-        write_total_liabilities(&env, liabilities);
+        write_token_liabilities(&env, &token_address, liabilities)?;
+
+        let fee = charge_withdraw_fee(&env, stream_id, &token_address, withdrawable)?;
 
         acquire_reentrancy_lock(&env)?;
-        let transfer_result = push_token(&env, &destination, withdrawable);
+        let transfer_result = push_payout(&env, &token_address, &destination, withdrawable - fee);
         release_reentrancy_lock(&env);
         transfer_result?;
 
@@ -3763,6 +6111,16 @@ impl FluxoraStream {
 
         stream.recipient = pending.proposed_recipient.clone();
         save_stream(&env, &stream);
+        // The hook was chosen by the outgoing recipient.
+        set_recipient_hook(&env, stream_id, &None);
+        // A recipient-held position rotates with the recipient; an explicitly
+        // transferred one stays with its claim owner.
+        let position_moved = stream.claim_owner.is_none() && stream.is_pooled != Some(true);
+        if position_moved {
+            remove_owner_position(&env, &old_recipient, stream_id);
+            add_owner_position(&env, &stream.recipient, stream_id);
+            set_position_approval(&env, stream_id, &None);
+        }
         append_rotation_entry(
             &env,
             stream_id,
@@ -3783,10 +6141,21 @@ impl FluxoraStream {
             stream_id,
             RecipientUpdated {
                 stream_id,
-                old_recipient,
-                new_recipient: pending.proposed_recipient,
+                old_recipient: old_recipient.clone(),
+                new_recipient: pending.proposed_recipient.clone(),
             },
         );
+        if position_moved {
+            events::emit_position_transferred(
+                &env,
+                stream_id,
+                PositionTransferred {
+                    stream_id,
+                    from: old_recipient,
+                    to: pending.proposed_recipient,
+                },
+            );
+        }
 
         Ok(())
     }
@@ -3822,7 +6191,8 @@ impl FluxoraStream {
     ///
     /// `current_owner` must equal the recorded claim owner, or the stream recipient
     /// when ownership has not yet been transferred, and must authorize this call.
-    /// The sender has no authority in this flow.
+    /// The sender has no authority in this flow. Pooled streams return
+    /// `InvalidState`; members use `transfer_pool_claim_ownership`.
     pub fn transfer_claim_ownership(
         env: Env,
         stream_id: u64,
@@ -3831,6 +6201,10 @@ impl FluxoraStream {
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
+        // Pool members move their rights with `transfer_pool_claim_ownership`.
+        if stream.is_pooled == Some(true) {
+            return Err(ContractError::InvalidState);
+        }
 
         let actual_current = stream
             .claim_owner
@@ -3842,20 +6216,169 @@ impl FluxoraStream {
 
         current_owner.require_auth();
 
+        Self::transfer_position(&env, &mut stream, current_owner, new_owner);
+        Ok(())
+    }
+
+    /// Current holder of a stream position: the explicit claim owner, or the
+    /// recipient when ownership has never been transferred.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist (or has been closed).
+    pub fn owner_of(env: Env, stream_id: u64) -> Result<Address, ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Ok(stream.claim_owner.unwrap_or(stream.recipient))
+    }
+
+    /// Approve `approved` to move a single stream position with
+    /// [`transfer_from`](Self::transfer_from). Passing `None` revokes the
+    /// current approval. At most one approval exists per position and it is
+    /// cleared on every transfer.
+    ///
+    /// `owner` must be the current [`owner_of`](Self::owner_of) the stream and
+    /// must authorize this call.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `Unauthorized` if `owner` does not hold the position.
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        approved: Option<Address>,
+        stream_id: u64,
+    ) -> Result<(), ContractError> {
+        require_not_globally_paused(&env)?;
+        let stream = load_stream(&env, stream_id)?;
+        if stream.claim_owner.unwrap_or(stream.recipient) != owner {
+            return Err(ContractError::Unauthorized);
+        }
+        owner.require_auth();
+
+        set_position_approval(&env, stream_id, &approved);
+        events::emit_position_approved(
+            &env,
+            stream_id,
+            PositionApproved {
+                stream_id,
+                owner,
+                approved,
+            },
+        );
+        Ok(())
+    }
+
+    /// Address currently approved to transfer the position, if any.
+    pub fn get_approved(env: Env, stream_id: u64) -> Result<Option<Address>, ContractError> {
+        load_stream(&env, stream_id)?;
+        Ok(get_position_approval(&env, stream_id))
+    }
+
+    /// Move a stream position from `from` to `to` on behalf of `spender`.
+    ///
+    /// `spender` must authorize and be either the current owner or the
+    /// address approved for this position. The withdrawal right moves exactly
+    /// as with [`transfer_claim_ownership`](Self::transfer_claim_ownership):
+    /// `to` becomes the explicit claim owner, any approval, payout hook and
+    /// auto-claim destination are cleared, and both `claim_own` and `pos_xfer`
+    /// are emitted.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `Unauthorized` if `from` does not hold the position or `spender` is
+    ///   neither `from` nor the approved address.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        stream_id: u64,
+    ) -> Result<(), ContractError> {
+        require_not_globally_paused(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+
+        let owner = stream
+            .claim_owner
+            .clone()
+            .unwrap_or(stream.recipient.clone());
+        if owner != from {
+            return Err(ContractError::Unauthorized);
+        }
+        if spender != from && get_position_approval(&env, stream_id) != Some(spender.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+        spender.require_auth();
+
+        Self::transfer_position(&env, &mut stream, from, to);
+        Ok(())
+    }
+
+    /// Stream positions held by `owner`, sorted ascending by stream ID: the
+    /// streams it receives and has not transferred away, plus the streams
+    /// transferred to it. Pooled streams are not positions.
+    ///
+    /// Returns at most `RECIPIENT_STREAMS_PAGE_LIMIT` IDs (the first page);
+    /// use [`get_owner_positions_paginated`](Self::get_owner_positions_paginated)
+    /// for the rest.
+    pub fn get_owner_positions(env: Env, owner: Address) -> soroban_sdk::Vec<u64> {
+        Self::get_owner_positions_paginated(env, owner, 0, RECIPIENT_STREAMS_PAGE_LIMIT).stream_ids
+    }
+
+    /// Paginated list of the stream positions held by `owner`.
+    ///
+    /// Same cursor and limit rules as
+    /// [`get_recipient_streams_paginated`](Self::get_recipient_streams_paginated):
+    /// IDs ascending, `cursor` inclusive (`0` to start), `limit` capped at
+    /// `RECIPIENT_STREAMS_PAGE_LIMIT`, and `next_cursor == 0` on the last page.
+    pub fn get_owner_positions_paginated(
+        env: Env,
+        owner: Address,
+        cursor: u64,
+        limit: u32,
+    ) -> Page {
+        let positions = load_owner_positions(&env, &owner);
+        Self::page_stream_index(&env, &positions, cursor, limit, None)
+    }
+
+    /// Number of stream positions held by `owner`. Read from a counter kept
+    /// in step with every ownership change, so the cost does not grow with
+    /// the number of positions.
+    pub fn balance_of(env: Env, owner: Address) -> u32 {
+        get_owner_position_count(&env, &owner)
+    }
+
+    /// Shared position transfer used by `transfer_claim_ownership` and
+    /// `transfer_from`. Callers have already checked ownership and auth.
+    fn transfer_position(env: &Env, stream: &mut Stream, from: Address, to: Address) {
+        let stream_id = stream.stream_id;
         let old_owner = stream.claim_owner.clone();
-        stream.claim_owner = Some(new_owner.clone());
-        save_stream(&env, &stream);
+        remove_owner_position(env, &from, stream_id);
+        stream.claim_owner = Some(to.clone());
+        save_stream(env, stream);
+        add_owner_position(env, &to, stream_id);
+        set_position_approval(env, stream_id, &None);
+        // The hook and auto-claim destination were chosen by the outgoing owner.
+        set_recipient_hook(env, stream_id, &None);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AutoClaimDestination(stream_id));
 
         env.events().publish(
             (symbol_short!("claim_own"), stream_id),
             ClaimOwnershipTransferred {
                 stream_id,
                 old_owner,
-                new_owner,
+                new_owner: to.clone(),
+            },
+        );
+        events::emit_position_transferred(
+            env,
+            stream_id,
+            PositionTransferred {
+                stream_id,
+                from,
+                to,
             },
         );
-
-        Ok(())
     }
 
     /// Withdraw accrued tokens from multiple streams in one call (recipient-only).
@@ -3942,19 +6465,33 @@ impl FluxoraStream {
             }
         }
 
-        // Fetch initial contract balance and track remaining safety buffer
-        let token_address = get_token(&env)?;
-        let mut contract_balance =
-            token::Client::new(&env, &token_address).balance(&env.current_contract_address());
+        // Running contract balance (safety buffer) and liabilities per token.
+        // Streams in one batch may be denominated in different tokens, so both
+        // are loaded lazily on first use and liabilities are flushed once per
+        // token after the batch.
+        let mut balances: Map<Address, i128> = Map::new(&env);
+        let mut liabilities: Map<Address, i128> = Map::new(&env);
         let mut results = soroban_sdk::Vec::new(&env);
 
         // Cache ledger timestamp once — it is constant within a single transaction.
         let now = current_accrual_timestamp(&env)?;
-        let mut total_liabilities = read_total_liabilities(&env);
         let mut liabilities_changed = false;
 
         for param in withdrawals.iter() {
             let mut stream = load_stream(&env, param.stream_id)?;
+            let token_address = load_stream_token(&env, param.stream_id)?;
+            if !balances.contains_key(token_address.clone()) {
+                balances.set(
+                    token_address.clone(),
+                    token::Client::new(&env, &token_address)
+                        .balance(&env.current_contract_address()),
+                );
+                liabilities.set(
+                    token_address.clone(),
+                    read_token_liabilities(&env, &token_address)?,
+                );
+            }
+            let contract_balance = balances.get(token_address.clone()).unwrap_or(0);
 
             let current_owner = stream
                 .claim_owner
@@ -3980,22 +6517,11 @@ impl FluxoraStream {
                 0
             } else {
                 let effective_now = if stream.status == StreamStatus::Cancelled {
-                    stream.cancelled_at.ok_or(ContractError::InvalidState)?
+                    cancelled_accrual_time(&env, &stream)?
                 } else {
                     now
                 };
-                let accrued = accrual::calculate_accrued_amount_checkpointed(
-                    accrual::CheckpointState {
-                        checkpointed_amount: stream.checkpointed_amount,
-                        checkpointed_at: stream.checkpointed_at,
-                        cliff_time: stream.cliff_time,
-                        end_time: stream.end_time,
-                        deposit_amount: stream.deposit_amount,
-                        kind: stream.kind,
-                    },
-                    stream.rate_per_second,
-                    effective_now,
-                );
+                let accrued = stream_accrued_at(&env, &stream, effective_now);
                 apply_lookback_cap(
                     &env,
                     &stream,
@@ -4019,7 +6545,7 @@ impl FluxoraStream {
 
             if withdrawable > 0 {
                 // Decrement running balance before the transfer to ensure atomicity
-                contract_balance -= withdrawable;
+                balances.set(token_address.clone(), contract_balance - withdrawable);
 
                 stream.withdrawn_amount += withdrawable;
                 let current_ledger = env.ledger().sequence();
@@ -4034,15 +6560,36 @@ impl FluxoraStream {
                 save_stream(&env, &stream);
                 reconcile_paused_stream_count(&env, previous_status, stream.status);
 
-                // Reduce liabilities locally as tokens leave the contract, then
-                // flush the shared TotalLiabilities slot once after the batch.
-                total_liabilities = total_liabilities.checked_sub(withdrawable).unwrap_or(0);
+                let fee = charge_withdraw_fee(&env, param.stream_id, &token_address, withdrawable)?;
+                let credited = get_claim_to_balance(&env, &param.destination);
+
+                // Reduce liabilities locally, then flush each token's liability
+                // slot once after the batch. Transferred tokens leave the
+                // contract; credited amounts stay in it and remain a liability,
+                // so only the fee (now a protocol fee liability) is released.
+                let released = if credited { fee } else { withdrawable };
+                let token_liabilities = liabilities
+                    .get(token_address.clone())
+                    .unwrap_or(0)
+                    .checked_sub(released)
+                    .unwrap_or(0);
+                liabilities.set(token_address.clone(), token_liabilities);
                 liabilities_changed = true;
 
-                acquire_reentrancy_lock(&env)?;
-                let transfer_result = push_token(&env, &param.destination, withdrawable);
-                release_reentrancy_lock(&env);
-                transfer_result?;
+                if credited {
+                    credit_recipient_balance(
+                        &env,
+                        &param.destination,
+                        &token_address,
+                        withdrawable - fee,
+                    )?;
+                } else {
+                    acquire_reentrancy_lock(&env)?;
+                    let transfer_result =
+                        push_token_of(&env, &token_address, &param.destination, withdrawable - fee);
+                    release_reentrancy_lock(&env);
+                    transfer_result?;
+                }
 
                 events::emit_withdrawal_to(
                     &env,
@@ -4067,7 +6614,9 @@ impl FluxoraStream {
         }
 
         if liabilities_changed {
-            write_total_liabilities(&env, total_liabilities);
+            for (token_address, amount) in liabilities.iter() {
+                write_token_liabilities(&env, &token_address, amount)?;
+            }
         }
 
         Ok(results)
@@ -4113,6 +6662,18 @@ impl FluxoraStream {
     /// - `BelowMinimumAmount` (16): Withdrawable amount is below `expected_minimum_amount`.
     /// - `InvalidState`: Stream is paused (non-terminal) or completed.
     /// - `StreamNotFound`: `stream_id` does not exist.
+    ///
+    /// # Pooled streams
+    /// `recipient_public_key` selects the pool member with that key (a current
+    /// member or a removed one with settled credit), the nonce is that
+    /// member's (`get_delegated_nonce(member)`), and the member's share is
+    /// paid out exactly as `withdraw_from_pool` would.
+    ///
+    /// # Transferred positions
+    /// "Recipient" above means the position owner ([`owner_of`](Self::owner_of)):
+    /// after `transfer_from` or `transfer_claim_ownership` the new owner's key
+    /// signs, its nonce is used and it is paid, so the original recipient can
+    /// no longer relay withdrawals.
     pub fn delegated_withdraw(
         env: Env,
         stream_id: u64,
@@ -4130,6 +6691,21 @@ impl FluxoraStream {
         // replaced by the ed25519 signature check below.
         relayer.require_auth();
 
+        // Pooled streams pay the member whose key signed the message.
+        if load_stream(&env, stream_id).is_ok_and(|stream| stream.is_pooled == Some(true)) {
+            return Self::delegated_pool_withdraw(
+                &env,
+                stream_id,
+                relayer,
+                recipient_public_key,
+                nonce,
+                deadline,
+                expected_minimum_amount,
+                relayer_fee,
+                signature,
+            );
+        }
+
         // 1. Validate delegation parameters (deadline, nonce, & fee >= 0).
         delegation::validate_delegation_params(&env, stream_id, nonce, deadline, relayer_fee)?;
 
@@ -4145,10 +6721,12 @@ impl FluxoraStream {
             return Err(ContractError::WithdrawalTooFrequent);
         }
 
-        // 4. Verify the supplied public key matches the stream recipient.
-        if Self::ed25519_pubkey_from_address(&env, &stream.recipient)
-            != recipient_public_key.to_array()
-        {
+        // 4. Verify the supplied public key matches the position owner.
+        let owner = stream
+            .claim_owner
+            .clone()
+            .unwrap_or(stream.recipient.clone());
+        if Self::ed25519_pubkey_from_address(&env, &owner) != recipient_public_key.to_array() {
             return Err(ContractError::InvalidSignature);
         }
 
@@ -4188,17 +6766,18 @@ impl FluxoraStream {
         );
 
         // Cap by contract balance for safety.
-        let token_address = get_token(&env)?;
+        let token_address = load_stream_token(&env, stream_id)?;
         let contract_balance =
             token::Client::new(&env, &token_address).balance(&env.current_contract_address());
         gross_withdrawable = gross_withdrawable.min(contract_balance);
 
-        if gross_withdrawable < relayer_fee {
+        // 8. Deduct the protocol withdrawal fee, then the relayer fee, to get
+        //    the net payout for the recipient.
+        let protocol_fee = withdraw_fee_for(&env, gross_withdrawable)?;
+        if gross_withdrawable - protocol_fee < relayer_fee {
             return Err(ContractError::InsufficientBalance);
         }
-
-        // 8. Deduct relayer fee to get net payout for recipient
-        let net_amount = gross_withdrawable - relayer_fee;
+        let net_amount = gross_withdrawable - protocol_fee - relayer_fee;
 
         // 9. Enforce minimum amount guard on NET amount
         if net_amount < expected_minimum_amount {
@@ -4223,17 +6802,24 @@ impl FluxoraStream {
         reconcile_paused_stream_count(&env, previous_status, stream.status);
 
         // 11. Increment nonce to prevent replay.
-        increment_delegated_nonce(&env, &stream.recipient);
+        increment_delegated_nonce(&env, &owner);
+
+        // Reduce liabilities by the gross amount (recipient payout plus relayer fee).
+        let liabilities = read_token_liabilities(&env, &token_address)?
+            .checked_sub(gross_withdrawable)
+            .unwrap_or(0);
+        write_token_liabilities(&env, &token_address, liabilities)?;
+        charge_withdraw_fee(&env, stream_id, &token_address, gross_withdrawable)?;
 
         // 12. Transfers via push_token: Net payout to RECIPIENT first, Fee to RELAYER second
         // Cross-entrypoint idempotency: reentrancy lock prevents nested token callbacks
         // from corrupting withdrawn_amount or liability tracking.
         acquire_reentrancy_lock(&env)?;
         if net_amount > 0 {
-            push_token(&env, &stream.recipient, net_amount)?;
+            push_payout(&env, &token_address, &owner, net_amount)?;
         }
         if relayer_fee > 0 {
-            push_token(&env, &relayer, relayer_fee)?;
+            push_token_of(&env, &token_address, &relayer, relayer_fee)?;
         }
         release_reentrancy_lock(&env);
 
@@ -4329,23 +6915,12 @@ impl FluxoraStream {
         }
 
         let now = if stream.status == StreamStatus::Cancelled {
-            stream.cancelled_at.ok_or(ContractError::InvalidState)?
+            cancelled_accrual_time(&env, &stream)?
         } else {
             current_accrual_timestamp(&env)?
         };
 
-        Ok(accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        ))
+        Ok(stream_accrued_at(&env, &stream, now))
     }
 
     /// Set or clear the per-withdrawal lookback window for a stream.
@@ -4394,9 +6969,16 @@ impl FluxoraStream {
     ///
     /// # Errors
     /// - Returns `ContractError::StreamNotFound` if the stream does not exist.
+    /// - Returns `ContractError::InvalidState` for pooled streams, which have no
+    ///   single recipient; use `get_pool_member_withdrawable`.
     pub fn get_withdrawable(env: Env, stream_id: u64) -> Result<i128, ContractError> {
         let stream = load_stream(&env, stream_id)?;
 
+        // Pooled streams are claimed per member; see `get_pool_member_withdrawable`.
+        if stream.is_pooled == Some(true) {
+            return Err(ContractError::InvalidState);
+        }
+
         // If the stream is completed or paused, withdrawals are not allowed.
         if stream.status == StreamStatus::Completed || stream.status == StreamStatus::Paused {
             return Ok(0);
@@ -4413,7 +6995,7 @@ impl FluxoraStream {
         );
 
         // Cap by contract balance for consistency with withdraw() (#39)
-        let token_address = get_token(&env)?;
+        let token_address = load_stream_token(&env, stream_id)?;
         let contract_balance =
             token::Client::new(&env, &token_address).balance(&env.current_contract_address());
         withdrawable = withdrawable.min(contract_balance);
@@ -4463,24 +7045,13 @@ impl FluxoraStream {
         let effective_time = match stream.status {
             StreamStatus::Cancelled => {
                 let at = stream.cancelled_at.ok_or(ContractError::InvalidState)?;
-                timestamp.min(at)
+                timestamp.min(cancelled_vesting_end(&env, &stream, at))
             }
             StreamStatus::Active | StreamStatus::Paused => timestamp,
             StreamStatus::Completed => unreachable!("returned above"),
         };
 
-        let accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            effective_time,
-        );
+        let accrued = stream_accrued_at(&env, &stream, effective_time);
 
         let claimable = accrued - stream.withdrawn_amount;
         let claimable = apply_lookback_cap(&env, &stream, effective_time, accrued, claimable);
@@ -4693,18 +7264,35 @@ impl FluxoraStream {
     ///   timestamp, consistent with `calculate_accrued`'s frozen-accrual
     ///   semantics — a cancelled stream's cliff status does not keep
     ///   advancing with wall-clock time after cancellation.
+    /// - Once the cliff has passed, `Tranched` streams are classified against
+    ///   their next pending unlock (see `get_next_unlock`) instead, and report
+    ///   `Unlocked` only after the last tranche.
     pub fn get_cliff_status(env: Env, stream_id: u64) -> Result<accrual::CliffStatus, ContractError> {
         let stream = load_stream(&env, stream_id)?;
+        let now = Self::cliff_status_time(&env, &stream)?;
 
-        let now = if stream.status == StreamStatus::Cancelled {
-            stream.cancelled_at.ok_or(ContractError::InvalidState)?
-        } else {
-            current_accrual_timestamp(&env)?
-        };
+        if stream.kind == StreamKind::Tranched && now >= stream.cliff_time {
+            if let Some(next) =
+                accrual::next_tranche(load_tranche_schedule(&env, stream_id).iter(), now)
+            {
+                return Ok(accrual::cliff_status(now, next.unlock_time));
+            }
+        }
 
         Ok(accrual::cliff_status(now, stream.cliff_time))
     }
 
+    /// Evaluation time of `get_cliff_status`: `cancelled_at` for cancelled
+    /// streams (later while a `GoodLeaver` window is open), the current
+    /// accrual timestamp otherwise.
+    fn cliff_status_time(env: &Env, stream: &Stream) -> Result<u64, ContractError> {
+        if stream.status == StreamStatus::Cancelled {
+            cancelled_accrual_time(env, stream)
+        } else {
+            current_accrual_timestamp(env)
+        }
+    }
+
     /// Returns the total duration (in seconds) the stream has been in Paused state.
     ///
     /// This includes all past pause cycles. If the stream is currently paused,
@@ -4847,7 +7435,8 @@ impl FluxoraStream {
     }
 
     /// Returns the contract's current total outstanding liabilities: the sum
-    /// of every stream's remaining (not-yet-withdrawn) balance.
+    /// of every stream's remaining (not-yet-withdrawn) balance and every sender
+    /// vault and recipient balance in the default token.
     ///
     /// Auth-free, read-only view. Used to cross-check that the contract's
     /// token balance never falls short of what it owes across all streams.
@@ -4855,6 +7444,15 @@ impl FluxoraStream {
         read_total_liabilities(&env)
     }
 
+    /// Returns the outstanding liabilities denominated in `token`.
+    ///
+    /// For the contract's default token (`Config.token`) this equals
+    /// [`get_total_liabilities`](Self::get_total_liabilities); other tokens are
+    /// tracked separately so each asset's balance can be checked on its own.
+    pub fn get_token_liabilities(env: Env, token: Address) -> Result<i128, ContractError> {
+        read_token_liabilities(&env, &token)
+    }
+
     /// Return the protocol-wide number of streams currently in `StreamStatus::Paused`.
     ///
     /// This view is O(1): it reads the maintained `DataKey::PausedStreamCount` instance key
@@ -4903,6 +7501,17 @@ impl FluxoraStream {
         env: Env,
         stream_id: u64,
         new_rate_per_second: i128,
+    ) -> Result<(), ContractError> {
+        Self::update_rate_per_second_internal(env, stream_id, new_rate_per_second, None)
+    }
+
+    /// Body of [`update_rate_per_second`](Self::update_rate_per_second).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn update_rate_per_second_internal(
+        env: Env,
+        stream_id: u64,
+        new_rate_per_second: i128,
+        batch_caller: Option<&Address>,
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
@@ -4918,7 +7527,7 @@ impl FluxoraStream {
         check_and_bump_rate_cooldown(&env, &mut stream)?;
 
         // Only the original sender can update the rate.
-        Self::require_stream_sender(&stream.sender);
+        Self::require_op_auth(&stream.sender, batch_caller)?;
 
         // Only mutable (non-terminal) streams can be updated.
         if stream.status != StreamStatus::Active && stream.status != StreamStatus::Paused {
@@ -5051,6 +7660,17 @@ impl FluxoraStream {
         env: Env,
         stream_id: u64,
         new_rate_per_second: i128,
+    ) -> Result<(), ContractError> {
+        Self::decrease_rate_per_second_internal(env, stream_id, new_rate_per_second, None)
+    }
+
+    /// Body of [`decrease_rate_per_second`](Self::decrease_rate_per_second).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn decrease_rate_per_second_internal(
+        env: Env,
+        stream_id: u64,
+        new_rate_per_second: i128,
+        batch_caller: Option<&Address>,
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
@@ -5066,7 +7686,7 @@ impl FluxoraStream {
         check_and_bump_rate_cooldown(&env, &mut stream)?;
 
         // Sender-only: only the original creator may reduce the rate.
-        Self::require_stream_sender(&stream.sender);
+        Self::require_op_auth(&stream.sender, batch_caller)?;
 
         // Terminal streams cannot be mutated.
         if stream.status == StreamStatus::Completed || stream.status == StreamStatus::Cancelled {
@@ -5140,11 +7760,19 @@ impl FluxoraStream {
         // Refund the now-unreachable portion of the deposit to the sender.
         if refund_amount > 0 {
             // Reduce liabilities by the refunded portion (no longer owed to recipient).
-            let liabilities = read_total_liabilities(&env)
+            let token_address = load_stream_token(&env, stream_id)?;
+            let liabilities = read_token_liabilities(&env, &token_address)?
                 .checked_sub(refund_amount)
                 .unwrap_or(0);
-            write_total_liabilities(&env, liabilities);
-            push_token(&env, &stream.sender, refund_amount)?;
+            write_token_liabilities(&env, &token_address, liabilities)?;
+            Self::push_refund(
+                &env,
+                &stream,
+                &token_address,
+                refund_amount,
+                RefundReason::DecreaseRate,
+                true,
+            )?;
         }
 
         events::emit_rate_decreased(
@@ -5183,7 +7811,7 @@ impl FluxoraStream {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
 
-        if stream.kind == StreamKind::CliffOnly || stream.kind == StreamKind::CliffSlope {
+        if stream.kind != StreamKind::Linear {
             return Err(ContractError::UnsupportedStreamKind);
         }
 
@@ -5321,7 +7949,10 @@ impl FluxoraStream {
         };
 
         save_stream(&env, &child_stream);
+        // The child is funded from the parent's deposit, so it shares its token.
+        save_stream_token(&env, child_stream_id, &load_stream_token(&env, stream_id)?)?;
         add_stream_to_recipient_index(&env, &new_recipient, child_stream_id, Some(stream.end_time));
+        add_owner_position(&env, &new_recipient, child_stream_id);
         add_stream_to_sender_index(&env, &stream.sender, child_stream_id, Some(stream.end_time));
 
         env.events().publish(
@@ -5340,6 +7971,317 @@ impl FluxoraStream {
         Ok(child_stream_id)
     }
 
+    /// Split a stream into two fully independent streams.
+    ///
+    /// Unlike [`delegate_recipient_share`](Self::delegate_recipient_share), the
+    /// new stream has no `parent_stream_id` and no delegation depth: it can be
+    /// paused, cancelled, transferred or split again on its own.
+    ///
+    /// The remaining deposit, rate, checkpointed accrual and withdrawn amount
+    /// are divided according to `share` (see [`accrual::split_stream_terms`]
+    /// for the rounding rules). Every balance sums back to the original, so
+    /// token liabilities are unchanged and no tokens move. Both streams keep
+    /// the original `start_time`, `cliff_time`, `end_time`, kind, token, memo,
+    /// metadata, dust threshold and termination policy; per-stream settings
+    /// such as auto-renew, auto-claim or lookback windows stay with the
+    /// original only. A `BadLeaver` floor is an absolute amount, so each
+    /// stream keeps the full floor.
+    ///
+    /// The sender initiates the split, and because the new stream takes a
+    /// share of already-accrued funds, the current position owner
+    /// ([`owner_of`](Self::owner_of)) must authorize it too.
+    ///
+    /// Emits `created` for the new stream, so indexers see it like any other
+    /// stream, followed by `split` on the original.
+    ///
+    /// # Returns
+    /// - `u64`: ID of the new stream, whose recipient is `new_recipient`.
+    ///
+    /// # Errors
+    /// - `StreamNotFound` if the stream does not exist.
+    /// - `InvalidState` if the stream is not `Active`.
+    /// - `UnsupportedStreamKind` for `Piecewise`, `Tranched`,
+    ///   `ExponentialDecay`, `Quadratic` and pooled streams.
+    /// - `InvalidParams` if `new_recipient` is the sender or this contract, or
+    ///   if the share is out of range or would leave either stream without a
+    ///   balance or (except `CliffOnly`) without a rate.
+    pub fn split_stream(
+        env: Env,
+        stream_id: u64,
+        share: SplitShare,
+        new_recipient: Address,
+    ) -> Result<u64, ContractError> {
+        require_not_globally_paused(&env)?;
+        let mut stream = load_stream(&env, stream_id)?;
+
+        stream.sender.require_auth();
+        let owner = stream
+            .claim_owner
+            .clone()
+            .unwrap_or(stream.recipient.clone());
+        if owner != stream.sender {
+            owner.require_auth();
+        }
+
+        if stream.status != StreamStatus::Active {
+            return Err(ContractError::InvalidState);
+        }
+        if stream.is_pooled.unwrap_or(false) {
+            return Err(ContractError::UnsupportedStreamKind);
+        }
+        // Same party rule as `validate_stream_window`; the contract itself
+        // cannot hold a position.
+        if new_recipient == stream.sender || !Self::is_valid_destination(&env, &new_recipient) {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let (share_num, share_den) = match share {
+            SplitShare::Bps(bps) => (bps as i128, 10_000),
+            SplitShare::Amount(amount) => (amount, stream.deposit_amount - stream.withdrawn_amount),
+        };
+        let now = current_accrual_timestamp(&env)?;
+        let (kept, split) = accrual::split_stream_terms(
+            accrual::CheckpointState {
+                checkpointed_amount: stream.checkpointed_amount,
+                checkpointed_at: stream.checkpointed_at,
+                cliff_time: stream.cliff_time,
+                end_time: stream.end_time,
+                deposit_amount: stream.deposit_amount,
+                kind: stream.kind,
+            },
+            stream.rate_per_second,
+            stream.withdrawn_amount,
+            share_num,
+            share_den,
+            now,
+        )?;
+
+        for terms in [&kept, &split] {
+            if terms.deposit_amount <= terms.withdrawn_amount
+                || (stream.kind != StreamKind::CliffOnly && terms.rate_per_second <= 0)
+            {
+                return Err(ContractError::InvalidParams);
+            }
+        }
+
+        stream.deposit_amount = kept.deposit_amount;
+        stream.rate_per_second = kept.rate_per_second;
+        stream.checkpointed_amount = kept.checkpointed_amount;
+        stream.withdrawn_amount = kept.withdrawn_amount;
+        save_stream(&env, &stream);
+
+        let new_stream_id = next_stream_id_for(&env, &stream.sender);
+        let new_stream = Stream {
+            stream_id: new_stream_id,
+            recipient: new_recipient.clone(),
+            claim_owner: None,
+            deposit_amount: split.deposit_amount,
+            rate_per_second: split.rate_per_second,
+            checkpointed_amount: split.checkpointed_amount,
+            withdrawn_amount: split.withdrawn_amount,
+            last_pause_toggle_ledger: 0,
+            last_withdraw_ledger: 0,
+            last_rate_change_ledger: 0,
+            parent_stream_id: None,
+            delegation_depth: 0,
+            ..stream.clone()
+        };
+        save_stream(&env, &new_stream);
+        save_stream_token(&env, new_stream_id, &load_stream_token(&env, stream_id)?)?;
+        if let Some(policy) = get_termination_policy(&env, stream_id) {
+            set_termination_policy(&env, new_stream_id, Some(&policy));
+        }
+        add_stream_to_recipient_index(&env, &new_recipient, new_stream_id, Some(stream.end_time));
+        add_owner_position(&env, &new_recipient, new_stream_id);
+        add_stream_to_sender_index(&env, &stream.sender, new_stream_id, Some(stream.end_time));
+
+        events::emit_stream_created(
+            &env,
+            new_stream_id,
+            StreamCreated {
+                stream_id: new_stream_id,
+                sender: stream.sender.clone(),
+                recipient: new_recipient.clone(),
+                deposit_amount: new_stream.deposit_amount,
+                rate_per_second: new_stream.rate_per_second,
+                start_time: new_stream.start_time,
+                cliff_time: new_stream.cliff_time,
+                end_time: new_stream.end_time,
+                withdraw_dust_threshold: new_stream.withdraw_dust_threshold,
+                memo: new_stream.memo,
+                metadata: new_stream.metadata,
+            },
+        );
+        events::emit_stream_split(
+            &env,
+            stream_id,
+            StreamSplit {
+                stream_id,
+                new_stream_id,
+                new_recipient,
+                deposit_amount: split.deposit_amount,
+                rate_per_second: split.rate_per_second,
+                withdrawn_amount: split.withdrawn_amount,
+            },
+        );
+
+        Ok(new_stream_id)
+    }
+
+    /// Consolidate several streams between the same parties into one.
+    ///
+    /// The first ID in `stream_ids` survives; every other stream is folded
+    /// into it and closed. For each stream the amount accrued so far is
+    /// settled into the survivor's checkpoint, and deposits, rates and
+    /// withdrawn amounts are summed, so the recipient's claimable balance and
+    /// future accrual are exactly the sum of the merged streams and
+    /// liabilities are unchanged. No tokens move.
+    ///
+    /// Streams are compatible when they are `Active`, `Linear`, not pooled,
+    /// already started, and share sender, recipient, position owner, token,
+    /// `end_time`, irrevocability and termination policy (no policy counts as
+    /// `Standard`). Their `cliff_time` must match unless
+    /// every cliff has already passed. Streams with a pending recipient update
+    /// cannot be merged. Per-stream settings of the closed streams (auto-renew,
+    /// auto-claim, lookback window, approvals) are discarded.
+    ///
+    /// Only the common sender may merge. Emits `merged` on the surviving
+    /// stream with the list of closed IDs.
+    ///
+    /// # Errors
+    /// - `InvalidParams` for fewer than 2 or more than [`MAX_MERGE_STREAMS`]
+    ///   IDs, or streams whose parties, token, schedule or termination policy
+    ///   differ.
+    /// - `DuplicateStreamId` if an ID repeats.
+    /// - `StreamNotFound` if any stream does not exist.
+    /// - `Unauthorized` if the streams do not share a sender.
+    /// - `InvalidState` if any stream is not `Active`, has not started, or has a
+    ///   pending recipient update.
+    /// - `UnsupportedStreamKind` for non-`Linear` or pooled streams.
+    pub fn merge_streams(
+        env: Env,
+        stream_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<u64, ContractError> {
+        require_not_globally_paused(&env)?;
+        if stream_ids.len() < 2 || stream_ids.len() > MAX_MERGE_STREAMS {
+            return Err(ContractError::InvalidParams);
+        }
+        reject_duplicate_ids(&env, &stream_ids)?;
+
+        let mut survivor = load_stream(&env, stream_ids.get_unchecked(0))?;
+        survivor.sender.require_auth();
+        let owner = survivor
+            .claim_owner
+            .clone()
+            .unwrap_or(survivor.recipient.clone());
+        let token_address = load_stream_token(&env, survivor.stream_id)?;
+        let policy =
+            get_termination_policy(&env, survivor.stream_id).unwrap_or(TerminationPolicy::Standard);
+        let now = current_accrual_timestamp(&env)?;
+
+        let mut streams = soroban_sdk::Vec::new(&env);
+        for stream_id in stream_ids.iter() {
+            let stream = if stream_id == survivor.stream_id {
+                survivor.clone()
+            } else {
+                load_stream(&env, stream_id)?
+            };
+            if stream.sender != survivor.sender {
+                return Err(ContractError::Unauthorized);
+            }
+            if stream.status != StreamStatus::Active
+                || stream.start_time > now
+                || env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::PendingRecipientUpdate(stream_id))
+            {
+                return Err(ContractError::InvalidState);
+            }
+            if stream.kind != StreamKind::Linear || stream.is_pooled.unwrap_or(false) {
+                return Err(ContractError::UnsupportedStreamKind);
+            }
+            let stream_owner = stream
+                .claim_owner
+                .clone()
+                .unwrap_or(stream.recipient.clone());
+            let same_cliff = stream.cliff_time == survivor.cliff_time
+                || (stream.cliff_time <= now && survivor.cliff_time <= now);
+            if stream.recipient != survivor.recipient
+                || stream_owner != owner
+                || stream.end_time != survivor.end_time
+                || stream.irrevocable.unwrap_or(false) != survivor.irrevocable.unwrap_or(false)
+                || !same_cliff
+                || load_stream_token(&env, stream_id)? != token_address
+                || get_termination_policy(&env, stream_id).unwrap_or(TerminationPolicy::Standard)
+                    != policy
+            {
+                return Err(ContractError::InvalidParams);
+            }
+            streams.push_back(stream);
+        }
+
+        let mut deposit_amount: i128 = 0;
+        let mut rate_per_second: i128 = 0;
+        let mut checkpointed_amount: i128 = 0;
+        let mut withdrawn_amount: i128 = 0;
+        for stream in streams.iter() {
+            // Accrual ignoring the (shared or already passed) cliff gate, so
+            // pre-cliff accrual carries over and the survivor's cliff gates it.
+            let accrued = accrual::calculate_accrued_amount_checkpointed(
+                accrual::CheckpointState {
+                    checkpointed_amount: stream.checkpointed_amount,
+                    checkpointed_at: stream.checkpointed_at,
+                    cliff_time: 0,
+                    end_time: stream.end_time,
+                    deposit_amount: stream.deposit_amount,
+                    kind: stream.kind,
+                },
+                stream.rate_per_second,
+                now,
+            );
+            let add = |total: i128, amount: i128| {
+                total
+                    .checked_add(amount)
+                    .ok_or(ContractError::ArithmeticOverflow)
+            };
+            deposit_amount = add(deposit_amount, stream.deposit_amount)?;
+            rate_per_second = add(rate_per_second, stream.rate_per_second)?;
+            checkpointed_amount = add(checkpointed_amount, accrued)?;
+            withdrawn_amount = add(withdrawn_amount, stream.withdrawn_amount)?;
+        }
+
+        survivor.deposit_amount = deposit_amount;
+        survivor.rate_per_second = rate_per_second;
+        survivor.checkpointed_amount = checkpointed_amount;
+        survivor.checkpointed_at = now;
+        survivor.withdrawn_amount = withdrawn_amount;
+        save_stream(&env, &survivor);
+
+        let mut merged_stream_ids = soroban_sdk::Vec::new(&env);
+        for stream in streams.iter().skip(1) {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::AutoClaimDestination(stream.stream_id));
+            Self::purge_stream(&env, &stream);
+            merged_stream_ids.push_back(stream.stream_id);
+        }
+
+        events::emit_streams_merged(
+            &env,
+            survivor.stream_id,
+            StreamsMerged {
+                stream_id: survivor.stream_id,
+                merged_stream_ids,
+                deposit_amount,
+                rate_per_second,
+                withdrawn_amount,
+            },
+        );
+
+        Ok(survivor.stream_id)
+    }
+
     /// Shorten a stream's `end_time` and refund unstreamed tokens to the sender.
     ///
     /// This operation safely reduces the remaining duration of an **Active** or **Paused**
@@ -5373,6 +8315,17 @@ impl FluxoraStream {
         env: Env,
         stream_id: u64,
         new_end_time: u64,
+    ) -> Result<(), ContractError> {
+        Self::shorten_stream_end_time_internal(env, stream_id, new_end_time, None)
+    }
+
+    /// Body of [`shorten_stream_end_time`](Self::shorten_stream_end_time).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn shorten_stream_end_time_internal(
+        env: Env,
+        stream_id: u64,
+        new_end_time: u64,
+        batch_caller: Option<&Address>,
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
@@ -5382,7 +8335,7 @@ impl FluxoraStream {
         }
 
         // Only the original sender can modify the schedule.
-        Self::require_stream_sender(&stream.sender);
+        Self::require_op_auth(&stream.sender, batch_caller)?;
 
         // Only non-terminal streams may be shortened.
         Self::require_cancellable_status(stream.status)?;
@@ -5412,18 +8365,7 @@ impl FluxoraStream {
         // Already-accrued entitlement must never be reduced by a schedule change.
         // Lock in the accrual at the current timestamp and use it as a floor for the
         // new deposit, mirroring the safety invariant in `decrease_rate_per_second`.
-        let accrued_now = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
+        let accrued_now = stream_accrued_at(&env, &stream, now);
         let new_deposit = new_max_streamable.max(accrued_now);
 
         // Deposit must still be sufficient to cover the shortened schedule (by construction
@@ -5444,11 +8386,19 @@ impl FluxoraStream {
 
         if refund_amount > 0 {
             // Reduce liabilities by the refunded portion (no longer owed to recipient).
-            let liabilities = read_total_liabilities(&env)
+            let token_address = load_stream_token(&env, stream_id)?;
+            let liabilities = read_token_liabilities(&env, &token_address)?
                 .checked_sub(refund_amount)
                 .unwrap_or(0);
-            write_total_liabilities(&env, liabilities);
-            push_token(&env, &stream.sender, refund_amount)?;
+            write_token_liabilities(&env, &token_address, liabilities)?;
+            Self::push_refund(
+                &env,
+                &stream,
+                &token_address,
+                refund_amount,
+                RefundReason::ShortenEndTime,
+                true,
+            )?;
         }
 
         events::emit_stream_end_shortened(
@@ -5495,6 +8445,17 @@ impl FluxoraStream {
         env: Env,
         stream_id: u64,
         new_end_time: u64,
+    ) -> Result<(), ContractError> {
+        Self::extend_stream_end_time_internal(env, stream_id, new_end_time, None)
+    }
+
+    /// Body of [`extend_stream_end_time`](Self::extend_stream_end_time).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn extend_stream_end_time_internal(
+        env: Env,
+        stream_id: u64,
+        new_end_time: u64,
+        batch_caller: Option<&Address>,
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         let mut stream = load_stream(&env, stream_id)?;
@@ -5508,7 +8469,7 @@ impl FluxoraStream {
         }
 
         // Only the original sender can modify the schedule.
-        Self::require_stream_sender(&stream.sender);
+        Self::require_op_auth(&stream.sender, batch_caller)?;
 
         // Only non-terminal streams may be extended.
         Self::require_cancellable_status(stream.status)?;
@@ -5574,6 +8535,7 @@ impl FluxoraStream {
     /// - Increases `deposit_amount` by `amount` (with overflow protection).
     /// - Persists the increased deposit before calling the token contract to pull
     ///   `amount` from `funder`.
+    /// - Pulls the deposit fee on `amount` from `funder` on top of it.
     /// - Does **not** modify `rate_per_second` or any timing fields.
     /// - Leaves `status`, `withdrawn_amount`, and all schedule fields unchanged.
     ///
@@ -5603,6 +8565,18 @@ impl FluxoraStream {
         stream_id: u64,
         funder: Address,
         amount: i128,
+    ) -> Result<(), ContractError> {
+        Self::top_up_stream_internal(env, stream_id, funder, amount, None)
+    }
+
+    /// Body of [`top_up_stream`](Self::top_up_stream).
+    /// `batch_caller` is described on [`require_op_auth`](Self::require_op_auth).
+    fn top_up_stream_internal(
+        env: Env,
+        stream_id: u64,
+        funder: Address,
+        amount: i128,
+        batch_caller: Option<&Address>,
     ) -> Result<(), ContractError> {
         require_not_globally_paused(&env)?;
         // --- Checks ---
@@ -5633,7 +8607,7 @@ impl FluxoraStream {
         }
 
         // Allow any authorized address to top up (third-party funding support).
-        funder.require_auth();
+        Self::require_op_auth(&funder, batch_caller)?;
 
         // --- Effects ---
         // Increase deposit_amount with overflow protection.
@@ -5650,16 +8624,18 @@ impl FluxoraStream {
         save_stream(&env, &stream);
 
         // --- Interactions ---
-        pull_token(&env, &funder, amount)?;
+        let token_address = load_stream_token(&env, stream_id)?;
+        pull_sender_funds(&env, &token_address, &funder, amount)?;
 
         // Increase liabilities to match the additional deposit.
         // Checked arithmetic: a silent wrap here would corrupt the global
         // liability counter and allow the contract to believe it owes far less
         // than it actually does (severe fund-accounting bug).
-        let liabilities = read_total_liabilities(&env)
+        let liabilities = read_token_liabilities(&env, &token_address)?
             .checked_add(amount)
             .ok_or(ContractError::ArithmeticOverflow)?;
-        write_total_liabilities(&env, liabilities);
+        write_token_liabilities(&env, &token_address, liabilities)?;
+        charge_deposit_fee(&env, stream_id, &token_address, &funder, amount)?;
 
         events::emit_stream_topped_up(
             &env,
@@ -5765,7 +8741,7 @@ impl FluxoraStream {
     /// threshold. Auto-renew remains enabled on the new stream.
     ///
     /// The sender must have both sufficient token balance and allowance for the
-    /// contract. These checks return `AutoRenewFundingUnavailable` before any
+    /// contract to cover the deposit plus the deposit fee. These checks return `AutoRenewFundingUnavailable` before any
     /// state or token mutation; a transfer failure also reverts the transaction.
     pub fn renew_stream(env: Env, stream_id: u64) -> Result<u64, ContractError> {
         require_not_globally_paused(&env)?;
@@ -5804,11 +8780,17 @@ impl FluxoraStream {
             stream.kind,
         )?;
 
-        let token_address = get_token(&env)?;
+        // The renewal is denominated in the completed stream's token.
+        let token_address = load_stream_token(&env, stream_id)?;
         let token_client = token::Client::new(&env, &token_address);
         let contract_address = env.current_contract_address();
-        if token_client.balance(&stream.sender) < stream.deposit_amount
-            || token_client.allowance(&stream.sender, &contract_address) < stream.deposit_amount
+        let fee = deposit_fee_for(&env, stream.deposit_amount)?;
+        let total = stream
+            .deposit_amount
+            .checked_add(fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        if token_client.balance(&stream.sender) < total
+            || token_client.allowance(&stream.sender, &contract_address) < total
         {
             return Err(ContractError::AutoRenewFundingUnavailable);
         }
@@ -5816,7 +8798,7 @@ impl FluxoraStream {
         // Disable the consumed opt-in before the external call. Atomic
         // transaction rollback restores it if token transfer or persistence fails.
         set_auto_renew_enabled(&env, stream_id, false);
-        pull_token(&env, &stream.sender, stream.deposit_amount)?;
+        pull_token_of(&env, &token_address, &stream.sender, total)?;
 
         // Inherit irrevocable and witness settings from the source stream.
         // If a stream was designated irrevocable or assigned a compliance witness
@@ -5825,6 +8807,7 @@ impl FluxoraStream {
         // remain in force for the renewed stream period rather than silently lapsing.
         let new_stream_id = Self::persist_new_stream(
             &env,
+            &token_address,
             stream.sender.clone(),
             stream.recipient.clone(),
             stream.deposit_amount,
@@ -5839,6 +8822,7 @@ impl FluxoraStream {
             stream.irrevocable,
             stream.witness.clone(),
         )?;
+        book_deposit_fee(&env, new_stream_id, &token_address, fee)?;
         set_auto_renew_enabled(&env, new_stream_id, true);
 
         env.events().publish(
@@ -5852,6 +8836,405 @@ impl FluxoraStream {
         Ok(new_stream_id)
     }
 
+    /// Turn a stream into a subscription (or stop one, with `period = None`).
+    ///
+    /// While set, any keeper may call [`Self::renew_subscription`] during the
+    /// stream's final `period` seconds to pull `rate_per_second × period` from
+    /// the sender via `transfer_from` and push `end_time` out by `period`. The
+    /// stream keeps its ID, recipient and rate. The sender funds renewals by
+    /// keeping a token allowance for this contract.
+    ///
+    /// Unlike [`Self::set_auto_renew`], renewal happens before the stream
+    /// completes, so the recipient sees one uninterrupted stream.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream sender
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    /// - `ContractError::UnsupportedStreamKind`: Stream is not `Linear`
+    /// - `ContractError::InvalidState`: Stream is terminal or decommissioned
+    /// - `ContractError::InvalidParams`: `period` is zero
+    /// - `ContractError::ArithmeticOverflow`: `rate_per_second × period` overflows
+    ///
+    /// # Events
+    /// - Publishes `sub_set(stream_id)` with `SubscriptionSet`
+    pub fn set_subscription(
+        env: Env,
+        stream_id: u64,
+        period: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::require_stream_sender(&stream.sender);
+
+        if let Some(period) = period {
+            if stream.kind != StreamKind::Linear {
+                return Err(ContractError::UnsupportedStreamKind);
+            }
+            if stream.decommissioned.unwrap_or(false) {
+                return Err(ContractError::InvalidState);
+            }
+            Self::require_cancellable_status(stream.status)?;
+            if period == 0 {
+                return Err(ContractError::InvalidParams);
+            }
+            stream
+                .rate_per_second
+                .checked_mul(period as i128)
+                .ok_or(ContractError::ArithmeticOverflow)?;
+        }
+
+        set_subscription_period(&env, stream_id, period);
+        events::emit_subscription_set(
+            &env,
+            stream_id,
+            SubscriptionSet {
+                stream_id,
+                sender: stream.sender,
+                period,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get a stream's subscription period in seconds, if it is a subscription.
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    pub fn get_subscription(env: Env, stream_id: u64) -> Result<Option<u64>, ContractError> {
+        let _stream = load_stream(&env, stream_id)?;
+        Ok(get_subscription_period(&env, stream_id))
+    }
+
+    /// Fund the next period of a subscription stream in place.
+    ///
+    /// Permissionless keeper entrypoint. Once `now >= end_time - period`, it
+    /// pulls `rate_per_second × period` plus its deposit fee from the sender
+    /// via `transfer_from`, adds the period amount to `deposit_amount` and
+    /// extends `end_time` by `period`.
+    ///
+    /// If the sender's balance or allowance no longer covers a period and its
+    /// fee, the
+    /// subscription is cleared, `sub_stop` is emitted, and the call returns
+    /// `Ok(false)` without moving funds; the stream runs out its current
+    /// schedule. Returns `Ok(true)` after a successful renewal.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `keeper` (any address)
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    /// - `ContractError::InvalidState`: Not a subscription, or the stream is
+    ///   not `Active` or is decommissioned
+    /// - `ContractError::KeeperGracePeriodNotElapsed`: The final period has not begun
+    /// - `ContractError::ArithmeticOverflow`: Deposit or `end_time` overflows
+    ///
+    /// # Events
+    /// - Publishes `sub_renew(stream_id)` with `SubscriptionRenewed`, or
+    ///   `sub_stop(stream_id)` with `SubscriptionStopped`
+    pub fn renew_subscription(
+        env: Env,
+        stream_id: u64,
+        keeper: Address,
+    ) -> Result<bool, ContractError> {
+        require_not_globally_paused(&env)?;
+        keeper.require_auth();
+        let mut stream = load_stream(&env, stream_id)?;
+        let period = get_subscription_period(&env, stream_id).ok_or(ContractError::InvalidState)?;
+
+        if stream.status != StreamStatus::Active || stream.decommissioned.unwrap_or(false) {
+            return Err(ContractError::InvalidState);
+        }
+
+        let now = current_accrual_timestamp(&env)?;
+        if now < stream.end_time.saturating_sub(period) {
+            return Err(ContractError::KeeperGracePeriodNotElapsed);
+        }
+
+        let amount = stream
+            .rate_per_second
+            .checked_mul(period as i128)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        let new_deposit = stream
+            .deposit_amount
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        let new_end_time = stream
+            .end_time
+            .checked_add(period)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+
+        let fee = deposit_fee_for(&env, amount)?;
+        let required = amount
+            .checked_add(fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+
+        let token_address = load_stream_token(&env, stream_id)?;
+        let token_client = token::Client::new(&env, &token_address);
+        let stop_reason = if token_client.balance(&stream.sender) < required {
+            Some(SubscriptionStopReason::InsufficientBalance)
+        } else if token_client.allowance(&stream.sender, &env.current_contract_address()) < required
+        {
+            Some(SubscriptionStopReason::InsufficientAllowance)
+        } else {
+            None
+        };
+        if let Some(reason) = stop_reason {
+            set_subscription_period(&env, stream_id, None);
+            events::emit_subscription_stopped(
+                &env,
+                stream_id,
+                SubscriptionStopped {
+                    stream_id,
+                    required,
+                    reason,
+                },
+            );
+            return Ok(false);
+        }
+
+        // Persist the extended schedule before the external pull (CEI).
+        stream.deposit_amount = new_deposit;
+        stream.end_time = new_end_time;
+        save_stream(&env, &stream);
+
+        pull_token_of(&env, &token_address, &stream.sender, required)?;
+        let liabilities = read_token_liabilities(&env, &token_address)?
+            .checked_add(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        write_token_liabilities(&env, &token_address, liabilities)?;
+        book_deposit_fee(&env, stream_id, &token_address, fee)?;
+
+        events::emit_subscription_renewed(
+            &env,
+            stream_id,
+            SubscriptionRenewed {
+                stream_id,
+                keeper,
+                amount,
+                new_deposit_amount: new_deposit,
+                new_end_time,
+            },
+        );
+        Ok(true)
+    }
+
+    /// Deposit `amount` of `token` from `sender` into its internal vault.
+    ///
+    /// Vault balances stay in the contract and are counted in the token's
+    /// liabilities (see [`get_token_liabilities`](Self::get_token_liabilities)),
+    /// so `sweep_excess` never touches them. With vault funding switched on
+    /// via [`set_vault_funding`](Self::set_vault_funding), stream creation,
+    /// top-ups and deposit fees are debited from the vault and sender refunds
+    /// are credited to it, with no token transfer.
+    ///
+    /// Returns the vault balance after the deposit.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender`
+    ///
+    /// # Errors
+    /// - `ContractError::ContractPaused`: Global emergency pause is active
+    /// - `ContractError::InvalidParams`: `amount` is not positive
+    /// - `ContractError::TokenNotAllowed`: `token` is not an allowed stream token
+    /// - `ContractError::ArithmeticOverflow`: Balance or liabilities overflow
+    ///
+    /// # Events
+    /// - Publishes `vault_dep(sender)` with `VaultBalanceChanged`
+    pub fn deposit_to_vault(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        sender.require_auth();
+        require_not_globally_paused(&env)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidParams);
+        }
+        if !is_token_allowed(&env, &token)? {
+            return Err(ContractError::TokenNotAllowed);
+        }
+
+        pull_token_of(&env, &token, &sender, amount)?;
+        let balance = credit_vault(&env, &sender, &token, amount)?;
+        events::emit_vault_deposited(
+            &env,
+            VaultBalanceChanged {
+                sender,
+                token,
+                amount,
+                balance,
+            },
+        );
+        Ok(balance)
+    }
+
+    /// Withdraw `amount` of `token` from `sender`'s vault back to `sender`.
+    ///
+    /// Works for tokens later removed from the allowlist, so vault funds can
+    /// always be recovered. Returns the vault balance after the withdrawal.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender`
+    ///
+    /// # Errors
+    /// - `ContractError::ContractPaused`: Global emergency pause is active
+    /// - `ContractError::InvalidParams`: `amount` is not positive
+    /// - `ContractError::InsufficientBalance`: Vault balance below `amount`
+    ///
+    /// # Events
+    /// - Publishes `vault_wdr(sender)` with `VaultBalanceChanged`
+    pub fn withdraw_from_vault(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        sender.require_auth();
+        require_not_globally_paused(&env)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidParams);
+        }
+        let balance = read_vault_balance(&env, &sender, &token);
+        if balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        // CEI: debit the vault and release the liability before the transfer.
+        let balance = balance - amount;
+        write_vault_balance(&env, &sender, &token, balance);
+        let liabilities = read_token_liabilities(&env, &token)?
+            .checked_sub(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        write_token_liabilities(&env, &token, liabilities)?;
+
+        push_token_of(&env, &token, &sender, amount)?;
+        events::emit_vault_withdrawn(
+            &env,
+            VaultBalanceChanged {
+                sender,
+                token,
+                amount,
+                balance,
+            },
+        );
+        Ok(balance)
+    }
+
+    /// Get `sender`'s vault balance in `token` (0 when it has none).
+    pub fn get_vault_balance(env: Env, sender: Address, token: Address) -> i128 {
+        read_vault_balance(&env, &sender, &token)
+    }
+
+    /// Switch vault funding on or off for `sender`.
+    ///
+    /// While on, every sender-authorized funding path debits the vault of the
+    /// stream's token instead of calling `transfer_from`: `create_stream`,
+    /// `create_stream_with_token`, piecewise and pooled creation,
+    /// `create_streams`, `create_streams_partial`, `clone_stream`,
+    /// `top_up_stream` (for the funder) and the deposit fee. Refunds to the
+    /// sender from `cancel_stream`, `bulk_cancel_streams`, `keeper_cancel`,
+    /// `decrease_rate_per_second` and `shorten_stream_end_time` are credited
+    /// to the vault. Stream offers and keeper-driven renewals
+    /// (`renew_stream`, `renew_subscription`) keep using the allowance.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `sender`
+    ///
+    /// # Events
+    /// - Publishes `vault_set(sender)` with `VaultFundingSet`
+    pub fn set_vault_funding(env: Env, sender: Address, enabled: bool) {
+        sender.require_auth();
+        set_vault_funding(&env, &sender, enabled);
+        events::emit_vault_funding_set(&env, VaultFundingSet { sender, enabled });
+    }
+
+    /// Whether `sender` funds streams from its vault.
+    pub fn get_vault_funding(env: Env, sender: Address) -> bool {
+        get_vault_funding(&env, &sender)
+    }
+
+    /// Switch claim-to-balance mode on or off for `recipient`.
+    ///
+    /// While on, withdrawals paid to `recipient` are credited to its internal
+    /// balance in the stream's token instead of transferred: `withdraw`,
+    /// `withdraw_to` and `batch_withdraw_to` (when `recipient` is the
+    /// destination), `batch_withdraw`, pooled withdrawals and the recipient
+    /// payout of `delegated_withdraw`. The relayer fee, auto-claim payouts and
+    /// the recipient share paid out on cancellation are still transferred.
+    /// [`withdraw_recipient_balance`](Self::withdraw_recipient_balance) pays the
+    /// balance out in one transfer.
+    ///
+    /// Recipient balances are counted in token liabilities and are not tied to
+    /// any stream, so they survive `close_completed_stream`.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `recipient`
+    ///
+    /// # Events
+    /// - Publishes `bal_mode(recipient)` with `ClaimToBalanceSet`
+    pub fn set_claim_to_balance(env: Env, recipient: Address, enabled: bool) {
+        recipient.require_auth();
+        set_claim_to_balance(&env, &recipient, enabled);
+        events::emit_claim_to_balance_set(&env, ClaimToBalanceSet { recipient, enabled });
+    }
+
+    /// Whether withdrawals paid to `recipient` are credited to its balance.
+    pub fn get_claim_to_balance(env: Env, recipient: Address) -> bool {
+        get_claim_to_balance(&env, &recipient)
+    }
+
+    /// Get `recipient`'s internal balance in `token` (0 when it has none).
+    pub fn get_recipient_balance(env: Env, recipient: Address, token: Address) -> i128 {
+        read_recipient_balance(&env, &recipient, &token)
+    }
+
+    /// Transfer `recipient`'s whole internal balance in `token` to it.
+    ///
+    /// Returns the amount transferred; `0` (no transfer, no event) when the
+    /// balance is empty. Works whether or not claim-to-balance mode is on.
+    ///
+    /// # Authorization
+    /// - Requires authorization from `recipient`
+    ///
+    /// # Errors
+    /// - `ContractError::ContractPaused`: Global emergency pause is active
+    ///
+    /// # Events
+    /// - Publishes `bal_wdr(recipient)` with `RecipientBalanceChanged`
+    pub fn withdraw_recipient_balance(
+        env: Env,
+        recipient: Address,
+        token: Address,
+    ) -> Result<i128, ContractError> {
+        recipient.require_auth();
+        require_not_globally_paused(&env)?;
+        let amount = read_recipient_balance(&env, &recipient, &token);
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        // CEI: clear the balance and release the liability before the transfer.
+        write_recipient_balance(&env, &recipient, &token, 0);
+        let liabilities = read_token_liabilities(&env, &token)?
+            .checked_sub(amount)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+        write_token_liabilities(&env, &token, liabilities)?;
+
+        push_token_of(&env, &token, &recipient, amount)?;
+        events::emit_recipient_balance_withdrawn(
+            &env,
+            RecipientBalanceChanged {
+                recipient,
+                token,
+                amount,
+                balance: 0,
+            },
+        );
+        Ok(amount)
+    }
+
     /// Close (archive) a completed stream to reduce long-term storage.
     ///
     /// Permanently removes the stream's persistent storage entry. Only streams in
@@ -5893,18 +9276,8 @@ impl FluxoraStream {
         // Closing before full settlement would destroy recipient funds.
         if stream.status == StreamStatus::Cancelled {
             let cancelled_at = stream.cancelled_at.ok_or(ContractError::InvalidState)?;
-            let accrued = accrual::calculate_accrued_amount_checkpointed(
-                accrual::CheckpointState {
-                    checkpointed_amount: stream.checkpointed_amount,
-                    checkpointed_at: stream.checkpointed_at,
-                    cliff_time: stream.cliff_time,
-                    end_time: stream.end_time,
-                    deposit_amount: stream.deposit_amount,
-                    kind: stream.kind,
-                },
-                stream.rate_per_second,
-                cancelled_at,
-            );
+            let vesting_end = cancelled_vesting_end(&env, &stream, cancelled_at);
+            let accrued = stream_accrued_at(&env, &stream, vesting_end);
             let claimable = accrued.saturating_sub(stream.withdrawn_amount).max(0);
             if claimable > 0 {
                 return Err(ContractError::InvalidState);
@@ -5913,17 +9286,7 @@ impl FluxoraStream {
 
         events::emit_stream_closed(&env, stream_id);
 
-        // Remove stream from recipient's index before deleting the stream
-        remove_stream_from_recipient_index(&env, &stream.recipient, stream_id);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::AutoRenewEnabled(stream_id));
-        env.storage()
-            .persistent()
-            .remove(&DataKey::MaxLookbackLedgers(stream_id));
-        // Remove stream from sender's portfolio index.
-        remove_stream_from_sender_index(&env, &stream.sender, stream_id);
-        remove_stream(&env, stream_id);
+        Self::purge_stream(&env, &stream);
 
         Ok(())
     }
@@ -5959,18 +9322,8 @@ impl FluxoraStream {
 
         // Ensure recipient has fully withdrawn the frozen accrued amount at cancel time.
         let cancelled_at = stream.cancelled_at.ok_or(ContractError::InvalidState)?;
-        let accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            cancelled_at,
-        );
+        let vesting_end = cancelled_vesting_end(&env, &stream, cancelled_at);
+        let accrued = stream_accrued_at(&env, &stream, vesting_end);
         let claimable = accrued.saturating_sub(stream.withdrawn_amount).max(0);
         if claimable > 0 {
             return Err(ContractError::InvalidState);
@@ -5979,18 +9332,36 @@ impl FluxoraStream {
         events::emit_stream_closed(&env, stream_id);
 
         // Remove from recipient index and delete stream storage.
-        remove_stream_from_recipient_index(&env, &stream.recipient, stream_id);
+        Self::purge_stream(&env, &stream);
+
+        Ok(())
+    }
+
+    /// Delete a stream and its per-stream entries, and drop it from the
+    /// recipient, sender and owner indexes. Shared by the close entrypoints and
+    /// `merge_streams`.
+    fn purge_stream(env: &Env, stream: &Stream) {
+        let stream_id = stream.stream_id;
+        remove_stream_from_recipient_index(env, &stream.recipient, stream_id);
         env.storage()
             .persistent()
             .remove(&DataKey::AutoRenewEnabled(stream_id));
         env.storage()
             .persistent()
             .remove(&DataKey::MaxLookbackLedgers(stream_id));
+        remove_rate_schedule(env, stream_id);
+        remove_tranche_schedule(env, stream_id);
+        set_decay_half_life(env, stream_id, None);
+        remove_stream_token(env, stream_id);
+        remove_position(env, stream);
+        set_recipient_hook(env, stream.stream_id, &None);
+        set_refund_hook(env, stream.stream_id, &None);
+        set_subscription_period(env, stream_id, None);
+        set_termination_policy(env, stream_id, None);
+        set_termination_vested(env, stream_id, None);
         // Remove stream from sender's portfolio index.
-        remove_stream_from_sender_index(&env, &stream.sender, stream_id);
-        remove_stream(&env, stream_id);
-
-        Ok(())
+        remove_stream_from_sender_index(env, &stream.sender, stream_id);
+        remove_stream(env, stream_id);
     }
 
     /// Register a reusable relative schedule (start/cliff/duration offsets only).
@@ -6185,47 +9556,7 @@ impl FluxoraStream {
         limit: u32,
     ) -> Page {
         let streams = load_recipient_streams(&env, &recipient);
-        let total = streams.len();
-
-        // Apply limit cap
-        let effective_limit = limit.min(RECIPIENT_STREAMS_PAGE_LIMIT);
-
-        // Find starting position.
-        //
-        // `next_cursor` (below) is produced as the ID of the first
-        // *not-yet-returned* item — i.e. "resume starting AT this ID",
-        // inclusive. The lookup here must match that producer semantics:
-        // when the cursor ID is found, start AT its position, not after it.
-        // (Starting after it — `pos + 1` — silently dropped exactly one
-        // stream per page boundary crossed; caught by
-        // `test_get_recipient_streams_paginated_basic` and
-        // `test_paginated_covers_all_streams`.)
-        let start_idx = if cursor == 0 {
-            0
-        } else {
-            match streams.binary_search(cursor) {
-                Ok(pos) => pos,  // Start at the cursor (inclusive)
-                Err(pos) => pos, // Insert position if not found
-            }
-        };
-
-        // Calculate end position
-        let end_idx = (start_idx as u32 + effective_limit).min(total as u32);
-
-        let mut next_cursor = 0u64;
-        if (end_idx as usize) < total as usize {
-            next_cursor = streams.get(end_idx).unwrap();
-        }
-
-        let mut page_streams = soroban_sdk::Vec::new(&env);
-        for i in start_idx..end_idx {
-            page_streams.push_back(streams.get(i).unwrap());
-        }
-
-        Page {
-            stream_ids: page_streams,
-            next_cursor,
-        }
+        Self::page_stream_index(&env, &streams, cursor, limit, None)
     }
 
     /// Count the total number of streams for a recipient.
@@ -6254,6 +9585,61 @@ impl FluxoraStream {
         load_recipient_streams(&env, &recipient).len() as u64
     }
 
+    /// Page through a recipient's streams, keeping only those in `status`.
+    ///
+    /// Uses the same cursor and limit rules as
+    /// [`get_recipient_streams_paginated`](Self::get_recipient_streams_paginated).
+    /// `limit` bounds the number of index entries examined, not the number of
+    /// matches, so a page may hold fewer than `limit` IDs (or none) while
+    /// `next_cursor` is still non-zero. Keep paging until `next_cursor == 0`.
+    pub fn get_recipient_streams_by_status(
+        env: Env,
+        recipient: Address,
+        status: StreamStatus,
+        cursor: u64,
+        limit: u32,
+    ) -> Page {
+        let streams = load_recipient_streams(&env, &recipient);
+        Self::page_stream_index(&env, &streams, cursor, limit, Some(status))
+    }
+
+    /// Paginated list of the streams created by `sender`.
+    ///
+    /// Mirrors [`get_recipient_streams_paginated`](Self::get_recipient_streams_paginated)
+    /// over the `SenderStreams` index: IDs ascending, `cursor` inclusive (`0` to
+    /// start), `limit` capped at `RECIPIENT_STREAMS_PAGE_LIMIT`, and
+    /// `next_cursor == 0` on the last page. Closed streams are not listed.
+    /// No authorization required.
+    pub fn get_sender_streams_paginated(
+        env: Env,
+        sender: Address,
+        cursor: u64,
+        limit: u32,
+    ) -> Page {
+        let streams = load_sender_streams(&env, &sender);
+        Self::page_stream_index(&env, &streams, cursor, limit, None)
+    }
+
+    /// Page through a sender's streams, keeping only those in `status`.
+    ///
+    /// Same paging rules as
+    /// [`get_recipient_streams_by_status`](Self::get_recipient_streams_by_status).
+    pub fn get_sender_streams_by_status(
+        env: Env,
+        sender: Address,
+        status: StreamStatus,
+        cursor: u64,
+        limit: u32,
+    ) -> Page {
+        let streams = load_sender_streams(&env, &sender);
+        Self::page_stream_index(&env, &streams, cursor, limit, Some(status))
+    }
+
+    /// Count the streams in a sender's index (closed streams are not included).
+    pub fn get_sender_stream_count(env: Env, sender: Address) -> u64 {
+        load_sender_streams(&env, &sender).len() as u64
+    }
+
     /// Paginated aggregate health view for a sender's entire stream portfolio.
     ///
     /// Iterates through `sender`'s streams in the `SenderStreams` index (sorted
@@ -6473,6 +9859,26 @@ impl FluxoraStream {
         result
     }
 
+    /// Authorize one sender-side mutation.
+    ///
+    /// Outside a batch (`batch_caller == None`) this is `addr.require_auth()`.
+    /// Inside [`execute_batch`](Self::execute_batch) the caller has already
+    /// authorized the whole batch once, so the op only has to belong to it:
+    /// any other `addr` is rejected with `Unauthorized`.
+    fn require_op_auth(
+        addr: &Address,
+        batch_caller: Option<&Address>,
+    ) -> Result<(), ContractError> {
+        match batch_caller {
+            None => {
+                addr.require_auth();
+                Ok(())
+            }
+            Some(caller) if caller == addr => Ok(()),
+            Some(_) => Err(ContractError::Unauthorized),
+        }
+    }
+
     /// Internal helper to require authorization from the stream sender.
     ///
     /// Admin override paths are handled by dedicated `*_as_admin` entrypoints.
@@ -6480,6 +9886,69 @@ impl FluxoraStream {
         sender.require_auth();
     }
 
+    /// Slice one page out of a sorted sender or recipient stream index.
+    ///
+    /// Examines at most `limit` (capped at `RECIPIENT_STREAMS_PAGE_LIMIT`)
+    /// index entries starting at `cursor`. With a `status` filter, entries
+    /// whose stream is in another status, or no longer loads, are skipped
+    /// but still count toward the limit, so the work per call stays bounded.
+    fn page_stream_index(
+        env: &Env,
+        streams: &soroban_sdk::Vec<u64>,
+        cursor: u64,
+        limit: u32,
+        status: Option<StreamStatus>,
+    ) -> Page {
+        let total = streams.len();
+
+        // Apply limit cap
+        let effective_limit = limit.min(RECIPIENT_STREAMS_PAGE_LIMIT);
+
+        // Find starting position.
+        //
+        // `next_cursor` (below) is produced as the ID of the first
+        // *not-yet-returned* item — i.e. "resume starting AT this ID",
+        // inclusive. The lookup here must match that producer semantics:
+        // when the cursor ID is found, start AT its position, not after it.
+        // (Starting after it — `pos + 1` — silently dropped exactly one
+        // stream per page boundary crossed; caught by
+        // `test_get_recipient_streams_paginated_basic` and
+        // `test_paginated_covers_all_streams`.)
+        let start_idx = if cursor == 0 {
+            0
+        } else {
+            match streams.binary_search(cursor) {
+                Ok(pos) => pos,  // Start at the cursor (inclusive)
+                Err(pos) => pos, // Insert position if not found
+            }
+        };
+
+        // Calculate end position
+        let end_idx = start_idx.saturating_add(effective_limit).min(total);
+
+        let mut next_cursor = 0u64;
+        if end_idx < total {
+            next_cursor = streams.get(end_idx).unwrap();
+        }
+
+        let mut page_streams = soroban_sdk::Vec::new(env);
+        for i in start_idx..end_idx {
+            let stream_id = streams.get(i).unwrap();
+            if let Some(status) = status {
+                match load_stream(env, stream_id) {
+                    Ok(stream) if stream.status == status => {}
+                    _ => continue,
+                }
+            }
+            page_streams.push_back(stream_id);
+        }
+
+        Page {
+            stream_ids: page_streams,
+            next_cursor,
+        }
+    }
+
     fn require_cancellable_status(status: StreamStatus) -> Result<(), ContractError> {
         if status != StreamStatus::Active && status != StreamStatus::Paused {
             return Err(ContractError::InvalidState);
@@ -6493,26 +9962,25 @@ impl FluxoraStream {
     /// - same state transition (`status = Cancelled`, `cancelled_at = now`)
     /// - same refund rule (`refund = deposit_amount - accrued_at_now`)
     /// - same event shape (`StreamCancelled(stream_id)`)
-    fn cancel_stream_internal(env: &Env, stream: &mut Stream) -> Result<(), ContractError> {
+    ///
+    /// `sender_initiated` is `false` for admin and witness cancellations: the
+    /// sender's refund hook is still notified, but its `Revert` policy is
+    /// treated as `Ignore` so the sender cannot block a cancellation it did
+    /// not start.
+    fn cancel_stream_internal(
+        env: &Env,
+        stream: &mut Stream,
+        sender_initiated: bool,
+    ) -> Result<(), ContractError> {
         if stream.irrevocable.unwrap_or(false) {
             return Err(ContractError::Unauthorized);
         }
         Self::require_cancellable_status(stream.status)?;
 
         let now = current_accrual_timestamp(env)?;
-        // Use checkpoint-aware accrual so rate-decreased streams are cancelled correctly.
-        let accrued_at_cancel = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
+        // Checkpoint-aware accrual (so rate-decreased streams are cancelled
+        // correctly), adjusted by the stream's termination policy.
+        let accrued_at_cancel = settle_termination(env, stream, now)?;
 
         let refund_amount = stream
             .deposit_amount
@@ -6529,21 +9997,44 @@ impl FluxoraStream {
         // Reduce liabilities by the refunded (unstreamed) portion.
         // The accrued portion remains a liability until the recipient withdraws.
         if refund_amount > 0 {
-            let liabilities = read_total_liabilities(env)
+            let token_address = load_stream_token(env, stream.stream_id)?;
+            let liabilities = read_token_liabilities(env, &token_address)?
                 .checked_sub(refund_amount)
                 .unwrap_or(0);
-            write_total_liabilities(env, liabilities);
-
-            // Reentrancy guard around the external token transfer, mirroring
-            // `withdraw`/`delegated_withdraw`. Terminal state is already persisted
-            // above (CEI), so a malicious token re-entering any cancel or
-            // withdraw path during this transfer hits the held lock and reverts.
-            // Capture the result, always release, then propagate so the lock is
-            // never left stuck on a failed transfer.
+            write_token_liabilities(env, &token_address, liabilities)?;
+
+            // Reentrancy guard around the external token transfer and refund
+            // hook, mirroring `withdraw`/`delegated_withdraw`. Terminal state is
+            // already persisted above (CEI), so a malicious token or hook
+            // re-entering any cancel or withdraw path hits the held lock and
+            // reverts. `push_refund` always releases the lock before propagating.
+            Self::push_refund(
+                env,
+                stream,
+                &token_address,
+                refund_amount,
+                RefundReason::Cancel,
+                sender_initiated,
+            )?;
+        }
+
+        // Tell the recipient hook what is still claimable. Failures are ignored
+        // so a recipient cannot block the sender's cancellation.
+        if let Some(hook) = get_recipient_hook(env, stream.stream_id) {
+            let token_address = load_stream_token(env, stream.stream_id)?;
+            let claimable = stream_accrued_at(env, stream, now)
+                .saturating_sub(stream.withdrawn_amount)
+                .max(0);
             acquire_reentrancy_lock(env)?;
-            let transfer_result = push_token(env, &stream.sender, refund_amount);
+            hooks::call_recipient_hook(
+                env,
+                &hook,
+                stream.stream_id,
+                &token_address,
+                claimable,
+                PayoutReason::Cancel,
+            );
             release_reentrancy_lock(env);
-            transfer_result?;
         }
 
         events::emit_stream_cancelled(env, stream.stream_id);
@@ -6656,12 +10147,16 @@ impl FluxoraStream {
     /// - Mirrors `cancel_stream`: accrued value is never refunded to the sender.
     /// - Accrued funds stay in the contract until the recipient calls `withdraw()`.
     /// - No auto-transfer of accrued funds to the recipient occurs on admin cancel.
+    ///
+    /// # Refund hook
+    /// - The sender's refund hook is still notified, but a failing hook is
+    ///   ignored even under `HookFailurePolicy::Revert`.
     pub fn cancel_stream_as_admin(env: Env, stream_id: u64) -> Result<(), ContractError> {
         get_admin(&env)?.require_auth();
 
         let mut stream = load_stream(&env, stream_id)?;
 
-        Self::cancel_stream_internal(&env, &mut stream)
+        Self::cancel_stream_internal(&env, &mut stream, false)
     }
 
     /// Cancel a payment stream using an off-chain compliance witness attestation.
@@ -6718,7 +10213,7 @@ impl FluxoraStream {
         env.crypto()
             .ed25519_verify(&witness_public_key, &msg, &witness_signature);
 
-        Self::cancel_stream_internal(&env, &mut stream)
+        Self::cancel_stream_internal(&env, &mut stream, false)
     }
 
     /// Permissionless keeper entrypoint to cancel a stream that has expired and been
@@ -6749,7 +10244,9 @@ impl FluxoraStream {
     ///
     /// # Token distribution (CEI order: persist then transfer)
     ///
-    /// 1. `recipient_amount = accrued - withdrawn_amount` → transferred to stream recipient.
+    /// 1. `recipient_amount = accrued - withdrawn_amount` → paid to the position owner
+    ///    ([`owner_of`](Self::owner_of)), net of the withdrawal fee; credited instead of
+    ///    transferred when the owner is in claim-to-balance mode.
     /// 2. `sender_refund_gross = deposit_amount - accrued` (unstreamed portion).
     /// 3. `keeper_fee = sender_refund_gross × KEEPER_FEE_BPS / 10_000` → transferred to keeper.
     /// 4. `sender_refund = sender_refund_gross - keeper_fee` → transferred to stream sender.
@@ -6765,6 +10262,8 @@ impl FluxoraStream {
     /// - CEI pattern: stream is marked Cancelled before any token transfer.
     /// - Keeper fee is deducted from the sender's unstreamed refund, never from the recipient.
     /// - Reentrancy is mitigated by the terminal-state write preceding all transfers.
+    /// - The sender's refund hook is notified of a non-zero `sender_refund`, but
+    ///   its failures are ignored regardless of `HookFailurePolicy`.
     pub fn keeper_cancel(env: Env, stream_id: u64, keeper: Address) -> Result<(), ContractError> {
         keeper.require_auth();
 
@@ -6788,20 +10287,10 @@ impl FluxoraStream {
             return Err(ContractError::KeeperGracePeriodNotElapsed);
         }
 
-        // Compute accrued amount at the moment of keeper cancellation.
-        // Since now >= end_time, this is capped at deposit_amount.
-        let accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
+        // Compute the vested amount at the moment of keeper cancellation.
+        // Since now >= end_time, accrual is capped at deposit_amount; only a
+        // `BadLeaver` termination policy can lower it.
+        let accrued = settle_termination(&env, &stream, now)?;
 
         // Recipient's outstanding claimable balance (accrued minus prior withdrawals).
         let recipient_amount = accrued.saturating_sub(stream.withdrawn_amount).max(0);
@@ -6837,27 +10326,55 @@ impl FluxoraStream {
         let total_outstanding = recipient_amount
             .checked_add(sender_refund_gross)
             .ok_or(ContractError::ArithmeticOverflow)?;
+        let token_address = load_stream_token(&env, stream.stream_id)?;
         if total_outstanding > 0 {
-            let liabilities = read_total_liabilities(&env)
+            let liabilities = read_token_liabilities(&env, &token_address)?
                 .checked_sub(total_outstanding)
                 .unwrap_or(0);
-            write_total_liabilities(&env, liabilities);
+            write_token_liabilities(&env, &token_address, liabilities)?;
         }
+        let withdraw_fee =
+            charge_withdraw_fee(&env, stream.stream_id, &token_address, recipient_amount)?;
 
-        // Transfer accrued portion directly to the recipient.
-        if recipient_amount > 0 {
-            push_token(&env, &stream.recipient, recipient_amount)?;
+        // Pay the accrued portion, net of the withdrawal fee, to the position owner.
+        if recipient_amount > withdraw_fee {
+            let owner = stream
+                .claim_owner
+                .clone()
+                .unwrap_or(stream.recipient.clone());
+            push_payout(
+                &env,
+                &token_address,
+                &owner,
+                recipient_amount - withdraw_fee,
+            )?;
         }
 
-        // Transfer sender refund (net of keeper fee).
+        // Transfer sender refund (net of keeper fee), then notify the refund
+        // hook. The keeper, not the sender, started this call, so hook
+        // failures are ignored.
         if sender_refund > 0 {
-            push_token(&env, &stream.sender, sender_refund)?;
+            acquire_reentrancy_lock(&env)?;
+            let transfer_result =
+                push_sender_funds(&env, &token_address, &stream.sender, sender_refund).and_then(
+                    |()| {
+                        Self::notify_refund_hook(
+                            &env,
+                            stream.stream_id,
+                            sender_refund,
+                            RefundReason::Cancel,
+                            false,
+                        )
+                    },
+                );
+            release_reentrancy_lock(&env);
+            transfer_result?;
         }
 
         // Transfer keeper incentive.
         // Counter is incremented AFTER the transfer succeeds (CEI ordering).
         if keeper_fee > 0 {
-            push_token(&env, &keeper, keeper_fee)?;
+            push_token_of(&env, &token_address, &keeper, keeper_fee)?;
             increment_total_keeper_fees_paid(&env, keeper_fee)?;
         }
 
@@ -6920,18 +10437,7 @@ impl FluxoraStream {
             return Ok((0, 0));
         }
 
-        let accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
+        let (_, accrued) = termination_split(&env, &stream, now)?;
 
         let sender_refund_gross = stream
             .deposit_amount
@@ -7497,8 +11003,9 @@ impl FluxoraStream {
     /// ```
     ///
     /// Where `total_liabilities` is the sum of all active stream deposits that haven't
-    /// been withdrawn or refunded yet. This intrinsic liability calculation ensures
-    /// that sweep_excess NEVER touches recipient-owed balances or accrued protocol fees.
+    /// been withdrawn or refunded yet, plus sender vault and recipient balances. This
+    /// intrinsic liability calculation ensures that sweep_excess NEVER touches
+    /// recipient-owed balances, sender vaults or accrued protocol fees.
     ///
     /// # Usage Notes
     /// - Safe to call even when no excess exists (returns 0, no transfer)
@@ -7514,6 +11021,22 @@ impl FluxoraStream {
     ///    fails, tokens remain in contract
     /// 3. Rounding errors accumulate over many streams → small excess builds up
     pub fn sweep_excess(env: Env, recipient: Address) -> Result<i128, ContractError> {
+        let token_address = get_token(&env)?;
+        Self::sweep_excess_for_token(env, token_address, recipient)
+    }
+
+    /// Sweep excess balance of a specific `token` to `recipient` (admin only).
+    ///
+    /// Identical to [`sweep_excess`](Self::sweep_excess) but for any token the
+    /// contract holds, including tokens that were never allowlisted (e.g. sent
+    /// to the contract by mistake). Excess is computed against that token's own
+    /// liabilities (see [`get_token_liabilities`](Self::get_token_liabilities)),
+    /// so balances owed to streams denominated in `token` are never touched.
+    pub fn sweep_excess_for_token(
+        env: Env,
+        token: Address,
+        recipient: Address,
+    ) -> Result<i128, ContractError> {
         // Only admin can sweep excess tokens
         let admin = get_admin(&env)?;
         admin.require_auth();
@@ -7524,50 +11047,188 @@ impl FluxoraStream {
         // wallets that cannot sign Soroban transactions.
 
         // Get contract's token balance
-        let token_address = get_token(&env)?;
+        let token_address = token;
         let token_client = token::Client::new(&env, &token_address);
         let contract_balance = token_client.balance(&env.current_contract_address());
 
-        // Get total outstanding liabilities (sum of all active stream deposits)
-        let total_liabilities = read_total_liabilities(&env);
+        // Get outstanding liabilities in this token (sum of its active stream deposits)
+        // plus protocol fees collected in it and not yet claimed by the treasury.
+        let total_liabilities = read_token_liabilities(&env, &token_address)?
+            .saturating_add(read_protocol_fee_liabilities(&env, &token_address));
 
         // Calculate excess: balance - liabilities
         // If liabilities exceed balance, there's no excess (should not happen in normal operation)
         let excess = contract_balance.saturating_sub(total_liabilities);
 
-        // If no excess, return early (no transfer needed)
-        if excess <= 0 {
+        // If no excess, return early (no transfer needed)
+        if excess <= 0 {
+            return Ok(0);
+        }
+
+        // CEI pattern: Emit event before transfer
+        events::emit_excess_swept(
+            &env,
+            recipient.clone(),
+            ExcessSwept {
+                to: recipient.clone(),
+                amount: excess,
+            },
+        );
+
+        // Acquire reentrancy lock before token transfer
+        acquire_reentrancy_lock(&env)?;
+
+        // Transfer excess tokens to recipient
+        let transfer_result = push_token_of(&env, &token_address, &recipient, excess);
+
+        // Release reentrancy lock
+        release_reentrancy_lock(&env);
+
+        // Propagate any transfer errors
+        transfer_result?;
+
+        Ok(excess)
+    }
+
+    /// Set the protocol fee rates (admin or governance).
+    ///
+    /// - `deposit_fee_bps`: charged on top of every deposit: each stream
+    ///   creation path (single, batch, scheduled, pooled, cloned, renewed and
+    ///   offered), `renew_subscription` and `top_up_stream`. The payer
+    ///   transfers `deposit + fee`; the stream's `deposit_amount` is unchanged.
+    /// - `withdraw_fee_bps`: deducted from every recipient payout: `withdraw`,
+    ///   `withdraw_to`, batch, relayed, auto-claim and pooled withdrawals, and
+    ///   the payouts of `keeper_cancel` and `bulk_cancel_streams`. The stream
+    ///   is debited the full amount and the recipient receives the amount
+    ///   minus the fee.
+    ///
+    /// Fees accumulate in the contract as protocol fee liabilities per token
+    /// (see [`get_protocol_fee_liabilities`](Self::get_protocol_fee_liabilities)),
+    /// are excluded from `sweep_excess`, and are paid out with
+    /// [`claim_protocol_fees`](Self::claim_protocol_fees). Rate changes apply to
+    /// subsequent deposits and withdrawals only.
+    ///
+    /// # Authorization
+    /// - Requires admin authorization.
+    ///
+    /// # Events
+    /// - Publishes `fee_cfg` with `ProtocolFeeUpdated`.
+    ///
+    /// # Errors
+    /// - `InvalidParams` (3) if either rate exceeds `MAX_PROTOCOL_FEE_BPS`.
+    pub fn set_protocol_fee(
+        env: Env,
+        deposit_fee_bps: u32,
+        withdraw_fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        get_admin(&env)?.require_auth();
+
+        if deposit_fee_bps > MAX_PROTOCOL_FEE_BPS || withdraw_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(ContractError::InvalidParams);
+        }
+
+        set_protocol_fee(
+            &env,
+            &ProtocolFeeConfig {
+                deposit_fee_bps,
+                withdraw_fee_bps,
+            },
+        );
+
+        events::emit_protocol_fee_updated(
+            &env,
+            ProtocolFeeUpdated {
+                deposit_fee_bps,
+                withdraw_fee_bps,
+            },
+        );
+        Ok(())
+    }
+
+    /// Current protocol fee rates (both 0 until `set_protocol_fee` is called).
+    pub fn get_protocol_fee(env: Env) -> ProtocolFeeConfig {
+        get_protocol_fee(&env)
+    }
+
+    /// Set the address that receives claimed protocol fees (admin or governance).
+    ///
+    /// # Authorization
+    /// - Requires admin authorization.
+    ///
+    /// # Events
+    /// - Publishes `treasury` with `TreasuryUpdated`.
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), ContractError> {
+        get_admin(&env)?.require_auth();
+
+        let old_treasury = get_treasury(&env);
+        set_treasury(&env, &treasury);
+
+        events::emit_treasury_updated(
+            &env,
+            TreasuryUpdated {
+                old_treasury,
+                new_treasury: treasury,
+            },
+        );
+        Ok(())
+    }
+
+    /// Configured protocol fee treasury, if any.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        get_treasury(&env)
+    }
+
+    /// Protocol fees collected in `token` and not yet claimed by the treasury.
+    ///
+    /// Unlike `get_protocol_fees_accrued` (the lifetime keeper-fee counter),
+    /// this is a live balance that drops to 0 after `claim_protocol_fees`.
+    pub fn get_protocol_fee_liabilities(env: Env, token: Address) -> i128 {
+        read_protocol_fee_liabilities(&env, &token)
+    }
+
+    /// Pay all protocol fees collected in `token` to the treasury.
+    ///
+    /// Permissionless: the destination is always the configured treasury, so
+    /// anyone (e.g. a keeper) may trigger the payout. Returns the amount paid,
+    /// or 0 when nothing is owed.
+    ///
+    /// # Events
+    /// - Publishes `fee_claim(token)` with `ProtocolFeesClaimed` when > 0.
+    ///
+    /// # Errors
+    /// - `TreasuryNotSet` (48) if no treasury has been configured.
+    pub fn claim_protocol_fees(env: Env, token: Address) -> Result<i128, ContractError> {
+        let treasury = get_treasury(&env).ok_or(ContractError::TreasuryNotSet)?;
+
+        let amount = read_protocol_fee_liabilities(&env, &token);
+        if amount <= 0 {
             return Ok(0);
         }
 
-        // CEI pattern: Emit event before transfer
-        events::emit_excess_swept(
-            &env,
-            recipient.clone(),
-            ExcessSwept {
-                to: recipient.clone(),
-                amount: excess,
-            },
-        );
+        // CEI: clear the fee liability before the external transfer.
+        write_protocol_fee_liabilities(&env, &token, 0);
 
-        // Acquire reentrancy lock before token transfer
         acquire_reentrancy_lock(&env)?;
-
-        // Transfer excess tokens to recipient
-        let transfer_result = push_token(&env, &recipient, excess);
-
-        // Release reentrancy lock
+        let transfer_result = push_token_of(&env, &token, &treasury, amount);
         release_reentrancy_lock(&env);
-
-        // Propagate any transfer errors
         transfer_result?;
 
-        Ok(excess)
+        events::emit_protocol_fees_claimed(
+            &env,
+            ProtocolFeesClaimed {
+                token,
+                treasury,
+                amount,
+            },
+        );
+        Ok(amount)
     }
 
     /// Set an auto-claim destination for a stream.
     ///
-    /// Allows the recipient to opt in to permissionless final withdrawal at `end_time`.
+    /// Allows the position owner ([`owner_of`](Self::owner_of)) to opt in to
+    /// permissionless final withdrawal at `end_time`. The destination is
+    /// cleared when the position is transferred.
     /// Once set, anyone can call `trigger_auto_claim` to send the final withdrawal to
     /// the specified destination address.
     ///
@@ -7576,21 +11237,22 @@ impl FluxoraStream {
     /// - `destination`: Address where tokens will be sent when auto-claim is triggered
     ///
     /// # Authorization
-    /// - Requires authorization from the stream recipient
+    /// - Requires authorization from the position owner
     ///
     /// # Returns
     /// - `Ok(())` on success
     ///
     /// # Errors
     /// - `ContractError::StreamNotFound`: Stream does not exist
-    /// - `ContractError::Unauthorized`: Caller is not the recipient
+    /// - `ContractError::Unauthorized`: Caller is not the position owner
     /// - `ContractError::InvalidParams`: Destination is zero address or contract itself
+    /// - `ContractError::InvalidState`: Stream is pooled (use `set_pool_auto_claim`)
     ///
     /// # Events
     /// - Publishes `AutoClaimSet { stream_id, destination }` event
     ///
     /// # Security
-    /// - Only recipient can set/change destination
+    /// - Only the position owner can set/change destination
     /// - Destination is validated (non-zero, not contract)
     /// - Can be called multiple times to change destination
     /// - Use `revoke_auto_claim` to remove the destination
@@ -7606,7 +11268,14 @@ impl FluxoraStream {
         destination: Address,
     ) -> Result<(), ContractError> {
         let stream = load_stream(&env, stream_id)?;
-        stream.recipient.require_auth();
+        // Pool members opt in with `set_pool_auto_claim`.
+        if stream.is_pooled == Some(true) {
+            return Err(ContractError::InvalidState);
+        }
+        stream
+            .claim_owner
+            .unwrap_or(stream.recipient)
+            .require_auth();
 
         // Validate destination
         if !Self::is_valid_destination(&env, &destination) {
@@ -7638,20 +11307,20 @@ impl FluxoraStream {
     /// Revoke the auto-claim destination for a stream.
     ///
     /// Removes the auto-claim destination, preventing `trigger_auto_claim` from being called.
-    /// The recipient can set a new destination later via `set_auto_claim`.
+    /// The position owner can set a new destination later via `set_auto_claim`.
     ///
     /// # Parameters
     /// - `stream_id`: The stream to configure
     ///
     /// # Authorization
-    /// - Requires authorization from the stream recipient
+    /// - Requires authorization from the position owner
     ///
     /// # Returns
     /// - `Ok(())` on success
     ///
     /// # Errors
     /// - `ContractError::StreamNotFound`: Stream does not exist
-    /// - `ContractError::Unauthorized`: Caller is not the recipient
+    /// - `ContractError::Unauthorized`: Caller is not the position owner
     ///
     /// # Events
     /// - Publishes `AutoClaimRevoked { stream_id }` event
@@ -7662,7 +11331,10 @@ impl FluxoraStream {
     /// - Useful for cleaning up storage after stream cancellation
     pub fn revoke_auto_claim(env: Env, stream_id: u64) -> Result<(), ContractError> {
         let stream = load_stream(&env, stream_id)?;
-        stream.recipient.require_auth();
+        stream
+            .claim_owner
+            .unwrap_or(stream.recipient)
+            .require_auth();
 
         // Remove destination
         let key = DataKey::AutoClaimDestination(stream_id);
@@ -7677,8 +11349,8 @@ impl FluxoraStream {
     /// Trigger an auto-claim for a stream (permissionless).
     ///
     /// Anyone can call this function to execute the final withdrawal for a stream
-    /// that has reached `end_time` and has an auto-claim destination set by the recipient.
-    /// Tokens are sent to the destination address chosen by the recipient.
+    /// that has reached `end_time` and has an auto-claim destination set by the position owner.
+    /// Tokens are sent to the destination address chosen by the position owner.
     ///
     /// # Parameters
     /// - `stream_id`: The stream to claim
@@ -7691,7 +11363,7 @@ impl FluxoraStream {
     ///
     /// # Errors
     /// - `ContractError::StreamNotFound`: Stream does not exist
-    /// - `ContractError::InvalidState`: Stream is Completed, Cancelled, or before end_time
+    /// - `ContractError::InvalidState`: Stream is Completed, Cancelled, pooled, or before end_time
     /// - `ContractError::InvalidParams`: No auto-claim destination set, or destination is invalid
     /// - `ContractError::ContractPaused`: Global emergency pause is active
     ///
@@ -7700,7 +11372,7 @@ impl FluxoraStream {
     /// - May also publish `Withdrawal` and `Completed` events (same as withdraw)
     ///
     /// # Security
-    /// - Caller cannot influence destination (set by recipient)
+    /// - Caller cannot influence destination (set by the position owner)
     /// - Destination validity is checked before transfer
     /// - CEI pattern: state updated before token transfer
     /// - Reentrancy protected
@@ -7723,8 +11395,11 @@ impl FluxoraStream {
 
         let mut stream = load_stream(&env, stream_id)?;
 
-        // Check stream is not terminal
-        if stream.status == StreamStatus::Completed || stream.status == StreamStatus::Cancelled {
+        // Check stream is not terminal; pooled members use `trigger_pool_auto_claim`
+        if stream.status == StreamStatus::Completed
+            || stream.status == StreamStatus::Cancelled
+            || stream.is_pooled == Some(true)
+        {
             return Err(ContractError::InvalidState);
         }
 
@@ -7755,18 +11430,7 @@ impl FluxoraStream {
         );
 
         // Calculate withdrawable amount (same logic as withdraw)
-        let accrued = accrual::calculate_accrued_amount_checkpointed(
-            accrual::CheckpointState {
-                checkpointed_amount: stream.checkpointed_amount,
-                checkpointed_at: stream.checkpointed_at,
-                cliff_time: stream.cliff_time,
-                end_time: stream.end_time,
-                deposit_amount: stream.deposit_amount,
-                kind: stream.kind,
-            },
-            stream.rate_per_second,
-            now,
-        );
+        let accrued = stream_accrued_at(&env, &stream, now);
 
         let withdrawable = apply_lookback_cap(
             &env,
@@ -7797,10 +11461,12 @@ impl FluxoraStream {
         reconcile_paused_stream_count(&env, previous_status, stream.status);
 
         // Reduce liabilities as tokens leave the contract.
-        let liabilities = read_total_liabilities(&env)
+        let token_address = load_stream_token(&env, stream_id)?;
+        let liabilities = read_token_liabilities(&env, &token_address)?
             .checked_sub(withdrawable)
             .unwrap_or(0);
-        write_total_liabilities(&env, liabilities);
+        write_token_liabilities(&env, &token_address, liabilities)?;
+        let fee = charge_withdraw_fee(&env, stream_id, &token_address, withdrawable)?;
 
         // Emit auto-claim triggered event
         events::emit_auto_claim_triggered(
@@ -7833,8 +11499,17 @@ impl FluxoraStream {
         // Acquire reentrancy lock
         acquire_reentrancy_lock(&env)?;
 
-        // Transfer tokens to destination
-        let transfer_result = push_token(&env, &destination, withdrawable);
+        // Transfer tokens to destination, then notify the recipient hook
+        let transfer_result = push_token_of(&env, &token_address, &destination, withdrawable - fee)
+            .and_then(|()| {
+                Self::notify_recipient_hook(
+                    &env,
+                    stream_id,
+                    &token_address,
+                    withdrawable - fee,
+                    PayoutReason::AutoClaim,
+                )
+            });
 
         // Release reentrancy lock
         release_reentrancy_lock(&env);
@@ -7912,18 +11587,7 @@ impl FluxoraStream {
 
                 // Calculate claimable amount
                 let now = current_accrual_timestamp(&env)?;
-                let accrued = accrual::calculate_accrued_amount_checkpointed(
-                    accrual::CheckpointState {
-                        checkpointed_amount: stream.checkpointed_amount,
-                        checkpointed_at: stream.checkpointed_at,
-                        cliff_time: stream.cliff_time,
-                        end_time: stream.end_time,
-                        deposit_amount: stream.deposit_amount,
-                        kind: stream.kind,
-                    },
-                    stream.rate_per_second,
-                    now,
-                );
+                let accrued = stream_accrued_at(&env, &stream, now);
 
                 let claimable = accrued.saturating_sub(stream.withdrawn_amount).max(0);
                 let claimable = apply_lookback_cap(&env, &stream, now, accrued, claimable);
@@ -7965,6 +11629,187 @@ impl FluxoraStream {
         Ok(env.storage().persistent().get(&key))
     }
 
+    /// Register (or clear, with `hook = None`) the recipient payout hook for a stream.
+    ///
+    /// The hook contract must implement [`hooks::RecipientHook`]. It is called
+    /// after the payout in `withdraw` and `trigger_auto_claim`, and on
+    /// `cancel_stream`, while the reentrancy lock is held, so it can account
+    /// for funds it did not pull itself (e.g. a savings vault). The
+    /// [`PayoutReason`] tells it whether tokens were transferred, credited to
+    /// the recipient balance, or are only claimable. See [`hooks`] for the
+    /// amount passed per entry point and the failure policy.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the position owner: the claim owner if
+    ///   the position was transferred, otherwise the recipient
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    /// - `ContractError::InvalidParams`: `hook` is this contract
+    ///
+    /// # Events
+    /// - Publishes `rcpt_hook(stream_id)` with `RecipientHookSet`; its
+    ///   `recipient` field is the authorizing owner
+    ///
+    /// # Usage Notes
+    /// - The hook is cleared when the position is transferred, when the
+    ///   recipient is rotated via `accept_recipient_update` and when the
+    ///   stream is closed.
+    /// - A hook that fails makes `withdraw` and `trigger_auto_claim` return
+    ///   `RecipientHookFailed`; clear it to withdraw without notification.
+    pub fn set_recipient_hook(
+        env: Env,
+        stream_id: u64,
+        hook: Option<Address>,
+    ) -> Result<(), ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        let owner = stream.claim_owner.unwrap_or(stream.recipient);
+        owner.require_auth();
+
+        if hook.as_ref() == Some(&env.current_contract_address()) {
+            return Err(ContractError::InvalidParams);
+        }
+
+        set_recipient_hook(&env, stream_id, &hook);
+        events::emit_recipient_hook_set(
+            &env,
+            stream_id,
+            RecipientHookSet {
+                stream_id,
+                recipient: owner,
+                hook,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the recipient payout hook registered for a stream, if any.
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    pub fn get_recipient_hook(env: Env, stream_id: u64) -> Result<Option<Address>, ContractError> {
+        let _stream = load_stream(&env, stream_id)?;
+        Ok(get_recipient_hook(&env, stream_id))
+    }
+
+    /// Register (or clear, with `config = None`) the sender refund hook for a stream.
+    ///
+    /// The hook contract must implement [`hooks::RefundHook`]. It is called
+    /// with `(stream_id, amount, reason)` after any cancellation,
+    /// `shorten_stream_end_time` or `decrease_rate_per_second` transfers a
+    /// non-zero refund to the sender, while the reentrancy lock is held.
+    /// `config.on_failure` decides whether a failing hook is ignored or
+    /// reverts the refunding call with `RefundHookFailed`; admin, witness and
+    /// keeper cancellations always ignore failures.
+    ///
+    /// # Authorization
+    /// - Requires authorization from the stream sender
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    /// - `ContractError::InvalidParams`: `config.hook` is this contract
+    ///
+    /// # Events
+    /// - Publishes `rfnd_hook(stream_id)` with `RefundHookSet`
+    pub fn set_refund_hook(
+        env: Env,
+        stream_id: u64,
+        config: Option<RefundHookConfig>,
+    ) -> Result<(), ContractError> {
+        let stream = load_stream(&env, stream_id)?;
+        Self::require_stream_sender(&stream.sender);
+
+        if let Some(config) = &config {
+            if config.hook == env.current_contract_address() {
+                return Err(ContractError::InvalidParams);
+            }
+        }
+
+        set_refund_hook(&env, stream_id, &config);
+        events::emit_refund_hook_set(
+            &env,
+            stream_id,
+            RefundHookSet {
+                stream_id,
+                sender: stream.sender,
+                hook: config.as_ref().map(|config| config.hook.clone()),
+                on_failure: config
+                    .map(|config| config.on_failure)
+                    .unwrap_or(HookFailurePolicy::Ignore),
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the sender refund hook registered for a stream, if any.
+    ///
+    /// # Errors
+    /// - `ContractError::StreamNotFound`: Stream does not exist
+    pub fn get_refund_hook(
+        env: Env,
+        stream_id: u64,
+    ) -> Result<Option<RefundHookConfig>, ContractError> {
+        let _stream = load_stream(&env, stream_id)?;
+        Ok(get_refund_hook(&env, stream_id))
+    }
+
+    /// Push a refund to the sender (or credit its vault, see
+    /// [`set_vault_funding`](Self::set_vault_funding)) and notify the stream's
+    /// refund hook, if any, under the reentrancy lock. A failing hook reverts only when its policy
+    /// is `Revert` and the sender started the call (`sender_initiated`).
+    fn push_refund(
+        env: &Env,
+        stream: &Stream,
+        token: &Address,
+        amount: i128,
+        reason: RefundReason,
+        sender_initiated: bool,
+    ) -> Result<(), ContractError> {
+        acquire_reentrancy_lock(env)?;
+        let transfer_result =
+            push_sender_funds(env, token, &stream.sender, amount).and_then(|()| {
+                Self::notify_refund_hook(env, stream.stream_id, amount, reason, sender_initiated)
+            });
+        release_reentrancy_lock(env);
+        transfer_result
+    }
+
+    /// Notify the stream's refund hook, if one is registered, of a refund.
+    /// Callers hold the reentrancy lock. A `Revert` policy is only honoured
+    /// when `sender_initiated`; admin, witness and keeper paths ignore failures.
+    fn notify_refund_hook(
+        env: &Env,
+        stream_id: u64,
+        amount: i128,
+        reason: RefundReason,
+        sender_initiated: bool,
+    ) -> Result<(), ContractError> {
+        if let Some(config) = get_refund_hook(env, stream_id) {
+            let delivered = hooks::call_refund_hook(env, &config.hook, stream_id, amount, reason);
+            if !delivered && sender_initiated && config.on_failure == HookFailurePolicy::Revert {
+                return Err(ContractError::RefundHookFailed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Notify the stream's recipient hook, if one is registered, of a payout.
+    /// Callers hold the reentrancy lock.
+    fn notify_recipient_hook(
+        env: &Env,
+        stream_id: u64,
+        token: &Address,
+        amount: i128,
+        reason: PayoutReason,
+    ) -> Result<(), ContractError> {
+        if let Some(hook) = get_recipient_hook(env, stream_id) {
+            if !hooks::call_recipient_hook(env, &hook, stream_id, token, amount, reason) {
+                return Err(ContractError::RecipientHookFailed);
+            }
+        }
+        Ok(())
+    }
+
     /// Clone an existing stream into a new stream with a different recipient and timing.
     ///
     /// Copies `rate_per_second`, the cliff offset (relative to `start_time`), the
@@ -8058,6 +11903,7 @@ impl FluxoraStream {
     ) -> Result<u64, ContractError> {
         // ── 1. Pause guard ────────────────────────────────────────────────────
         require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
 
         // ── 2. Load source stream ─────────────────────────────────────────────
         let source = load_stream(&env, stream_id)?;
@@ -8112,11 +11958,14 @@ impl FluxoraStream {
         )?;
 
         // ── 7. Pull deposit tokens from sender ────────────────────────────────
-        pull_token(&env, &source.sender, deposit)?;
+        // The clone is denominated in the source stream's token.
+        let token = load_stream_token(&env, stream_id)?;
+        pull_sender_funds(&env, &token, &source.sender, deposit)?;
 
         // ── 8. Persist the new stream ─────────────────────────────────────────
         let new_stream_id = Self::persist_new_stream(
             &env,
+            &token,
             source.sender.clone(),
             new_recipient.clone(),
             deposit,
@@ -8131,6 +11980,7 @@ impl FluxoraStream {
             source.irrevocable,
             source.witness.clone(),
         )?;
+        charge_deposit_fee(&env, new_stream_id, &token, &source.sender, deposit)?;
 
         // ── 9. Emit clone-specific event for indexer correlation ──────────────
         events::emit_stream_cloned(
@@ -8326,7 +12176,9 @@ impl FluxoraStream {
     ///    - Duplicate `stream_id`s are rejected (`DuplicateStreamId`).
     /// 2. **Execution phase**: Only after all validations pass:
     ///    - Per-stream accrued amount is computed.
-    ///    - Recipient is paid their accrued entitlement (individual transfers).
+    ///    - The position owner ([`owner_of`](Self::owner_of)) is paid the accrued
+    ///      entitlement net of the withdrawal fee (individual transfers, or a
+    ///      credit in claim-to-balance mode).
     ///    - Stream is marked `Cancelled` with `cancelled_at` timestamp.
     ///    - `StreamCancelled` event is emitted per stream.
     ///    - Aggregate refund is computed and sent to the sender in **one** token transfer.
@@ -8352,8 +12204,8 @@ impl FluxoraStream {
     ///
     /// # Gas efficiency
     /// - One auth check for the entire batch.
-    /// - One token transfer for the aggregate refund (vs. N transfers for N individual
-    ///   `cancel_stream` calls).
+    /// - One token transfer per token for the aggregate refund (vs. N transfers for
+    ///   N individual `cancel_stream` calls).
     /// - Per-stream recipient transfers are still individual (required for correct
     ///   accounting and event emission), but the sender refund is batched.
     ///
@@ -8366,6 +12218,9 @@ impl FluxoraStream {
     /// - CEI pattern: state is persisted before every external token transfer.
     /// - Liabilities are accumulated locally and flushed once after all stream
     ///   cancellations succeed.
+    /// - After the aggregate refunds, each stream's refund hook is notified of
+    ///   that stream's refund under the reentrancy lock; a failing hook with
+    ///   `HookFailurePolicy::Revert` reverts the whole batch.
     pub fn bulk_cancel_streams(
         env: Env,
         sender: Address,
@@ -8407,25 +12262,24 @@ impl FluxoraStream {
 
         // ── Phase 2: Execute cancellations ────────────────────────────────────
         let now = env.ledger().timestamp();
-        let mut aggregate_refund: i128 = 0;
-        let mut total_liabilities = read_total_liabilities(&env);
+        // Streams may be denominated in different tokens: refunds and
+        // liabilities are aggregated per token and flushed once at the end.
+        let mut aggregate_refunds: Map<Address, i128> = Map::new(&env);
+        let mut liabilities: Map<Address, i128> = Map::new(&env);
+        // Per-stream refunds, reported to refund hooks once the aggregate
+        // transfers have gone out.
+        let mut refunds = soroban_sdk::Vec::<(u64, i128)>::new(&env);
 
         for i in 0..n {
             let mut stream = streams.get(i).unwrap();
             let stream_id = stream.stream_id;
+            let token_address = load_stream_token(&env, stream_id)?;
+            let mut total_liabilities = match liabilities.get(token_address.clone()) {
+                Some(amount) => amount,
+                None => read_token_liabilities(&env, &token_address)?,
+            };
 
-            let accrued_at_cancel = accrual::calculate_accrued_amount_checkpointed(
-                accrual::CheckpointState {
-                    checkpointed_amount: stream.checkpointed_amount,
-                    checkpointed_at: stream.checkpointed_at,
-                    cliff_time: stream.cliff_time,
-                    end_time: stream.end_time,
-                    deposit_amount: stream.deposit_amount,
-                    kind: stream.kind,
-                },
-                stream.rate_per_second,
-                now,
-            );
+            let accrued_at_cancel = settle_termination(&env, &stream, now)?;
 
             let refund_amount = stream
                 .deposit_amount
@@ -8435,20 +12289,43 @@ impl FluxoraStream {
             let (was_underfunded, _, _) = compute_stream_health(&stream, now);
 
             // ── Pay recipient their accrued entitlement first ─────────────────
-            let recipient_accrual = accrued_at_cancel
-                .saturating_sub(stream.withdrawn_amount)
-                .max(0);
+            // A good leaver's grant keeps vesting after cancellation, so only
+            // the part vested so far is paid; the rest stays claimable.
+            let vested_now = match get_termination_policy(&env, stream_id) {
+                Some(TerminationPolicy::GoodLeaver(_)) => {
+                    accrued_at_cancel.min(stream_accrued_at(&env, &stream, now))
+                }
+                _ => accrued_at_cancel,
+            };
+            let recipient_accrual = vested_now.saturating_sub(stream.withdrawn_amount).max(0);
             if recipient_accrual > 0 {
                 stream.withdrawn_amount = stream
                     .withdrawn_amount
                     .checked_add(recipient_accrual)
                     .unwrap_or(i128::MAX);
 
-                total_liabilities = total_liabilities
-                    .checked_sub(recipient_accrual)
-                    .unwrap_or(0);
-
-                push_token(&env, &stream.recipient, recipient_accrual)?;
+                let fee = charge_withdraw_fee(&env, stream_id, &token_address, recipient_accrual)?;
+
+                // The position owner is paid. A credited payout stays in the
+                // contract as a liability, so only the fee is released for it.
+                let owner = stream
+                    .claim_owner
+                    .clone()
+                    .unwrap_or(stream.recipient.clone());
+                let credited = get_claim_to_balance(&env, &owner);
+                let released = if credited { fee } else { recipient_accrual };
+                total_liabilities = total_liabilities.checked_sub(released).unwrap_or(0);
+
+                if credited {
+                    credit_recipient_balance(
+                        &env,
+                        &owner,
+                        &token_address,
+                        recipient_accrual - fee,
+                    )?;
+                } else {
+                    push_token_of(&env, &token_address, &owner, recipient_accrual - fee)?;
+                }
 
                 events::emit_withdrawal(
                     &env,
@@ -8470,28 +12347,151 @@ impl FluxoraStream {
 
             // ── Accumulate sender refund ──────────────────────────────────────
             if refund_amount > 0 {
-                aggregate_refund = aggregate_refund
+                let aggregate_refund = aggregate_refunds
+                    .get(token_address.clone())
+                    .unwrap_or(0)
                     .checked_add(refund_amount)
                     .ok_or(ContractError::ArithmeticOverflow)?;
+                aggregate_refunds.set(token_address.clone(), aggregate_refund);
+                refunds.push_back((stream_id, refund_amount));
 
                 total_liabilities = total_liabilities.checked_sub(refund_amount).unwrap_or(0);
             }
+            liabilities.set(token_address, total_liabilities);
 
             events::emit_stream_cancelled(&env, stream_id);
 
             maybe_emit_health_changed(&env, &stream, was_underfunded, now);
         }
 
-        // ── Single aggregate refund to sender ─────────────────────────────────
-        write_total_liabilities(&env, total_liabilities);
+        // ── Single aggregate refund to sender (one per token) ─────────────────
+        for (token_address, total_liabilities) in liabilities.iter() {
+            write_token_liabilities(&env, &token_address, total_liabilities)?;
+        }
+
+        acquire_reentrancy_lock(&env)?;
+        let transfer_result = (|| {
+            for (token_address, aggregate_refund) in aggregate_refunds.iter() {
+                if aggregate_refund > 0 {
+                    push_sender_funds(&env, &token_address, &sender, aggregate_refund)?;
+                }
+            }
+            for (stream_id, refund_amount) in refunds.iter() {
+                Self::notify_refund_hook(
+                    &env,
+                    stream_id,
+                    refund_amount,
+                    RefundReason::Cancel,
+                    true,
+                )?;
+            }
+            Ok(())
+        })();
+        release_reentrancy_lock(&env);
+        transfer_result
+    }
 
-        if aggregate_refund > 0 {
-            push_token(&env, &sender, aggregate_refund)?;
+    /// Apply a list of sender-side mutations atomically under one authorization.
+    ///
+    /// `caller` authorizes once for the whole batch. Ops run in order, each
+    /// with the validation of the entrypoint named on its [`StreamOp`]
+    /// variant. Every op except `TopUp` must target a stream whose sender is
+    /// `caller`; `TopUp` is funded by `caller`, exactly like `top_up_stream`.
+    /// The same stream may appear in several ops, e.g. top up and then extend.
+    ///
+    /// # Errors
+    /// - `ContractError::InvalidParams`: more than `MAX_PAGE_SIZE` ops.
+    /// - `ContractError::Unauthorized`: an op targets another sender's stream.
+    /// - Any error of an individual op. The first failure reverts the whole batch.
+    pub fn execute_batch(
+        env: Env,
+        caller: Address,
+        ops: soroban_sdk::Vec<StreamOp>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if ops.len() as u64 > MAX_PAGE_SIZE {
+            return Err(ContractError::InvalidParams);
         }
 
+        for op in ops.iter() {
+            Self::apply_stream_op(&env, &caller, op)?;
+        }
         Ok(())
     }
 
+    /// Like [`execute_batch`](Self::execute_batch), but a failing op is
+    /// recorded and skipped instead of reverting the batch.
+    ///
+    /// An op fails during its checks, before it writes state or moves tokens,
+    /// so a skipped op changes nothing. It may still leave the event its
+    /// entrypoint emits on rejection: an `UpdateRate` above the governance cap
+    /// emits `rate_cap` before failing with `RateCapExceeded`, and that event
+    /// survives the skip. The exception is a hook that fails under
+    /// `HookFailurePolicy::Revert` after the op has paid out: `RefundHookFailed`
+    /// and `RecipientHookFailed` still revert the whole batch.
+    ///
+    /// # Returns
+    /// - `Vec<StreamOpResult>`: Per-op success/failure results in input order
+    pub fn execute_batch_partial(
+        env: Env,
+        caller: Address,
+        ops: soroban_sdk::Vec<StreamOp>,
+    ) -> Result<soroban_sdk::Vec<StreamOpResult>, ContractError> {
+        caller.require_auth();
+        if ops.len() as u64 > MAX_PAGE_SIZE {
+            return Err(ContractError::InvalidParams);
+        }
+
+        let mut results = soroban_sdk::Vec::new(&env);
+        for op in ops.iter() {
+            match Self::apply_stream_op(&env, &caller, op) {
+                Ok(()) => results.push_back(StreamOpResult {
+                    success: true,
+                    error: None,
+                }),
+                Err(e @ (ContractError::RefundHookFailed | ContractError::RecipientHookFailed)) => {
+                    return Err(e)
+                }
+                Err(e) => results.push_back(StreamOpResult {
+                    success: false,
+                    error: Some(e as u32),
+                }),
+            }
+        }
+        Ok(results)
+    }
+
+    /// Dispatch one [`StreamOp`] for an already-authorized batch `caller`.
+    fn apply_stream_op(env: &Env, caller: &Address, op: StreamOp) -> Result<(), ContractError> {
+        let env = env.clone();
+        let batch = Some(caller);
+        match op {
+            StreamOp::Pause(id, reason) => Self::pause_stream_internal(env, id, reason, batch),
+            StreamOp::Resume(id) => Self::resume_stream_internal(env, id, batch),
+            StreamOp::Cancel(id) => {
+                require_not_globally_paused(&env)?;
+                let mut stream = load_stream(&env, id)?;
+                Self::require_op_auth(&stream.sender, batch)?;
+                Self::cancel_stream_internal(&env, &mut stream, true)
+            }
+            StreamOp::UpdateRate(id, rate) => {
+                Self::update_rate_per_second_internal(env, id, rate, batch)
+            }
+            StreamOp::DecreaseRate(id, rate) => {
+                Self::decrease_rate_per_second_internal(env, id, rate, batch)
+            }
+            StreamOp::TopUp(id, amount) => {
+                Self::top_up_stream_internal(env, id, caller.clone(), amount, batch)
+            }
+            StreamOp::ExtendEndTime(id, end) => {
+                Self::extend_stream_end_time_internal(env, id, end, batch)
+            }
+            StreamOp::ShortenEndTime(id, end) => {
+                Self::shorten_stream_end_time_internal(env, id, end, batch)
+            }
+        }
+    }
+
     // =========================================================================
     // Two-phase offer-then-accept stream creation
     // =========================================================================
@@ -8508,7 +12508,8 @@ impl FluxoraStream {
     /// - `sender.require_auth()`
     ///
     /// # Parameters
-    /// - `sender`: Address funding the offer (deposit pulled immediately).
+    /// - `sender`: Address funding the offer (deposit and deposit fee pulled
+    ///   immediately).
     /// - `recipient`: Address of the intended stream recipient.
     /// - `deposit_amount`: Total tokens to deposit (same rules as `create_stream`).
     /// - `rate_per_second`: Streaming rate (0 for `CliffOnly` streams).
@@ -8548,6 +12549,7 @@ impl FluxoraStream {
     ) -> Result<u64, ContractError> {
         sender.require_auth();
         require_not_creation_paused(&env)?;
+        require_stream_creator(&env);
         let now = env.ledger().timestamp();
 
         // Validate expiry is in the future if provided.
@@ -8601,6 +12603,12 @@ impl FluxoraStream {
 
         // ── CEI: state changes before token transfer ──────────────────────────
 
+        let deposit_fee = deposit_fee_for(&env, params.deposit_amount)?;
+        let escrow = params
+            .deposit_amount
+            .checked_add(deposit_fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
+
         // Allocate offer ID from the global stream counter.
         let offer_id = next_stream_id_for(&env, &sender);
 
@@ -8609,6 +12617,7 @@ impl FluxoraStream {
             sender: sender.clone(),
             recipient: params.recipient.clone(),
             deposit_amount: params.deposit_amount,
+            deposit_fee,
             rate_per_second: final_rate,
             start_time: params.start_time,
             cliff_time: params.cliff_time,
@@ -8626,7 +12635,7 @@ impl FluxoraStream {
         add_offer_to_recipient_pending(&env, &params.recipient, offer_id);
 
         // ── CEI: token transfer ───────────────────────────────────────────────
-        pull_token(&env, &sender, params.deposit_amount)?;
+        pull_token(&env, &sender, escrow)?;
 
         // ── Emit event ────────────────────────────────────────────────────────
         env.events().publish(
@@ -8775,14 +12784,18 @@ impl FluxoraStream {
 
         save_stream(&env, &stream);
 
-        // Add to RecipientStreams index (the offer was intentionally excluded from it).
+        // Add to the recipient and sender indexes (the offer was intentionally
+        // excluded from both).
         add_stream_to_recipient_index(&env, &offer.recipient, offer_id, Some(effective_end));
+        add_owner_position(&env, &offer.recipient, offer_id);
+        add_stream_to_sender_index(&env, &offer.sender, offer_id, Some(effective_end));
 
         // Track liability: the full deposit is now owed to the recipient.
         let liabilities = read_total_liabilities(&env)
             .checked_add(offer.deposit_amount)
             .unwrap_or(i128::MAX);
         write_total_liabilities(&env, liabilities);
+        book_deposit_fee(&env, offer_id, &get_token(&env)?, offer.deposit_fee)?;
 
         // ── Emit events ───────────────────────────────────────────────────────
         events::emit_stream_created(
@@ -8818,7 +12831,8 @@ impl FluxoraStream {
     /// Reject a pending stream offer (recipient-initiated).
     ///
     /// The caller must be the intended recipient of the offer. The offer is
-    /// removed and the escrowed deposit is refunded to the original sender.
+    /// removed and the escrowed deposit and deposit fee are refunded to the
+    /// original sender.
     ///
     /// # Authorization
     /// - `recipient.require_auth()`
@@ -8843,7 +12857,10 @@ impl FluxoraStream {
         }
 
         let sender = offer.sender.clone();
-        let deposit = offer.deposit_amount;
+        let deposit = offer
+            .deposit_amount
+            .checked_add(offer.deposit_fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
 
         // ── CEI: remove state before token transfer ───────────────────────────
         remove_stream_offer(&env, offer_id);
@@ -8867,8 +12884,9 @@ impl FluxoraStream {
     /// Cancel a pending stream offer (sender-initiated).
     ///
     /// The caller must be the original sender of the offer. The offer is
-    /// removed and the escrowed deposit is refunded to the sender. This can be
-    /// called at any time, including after the offer has expired.
+    /// removed and the escrowed deposit and deposit fee are refunded to the
+    /// sender. This can be called at any time, including after the offer has
+    /// expired.
     ///
     /// # Authorization
     /// - `sender.require_auth()`
@@ -8892,7 +12910,10 @@ impl FluxoraStream {
             return Err(ContractError::OfferWrongSender);
         }
 
-        let deposit = offer.deposit_amount;
+        let deposit = offer
+            .deposit_amount
+            .checked_add(offer.deposit_fee)
+            .ok_or(ContractError::ArithmeticOverflow)?;
         let recipient = offer.recipient.clone();
 
         // ── CEI: remove state before token transfer ───────────────────────────
@@ -8947,6 +12968,40 @@ impl FluxoraStream {
     pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) -> Result<(), ContractError> {
         crate::upgrade(env, new_wasm_hash)
     }
+
+    /// Number of successful upgrades recorded in the upgrade history.
+    ///
+    /// # Authorization
+    /// None — public read-only query.
+    pub fn get_upgrade_count(env: Env) -> u32 {
+        get_upgrade_count(&env)
+    }
+
+    /// Page through the upgrade history, oldest first.
+    ///
+    /// Returns the records at indices `start..start + limit`, with `limit`
+    /// capped at [`MAX_PAGE_SIZE`]. Indices past `get_upgrade_count()` are
+    /// skipped, so an out-of-range `start` returns an empty vector.
+    ///
+    /// # Authorization
+    /// None — public read-only query.
+    pub fn get_upgrade_history(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> soroban_sdk::Vec<UpgradeRecord> {
+        let count = get_upgrade_count(&env);
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE as u32))
+            .min(count);
+        let mut records = soroban_sdk::Vec::new(&env);
+        for index in start..end {
+            if let Some(record) = get_upgrade_record(&env, index) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -8969,6 +13024,25 @@ pub struct ContractUpgraded {
     pub upgraded_by: Address,
 }
 
+/// One entry of the on-chain upgrade history, appended by every successful
+/// `upgrade` call and read through `get_upgrade_history`.
+///
+/// `old_wasm_hash` is the `new_wasm_hash` of the previous record, or all zeroes
+/// for the first recorded upgrade: Soroban does not expose the executing
+/// contract's own WASM hash, so the originally deployed hash is not known
+/// on-chain.
+/// `contract_version` has the same meaning as `ContractUpgraded.new_version` —
+/// the version of the WASM that executed the upgrade.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeRecord {
+    pub old_wasm_hash: soroban_sdk::BytesN<32>,
+    pub new_wasm_hash: soroban_sdk::BytesN<32>,
+    pub ledger: u32,
+    pub upgraded_at: u64,
+    pub contract_version: u32,
+}
+
 // Add to the contract impl block (FluxoraStream)
 
 /// Upgrade the contract WASM to a new version.
@@ -8989,7 +13063,8 @@ pub struct ContractUpgraded {
 /// # Behavior
 /// 1. Loads the configured admin and requires that address's authorization.
 /// 2. Calls `env.deployer().update_current_contract_wasm(new_wasm_hash)`.
-/// 3. Bumps instance TTL and emits the compatibility application events.
+/// 3. Appends an `UpgradeRecord` to the upgrade history.
+/// 4. Bumps instance TTL and emits the compatibility application events.
 ///
 /// The current invocation continues running this WASM after the host update is
 /// requested. Consequently, event version fields contain this WASM's
@@ -9054,6 +13129,8 @@ pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) -> Result<(), C
     env.deployer()
         .update_current_contract_wasm(new_wasm_hash.clone());
 
+    record_upgrade(&env, &new_wasm_hash, executing_version);
+
     // Bump TTL after upgrade to ensure the contract stays alive
     bump_instance_ttl(&env);
 
@@ -9081,6 +13158,31 @@ pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) -> Result<(), C
     Ok(())
 }
 
+/// Append an `UpgradeRecord` for `new_wasm_hash`, chaining `old_wasm_hash`
+/// from the previous record.
+pub(crate) fn record_upgrade(
+    env: &Env,
+    new_wasm_hash: &soroban_sdk::BytesN<32>,
+    contract_version: u32,
+) {
+    let count = get_upgrade_count(env);
+    let old_wasm_hash = match count {
+        0 => None,
+        n => get_upgrade_record(env, n - 1).map(|record| record.new_wasm_hash),
+    }
+    .unwrap_or_else(|| soroban_sdk::BytesN::from_array(env, &[0u8; 32]));
+    append_upgrade_record(
+        env,
+        &UpgradeRecord {
+            old_wasm_hash,
+            new_wasm_hash: new_wasm_hash.clone(),
+            ledger: env.ledger().sequence(),
+            upgraded_at: env.ledger().timestamp(),
+            contract_version,
+        },
+    );
+}
+
 // ---------------------------------------------------------------------------
 // A note on the `#[ignore]`d tests in `test`, `test_token_edge_cases`, and
 // `test_withdrawable_props` below (CI restoration, 2026-07):
@@ -175,6 +175,28 @@ pub fn require_not_creation_paused(env: &Env) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Contract allowed to create streams on this instance, if creation is
+/// locked (see `FluxoraStream::init_with_creator`).
+pub fn get_stream_creator(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKeyExt::StreamCreator)
+}
+
+pub fn set_stream_creator(env: &Env, creator: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::StreamCreator, creator);
+}
+
+/// Blocks new stream creation unless the locked stream creator authorized it.
+///
+/// A no-op when creation is not locked. A contract creator authorizes its own
+/// direct calls, so this only rejects calls that do not come through it.
+pub fn require_stream_creator(env: &Env) {
+    if let Some(creator) = get_stream_creator(env) {
+        creator.require_auth();
+    }
+}
+
 /// Returns whether the protocol is globally paused (checks both GlobalEmergencyPaused and CreationPaused).
 /// Default is false (not paused) if no pause keys are set.
 pub fn is_protocol_paused(env: &Env) -> bool {
@@ -452,82 +474,687 @@ pub fn load_sender_streams(env: &Env, sender: &Address) -> soroban_sdk::Vec<u64>
         .get(&key)
         .unwrap_or_else(|| soroban_sdk::Vec::new(env));
 
-    if !streams.is_empty() {
+    if !streams.is_empty() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    streams
+}
+
+/// Persist the sender stream index (maintains sorted order).
+pub fn save_sender_streams(
+    env: &Env,
+    sender: &Address,
+    streams: &soroban_sdk::Vec<u64>,
+    end_time: Option<u64>,
+) {
+    let key = DataKey::SenderStreams(sender.clone());
+    if streams.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, streams);
+        let bump = end_time
+            .map(|et| compute_adaptive_ttl(env.ledger().timestamp(), et))
+            .unwrap_or(PERSISTENT_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, bump);
+    }
+}
+
+/// Insert a stream ID into the sender's index, maintaining ascending sort order.
+/// No-op if the ID is already present.
+pub fn add_stream_to_sender_index(
+    env: &Env,
+    sender: &Address,
+    stream_id: u64,
+    end_time: Option<u64>,
+) {
+    let mut streams = load_sender_streams(env, sender);
+    let insert_pos = match streams.binary_search(stream_id) {
+        Ok(_) => return, // already present – idempotent
+        Err(pos) => pos,
+    };
+    streams.insert(insert_pos, stream_id);
+    save_sender_streams(env, sender, &streams, end_time);
+}
+
+/// Remove a stream ID from the sender's index.
+/// No-op if the ID is not present.
+pub fn remove_stream_from_sender_index(env: &Env, sender: &Address, stream_id: u64) {
+    let mut streams = load_sender_streams(env, sender);
+    if let Ok(idx) = streams.binary_search(stream_id) {
+        streams.remove(idx);
+        save_sender_streams(env, sender, &streams, None);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Liability tracking (total escrow owed to recipients)
+// ---------------------------------------------------------------------------
+
+pub fn read_total_liabilities(env: &Env) -> i128 {
+    bump_instance_ttl(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::TotalLiabilities)
+        .unwrap_or(0i128)
+}
+
+pub fn write_total_liabilities(env: &Env, amount: i128) {
+    let safe_amount = if amount < 0 { 0 } else { amount };
+    env.storage()
+        .instance()
+        .set(&DataKey::TotalLiabilities, &safe_amount);
+    bump_instance_ttl(env);
+}
+
+/// Outstanding liabilities denominated in `token`.
+///
+/// The default token (`Config.token`) keeps using the legacy
+/// `DataKey::TotalLiabilities` slot so existing deployments need no migration;
+/// every other allowlisted token has its own `DataKey::TokenLiabilities` entry.
+pub fn read_token_liabilities(env: &Env, token: &Address) -> Result<i128, ContractError> {
+    if *token == get_token(env)? {
+        return Ok(read_total_liabilities(env));
+    }
+    bump_instance_ttl(env);
+    Ok(env
+        .storage()
+        .instance()
+        .get(&DataKey::TokenLiabilities(token.clone()))
+        .unwrap_or(0i128))
+}
+
+pub fn write_token_liabilities(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if *token == get_token(env)? {
+        write_total_liabilities(env, amount);
+        return Ok(());
+    }
+    let safe_amount = if amount < 0 { 0 } else { amount };
+    env.storage()
+        .instance()
+        .set(&DataKey::TokenLiabilities(token.clone()), &safe_amount);
+    bump_instance_ttl(env);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Protocol fee helpers
+// ---------------------------------------------------------------------------
+
+pub fn get_protocol_fee(env: &Env) -> ProtocolFeeConfig {
+    bump_instance_ttl(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::ProtocolFee)
+        .unwrap_or_default()
+}
+
+pub fn set_protocol_fee(env: &Env, config: &ProtocolFeeConfig) {
+    env.storage().instance().set(&DataKey::ProtocolFee, config);
+    bump_instance_ttl(env);
+}
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    bump_instance_ttl(env);
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&DataKey::Treasury, treasury);
+    bump_instance_ttl(env);
+}
+
+/// Protocol fees held in `token` and owed to the treasury.
+pub fn read_protocol_fee_liabilities(env: &Env, token: &Address) -> i128 {
+    bump_instance_ttl(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::ProtocolFeeLiabilities(token.clone()))
+        .unwrap_or(0i128)
+}
+
+pub fn write_protocol_fee_liabilities(env: &Env, token: &Address, amount: i128) {
+    let safe_amount = if amount < 0 { 0 } else { amount };
+    env.storage().instance().set(
+        &DataKey::ProtocolFeeLiabilities(token.clone()),
+        &safe_amount,
+    );
+    bump_instance_ttl(env);
+}
+
+// ---------------------------------------------------------------------------
+// Multi-token helpers
+// ---------------------------------------------------------------------------
+
+/// Token a stream is denominated in.
+///
+/// Streams created before multi-token support (and every stream created in the
+/// default token) have no `DataKey::StreamToken` entry and resolve to
+/// `Config.token`.
+pub fn load_stream_token(env: &Env, stream_id: u64) -> Result<Address, ContractError> {
+    let key = DataKey::StreamToken(stream_id);
+    let token: Option<Address> = env.storage().persistent().get(&key);
+    match token {
+        Some(token) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            Ok(token)
+        }
+        None => get_token(env),
+    }
+}
+
+/// Record the token of a new stream. Default-token streams write nothing.
+pub fn save_stream_token(env: &Env, stream_id: u64, token: &Address) -> Result<(), ContractError> {
+    if *token == get_token(env)? {
+        return Ok(());
+    }
+    let key = DataKey::StreamToken(stream_id);
+    env.storage().persistent().set(&key, token);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    Ok(())
+}
+
+pub fn remove_stream_token(env: &Env, stream_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::StreamToken(stream_id));
+}
+
+/// Whether new streams may be created in `token`. `Config.token` is always allowed.
+pub fn is_token_allowed(env: &Env, token: &Address) -> Result<bool, ContractError> {
+    if *token == get_token(env)? {
+        return Ok(true);
+    }
+    let key = DataKey::AllowedToken(token.clone());
+    let allowed = env.storage().persistent().has(&key);
+    if allowed {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    Ok(allowed)
+}
+
+pub fn set_token_allowed(env: &Env, token: &Address, allowed: bool) {
+    let key = DataKey::AllowedToken(token.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stream position (claim ownership) helpers
+// ---------------------------------------------------------------------------
+
+/// Every stream position held by `owner`, sorted ascending: streams it
+/// receives and has not transferred away, plus streams transferred to it.
+/// Pooled streams are not positions and never appear here.
+pub fn load_owner_positions(env: &Env, owner: &Address) -> soroban_sdk::Vec<u64> {
+    let key = DataKey::OwnerPositions(owner.clone());
+    let positions: soroban_sdk::Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| soroban_sdk::Vec::new(env));
+
+    if !positions.is_empty() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    positions
+}
+
+fn save_owner_positions(env: &Env, owner: &Address, positions: &soroban_sdk::Vec<u64>) {
+    let key = DataKey::OwnerPositions(owner.clone());
+    if positions.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, positions);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    set_owner_position_count(env, owner, positions.len());
+}
+
+/// Number of positions held by `owner`, kept in step with its
+/// `OwnerPositions` index so `balance_of` is a single read.
+pub fn get_owner_position_count(env: &Env, owner: &Address) -> u32 {
+    let key = DataKeyExt::OwnerPositionCount(owner.clone());
+    let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    if count > 0 {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    count
+}
+
+fn set_owner_position_count(env: &Env, owner: &Address, count: u32) {
+    let key = DataKeyExt::OwnerPositionCount(owner.clone());
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+}
+
+/// Add a stream ID to `owner`'s position index (idempotent).
+pub fn add_owner_position(env: &Env, owner: &Address, stream_id: u64) {
+    let mut positions = load_owner_positions(env, owner);
+    if let Err(pos) = positions.binary_search(stream_id) {
+        positions.insert(pos, stream_id);
+        save_owner_positions(env, owner, &positions);
+    }
+}
+
+/// Add several stream IDs to `owner`'s position index with one read and one
+/// write, for batch creation.
+pub fn add_owner_positions(env: &Env, owner: &Address, stream_ids: &soroban_sdk::Vec<u64>) {
+    let mut positions = load_owner_positions(env, owner);
+    for stream_id in stream_ids.iter() {
+        if let Err(pos) = positions.binary_search(stream_id) {
+            positions.insert(pos, stream_id);
+        }
+    }
+    save_owner_positions(env, owner, &positions);
+}
+
+/// Remove a stream ID from `owner`'s position index.
+/// No-op if the ID is not present.
+pub fn remove_owner_position(env: &Env, owner: &Address, stream_id: u64) {
+    let mut positions = load_owner_positions(env, owner);
+    if let Ok(idx) = positions.binary_search(stream_id) {
+        positions.remove(idx);
+        save_owner_positions(env, owner, &positions);
+    }
+}
+
+/// Current holder of a non-pooled stream's position.
+pub fn position_owner(stream: &Stream) -> Option<Address> {
+    if stream.is_pooled == Some(true) {
+        return None;
+    }
+    Some(stream.claim_owner.clone().unwrap_or(stream.recipient.clone()))
+}
+
+pub fn get_position_approval(env: &Env, stream_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PositionApproval(stream_id))
+}
+
+pub fn set_position_approval(env: &Env, stream_id: u64, approved: &Option<Address>) {
+    let key = DataKey::PositionApproval(stream_id);
+    match approved {
+        Some(spender) => {
+            env.storage().persistent().set(&key, spender);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Drop all position state for a stream that is being closed.
+pub fn remove_position(env: &Env, stream: &Stream) {
+    if let Some(owner) = position_owner(stream) {
+        remove_owner_position(env, &owner, stream.stream_id);
+    }
+    set_position_approval(env, stream.stream_id, &None);
+}
+
+// ---------------------------------------------------------------------------
+// Recipient payout and sender refund hooks
+// ---------------------------------------------------------------------------
+
+pub fn get_recipient_hook(env: &Env, stream_id: u64) -> Option<Address> {
+    let key = DataKey::RecipientHook(stream_id);
+    let hook = env.storage().persistent().get(&key);
+    if hook.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    hook
+}
+
+pub fn set_recipient_hook(env: &Env, stream_id: u64, hook: &Option<Address>) {
+    let key = DataKey::RecipientHook(stream_id);
+    match hook {
+        Some(hook) => {
+            env.storage().persistent().set(&key, hook);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_refund_hook(env: &Env, stream_id: u64) -> Option<RefundHookConfig> {
+    let key = DataKey::RefundHook(stream_id);
+    let config = env.storage().persistent().get(&key);
+    if config.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    config
+}
+
+pub fn set_refund_hook(env: &Env, stream_id: u64, config: &Option<RefundHookConfig>) {
+    let key = DataKey::RefundHook(stream_id);
+    match config {
+        Some(config) => {
+            env.storage().persistent().set(&key, config);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Subscriptions
+// ---------------------------------------------------------------------------
+
+pub fn get_subscription_period(env: &Env, stream_id: u64) -> Option<u64> {
+    let key = DataKeyExt::Subscription(stream_id);
+    let period = env.storage().persistent().get(&key);
+    if period.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    period
+}
+
+pub fn set_subscription_period(env: &Env, stream_id: u64, period: Option<u64>) {
+    let key = DataKeyExt::Subscription(stream_id);
+    match period {
+        Some(period) => {
+            env.storage().persistent().set(&key, &period);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sender vault
+// ---------------------------------------------------------------------------
+
+/// Vault balance `sender` holds in `token`. Counted in that token's liabilities.
+pub fn read_vault_balance(env: &Env, sender: &Address, token: &Address) -> i128 {
+    let key = DataKeyExt::VaultBalance(sender.clone(), token.clone());
+    let balance = env.storage().persistent().get(&key);
+    if balance.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    balance.unwrap_or(0)
+}
+
+/// Store a vault balance. A zero balance removes the entry.
+pub fn write_vault_balance(env: &Env, sender: &Address, token: &Address, balance: i128) {
+    let key = DataKeyExt::VaultBalance(sender.clone(), token.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &balance);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+pub fn get_vault_funding(env: &Env, sender: &Address) -> bool {
+    let key = DataKeyExt::VaultFunding(sender.clone());
+    let enabled = env.storage().persistent().has(&key);
+    if enabled {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    enabled
+}
+
+pub fn set_vault_funding(env: &Env, sender: &Address, enabled: bool) {
+    let key = DataKeyExt::VaultFunding(sender.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Recipient balance
+// ---------------------------------------------------------------------------
+
+/// Internal balance `recipient` holds in `token`. Counted in that token's liabilities.
+pub fn read_recipient_balance(env: &Env, recipient: &Address, token: &Address) -> i128 {
+    let key = DataKeyExt::RecipientBalance(recipient.clone(), token.clone());
+    let balance = env.storage().persistent().get(&key);
+    if balance.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    balance.unwrap_or(0)
+}
+
+/// Store a recipient balance. A zero balance removes the entry.
+pub fn write_recipient_balance(env: &Env, recipient: &Address, token: &Address, balance: i128) {
+    let key = DataKeyExt::RecipientBalance(recipient.clone(), token.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &balance);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+pub fn get_claim_to_balance(env: &Env, recipient: &Address) -> bool {
+    let key = DataKeyExt::ClaimToBalance(recipient.clone());
+    let enabled = env.storage().persistent().has(&key);
+    if enabled {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    enabled
+}
+
+pub fn set_claim_to_balance(env: &Env, recipient: &Address, enabled: bool) {
+    let key = DataKeyExt::ClaimToBalance(recipient.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Termination policy
+// ---------------------------------------------------------------------------
+
+pub fn get_termination_policy(env: &Env, stream_id: u64) -> Option<TerminationPolicy> {
+    let key = DataKeyExt::TerminationPolicy(stream_id);
+    let policy = env.storage().persistent().get(&key);
+    if policy.is_some() {
         env.storage().persistent().extend_ttl(
             &key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
     }
-    streams
+    policy
 }
 
-/// Persist the sender stream index (maintains sorted order).
-pub fn save_sender_streams(
-    env: &Env,
-    sender: &Address,
-    streams: &soroban_sdk::Vec<u64>,
-    end_time: Option<u64>,
-) {
-    let key = DataKey::SenderStreams(sender.clone());
-    if streams.is_empty() {
-        env.storage().persistent().remove(&key);
-    } else {
-        env.storage().persistent().set(&key, streams);
-        let bump = end_time
-            .map(|et| compute_adaptive_ttl(env.ledger().timestamp(), et))
-            .unwrap_or(PERSISTENT_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, bump);
+pub fn set_termination_policy(env: &Env, stream_id: u64, policy: Option<&TerminationPolicy>) {
+    let key = DataKeyExt::TerminationPolicy(stream_id);
+    match policy {
+        Some(policy) => {
+            env.storage().persistent().set(&key, policy);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
     }
 }
 
-/// Insert a stream ID into the sender's index, maintaining ascending sort order.
-/// No-op if the ID is already present.
-pub fn add_stream_to_sender_index(
-    env: &Env,
-    sender: &Address,
-    stream_id: u64,
-    end_time: Option<u64>,
-) {
-    let mut streams = load_sender_streams(env, sender);
-    let insert_pos = match streams.binary_search(stream_id) {
-        Ok(_) => return, // already present – idempotent
-        Err(pos) => pos,
-    };
-    streams.insert(insert_pos, stream_id);
-    save_sender_streams(env, sender, &streams, end_time);
+/// Vested amount fixed when a cancellation applied a termination policy.
+pub fn get_termination_vested(env: &Env, stream_id: u64) -> Option<i128> {
+    let key = DataKeyExt::TerminationVested(stream_id);
+    let vested = env.storage().persistent().get(&key);
+    if vested.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    vested
 }
 
-/// Remove a stream ID from the sender's index.
-/// No-op if the ID is not present.
-pub fn remove_stream_from_sender_index(env: &Env, sender: &Address, stream_id: u64) {
-    let mut streams = load_sender_streams(env, sender);
-    if let Ok(idx) = streams.binary_search(stream_id) {
-        streams.remove(idx);
-        save_sender_streams(env, sender, &streams, None);
+pub fn set_termination_vested(env: &Env, stream_id: u64, vested: Option<i128>) {
+    let key = DataKeyExt::TerminationVested(stream_id);
+    match vested {
+        Some(vested) => {
+            env.storage().persistent().set(&key, &vested);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
     }
 }
 
 // ---------------------------------------------------------------------------
-// Liability tracking (total escrow owed to recipients)
+// Upgrade history
 // ---------------------------------------------------------------------------
 
-pub fn read_total_liabilities(env: &Env) -> i128 {
-    bump_instance_ttl(env);
+pub fn get_upgrade_count(env: &Env) -> u32 {
     env.storage()
         .instance()
-        .get(&DataKey::TotalLiabilities)
-        .unwrap_or(0i128)
+        .get(&DataKey::UpgradeCount)
+        .unwrap_or(0)
 }
 
-pub fn write_total_liabilities(env: &Env, amount: i128) {
-    let safe_amount = if amount < 0 { 0 } else { amount };
+pub fn get_upgrade_record(env: &Env, index: u32) -> Option<UpgradeRecord> {
+    let key = DataKey::UpgradeRecord(index);
+    let record = env.storage().persistent().get(&key);
+    if record.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    record
+}
+
+/// Append `record` at the next history index and bump `UpgradeCount`.
+pub fn append_upgrade_record(env: &Env, record: &UpgradeRecord) {
+    let index = get_upgrade_count(env);
+    let key = DataKey::UpgradeRecord(index);
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
     env.storage()
         .instance()
-        .set(&DataKey::TotalLiabilities, &safe_amount);
-    bump_instance_ttl(env);
+        .set(&DataKey::UpgradeCount, &(index + 1));
 }
 
 // ---------------------------------------------------------------------------
@@ -814,8 +1441,17 @@ pub fn append_rotation_entry(env: &Env, stream_id: u64, entry: RotationEntry) {
 ///
 /// See [`token-assumptions.md`](../../docs/token-assumptions.md) for complete token trust model.
 pub fn pull_token(env: &Env, from: &Address, amount: i128) -> Result<(), ContractError> {
-    let token_address = get_token(env)?;
-    let token_client = token::Client::new(env, &token_address);
+    pull_token_of(env, &get_token(env)?, from, amount)
+}
+
+/// Like [`pull_token`] but for an explicit token (multi-token streams).
+pub fn pull_token_of(
+    env: &Env,
+    token_address: &Address,
+    from: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let token_client = token::Client::new(env, token_address);
     token_client.transfer_from(
         &env.current_contract_address(),
         from,
@@ -857,8 +1493,17 @@ pub fn pull_token(env: &Env, from: &Address, amount: i128) -> Result<(), Contrac
 ///
 /// See [`token-assumptions.md`](../../docs/token-assumptions.md) for complete token trust model.
 pub fn push_token(env: &Env, to: &Address, amount: i128) -> Result<(), ContractError> {
-    let token_address = get_token(env)?;
-    let token_client = token::Client::new(env, &token_address);
+    push_token_of(env, &get_token(env)?, to, amount)
+}
+
+/// Like [`push_token`] but for an explicit token (multi-token streams).
+pub fn push_token_of(
+    env: &Env,
+    token_address: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let token_client = token::Client::new(env, token_address);
     #[cfg(test)]
     {
         let res = token_client.try_transfer(&env.current_contract_address(), to, &amount);
@@ -991,6 +1636,129 @@ pub fn read_pooled_stream_withdrawn(env: &Env, stream_id: u64, recipient: Addres
     amount
 }
 
+/// Pool-wide accrual at the last membership settlement; `0` when the share
+/// table has never changed since creation.
+pub fn read_pool_checkpoint(env: &Env, stream_id: u64) -> i128 {
+    let key = DataKeyExt::PoolCheckpoint(stream_id);
+    match env.storage().persistent().get(&key) {
+        Some(accrued) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            accrued
+        }
+        None => 0,
+    }
+}
+
+pub fn save_pool_checkpoint(env: &Env, stream_id: u64, accrued: i128) {
+    let key = DataKeyExt::PoolCheckpoint(stream_id);
+    env.storage().persistent().set(&key, &accrued);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Accrual credited to a pool member up to the pool checkpoint; `0` if none.
+pub fn read_pool_member_credit(env: &Env, stream_id: u64, member: Address) -> i128 {
+    let key = DataKeyExt::PoolMemberCredit(stream_id, member);
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount > 0 {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    amount
+}
+
+/// Store a pool member's credit; a zero amount removes the entry.
+pub fn save_pool_member_credit(env: &Env, stream_id: u64, member: Address, amount: i128) {
+    let key = DataKeyExt::PoolMemberCredit(stream_id, member);
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &amount);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Address holding a pool member's withdrawal right: its recorded claim
+/// owner, or the member itself when ownership was never transferred.
+pub fn read_pool_claim_owner(env: &Env, stream_id: u64, member: &Address) -> Address {
+    let key = DataKeyExt::PoolClaimOwner(stream_id, member.clone());
+    match env.storage().persistent().get(&key) {
+        Some(owner) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            owner
+        }
+        None => member.clone(),
+    }
+}
+
+/// Record a pool member's claim owner; `None` returns the right to the member.
+pub fn set_pool_claim_owner(env: &Env, stream_id: u64, member: &Address, owner: Option<&Address>) {
+    let key = DataKeyExt::PoolClaimOwner(stream_id, member.clone());
+    match owner {
+        Some(owner) => {
+            env.storage().persistent().set(&key, owner);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Auto-claim destination chosen for a pool member, if any.
+pub fn read_pool_auto_claim(env: &Env, stream_id: u64, member: &Address) -> Option<Address> {
+    let key = DataKeyExt::PoolAutoClaim(stream_id, member.clone());
+    let destination = env.storage().persistent().get(&key);
+    if destination.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+    destination
+}
+
+pub fn set_pool_auto_claim(
+    env: &Env,
+    stream_id: u64,
+    member: &Address,
+    destination: Option<&Address>,
+) {
+    let key = DataKeyExt::PoolAutoClaim(stream_id, member.clone());
+    match destination {
+        Some(destination) => {
+            env.storage().persistent().set(&key, destination);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
 /// Check a vector of stream IDs for duplicates and return `ContractError::DuplicateStreamId`
 /// if any ID appears more than once.
 ///
@@ -1221,3 +1989,142 @@ pub fn remove_offer_from_recipient_pending(env: &Env, recipient: &Address, offer
         save_recipient_pending_offers(env, recipient, &offers);
     }
 }
+
+// ---------------------------------------------------------------------------
+// Piecewise rate-schedule storage helpers
+// ---------------------------------------------------------------------------
+
+/// Persist a validated segment schedule for a `Piecewise` stream.
+///
+/// Each segment is stored as one `u128` word via
+/// [`accrual::pack_rate_segment`], halving the footprint of the unpacked
+/// `(i128, u64)` pair. Returns `InvalidParams` if a segment does not fit the
+/// packed layout (duration above `MAX_PACKABLE_DURATION_SECS` or rate above
+/// `MAX_PACKABLE_RATE_MAGNITUDE`); nothing is written in that case.
+pub fn save_rate_schedule(
+    env: &Env,
+    stream_id: u64,
+    segments: &soroban_sdk::Vec<accrual::RateSegment>,
+) -> Result<(), ContractError> {
+    let mut packed: soroban_sdk::Vec<u128> = soroban_sdk::Vec::new(env);
+    for segment in segments.iter() {
+        let duration_secs =
+            u32::try_from(segment.duration_secs).map_err(|_| ContractError::InvalidParams)?;
+        packed.push_back(accrual::pack_rate_segment(segment.rate, duration_secs)?);
+    }
+
+    let key = DataKey::RateSchedule(stream_id);
+    env.storage().persistent().set(&key, &packed);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+    Ok(())
+}
+
+/// Load and unpack the segment schedule of a `Piecewise` stream.
+///
+/// Returns an empty vector when no schedule is stored (non-Piecewise streams,
+/// or a closed stream whose schedule was removed).
+pub fn load_rate_schedule(env: &Env, stream_id: u64) -> soroban_sdk::Vec<accrual::RateSegment> {
+    let key = DataKey::RateSchedule(stream_id);
+    let mut segments = soroban_sdk::Vec::new(env);
+    let packed: Option<soroban_sdk::Vec<u128>> = env.storage().persistent().get(&key);
+    if let Some(packed) = packed {
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        for word in packed.iter() {
+            let (rate, duration_secs) = accrual::unpack_rate_segment(word);
+            segments.push_back(accrual::RateSegment {
+                rate,
+                duration_secs: duration_secs as u64,
+            });
+        }
+    }
+    segments
+}
+
+pub fn remove_rate_schedule(env: &Env, stream_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RateSchedule(stream_id));
+}
+
+// ---------------------------------------------------------------------------
+// Tranche schedule storage helpers
+// ---------------------------------------------------------------------------
+
+/// Persist a validated unlock schedule for a `Tranched` stream.
+pub fn save_tranche_schedule(
+    env: &Env,
+    stream_id: u64,
+    tranches: &soroban_sdk::Vec<accrual::Tranche>,
+) {
+    let key = DataKeyExt::TrancheSchedule(stream_id);
+    env.storage().persistent().set(&key, tranches);
+    env.storage().persistent().extend_ttl(
+        &key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+/// Load the unlock schedule of a `Tranched` stream.
+///
+/// Returns an empty vector when no schedule is stored (other stream kinds, or
+/// a closed stream whose schedule was removed).
+pub fn load_tranche_schedule(env: &Env, stream_id: u64) -> soroban_sdk::Vec<accrual::Tranche> {
+    let key = DataKeyExt::TrancheSchedule(stream_id);
+    match env.storage().persistent().get(&key) {
+        Some(tranches) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            tranches
+        }
+        None => soroban_sdk::Vec::new(env),
+    }
+}
+
+pub fn remove_tranche_schedule(env: &Env, stream_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKeyExt::TrancheSchedule(stream_id));
+}
+
+/// Half-life of an `ExponentialDecay` stream; `0` when none is stored.
+pub fn get_decay_half_life(env: &Env, stream_id: u64) -> u64 {
+    let key = DataKeyExt::DecayHalfLife(stream_id);
+    match env.storage().persistent().get(&key) {
+        Some(half_life) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            half_life
+        }
+        None => 0,
+    }
+}
+
+pub fn set_decay_half_life(env: &Env, stream_id: u64, half_life_secs: Option<u64>) {
+    let key = DataKeyExt::DecayHalfLife(stream_id);
+    match half_life_secs {
+        Some(half_life_secs) => {
+            env.storage().persistent().set(&key, &half_life_secs);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
@@ -8,6 +8,12 @@
 //! 3. **Withdrawal bound**: `withdrawn_amount <= deposit_amount`
 //! 4. **Withdrawable bound**: `get_withdrawable() <= deposit_amount`
 //!
+//! For every curve kind (`Quadratic`, `ExponentialDecay`) it additionally
+//! proves that accrual is bounded by the deposit, reaches it exactly at
+//! `end_time`, and is monotonically non-decreasing in time, both for the pure
+//! `accrual::calculate_curve_accrued_amount` over the full input range and for
+//! live streams.
+//!
 //! Run with: `cargo test -p fluxora_stream`
 //! Deeper coverage: `PROPTEST_CASES=10000 cargo test -p fluxora_stream`
 
@@ -20,6 +26,7 @@ use soroban_sdk::{
     Address, Env,
 };
 
+use crate::accrual::{calculate_curve_accrued_amount, MAX_DECAY_HALF_LIFE_SECS};
 use crate::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind, StreamStatus};
 
 // ---------------------------------------------------------------------------
@@ -471,6 +478,111 @@ proptest! {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Curve accrual properties
+// ---------------------------------------------------------------------------
+
+/// Every curve kind.
+fn curve_kind() -> impl Strategy<Value = StreamKind> {
+    prop_oneof![
+        Just(StreamKind::Quadratic),
+        Just(StreamKind::ExponentialDecay)
+    ]
+}
+
+/// Creates a curve stream from `0` to `duration` with its cliff at `cliff`.
+fn create_curve_stream(
+    ctx: &PropCtx,
+    kind: StreamKind,
+    deposit: i128,
+    cliff: u64,
+    duration: u64,
+    half_life: u64,
+) -> u64 {
+    let params = CreateStreamParams {
+        recipient: ctx.recipient.clone(),
+        deposit_amount: deposit,
+        rate_per_second: 0,
+        start_time: 0u64,
+        cliff_time: cliff,
+        end_time: duration,
+        withdraw_dust_threshold: Some(0),
+        memo: None,
+        metadata: None,
+        kind,
+        irrevocable: None,
+        witness: None,
+    };
+    if kind == StreamKind::ExponentialDecay {
+        ctx.client()
+            .create_stream_with_decay(&ctx.sender, &params, &half_life)
+    } else {
+        ctx.client().create_stream(&ctx.sender, &params)
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    /// Pure curve math over the full input range: `0 <= accrued <= deposit`,
+    /// non-decreasing in `now`, and exactly `deposit` from `end_time` on.
+    #[test]
+    fn prop_curve_accrual_bounded_and_monotonic(
+        kind in curve_kind(),
+        deposit in 1_i128..=i128::MAX,
+        start in 0_u64..=u64::MAX / 2,
+        duration in 1_u64..=u64::MAX / 2,
+        cliff_frac in 0_u64..=100,
+        half_life in 1_u64..=MAX_DECAY_HALF_LIFE_SECS,
+        mut times in proptest::collection::vec(any::<u64>(), 1..=16),
+    ) {
+        let end = start + duration;
+        let cliff = start + (duration as u128 * cliff_frac as u128 / 100) as u64;
+        times.sort();
+
+        let accrued = |now| {
+            calculate_curve_accrued_amount(kind, start, cliff, end, deposit, half_life, now)
+        };
+        let mut prev = 0_i128;
+        for now in times {
+            let value = accrued(now);
+            prop_assert!(value >= 0 && value <= deposit, "{kind:?} t={now}: {value}");
+            prop_assert!(value >= prev, "{kind:?} t={now}: {value} < {prev}");
+            prev = value;
+        }
+        prop_assert_eq!(accrued(end), deposit);
+        prop_assert_eq!(accrued(u64::MAX), deposit);
+    }
+
+    /// Live curve streams keep the withdrawable invariants and never report a
+    /// smaller accrual at a later timestamp, with withdrawals interleaved.
+    #[test]
+    fn prop_curve_stream_invariants(
+        kind in curve_kind(),
+        deposit in 1_i128..=1_000_000_i128,
+        duration in 1_u64..=1_000_u64,
+        cliff_frac in 0_u64..=100,
+        half_life in 1_u64..=2_000_u64,
+        times in time_sequence(1_000),
+    ) {
+        let ctx = PropCtx::new(deposit);
+        ctx.env.ledger().set_timestamp(0);
+        let cliff = duration * cliff_frac / 100;
+        let id = create_curve_stream(&ctx, kind, deposit, cliff, duration, half_life);
+
+        let mut prev = 0_i128;
+        for (i, t) in times.iter().enumerate() {
+            ctx.env.ledger().set_timestamp(*t);
+            ctx.env.ledger().set_sequence_number(100 + 10_000 * i as u32);
+            let accrued = ctx.client().calculate_accrued(&id);
+            prop_assert!(accrued >= prev, "{kind:?} t={t}: {accrued} < {prev}");
+            prev = accrued;
+            let _ = ctx.client().try_withdraw(&id, &None);
+            assert_invariants(&ctx, id, &std::format!("{kind:?} t={t}"));
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Deterministic regression tests — one per status transition path
 // ---------------------------------------------------------------------------
@@ -0,0 +1,246 @@
+//! Integration tests for the curve kinds `Quadratic` and `ExponentialDecay`.
+//!
+//! # What is tested
+//!
+//! 1. `Quadratic` streams, created through `create_stream` with a zero rate,
+//!    vest back-loaded through `calculate_accrued` and `get_claimable_at`.
+//! 2. `ExponentialDecay` streams, created through `create_stream_with_decay`,
+//!    vest front-loaded according to their half-life.
+//! 3. `withdraw` and `cancel_stream` settle against the curve, and the
+//!    half-life is removed when the stream is closed.
+//! 4. Creation rejects invalid kinds, rates and half-lives, and mutators
+//!    reject curve streams.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test curve_streams
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::accrual::MAX_DECAY_HALF_LIFE_SECS;
+use fluxora_stream::{
+    ContractError, CreateStreamParams, DataKeyExt, FluxoraStream, FluxoraStreamClient, StreamKind,
+    StreamStatus,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn params(&self, kind: StreamKind, deposit: i128, cliff: u64, end: u64) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: deposit,
+            rate_per_second: 0,
+            start_time: 0,
+            cliff_time: cliff,
+            end_time: end,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    /// 1_200 tokens over 400s with a 200s half-life: two half-lives, so the
+    /// first one pays roughly two thirds of the deposit.
+    fn create_decay(&self) -> u64 {
+        self.client().create_stream_with_decay(
+            &self.sender,
+            &self.params(StreamKind::ExponentialDecay, 1_200, 0, 400),
+            &200,
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+}
+
+#[test]
+fn quadratic_vests_back_loaded() {
+    let ctx = Ctx::setup();
+    let id = ctx.client().create_stream(
+        &ctx.sender,
+        &ctx.params(StreamKind::Quadratic, 1_000, 0, 1_000),
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&id).kind,
+        StreamKind::Quadratic
+    );
+    assert_eq!(ctx.client().get_decay_half_life(&id), 0);
+
+    let mut previous = 0;
+    for (t, expected) in [(0u64, 0i128), (100, 10), (500, 250), (900, 810)] {
+        ctx.advance(t);
+        let accrued = ctx.client().calculate_accrued(&id);
+        assert!((expected - 1..=expected).contains(&accrued), "t={t}");
+        assert!(accrued >= previous, "t={t}");
+        previous = accrued;
+    }
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().calculate_accrued(&id), 1_000);
+    assert_eq!(ctx.client().get_claimable_at(&id, &2_000), 1_000);
+}
+
+#[test]
+fn decay_vests_front_loaded() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_decay();
+    assert_eq!(ctx.client().get_decay_half_life(&id), 200);
+    assert_eq!(ctx.client().get_stream_state(&id).rate_per_second, 0);
+
+    // Rate halves at t=200: 1/3 of the deposit by t=100, 2/3 by t=200.
+    for (t, expected) in [(100u64, 400i128), (200, 800), (300, 1_000)] {
+        let accrued = ctx.client().get_claimable_at(&id, &t);
+        assert!((expected - 1..=expected).contains(&accrued), "t={t}");
+    }
+    assert_eq!(ctx.client().get_claimable_at(&id, &400), 1_200);
+}
+
+#[test]
+fn withdraw_cancel_and_close_settle_against_curve() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_decay();
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.advance(200);
+    let first = ctx.client().withdraw(&id, &None);
+    assert!((799..=800).contains(&first));
+
+    ctx.advance(300);
+    ctx.client().cancel_stream(&id);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Cancelled
+    );
+    let vested = ctx.client().calculate_accrued(&id);
+    assert_eq!(
+        ctx.token().balance(&ctx.sender),
+        sender_before + 1_200 - vested
+    );
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_total_liabilities()
+    );
+
+    ctx.advance(400);
+    assert_eq!(ctx.client().withdraw(&id, &None), vested - first);
+    ctx.client().close_cancelled_stream(&id);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+    assert_eq!(
+        ctx.client().try_get_decay_half_life(&id),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+    let cid = ctx.contract_id.clone();
+    ctx.env.as_contract(&cid, || {
+        assert!(!ctx
+            .env
+            .storage()
+            .persistent()
+            .has(&DataKeyExt::DecayHalfLife(id)));
+    });
+}
+
+#[test]
+fn creation_validates_curve_params() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let decay = ctx.params(StreamKind::ExponentialDecay, 1_000, 0, 1_000);
+
+    for half_life in [0, MAX_DECAY_HALF_LIFE_SECS + 1] {
+        assert_eq!(
+            client.try_create_stream_with_decay(&ctx.sender, &decay, &half_life),
+            Err(Ok(ContractError::InvalidParams))
+        );
+    }
+    let mut with_rate = decay.clone();
+    with_rate.rate_per_second = 1;
+    assert_eq!(
+        client.try_create_stream_with_decay(&ctx.sender, &with_rate, &100),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let quadratic = ctx.params(StreamKind::Quadratic, 1_000, 0, 1_000);
+    assert_eq!(
+        client.try_create_stream_with_decay(&ctx.sender, &quadratic, &100),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    // create_stream cannot create a decay stream without a half-life, and
+    // quadratic streams carry no rate.
+    assert_eq!(
+        client.try_create_stream(&ctx.sender, &decay),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+    let mut quadratic_with_rate = quadratic.clone();
+    quadratic_with_rate.rate_per_second = 1;
+    assert_eq!(
+        client.try_create_stream(&ctx.sender, &quadratic_with_rate),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(client.get_total_liabilities(), 0);
+
+    let id = client.create_stream(&ctx.sender, &quadratic);
+    assert_eq!(
+        client.try_update_rate_per_second(&id, &5),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+    assert_eq!(
+        client.try_top_up_stream(&id, &ctx.sender, &100),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+}
@@ -0,0 +1,207 @@
+//! Regression tests for liability accounting in `delegated_withdraw`.
+//!
+//! # What is tested
+//!
+//! 1. A relayed withdrawal releases its gross payout (recipient amount plus
+//!    relayer fee) from `TotalLiabilities`, so the liabilities keep matching
+//!    the tokens the contract still holds for its streams.
+//! 2. Draining a stream through relayed withdrawals leaves no liabilities, and
+//!    `sweep_excess` then finds nothing left to sweep.
+//!
+//! The recipient is an ed25519 account, which the Stellar Asset Contract
+//! cannot pay without a ledger account entry, so the stream runs on a minimal
+//! balance-map token.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test delegated_withdraw_liabilities
+//! ```
+
+#![cfg(test)]
+
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+use fluxora_stream::{CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as _, Ledger},
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, TryIntoVal,
+};
+
+#[contracttype]
+enum TokenKey {
+    Balance(Address),
+}
+
+/// Balance-map token with just enough of SEP-41 for stream funding and payout.
+#[contract]
+pub struct LedgerToken;
+
+#[contractimpl]
+impl LedgerToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TokenKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Self::mint(env.clone(), from, -amount);
+        Self::mint(env, to, amount);
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount);
+    }
+
+    pub fn approve(_env: Env, _from: Address, _spender: Address, _amount: i128, _expiry: u32) {}
+
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        i128::MAX
+    }
+}
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    signing_key: SigningKey,
+    recipient: Address,
+    relayer: Address,
+    stream_id: u64,
+}
+
+impl Ctx {
+    /// 1 token/s from 0 to 1_000, paid to an ed25519 account recipient.
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_id = env.register_contract(None, LedgerToken);
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let signing_key = SigningKey::from_bytes(&[0x24u8; 32]);
+        let pk = signing_key.verifying_key().to_bytes();
+        let recipient: Address =
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk))))
+                .try_into_val(&env)
+                .unwrap();
+
+        let client = FluxoraStreamClient::new(&env, &contract_id);
+        client.init(&token_id, &admin);
+        LedgerTokenClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        let stream_id = client.create_stream(
+            &sender,
+            &CreateStreamParams {
+                recipient: recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        );
+
+        Ctx {
+            relayer: Address::generate(&env),
+            env,
+            contract_id,
+            token_id,
+            signing_key,
+            recipient,
+            stream_id,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> LedgerTokenClient<'_> {
+        LedgerTokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 100);
+    }
+
+    /// Sign and submit a relayed withdrawal paying `relayer_fee`.
+    fn relay(&self, relayer_fee: i128) -> i128 {
+        let nonce = self.client().get_delegated_nonce(&self.recipient);
+        let deadline = 10_000u64;
+        let mut msg = Bytes::new(&self.env);
+        msg.extend_from_array(&self.stream_id.to_be_bytes());
+        msg.extend_from_array(&nonce.to_be_bytes());
+        msg.extend_from_array(&deadline.to_be_bytes());
+        msg.extend_from_array(&0i128.to_be_bytes());
+        msg.extend_from_array(&relayer_fee.to_be_bytes());
+        let bytes: std::vec::Vec<u8> = (0..msg.len()).map(|i| msg.get_unchecked(i)).collect();
+        let signature = BytesN::from_array(&self.env, &self.signing_key.sign(&bytes).to_bytes());
+
+        self.client().delegated_withdraw(
+            &self.stream_id,
+            &self.relayer,
+            &BytesN::from_array(&self.env, &self.signing_key.verifying_key().to_bytes()),
+            &nonce,
+            &deadline,
+            &0,
+            &relayer_fee,
+            &signature,
+        )
+    }
+}
+
+#[test]
+fn relayed_withdraw_releases_gross_payout() {
+    let ctx = Ctx::setup();
+    assert_eq!(ctx.client().get_total_liabilities(), 1_000);
+
+    ctx.advance(250);
+    assert_eq!(ctx.relay(10), 240);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 240);
+    assert_eq!(ctx.token().balance(&ctx.relayer), 10);
+    assert_eq!(ctx.client().get_total_liabilities(), 750);
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_total_liabilities()
+    );
+}
+
+#[test]
+fn drained_stream_leaves_no_liabilities() {
+    let ctx = Ctx::setup();
+
+    ctx.advance(400);
+    ctx.relay(0);
+    ctx.advance(1_000);
+    ctx.relay(5);
+
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().sweep_excess(&ctx.relayer), 0);
+}
@@ -0,0 +1,295 @@
+//! Integration tests for `execute_batch` and `execute_batch_partial`.
+//!
+//! # What is tested
+//!
+//! 1. A mixed batch (top up, extend, pause, cancel) applies in order under a
+//!    single authorization of the caller.
+//! 2. In atomic mode the first failing op reverts every earlier op.
+//! 3. Ops on another sender's stream are rejected with `Unauthorized`.
+//! 4. In partial mode failing ops are reported and skipped.
+//! 5. A skipped op above the rate cap changes nothing but keeps its
+//!    `rate_cap` rejection event.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test execute_batch
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, PauseReason, StreamKind,
+    StreamOp, StreamOpResult, StreamStatus,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn sender_balance(&self) -> i128 {
+        TokenClient::new(&self.env, &self.token_id).balance(&self.sender)
+    }
+
+    /// 1 token/s from 0 to 1_000, created by `sender`.
+    fn create_from(&self, sender: &Address) -> u64 {
+        self.client().create_stream(
+            sender,
+            &CreateStreamParams {
+                recipient: self.recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn create(&self) -> u64 {
+        self.create_from(&self.sender)
+    }
+
+    /// Move time forward and far enough in ledgers to clear pause and rate cooldowns.
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+
+    fn status(&self, stream_id: u64) -> StreamStatus {
+        self.client().get_stream_state(&stream_id).status
+    }
+}
+
+fn ok() -> StreamOpResult {
+    StreamOpResult {
+        success: true,
+        error: None,
+    }
+}
+
+fn failed(error: ContractError) -> StreamOpResult {
+    StreamOpResult {
+        success: false,
+        error: Some(error as u32),
+    }
+}
+
+#[test]
+fn batch_applies_ops_in_order_with_one_auth() {
+    let ctx = Ctx::setup();
+    let a = ctx.create();
+    let b = ctx.create();
+    let c = ctx.create();
+    ctx.advance(100);
+
+    let before = ctx.sender_balance();
+    ctx.client().execute_batch(
+        &ctx.sender,
+        &vec![
+            &ctx.env,
+            StreamOp::TopUp(a, 500),
+            StreamOp::ExtendEndTime(a, 1_500),
+            StreamOp::Pause(b, PauseReason::Operational),
+            StreamOp::Cancel(c),
+        ],
+    );
+
+    let auths = ctx.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, ctx.sender);
+
+    let stream_a = ctx.client().get_stream_state(&a);
+    assert_eq!(stream_a.deposit_amount, 1_500);
+    assert_eq!(stream_a.end_time, 1_500);
+    assert_eq!(ctx.status(b), StreamStatus::Paused);
+    assert_eq!(ctx.status(c), StreamStatus::Cancelled);
+    // 500 topped up, 900 refunded from the cancelled stream.
+    assert_eq!(ctx.sender_balance() - before, 400);
+}
+
+#[test]
+fn atomic_batch_reverts_on_first_failure() {
+    let ctx = Ctx::setup();
+    let a = ctx.create();
+    let b = ctx.create();
+    ctx.advance(100);
+
+    let before = ctx.sender_balance();
+    assert_eq!(
+        ctx.client().try_execute_batch(
+            &ctx.sender,
+            &vec![
+                &ctx.env,
+                StreamOp::TopUp(a, 500),
+                StreamOp::Pause(b, PauseReason::Operational),
+                StreamOp::ShortenEndTime(b, 50),
+            ],
+        ),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    assert_eq!(ctx.sender_balance(), before);
+    assert_eq!(ctx.client().get_stream_state(&a).deposit_amount, 1_000);
+    assert_eq!(ctx.status(b), StreamStatus::Active);
+}
+
+#[test]
+fn batch_rejects_other_senders_streams() {
+    let ctx = Ctx::setup();
+    let other = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&other, &1_000);
+    TokenClient::new(&ctx.env, &ctx.token_id).approve(
+        &other,
+        &ctx.contract_id,
+        &1_000,
+        &200_000u32,
+    );
+    let foreign = ctx.create_from(&other);
+    ctx.advance(100);
+
+    assert_eq!(
+        ctx.client()
+            .try_execute_batch(&ctx.sender, &vec![&ctx.env, StreamOp::Cancel(foreign)]),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        ctx.client().execute_batch_partial(
+            &ctx.sender,
+            &vec![&ctx.env, StreamOp::Pause(foreign, PauseReason::Operational)],
+        ),
+        vec![&ctx.env, failed(ContractError::Unauthorized)]
+    );
+    assert_eq!(ctx.status(foreign), StreamStatus::Active);
+}
+
+#[test]
+fn partial_batch_skips_failing_ops() {
+    let ctx = Ctx::setup();
+    let a = ctx.create();
+    let b = ctx.create();
+    ctx.advance(100);
+
+    let results = ctx.client().execute_batch_partial(
+        &ctx.sender,
+        &vec![
+            &ctx.env,
+            StreamOp::Pause(a, PauseReason::Operational),
+            StreamOp::ExtendEndTime(a, 10),
+            StreamOp::Resume(b),
+            StreamOp::Cancel(99),
+            StreamOp::DecreaseRate(b, 0),
+            StreamOp::Cancel(b),
+        ],
+    );
+    assert_eq!(
+        results,
+        vec![
+            &ctx.env,
+            ok(),
+            failed(ContractError::InvalidParams),
+            failed(ContractError::StreamNotPaused),
+            failed(ContractError::StreamNotFound),
+            failed(ContractError::InvalidParams),
+            ok(),
+        ]
+    );
+    assert_eq!(ctx.status(a), StreamStatus::Paused);
+    assert_eq!(ctx.client().get_stream_state(&a).end_time, 1_000);
+    assert_eq!(ctx.status(b), StreamStatus::Cancelled);
+
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("cancelled"));
+}
+
+#[test]
+fn partial_batch_keeps_rate_cap_event() {
+    let ctx = Ctx::setup();
+    let a = ctx.create();
+    ctx.client().set_max_rate_per_second(&2);
+    ctx.advance(100);
+
+    let results = ctx
+        .client()
+        .execute_batch_partial(&ctx.sender, &vec![&ctx.env, StreamOp::UpdateRate(a, 3)]);
+    assert_eq!(
+        results,
+        vec![&ctx.env, failed(ContractError::RateCapExceeded)]
+    );
+    assert_eq!(ctx.client().get_stream_state(&a).rate_per_second, 1);
+
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("rate_cap"));
+}
+
+#[test]
+fn batch_size_is_capped() {
+    let ctx = Ctx::setup();
+    let a = ctx.create();
+    let mut ops = Vec::new(&ctx.env);
+    for _ in 0..101 {
+        ops.push_back(StreamOp::Resume(a));
+    }
+    assert_eq!(
+        ctx.client().try_execute_batch(&ctx.sender, &ops),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_execute_batch_partial(&ctx.sender, &ops),
+        Err(Ok(ContractError::InvalidParams))
+    );
+}
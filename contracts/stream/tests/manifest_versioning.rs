@@ -638,12 +638,12 @@ fn edge_case_version_endpoint_permissionless_and_stable() {
     let version2 = ctx.client.version();
 
     assert_eq!(version1, version2);
-    assert_eq!(version1, 9); // Current CONTRACT_VERSION = 9
+    assert_eq!(version1, 10); // Current CONTRACT_VERSION = 10
 
     // Version should not be affected by stream creation or operations
     let _stream_id = ctx.create_test_stream(1000, 1, 1000);
     let version3 = ctx.client.version();
-    assert_eq!(version3, 9);
+    assert_eq!(version3, 10);
 }
 
 // ============================================================================
@@ -0,0 +1,235 @@
+//! Integration tests for `merge_streams`.
+//!
+//! # What is tested
+//!
+//! 1. Merging settles each stream's accrual into the surviving stream: the
+//!    claimable balance and future accrual equal the sum of the inputs and
+//!    `TotalLiabilities` is unchanged.
+//! 2. Merged streams are closed and dropped from the recipient and sender
+//!    indexes; `merged` lists the closed IDs.
+//! 3. Pre-cliff accrual carries over when the streams share a cliff.
+//! 4. Incompatible inputs are rejected: different recipient, different
+//!    end time, duplicates, non-active streams and non-`Linear` kinds.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test merge_streams
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    load_sender_streams, ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient,
+    PauseReason, StreamKind, StreamsMerged,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn create(&self, rate: i128, cliff_time: u64, end_time: u64) -> u64 {
+        self.create_for(
+            &self.recipient,
+            rate,
+            cliff_time,
+            end_time,
+            StreamKind::Linear,
+        )
+    }
+
+    fn create_for(
+        &self,
+        recipient: &Address,
+        rate: i128,
+        cliff_time: u64,
+        end_time: u64,
+        kind: StreamKind,
+    ) -> u64 {
+        self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: recipient.clone(),
+                deposit_amount: rate * end_time as i128,
+                rate_per_second: rate,
+                start_time: 0,
+                cliff_time,
+                end_time,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+#[test]
+fn merge_settles_accrual_into_survivor() {
+    let ctx = Ctx::setup();
+    let a = ctx.create(2, 0, 1_000);
+    let b = ctx.create(3, 0, 1_000);
+    let c = ctx.create(5, 0, 1_000);
+
+    ctx.advance(200);
+    ctx.client().withdraw(&b, &None); // 600 withdrawn from b
+    ctx.advance(400);
+
+    let claimable_before: i128 = [a, b, c]
+        .iter()
+        .map(|id| ctx.client().get_withdrawable(id))
+        .sum();
+    let liabilities = ctx.client().get_total_liabilities();
+
+    assert_eq!(ctx.client().merge_streams(&vec![&ctx.env, a, b, c]), a);
+
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("merged"));
+    let payload: StreamsMerged = last.2.into_val(&ctx.env);
+    assert_eq!(payload.merged_stream_ids, vec![&ctx.env, b, c]);
+
+    let merged = ctx.client().get_stream_state(&a);
+    assert_eq!(merged.deposit_amount, 10_000);
+    assert_eq!(merged.rate_per_second, 10);
+    assert_eq!(merged.withdrawn_amount, 600);
+    assert_eq!(ctx.client().get_withdrawable(&a), claimable_before);
+    assert_eq!(ctx.client().get_total_liabilities(), liabilities);
+
+    // Future accrual is the sum of the merged rates.
+    ctx.advance(700);
+    assert_eq!(ctx.client().get_withdrawable(&a), claimable_before + 3_000);
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().withdraw(&a, &None), 9_400);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.token_id).balance(&ctx.recipient),
+        10_000
+    );
+}
+
+#[test]
+fn merged_streams_are_closed_and_unindexed() {
+    let ctx = Ctx::setup();
+    let a = ctx.create(1, 0, 1_000);
+    let b = ctx.create(1, 0, 1_000);
+    ctx.client().merge_streams(&vec![&ctx.env, a, b]);
+
+    assert_eq!(
+        ctx.client().try_get_stream_state(&b),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+    assert_eq!(
+        ctx.client().get_recipient_streams(&ctx.recipient),
+        vec![&ctx.env, a]
+    );
+    let sender_streams = ctx.env.as_contract(&ctx.contract_id, || {
+        load_sender_streams(&ctx.env, &ctx.sender)
+    });
+    assert_eq!(sender_streams, vec![&ctx.env, a]);
+}
+
+#[test]
+fn pre_cliff_accrual_carries_over() {
+    let ctx = Ctx::setup();
+    let a = ctx.create(1, 500, 1_000);
+    let b = ctx.create(4, 500, 1_000);
+
+    ctx.advance(100);
+    ctx.client().merge_streams(&vec![&ctx.env, a, b]);
+    assert_eq!(ctx.client().get_withdrawable(&a), 0);
+
+    ctx.advance(500);
+    assert_eq!(ctx.client().get_withdrawable(&a), 2_500);
+}
+
+#[test]
+fn rejects_incompatible_streams() {
+    let ctx = Ctx::setup();
+    let a = ctx.create(1, 0, 1_000);
+    let other = Address::generate(&ctx.env);
+    let other_recipient = ctx.create_for(&other, 1, 0, 1_000, StreamKind::Linear);
+    let other_end = ctx.create(1, 0, 2_000);
+    let cliff_only = ctx.create_for(&ctx.recipient, 1, 0, 1_000, StreamKind::CliffOnly);
+
+    let client = ctx.client();
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a]),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a, a]),
+        Err(Ok(ContractError::DuplicateStreamId))
+    );
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a, other_recipient]),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a, other_end]),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a, cliff_only]),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+
+    let b = ctx.create(1, 0, 1_000);
+    client.pause_stream(&b, &PauseReason::Operational);
+    assert_eq!(
+        client.try_merge_streams(&vec![&ctx.env, a, b]),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
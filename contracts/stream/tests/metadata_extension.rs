@@ -81,7 +81,7 @@ extern crate std;
 // - Two independent streams do not share or leak metadata.
 //
 // ## Contract version + idempotency
-// - CONTRACT_VERSION is pinned to the expected constant (9).
+// - CONTRACT_VERSION is pinned to the expected constant (10).
 // - A second call to init on an already-initialised contract fails with
 //   ContractError::AlreadyInitialized, confirming idempotency.
 
@@ -1614,7 +1614,7 @@ fn test_metadata_all_keys_at_max_byte_length_within_aggregate() {
 #[test]
 fn test_contract_version_is_9() {
     let ctx = Ctx::setup();
-    assert_eq!(ctx.client().version(), 9, "CONTRACT_VERSION must be 9");
+    assert_eq!(ctx.client().version(), 10, "CONTRACT_VERSION must be 10");
 }
 
 /// A second call to `init` on an already-initialised contract must fail with
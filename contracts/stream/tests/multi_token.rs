@@ -0,0 +1,272 @@
+//! Integration tests for per-stream tokens and the admin token allowlist.
+//!
+//! # What is tested
+//!
+//! 1. `add_allowed_token` / `remove_allowed_token` manage the allowlist, emit
+//!    `tok_allow`, and the default token can never be removed.
+//! 2. `create_stream_with_token` rejects non-allowlisted tokens with
+//!    `TokenNotAllowed` and pulls the deposit in the requested token.
+//! 3. `withdraw` and `cancel_stream` pay out in the stream's own token and
+//!    keep per-token liabilities separate from `TotalLiabilities`.
+//! 4. `sweep_excess_for_token` only sweeps balance above that token's
+//!    liabilities.
+//! 5. Streams created through the legacy entrypoints report `Config.token`.
+//! 6. Removing a token from the allowlist blocks new streams but existing
+//!    streams keep settling in it.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test multi_token
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind, StreamStatus,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env, IntoVal, Symbol,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    default_token: Address,
+    other_token: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let default_token = env
+            .register_stellar_asset_contract_v2(token_admin.clone())
+            .address();
+        let other_token = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&default_token, &admin);
+        for token in [&default_token, &other_token] {
+            StellarAssetClient::new(&env, token).mint(&sender, &1_000_000_i128);
+            TokenClient::new(&env, token).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+        }
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            default_token,
+            other_token,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self, token: &Address) -> TokenClient<'_> {
+        TokenClient::new(&self.env, token)
+    }
+
+    fn params(&self) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: 1_000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn create_other(&self) -> u64 {
+        self.client().add_allowed_token(&self.other_token);
+        self.client()
+            .create_stream_with_token(&self.sender, &self.params(), &self.other_token)
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+#[test]
+fn allowlist_add_and_remove() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+
+    assert!(client.is_token_allowed(&ctx.default_token));
+    assert!(!client.is_token_allowed(&ctx.other_token));
+
+    client.add_allowed_token(&ctx.other_token);
+    assert!(client.is_token_allowed(&ctx.other_token));
+
+    let last = ctx.env.events().all().last().unwrap();
+    assert_eq!(last.0, ctx.contract_id);
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("tok_allow"));
+
+    client.remove_allowed_token(&ctx.other_token);
+    assert!(!client.is_token_allowed(&ctx.other_token));
+
+    assert_eq!(
+        client.try_remove_allowed_token(&ctx.default_token),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert!(client.is_token_allowed(&ctx.default_token));
+}
+
+#[test]
+fn create_rejects_tokens_not_on_allowlist() {
+    let ctx = Ctx::setup();
+    assert_eq!(
+        ctx.client()
+            .try_create_stream_with_token(&ctx.sender, &ctx.params(), &ctx.other_token),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+    assert_eq!(ctx.token(&ctx.other_token).balance(&ctx.contract_id), 0);
+
+    // The default token needs no allowlist entry.
+    let id = ctx
+        .client()
+        .create_stream_with_token(&ctx.sender, &ctx.params(), &ctx.default_token);
+    assert_eq!(ctx.client().get_stream_token(&id), ctx.default_token);
+    assert_eq!(ctx.client().get_total_liabilities(), 1_000);
+}
+
+#[test]
+fn deposit_and_withdraw_use_stream_token() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_other();
+
+    assert_eq!(ctx.client().get_stream_token(&id), ctx.other_token);
+    assert_eq!(ctx.token(&ctx.other_token).balance(&ctx.contract_id), 1_000);
+    assert_eq!(ctx.token(&ctx.default_token).balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().get_token_liabilities(&ctx.other_token), 1_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+
+    ctx.advance(400);
+    assert_eq!(ctx.client().get_withdrawable(&id), 400);
+    assert_eq!(ctx.client().withdraw(&id, &None), 400);
+
+    assert_eq!(ctx.token(&ctx.other_token).balance(&ctx.recipient), 400);
+    assert_eq!(ctx.token(&ctx.default_token).balance(&ctx.recipient), 0);
+    assert_eq!(ctx.client().get_token_liabilities(&ctx.other_token), 600);
+}
+
+#[test]
+fn cancel_refunds_in_stream_token() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_other();
+    let other_before = ctx.token(&ctx.other_token).balance(&ctx.sender);
+    let default_before = ctx.token(&ctx.default_token).balance(&ctx.sender);
+
+    ctx.advance(250);
+    ctx.client().cancel_stream(&id);
+
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Cancelled
+    );
+    assert_eq!(
+        ctx.token(&ctx.other_token).balance(&ctx.sender),
+        other_before + 750
+    );
+    assert_eq!(
+        ctx.token(&ctx.default_token).balance(&ctx.sender),
+        default_before
+    );
+    assert_eq!(ctx.client().get_token_liabilities(&ctx.other_token), 250);
+    assert_eq!(
+        ctx.token(&ctx.other_token).balance(&ctx.contract_id),
+        ctx.client().get_token_liabilities(&ctx.other_token)
+    );
+}
+
+#[test]
+fn sweep_is_per_token() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    ctx.create_other();
+    client.create_stream(&ctx.sender, &ctx.params());
+
+    // Stray transfers of both tokens become excess of their own token only.
+    ctx.token(&ctx.other_token)
+        .transfer(&ctx.sender, &ctx.contract_id, &70);
+    ctx.token(&ctx.default_token)
+        .transfer(&ctx.sender, &ctx.contract_id, &30);
+
+    let treasury = Address::generate(&ctx.env);
+    assert_eq!(
+        client.sweep_excess_for_token(&ctx.other_token, &treasury),
+        70
+    );
+    assert_eq!(ctx.token(&ctx.other_token).balance(&treasury), 70);
+    assert_eq!(ctx.token(&ctx.other_token).balance(&ctx.contract_id), 1_000);
+
+    assert_eq!(client.sweep_excess(&treasury), 30);
+    assert_eq!(ctx.token(&ctx.default_token).balance(&treasury), 30);
+    assert_eq!(
+        ctx.token(&ctx.default_token).balance(&ctx.contract_id),
+        1_000
+    );
+}
+
+#[test]
+fn removed_token_keeps_settling_existing_streams() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_other();
+    ctx.client().remove_allowed_token(&ctx.other_token);
+
+    assert_eq!(
+        ctx.client()
+            .try_create_stream_with_token(&ctx.sender, &ctx.params(), &ctx.other_token),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().withdraw(&id, &None), 1_000);
+    assert_eq!(ctx.token(&ctx.other_token).balance(&ctx.recipient), 1_000);
+    assert_eq!(ctx.client().get_token_liabilities(&ctx.other_token), 0);
+}
+
+#[test]
+fn legacy_streams_report_default_token() {
+    let ctx = Ctx::setup();
+    let id = ctx.client().create_stream(&ctx.sender, &ctx.params());
+    assert_eq!(ctx.client().get_stream_token(&id), ctx.default_token);
+    assert_eq!(
+        ctx.client().get_token_liabilities(&ctx.default_token),
+        ctx.client().get_total_liabilities()
+    );
+    assert_eq!(
+        ctx.client().try_get_stream_token(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
@@ -0,0 +1,320 @@
+//! Integration tests for `StreamKind::Piecewise` and `create_stream_with_schedule`.
+//!
+//! # What is tested
+//!
+//! 1. A three-segment payroll ramp (probation, full rate, bonus tail) accrues
+//!    segment by segment through `calculate_accrued`, `get_withdrawable` and
+//!    `get_claimable_at`.
+//! 2. The cliff hides accrual until reached, then exposes everything accrued
+//!    since `start_time`.
+//! 3. `withdraw` and `cancel_stream` settle against the schedule and keep
+//!    `TotalLiabilities` equal to the tokens held for the stream.
+//! 4. The schedule is persisted (and readable via `get_rate_schedule`) and is
+//!    removed when the stream is closed.
+//! 5. Creation rejects mismatched end times, under-funded deposits, invalid
+//!    segments and non-Piecewise kinds; `create_stream` rejects `Piecewise`.
+//! 6. Rate and top-up mutators reject Piecewise streams.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test piecewise_schedule
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::accrual::RateSegment;
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind, StreamStatus,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn params(&self, deposit: i128, start: u64, cliff: u64, end: u64) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: deposit,
+            rate_per_second: 0,
+            start_time: start,
+            cliff_time: cliff,
+            end_time: end,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Piecewise,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    /// Probation at 1/s for 100s, full rate at 3/s for 100s, bonus tail at
+    /// 2/s for 50s: 100 + 300 + 100 = 500 tokens over 250 seconds.
+    fn ramp(&self) -> Vec<RateSegment> {
+        vec![
+            &self.env,
+            RateSegment {
+                rate: 1,
+                duration_secs: 100,
+            },
+            RateSegment {
+                rate: 3,
+                duration_secs: 100,
+            },
+            RateSegment {
+                rate: 2,
+                duration_secs: 50,
+            },
+        ]
+    }
+
+    fn create_ramp(&self, cliff: u64) -> u64 {
+        self.client().create_stream_with_schedule(
+            &self.sender,
+            &self.params(500, 0, cliff, 250),
+            &self.ramp(),
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+#[test]
+fn ramp_accrues_segment_by_segment() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(0);
+
+    let stream = ctx.client().get_stream_state(&id);
+    assert_eq!(stream.kind, StreamKind::Piecewise);
+    assert_eq!(stream.rate_per_second, 0);
+
+    for (t, expected) in [
+        (0u64, 0i128),
+        (50, 50),
+        (100, 100),
+        (150, 250),
+        (200, 400),
+        (225, 450),
+        (250, 500),
+        (1_000, 500),
+    ] {
+        ctx.advance(t);
+        assert_eq!(ctx.client().calculate_accrued(&id), expected, "t={t}");
+        assert_eq!(ctx.client().get_withdrawable(&id), expected, "t={t}");
+    }
+}
+
+#[test]
+fn claimable_at_projects_the_schedule() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(0);
+
+    assert_eq!(ctx.client().get_claimable_at(&id, &100), 100);
+    assert_eq!(ctx.client().get_claimable_at(&id, &175), 325);
+    assert_eq!(ctx.client().get_claimable_at(&id, &250), 500);
+}
+
+#[test]
+fn cliff_gates_piecewise_accrual() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(120);
+
+    ctx.advance(119);
+    assert_eq!(ctx.client().get_withdrawable(&id), 0);
+
+    ctx.advance(120);
+    assert_eq!(ctx.client().get_withdrawable(&id), 160);
+}
+
+#[test]
+fn withdraw_and_cancel_settle_against_schedule() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(0);
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.advance(150);
+    assert_eq!(ctx.client().withdraw(&id, &None), 250);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 250);
+    assert_eq!(ctx.client().get_total_liabilities(), 250);
+
+    ctx.advance(175);
+    ctx.client().cancel_stream(&id);
+
+    let stream = ctx.client().get_stream_state(&id);
+    assert_eq!(stream.status, StreamStatus::Cancelled);
+    // Accrued at cancel: 100 + 75 * 3 = 325; refund = 500 - 325 = 175.
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 175);
+    assert_eq!(ctx.client().get_withdrawable(&id), 75);
+    assert_eq!(ctx.client().get_total_liabilities(), 75);
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_total_liabilities()
+    );
+}
+
+#[test]
+fn schedule_is_stored_and_removed_on_close() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(0);
+    assert_eq!(ctx.client().get_rate_schedule(&id), ctx.ramp());
+
+    ctx.advance(250);
+    ctx.client().withdraw(&id, &None);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Completed
+    );
+    ctx.client().close_completed_stream(&id);
+
+    assert_eq!(
+        ctx.client().try_get_rate_schedule(&id),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+    let cid = ctx.contract_id.clone();
+    ctx.env.as_contract(&cid, || {
+        assert!(!ctx
+            .env
+            .storage()
+            .persistent()
+            .has(&fluxora_stream::DataKey::RateSchedule(id)));
+    });
+}
+
+#[test]
+fn creation_validates_schedule() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+
+    // end_time must equal start_time + total schedule duration.
+    assert_eq!(
+        client.try_create_stream_with_schedule(
+            &ctx.sender,
+            &ctx.params(500, 0, 0, 300),
+            &ctx.ramp()
+        ),
+        Err(Ok(ContractError::RateScheduleInvalid))
+    );
+
+    // Deposit must cover the whole schedule.
+    assert_eq!(
+        client.try_create_stream_with_schedule(
+            &ctx.sender,
+            &ctx.params(499, 0, 0, 250),
+            &ctx.ramp()
+        ),
+        Err(Ok(ContractError::InsufficientDeposit))
+    );
+
+    // Zero-length segments are rejected.
+    let zero_len = vec![
+        &ctx.env,
+        RateSegment {
+            rate: 1,
+            duration_secs: 0,
+        },
+    ];
+    assert_eq!(
+        client.try_create_stream_with_schedule(&ctx.sender, &ctx.params(500, 0, 0, 1), &zero_len),
+        Err(Ok(ContractError::RateScheduleInvalid))
+    );
+
+    // Empty schedules and non-zero base rates are rejected.
+    assert_eq!(
+        client.try_create_stream_with_schedule(
+            &ctx.sender,
+            &ctx.params(500, 0, 0, 250),
+            &Vec::new(&ctx.env)
+        ),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let mut with_rate = ctx.params(500, 0, 0, 250);
+    with_rate.rate_per_second = 1;
+    assert_eq!(
+        client.try_create_stream_with_schedule(&ctx.sender, &with_rate, &ctx.ramp()),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    // The entrypoint only creates Piecewise streams.
+    let mut linear = ctx.params(500, 0, 0, 250);
+    linear.kind = StreamKind::Linear;
+    assert_eq!(
+        client.try_create_stream_with_schedule(&ctx.sender, &linear, &ctx.ramp()),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    // And create_stream cannot create a Piecewise stream without a schedule.
+    assert_eq!(
+        client.try_create_stream(&ctx.sender, &ctx.params(500, 0, 0, 250)),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+
+    assert_eq!(client.get_total_liabilities(), 0);
+}
+
+#[test]
+fn rate_and_top_up_mutators_reject_piecewise() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_ramp(0);
+
+    assert_eq!(
+        ctx.client().try_update_rate_per_second(&id, &5),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+    assert_eq!(
+        ctx.client().try_top_up_stream(&id, &ctx.sender, &100),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+}
@@ -0,0 +1,285 @@
+//! Integration tests for pooled-stream membership management.
+//!
+//! # What is tested
+//!
+//! 1. `add_pool_member` settles existing members first, so the newcomer only
+//!    shares in accrual from the moment it joins.
+//! 2. `remove_pool_member` keeps the departing member's accrued balance
+//!    claimable through `withdraw_from_pool` and `get_pool_member_withdrawable`.
+//! 3. `update_pool_share` re-weights future accrual only, and the pool still
+//!    pays out its whole deposit.
+//! 4. `rotate_pool_member` moves a member's share, credit, withdrawn total and
+//!    index entry to a new address.
+//! 5. Validation of every membership entrypoint.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test pool_membership
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, FluxoraStream, FluxoraStreamClient, PoolMemberUpdated, StreamKind, StreamStatus,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            alice,
+            bob,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 10 tokens/s from 0 to 100 shared by `members`.
+    fn create_pool(&self, members: &Vec<(Address, u32)>) -> u64 {
+        self.client().create_pooled_stream(
+            &self.sender,
+            members,
+            &1_000,
+            &10,
+            &0,
+            &0,
+            &100,
+            &0,
+            &None,
+            &StreamKind::Linear,
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+}
+
+#[test]
+fn added_member_only_shares_future_accrual() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_pool(&vec![&ctx.env, (ctx.alice.clone(), 1)]);
+
+    ctx.advance(50);
+    ctx.client().add_pool_member(&id, &ctx.bob, &1);
+    let event = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = event.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("pool_mbr"));
+    let updated = PoolMemberUpdated::try_from_val(&ctx.env, &event.2).unwrap();
+    assert_eq!((updated.old_share, updated.new_share), (0, 1));
+
+    assert!(ctx.client().get_recipient_streams(&ctx.bob).contains(id));
+    assert_eq!(ctx.client().get_pool_member_withdrawable(&id, &ctx.bob), 0);
+
+    ctx.advance(100);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.alice), 750);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.bob), 250);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn removed_member_keeps_accrued_balance() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_pool(&vec![
+        &ctx.env,
+        (ctx.alice.clone(), 1),
+        (ctx.bob.clone(), 1),
+    ]);
+
+    ctx.advance(40);
+    ctx.client().remove_pool_member(&id, &ctx.bob);
+    assert_eq!(
+        ctx.client().get_pool_members(&id),
+        vec![&ctx.env, (ctx.alice.clone(), 1)]
+    );
+
+    ctx.advance(100);
+    assert_eq!(
+        ctx.client().get_pool_member_withdrawable(&id, &ctx.bob),
+        200
+    );
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.bob), 200);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.alice), 800);
+    assert_eq!(ctx.token().balance(&ctx.bob), 200);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Completed
+    );
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+}
+
+#[test]
+fn share_update_reweights_future_accrual_only() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_pool(&vec![
+        &ctx.env,
+        (ctx.alice.clone(), 1),
+        (ctx.bob.clone(), 1),
+    ]);
+
+    ctx.advance(20);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.bob), 100);
+
+    // 500 accrued at t=50 is split 1:1; the remaining 500 is split 1:3.
+    ctx.advance(50);
+    ctx.client().update_pool_share(&id, &ctx.bob, &3);
+
+    ctx.advance(100);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.alice), 375);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.bob), 525);
+    assert_eq!(ctx.token().balance(&ctx.bob), 625);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Completed
+    );
+}
+
+#[test]
+fn rotation_moves_share_credit_and_withdrawn_total() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_pool(&vec![
+        &ctx.env,
+        (ctx.alice.clone(), 1),
+        (ctx.bob.clone(), 1),
+    ]);
+    let new_alice = Address::generate(&ctx.env);
+    let new_bob = Address::generate(&ctx.env);
+
+    ctx.advance(50);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &ctx.alice), 250);
+    ctx.client().remove_pool_member(&id, &ctx.bob);
+
+    ctx.client().rotate_pool_member(&id, &ctx.alice, &new_alice);
+    ctx.client().rotate_pool_member(&id, &ctx.bob, &new_bob);
+    assert_eq!(
+        ctx.client().get_pool_members(&id),
+        vec![&ctx.env, (new_alice.clone(), 1)]
+    );
+    assert!(!ctx.client().get_recipient_streams(&ctx.alice).contains(id));
+    assert!(ctx.client().get_recipient_streams(&new_alice).contains(id));
+
+    ctx.advance(100);
+    assert_eq!(
+        ctx.client().try_withdraw_from_pool(&id, &ctx.alice),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &new_alice), 500);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &new_bob), 250);
+}
+
+#[test]
+fn membership_changes_are_validated() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let id = ctx.create_pool(&vec![&ctx.env, (ctx.alice.clone(), 1)]);
+    let stranger = Address::generate(&ctx.env);
+
+    for result in [
+        client.try_add_pool_member(&id, &ctx.bob, &0),
+        client.try_add_pool_member(&id, &ctx.alice, &1),
+        client.try_update_pool_share(&id, &ctx.alice, &0),
+        client.try_update_pool_share(&id, &stranger, &1),
+        client.try_remove_pool_member(&id, &stranger),
+        client.try_remove_pool_member(&id, &ctx.alice),
+        client.try_rotate_pool_member(&id, &ctx.alice, &ctx.alice),
+    ] {
+        assert_eq!(result, Err(Ok(ContractError::InvalidParams)));
+    }
+    assert_eq!(
+        client.try_add_pool_member(&id, &ctx.bob, &u32::MAX),
+        Err(Ok(ContractError::ArithmeticOverflow))
+    );
+    assert_eq!(
+        client.try_rotate_pool_member(&id, &stranger, &ctx.bob),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.add_pool_member(&id, &ctx.bob, &1);
+    assert_eq!(
+        client.try_rotate_pool_member(&id, &ctx.alice, &ctx.bob),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    client.cancel_stream(&id);
+    assert_eq!(
+        client.try_add_pool_member(&id, &stranger, &1),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    let plain = client.create_stream(
+        &ctx.sender,
+        &fluxora_stream::CreateStreamParams {
+            recipient: ctx.alice.clone(),
+            deposit_amount: 100,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 100,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        },
+    );
+    assert_eq!(
+        client.try_get_pool_members(&plain),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        client.try_add_pool_member(&plain, &ctx.bob, &1),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
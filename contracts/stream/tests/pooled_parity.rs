@@ -0,0 +1,423 @@
+//! Integration tests for the per-member payout paths of pooled streams.
+//!
+//! # What is tested
+//!
+//! 1. `withdraw_from_pool_to` pays a member's share to another address.
+//! 2. `transfer_pool_claim_ownership` hands a member's claim rights to a new
+//!    owner, and `rotate_pool_member` carries them to the new member address.
+//! 3. `set_pool_auto_claim` / `trigger_pool_auto_claim` sweep a member's share
+//!    after the pool ends.
+//! 4. `delegated_withdraw` on a pooled stream settles the member whose key
+//!    signed the message and consumes that member's nonce, including a
+//!    removed member that still holds settled credit.
+//! 5. Single-recipient payout entrypoints and `get_withdrawable` reject pooled
+//!    streams, and `get_pool_member_withdrawable` follows the pool's status.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test pooled_parity
+//! ```
+
+#![cfg(test)]
+
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+use fluxora_stream::{
+    ContractError, FluxoraStream, FluxoraStreamClient, PauseReason, PoolClaimOwnerTransferred,
+    StreamKind, StreamStatus,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec,
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, TryIntoVal, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            alice,
+            bob,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 10 tokens/s from 0 to 100 shared by `members`.
+    fn create_pool(&self, members: &Vec<(Address, u32)>) -> u64 {
+        self.client().create_pooled_stream(
+            &self.sender,
+            members,
+            &1_000,
+            &10,
+            &0,
+            &0,
+            &100,
+            &0,
+            &None,
+            &StreamKind::Linear,
+        )
+    }
+
+    /// Alice and Bob with equal shares.
+    fn create_pair(&self) -> u64 {
+        self.create_pool(&vec![
+            &self.env,
+            (self.alice.clone(), 1),
+            (self.bob.clone(), 1),
+        ])
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+}
+
+/// Account address of an ed25519 signing key.
+fn account_of(env: &Env, signing_key: &SigningKey) -> Address {
+    let pk = signing_key.verifying_key().to_bytes();
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk))))
+        .try_into_val(env)
+        .unwrap()
+}
+
+/// Signature over the `delegated_withdraw` message with a zero minimum.
+fn sign_withdrawal(
+    env: &Env,
+    signing_key: &SigningKey,
+    stream_id: u64,
+    nonce: u64,
+    deadline: u64,
+    relayer_fee: i128,
+) -> BytesN<64> {
+    let mut msg = Bytes::new(env);
+    msg.extend_from_array(&stream_id.to_be_bytes());
+    msg.extend_from_array(&nonce.to_be_bytes());
+    msg.extend_from_array(&deadline.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    msg.extend_from_array(&relayer_fee.to_be_bytes());
+    let bytes: std::vec::Vec<u8> = (0..msg.len()).map(|i| msg.get_unchecked(i)).collect();
+    BytesN::from_array(env, &signing_key.sign(&bytes).to_bytes())
+}
+
+#[test]
+fn withdraw_from_pool_to_pays_destination() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_pair();
+    let destination = Address::generate(&ctx.env);
+
+    ctx.advance(40);
+    assert_eq!(
+        ctx.client()
+            .withdraw_from_pool_to(&id, &ctx.alice, &destination),
+        200
+    );
+    assert_eq!(ctx.token().balance(&destination), 200);
+    assert_eq!(ctx.token().balance(&ctx.alice), 0);
+    assert_eq!(
+        ctx.client().get_pool_member_withdrawable(&id, &ctx.alice),
+        0
+    );
+    assert_eq!(
+        ctx.client().get_pool_member_withdrawable(&id, &ctx.bob),
+        200
+    );
+
+    assert_eq!(
+        ctx.client()
+            .try_withdraw_from_pool_to(&id, &ctx.bob, &ctx.contract_id),
+        Err(Ok(ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn claim_ownership_moves_with_transfer_and_rotation() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let id = ctx.create_pair();
+    let owner = Address::generate(&ctx.env);
+    let stranger = Address::generate(&ctx.env);
+
+    assert_eq!(client.get_pool_claim_owner(&id, &ctx.alice), ctx.alice);
+    assert_eq!(
+        client.try_transfer_pool_claim_ownership(&id, &ctx.alice, &stranger, &owner),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_transfer_pool_claim_ownership(&id, &stranger, &stranger, &owner),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    client.transfer_pool_claim_ownership(&id, &ctx.alice, &ctx.alice, &owner);
+    let event = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = event.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("pool_own"));
+    let transferred = PoolClaimOwnerTransferred::try_from_val(&ctx.env, &event.2).unwrap();
+    assert_eq!(transferred.old_owner, ctx.alice);
+    assert_eq!(transferred.new_owner, owner);
+    assert_eq!(client.get_pool_claim_owner(&id, &ctx.alice), owner);
+
+    // Rotating the member keeps the claim owner attached to the share.
+    let new_alice = Address::generate(&ctx.env);
+    client.rotate_pool_member(&id, &ctx.alice, &new_alice);
+    assert_eq!(client.get_pool_claim_owner(&id, &new_alice), owner);
+    assert_eq!(client.get_pool_claim_owner(&id, &ctx.alice), ctx.alice);
+
+    ctx.advance(50);
+    assert_eq!(client.withdraw_from_pool_to(&id, &new_alice, &owner), 250);
+    assert_eq!(ctx.token().balance(&owner), 250);
+
+    // Handing the claim back to the member clears the record.
+    client.transfer_pool_claim_ownership(&id, &new_alice, &owner, &new_alice);
+    assert_eq!(client.get_pool_claim_owner(&id, &new_alice), new_alice);
+}
+
+#[test]
+fn pool_auto_claim_sweeps_after_end() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let id = ctx.create_pair();
+    let vault = Address::generate(&ctx.env);
+
+    assert_eq!(client.get_pool_auto_claim(&id, &ctx.alice), None);
+    assert_eq!(
+        client.try_trigger_pool_auto_claim(&id, &ctx.alice),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    client.set_pool_auto_claim(&id, &ctx.alice, &Some(vault.clone()));
+    client.set_pool_auto_claim(&id, &ctx.bob, &Some(vault.clone()));
+    assert_eq!(
+        client.get_pool_auto_claim(&id, &ctx.alice),
+        Some(vault.clone())
+    );
+    client.set_pool_auto_claim(&id, &ctx.bob, &None);
+    assert_eq!(client.get_pool_auto_claim(&id, &ctx.bob), None);
+
+    ctx.advance(50);
+    assert_eq!(
+        client.try_trigger_pool_auto_claim(&id, &ctx.alice),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    ctx.advance(100);
+    assert_eq!(client.trigger_pool_auto_claim(&id, &ctx.alice), 500);
+    assert_eq!(ctx.token().balance(&vault), 500);
+    assert_eq!(
+        client.try_trigger_pool_auto_claim(&id, &ctx.bob),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    assert_eq!(client.withdraw_from_pool(&id, &ctx.bob), 500);
+    assert_eq!(client.get_stream_state(&id).status, StreamStatus::Completed);
+}
+
+#[test]
+fn delegated_withdraw_settles_signing_member() {
+    let ctx = Ctx::setup();
+    let signing_key = SigningKey::from_bytes(&[0x42u8; 32]);
+    let member = account_of(&ctx.env, &signing_key);
+    let id = ctx.create_pool(&vec![&ctx.env, (member.clone(), 1), (ctx.bob.clone(), 1)]);
+    ctx.client().set_claim_to_balance(&member, &true);
+    assert!(ctx.client().get_recipient_streams(&member).contains(id));
+
+    ctx.advance(60);
+    let deadline = 10_000u64;
+    let signature = sign_withdrawal(&ctx.env, &signing_key, id, 0, deadline, 10);
+
+    let relayer = Address::generate(&ctx.env);
+    let public_key = BytesN::from_array(&ctx.env, &signing_key.verifying_key().to_bytes());
+    let paid = ctx.client().delegated_withdraw(
+        &id,
+        &relayer,
+        &public_key,
+        &0,
+        &deadline,
+        &0,
+        &10,
+        &signature,
+    );
+    assert_eq!(paid, 290);
+    assert_eq!(
+        ctx.client().get_recipient_balance(&member, &ctx.token_id),
+        290
+    );
+    assert_eq!(ctx.token().balance(&relayer), 10);
+    assert_eq!(ctx.client().get_delegated_nonce(&member), 1);
+    assert_eq!(ctx.client().get_pool_member_withdrawable(&id, &member), 0);
+    assert_eq!(
+        ctx.client().get_pool_member_withdrawable(&id, &ctx.bob),
+        300
+    );
+
+    // The consumed nonce cannot be replayed.
+    assert_eq!(
+        ctx.client().try_delegated_withdraw(
+            &id,
+            &relayer,
+            &public_key,
+            &0,
+            &deadline,
+            &0,
+            &10,
+            &signature,
+        ),
+        Err(Ok(ContractError::InvalidSignature))
+    );
+}
+
+#[test]
+fn delegated_withdraw_pays_removed_member_credit() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let signing_key = SigningKey::from_bytes(&[0x24u8; 32]);
+    let member = account_of(&ctx.env, &signing_key);
+    let id = ctx.create_pool(&vec![&ctx.env, (member.clone(), 1), (ctx.bob.clone(), 1)]);
+    client.set_claim_to_balance(&member, &true);
+
+    ctx.advance(40);
+    client.remove_pool_member(&id, &member);
+    assert_eq!(client.get_pool_member_withdrawable(&id, &member), 200);
+
+    let deadline = 10_000u64;
+    let relayer = Address::generate(&ctx.env);
+    let public_key = BytesN::from_array(&ctx.env, &signing_key.verifying_key().to_bytes());
+    let paid = client.delegated_withdraw(
+        &id,
+        &relayer,
+        &public_key,
+        &0,
+        &deadline,
+        &0,
+        &10,
+        &sign_withdrawal(&ctx.env, &signing_key, id, 0, deadline, 10),
+    );
+    assert_eq!(paid, 190);
+    assert_eq!(client.get_recipient_balance(&member, &ctx.token_id), 190);
+    assert_eq!(ctx.token().balance(&relayer), 10);
+    assert_eq!(client.get_delegated_nonce(&member), 1);
+
+    // A key with no share and no credit in the pool is rejected.
+    let stranger = SigningKey::from_bytes(&[0x25u8; 32]);
+    let stranger_key = BytesN::from_array(&ctx.env, &stranger.verifying_key().to_bytes());
+    assert_eq!(
+        client.try_delegated_withdraw(
+            &id,
+            &relayer,
+            &stranger_key,
+            &0,
+            &deadline,
+            &0,
+            &0,
+            &sign_withdrawal(&ctx.env, &stranger, id, 0, deadline, 0),
+        ),
+        Err(Ok(ContractError::InvalidSignature))
+    );
+}
+
+#[test]
+fn single_recipient_paths_reject_pools() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let id = ctx.create_pair();
+    let destination = Address::generate(&ctx.env);
+
+    ctx.advance(50);
+    assert_eq!(
+        client.try_withdraw(&id, &None),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        client.try_withdraw_to(&id, &destination),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        client.try_get_withdrawable(&id),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        client.try_set_auto_claim(&id, &destination),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        client.try_transfer_claim_ownership(&id, &ctx.sender, &destination),
+        Err(Ok(ContractError::InvalidState))
+    );
+    ctx.advance(100);
+    assert_eq!(
+        client.try_trigger_auto_claim(&id),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1_000);
+}
+
+#[test]
+fn member_withdrawable_follows_pool_status() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let id = ctx.create_pair();
+
+    ctx.advance(40);
+    assert_eq!(client.get_pool_member_withdrawable(&id, &ctx.alice), 200);
+    client.pause_stream(&id, &PauseReason::Operational);
+    assert_eq!(client.get_pool_member_withdrawable(&id, &ctx.alice), 0);
+    assert_eq!(
+        client.try_withdraw_from_pool(&id, &ctx.alice),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    ctx.advance(60);
+    client.resume_stream(&id);
+    // The paused interval pushes the schedule out, so the pool drains later.
+    ctx.advance(200);
+    assert_eq!(client.withdraw_from_pool(&id, &ctx.alice), 500);
+    assert_eq!(client.withdraw_from_pool(&id, &ctx.bob), 500);
+    assert_eq!(client.get_stream_state(&id).status, StreamStatus::Completed);
+    assert_eq!(client.get_pool_member_withdrawable(&id, &ctx.alice), 0);
+}
@@ -0,0 +1,627 @@
+//! Integration tests for the protocol fee and fee treasury.
+//!
+//! # What is tested
+//!
+//! 1. `set_protocol_fee` / `set_treasury` store the configuration, emit
+//!    events and reject rates above `MAX_PROTOCOL_FEE_BPS`.
+//! 2. The deposit fee is pulled on top of the deposit at creation and booked
+//!    as a protocol fee liability; the stream deposit is unchanged.
+//! 3. The withdrawal fee is deducted from `withdraw` / `withdraw_to` payouts
+//!    while the stream is debited the full amount.
+//! 4. `sweep_excess` never sweeps unclaimed protocol fees.
+//! 5. `claim_protocol_fees` pays the treasury, resets the liability and fails
+//!    with `TreasuryNotSet` without a treasury.
+//! 6. With no fee configured nothing changes for existing flows.
+//! 7. No other entrypoint avoids a fee: batch, relayed, auto-claim, pooled and
+//!    cancel payouts pay the withdrawal fee, and batch, scheduled, pooled,
+//!    cloned, renewed, topped-up and offered deposits pay the deposit fee.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test protocol_fee
+//! ```
+
+#![cfg(test)]
+
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+use fluxora_stream::accrual::{RateSegment, Tranche};
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, ProtocolFeeConfig,
+    StreamKind, WithdrawToParam, MAX_PROTOCOL_FEE_BPS,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec,
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryIntoVal,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+    treasury: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+            treasury,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 10 tokens/s from `now` for 1_000s.
+    fn params(&self) -> CreateStreamParams {
+        let now = self.env.ledger().timestamp();
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: 10_000,
+            rate_per_second: 10,
+            start_time: now,
+            cliff_time: now,
+            end_time: now + 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn create(&self) -> u64 {
+        self.client().create_stream(&self.sender, &self.params())
+    }
+
+    fn fee_liabilities(&self) -> i128 {
+        self.client().get_protocol_fee_liabilities(&self.token_id)
+    }
+
+    /// The contract holds exactly the stream and protocol fee liabilities.
+    fn assert_backed(&self) {
+        assert_eq!(
+            self.token().balance(&self.contract_id),
+            self.client().get_total_liabilities() + self.fee_liabilities()
+        );
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+
+    fn last_topic(&self) -> Symbol {
+        let last = self.env.events().all().last().unwrap();
+        last.1.get(0).unwrap().into_val(&self.env)
+    }
+}
+
+#[test]
+fn configuration_is_stored_and_bounded() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+
+    assert_eq!(client.get_protocol_fee(), ProtocolFeeConfig::default());
+    assert_eq!(client.get_treasury(), None);
+
+    client.set_protocol_fee(&30, &15);
+    assert_eq!(ctx.last_topic(), symbol_short!("fee_cfg"));
+    assert_eq!(
+        client.get_protocol_fee(),
+        ProtocolFeeConfig {
+            deposit_fee_bps: 30,
+            withdraw_fee_bps: 15,
+        }
+    );
+
+    client.set_treasury(&ctx.treasury);
+    assert_eq!(ctx.last_topic(), symbol_short!("treasury"));
+    assert_eq!(client.get_treasury(), Some(ctx.treasury.clone()));
+
+    assert_eq!(
+        client.try_set_protocol_fee(&(MAX_PROTOCOL_FEE_BPS + 1), &0),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        client.try_set_protocol_fee(&0, &(MAX_PROTOCOL_FEE_BPS + 1)),
+        Err(Ok(ContractError::InvalidParams))
+    );
+}
+
+#[test]
+fn deposit_fee_is_charged_on_top_of_deposit() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0); // 1%
+    let before = ctx.token().balance(&ctx.sender);
+
+    let id = ctx.create();
+
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 10_100);
+    assert_eq!(ctx.client().get_stream_state(&id).deposit_amount, 10_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 10_000);
+    assert_eq!(ctx.fee_liabilities(), 100);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 10_100);
+}
+
+#[test]
+fn withdraw_fee_is_deducted_from_payout() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&0, &250); // 2.5%
+    let id = ctx.create();
+
+    ctx.advance(400);
+    assert_eq!(ctx.client().withdraw(&id, &None), 4_000);
+    assert_eq!(ctx.last_topic(), symbol_short!("withdrew"));
+    assert_eq!(ctx.token().balance(&ctx.recipient), 3_900);
+    assert_eq!(ctx.client().get_stream_state(&id).withdrawn_amount, 4_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 6_000);
+    assert_eq!(ctx.fee_liabilities(), 100);
+
+    let destination = Address::generate(&ctx.env);
+    ctx.advance(600);
+    assert_eq!(ctx.client().withdraw_to(&id, &destination), 2_000);
+    assert_eq!(ctx.token().balance(&destination), 1_950);
+    assert_eq!(ctx.fee_liabilities(), 150);
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_total_liabilities() + ctx.fee_liabilities()
+    );
+}
+
+#[test]
+fn sweep_excludes_unclaimed_fees() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    ctx.create();
+    ctx.token().transfer(&ctx.sender, &ctx.contract_id, &42);
+
+    let sink = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().sweep_excess(&sink), 42);
+    assert_eq!(ctx.fee_liabilities(), 100);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 10_100);
+}
+
+#[test]
+fn claim_pays_treasury_and_resets_liability() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    ctx.create();
+
+    assert_eq!(
+        ctx.client().try_claim_protocol_fees(&ctx.token_id),
+        Err(Ok(ContractError::TreasuryNotSet))
+    );
+
+    ctx.client().set_treasury(&ctx.treasury);
+    assert_eq!(ctx.client().claim_protocol_fees(&ctx.token_id), 100);
+    assert_eq!(ctx.last_topic(), symbol_short!("fee_claim"));
+    assert_eq!(ctx.token().balance(&ctx.treasury), 100);
+    assert_eq!(ctx.fee_liabilities(), 0);
+    assert_eq!(ctx.client().claim_protocol_fees(&ctx.token_id), 0);
+    assert_eq!(
+        ctx.token().balance(&ctx.contract_id),
+        ctx.client().get_total_liabilities()
+    );
+}
+
+#[test]
+fn no_fee_by_default() {
+    let ctx = Ctx::setup();
+    let before = ctx.token().balance(&ctx.sender);
+    let id = ctx.create();
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 10_000);
+
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().withdraw(&id, &None), 10_000);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 10_000);
+    assert_eq!(ctx.fee_liabilities(), 0);
+}
+
+#[test]
+fn batch_withdrawals_charge_withdraw_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&0, &250);
+    let first = ctx.create();
+    let second = ctx.create();
+
+    ctx.advance(400);
+    ctx.client()
+        .batch_withdraw(&ctx.recipient, &vec![&ctx.env, first, second]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 7_800);
+    assert_eq!(ctx.fee_liabilities(), 200);
+
+    let destination = Address::generate(&ctx.env);
+    ctx.advance(1_000);
+    ctx.client().batch_withdraw_to(
+        &ctx.recipient,
+        &vec![
+            &ctx.env,
+            WithdrawToParam {
+                stream_id: first,
+                destination: destination.clone(),
+            },
+        ],
+    );
+    assert_eq!(ctx.token().balance(&destination), 5_850);
+    assert_eq!(ctx.fee_liabilities(), 350);
+    ctx.assert_backed();
+}
+
+#[test]
+fn auto_claim_charges_withdraw_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&0, &250);
+    let id = ctx.create();
+    let destination = Address::generate(&ctx.env);
+    ctx.client().set_auto_claim(&id, &destination);
+
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().trigger_auto_claim(&id), 10_000);
+    assert_eq!(ctx.token().balance(&destination), 9_750);
+    assert_eq!(ctx.fee_liabilities(), 250);
+    ctx.assert_backed();
+}
+
+#[test]
+fn pool_withdrawals_charge_withdraw_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&0, &250);
+    let alice = Address::generate(&ctx.env);
+    let bob = Address::generate(&ctx.env);
+    let id = ctx.client().create_pooled_stream(
+        &ctx.sender,
+        &vec![&ctx.env, (alice.clone(), 1), (bob.clone(), 1)],
+        &10_000,
+        &10,
+        &0,
+        &0,
+        &1_000,
+        &0,
+        &None,
+        &StreamKind::Linear,
+    );
+
+    ctx.advance(400);
+    assert_eq!(ctx.client().withdraw_from_pool(&id, &alice), 2_000);
+    assert_eq!(ctx.token().balance(&alice), 1_950);
+    let destination = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.client().withdraw_from_pool_to(&id, &bob, &destination),
+        2_000
+    );
+    assert_eq!(ctx.token().balance(&destination), 1_950);
+    assert_eq!(ctx.fee_liabilities(), 100);
+
+    let vault = Address::generate(&ctx.env);
+    ctx.client()
+        .set_pool_auto_claim(&id, &alice, &Some(vault.clone()));
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().trigger_pool_auto_claim(&id, &alice), 3_000);
+    assert_eq!(ctx.token().balance(&vault), 2_925);
+    assert_eq!(ctx.fee_liabilities(), 175);
+    ctx.assert_backed();
+}
+
+#[test]
+fn cancel_payouts_charge_withdraw_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&0, &250);
+    let bulk = ctx.create();
+    let abandoned = ctx.create();
+
+    ctx.advance(400);
+    ctx.client()
+        .bulk_cancel_streams(&ctx.sender, &vec![&ctx.env, bulk]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 3_900);
+    assert_eq!(ctx.fee_liabilities(), 100);
+
+    ctx.advance(1_000 + 7 * 24 * 3_600);
+    let keeper = Address::generate(&ctx.env);
+    ctx.client().keeper_cancel(&abandoned, &keeper);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 3_900 + 9_750);
+    assert_eq!(ctx.fee_liabilities(), 350);
+    ctx.assert_backed();
+}
+
+#[test]
+fn batch_creation_charges_deposit_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    let before = ctx.token().balance(&ctx.sender);
+
+    let ids = ctx
+        .client()
+        .create_streams(&ctx.sender, &vec![&ctx.env, ctx.params(), ctx.params()]);
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 20_200);
+    assert_eq!(ctx.fee_liabilities(), 200);
+
+    let mut underfunded = ctx.params();
+    underfunded.deposit_amount = 1;
+    let results = ctx
+        .client()
+        .create_streams_partial(&ctx.sender, &vec![&ctx.env, ctx.params(), underfunded]);
+    assert!(results.get(0).unwrap().success);
+    assert!(!results.get(1).unwrap().success);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 30_300);
+    assert_eq!(ctx.fee_liabilities(), 300);
+    ctx.assert_backed();
+}
+
+#[test]
+fn scheduled_creation_charges_deposit_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    let before = ctx.token().balance(&ctx.sender);
+
+    let mut params = ctx.params();
+    params.rate_per_second = 0;
+    params.kind = StreamKind::Piecewise;
+    ctx.client().create_stream_with_schedule(
+        &ctx.sender,
+        &params,
+        &vec![
+            &ctx.env,
+            RateSegment {
+                rate: 10,
+                duration_secs: 1_000,
+            },
+        ],
+    );
+
+    params.kind = StreamKind::Tranched;
+    ctx.client().create_stream_with_tranches(
+        &ctx.sender,
+        &params,
+        &vec![
+            &ctx.env,
+            Tranche {
+                unlock_time: 1_000,
+                cumulative_amount: 10_000,
+            },
+        ],
+    );
+
+    params.kind = StreamKind::ExponentialDecay;
+    ctx.client()
+        .create_stream_with_decay(&ctx.sender, &params, &200);
+
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 30_300);
+    assert_eq!(ctx.fee_liabilities(), 300);
+    ctx.assert_backed();
+}
+
+#[test]
+fn pooled_and_cloned_streams_charge_deposit_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    let before = ctx.token().balance(&ctx.sender);
+
+    let member = Address::generate(&ctx.env);
+    ctx.client().create_pooled_stream(
+        &ctx.sender,
+        &vec![&ctx.env, (member, 1)],
+        &10_000,
+        &10,
+        &0,
+        &0,
+        &1_000,
+        &0,
+        &None,
+        &StreamKind::Linear,
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 10_100);
+
+    let source = ctx.create();
+    let other = Address::generate(&ctx.env);
+    ctx.client()
+        .clone_stream(&source, &other, &0, &1_000, &10_000, &false);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 30_300);
+    assert_eq!(ctx.fee_liabilities(), 300);
+    ctx.assert_backed();
+}
+
+#[test]
+fn renewals_and_top_ups_charge_deposit_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    let renewing = ctx.create();
+    let subscription = ctx.create();
+    ctx.client().set_auto_renew(&renewing, &ctx.sender, &true);
+    ctx.client().set_subscription(&subscription, &Some(100));
+    let before = ctx.token().balance(&ctx.sender);
+
+    ctx.client()
+        .top_up_stream(&subscription, &ctx.sender, &1_000);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 1_010);
+
+    ctx.advance(1_000);
+    let keeper = Address::generate(&ctx.env);
+    assert!(ctx.client().renew_subscription(&subscription, &keeper));
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 2_020);
+
+    ctx.client().withdraw(&renewing, &None);
+    ctx.client().renew_stream(&renewing);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 12_120);
+    assert_eq!(ctx.fee_liabilities(), 200 + 120);
+    ctx.assert_backed();
+}
+
+#[test]
+fn offers_escrow_deposit_fee() {
+    let ctx = Ctx::setup();
+    ctx.client().set_protocol_fee(&100, &0);
+    let before = ctx.token().balance(&ctx.sender);
+
+    let accepted = ctx
+        .client()
+        .create_stream_offer(&ctx.sender, &ctx.params(), &None);
+    let cancelled = ctx
+        .client()
+        .create_stream_offer(&ctx.sender, &ctx.params(), &None);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 20_200);
+    assert_eq!(ctx.client().get_stream_offer(&accepted).deposit_fee, 100);
+    assert_eq!(ctx.fee_liabilities(), 0);
+
+    ctx.client().accept_stream_offer(&ctx.recipient, &accepted);
+    assert_eq!(ctx.fee_liabilities(), 100);
+    ctx.client().cancel_stream_offer(&ctx.sender, &cancelled);
+    assert_eq!(ctx.token().balance(&ctx.sender), before - 10_100);
+    ctx.assert_backed();
+}
+
+#[contracttype]
+enum TokenKey {
+    Balance(Address),
+}
+
+/// Balance-map token, since the Stellar Asset Contract cannot pay the ed25519
+/// account recipients of relayed withdrawals.
+#[contract]
+pub struct LedgerToken;
+
+#[contractimpl]
+impl LedgerToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .instance()
+            .set(&TokenKey::Balance(to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TokenKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Self::mint(env.clone(), from, -amount);
+        Self::mint(env, to, amount);
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount);
+    }
+
+    pub fn approve(_env: Env, _from: Address, _spender: Address, _amount: i128, _expiry: u32) {}
+
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        i128::MAX
+    }
+}
+
+#[test]
+fn delegated_withdraw_charges_withdraw_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FluxoraStream);
+    let token_id = env.register_contract(None, LedgerToken);
+    let client = FluxoraStreamClient::new(&env, &contract_id);
+    let token = LedgerTokenClient::new(&env, &token_id);
+
+    let sender = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let signing_key = SigningKey::from_bytes(&[0x31u8; 32]);
+    let public_key = signing_key.verifying_key().to_bytes();
+    let recipient: Address = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(
+        Uint256(public_key),
+    )))
+    .try_into_val(&env)
+    .unwrap();
+
+    client.init(&token_id, &Address::generate(&env));
+    client.set_protocol_fee(&0, &250);
+    token.mint(&sender, &10_000);
+    env.ledger().set_timestamp(0);
+    let id = client.create_stream(
+        &sender,
+        &CreateStreamParams {
+            recipient: recipient.clone(),
+            deposit_amount: 10_000,
+            rate_per_second: 10,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        },
+    );
+
+    env.ledger().set_timestamp(400);
+    let (nonce, deadline, relayer_fee) = (0u64, 10_000u64, 50i128);
+    let mut msg = Bytes::new(&env);
+    msg.extend_from_array(&id.to_be_bytes());
+    msg.extend_from_array(&nonce.to_be_bytes());
+    msg.extend_from_array(&deadline.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    msg.extend_from_array(&relayer_fee.to_be_bytes());
+    let bytes: std::vec::Vec<u8> = (0..msg.len()).map(|i| msg.get_unchecked(i)).collect();
+    let signature = BytesN::from_array(&env, &signing_key.sign(&bytes).to_bytes());
+
+    let net = client.delegated_withdraw(
+        &id,
+        &relayer,
+        &BytesN::from_array(&env, &public_key),
+        &nonce,
+        &deadline,
+        &0,
+        &relayer_fee,
+        &signature,
+    );
+    assert_eq!(net, 3_850);
+    assert_eq!(token.balance(&recipient), 3_850);
+    assert_eq!(token.balance(&relayer), 50);
+    assert_eq!(client.get_protocol_fee_liabilities(&token_id), 100);
+    assert_eq!(
+        token.balance(&contract_id),
+        client.get_total_liabilities() + 100
+    );
+}
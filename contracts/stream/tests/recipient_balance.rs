@@ -0,0 +1,234 @@
+//! Integration tests for recipient balances (claim-to-balance mode).
+//!
+//! # What is tested
+//!
+//! 1. `batch_withdraw` credits every stream's payout to the recipient balance
+//!    with no token transfer, and `withdraw_recipient_balance` pays it out in
+//!    one transfer. `TotalLiabilities` covers the balance throughout.
+//! 2. The balance survives `close_completed_stream` and `sweep_excess`.
+//! 3. A relayer's `delegated_withdraw` only moves accounting, and the
+//!    liabilities stay equal to what the contract still owes.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test recipient_balance
+//! ```
+
+#![cfg(test)]
+
+extern crate std;
+
+use ed25519_dalek::{Signer, SigningKey};
+use fluxora_stream::{
+    CreateStreamParams, FluxoraStream, FluxoraStreamClient, RecipientBalanceChanged, StreamKind,
+    StreamStatus,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec,
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, TryIntoVal,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 1 token/s from 0 to 1_000 for `recipient`.
+    fn create_for(&self, recipient: &Address) -> u64 {
+        self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn balance(&self, recipient: &Address) -> i128 {
+        self.client()
+            .get_recipient_balance(recipient, &self.token_id)
+    }
+}
+
+#[test]
+fn batch_withdraw_credits_balance_and_pays_out_once() {
+    let ctx = Ctx::setup();
+    let ids = vec![
+        &ctx.env,
+        ctx.create_for(&ctx.recipient),
+        ctx.create_for(&ctx.recipient),
+        ctx.create_for(&ctx.recipient),
+    ];
+    ctx.client().set_claim_to_balance(&ctx.recipient, &true);
+    assert!(ctx.client().get_claim_to_balance(&ctx.recipient));
+
+    ctx.env.ledger().set_timestamp(200);
+    let results = ctx.client().batch_withdraw(&ctx.recipient, &ids);
+    assert_eq!(results.get(0).unwrap().amount, 200);
+
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.balance(&ctx.recipient), 600);
+    assert_eq!(ctx.client().get_total_liabilities(), 3_000);
+
+    // A single withdraw keeps adding to the same balance.
+    ctx.env.ledger().set_timestamp(300);
+    ctx.env.ledger().set_sequence_number(1_000);
+    assert_eq!(ctx.client().withdraw(&ids.get(0).unwrap(), &None), 100);
+    assert_eq!(ctx.balance(&ctx.recipient), 700);
+
+    assert_eq!(
+        ctx.client()
+            .withdraw_recipient_balance(&ctx.recipient, &ctx.token_id),
+        700
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 700);
+    assert_eq!(ctx.balance(&ctx.recipient), 0);
+    assert_eq!(ctx.client().get_total_liabilities(), 2_300);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 2_300);
+
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("bal_wdr"));
+    let payload = RecipientBalanceChanged::try_from_val(&ctx.env, &last.2).unwrap();
+    assert_eq!(payload.amount, 700);
+
+    assert_eq!(
+        ctx.client()
+            .withdraw_recipient_balance(&ctx.recipient, &ctx.token_id),
+        0
+    );
+
+    // Switching the mode off transfers again.
+    ctx.client().set_claim_to_balance(&ctx.recipient, &false);
+    ctx.env.ledger().set_timestamp(400);
+    ctx.env.ledger().set_sequence_number(2_000);
+    ctx.client().withdraw(&ids.get(1).unwrap(), &None);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 900);
+    assert_eq!(ctx.balance(&ctx.recipient), 0);
+}
+
+#[test]
+fn balance_survives_close_and_sweep() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_for(&ctx.recipient);
+    ctx.client().set_claim_to_balance(&ctx.recipient, &true);
+
+    ctx.env.ledger().set_timestamp(1_000);
+    ctx.client().withdraw(&id, &None);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Completed
+    );
+    ctx.client().close_completed_stream(&id);
+    assert_eq!(ctx.balance(&ctx.recipient), 1_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 1_000);
+
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.contract_id, &50);
+    let treasury = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().sweep_excess(&treasury), 50);
+
+    assert_eq!(
+        ctx.client()
+            .withdraw_recipient_balance(&ctx.recipient, &ctx.token_id),
+        1_000
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 1_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+}
+
+#[test]
+fn delegated_withdraw_only_moves_accounting() {
+    let ctx = Ctx::setup();
+    let signing_key = SigningKey::from_bytes(&[0x42u8; 32]);
+    let pk = signing_key.verifying_key().to_bytes();
+    let recipient: Address =
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk))))
+            .try_into_val(&ctx.env)
+            .unwrap();
+    let id = ctx.create_for(&recipient);
+    ctx.client().set_claim_to_balance(&recipient, &true);
+
+    ctx.env.ledger().set_timestamp(250);
+    let deadline = 10_000u64;
+    let mut msg = Bytes::new(&ctx.env);
+    msg.extend_from_array(&id.to_be_bytes());
+    msg.extend_from_array(&0u64.to_be_bytes());
+    msg.extend_from_array(&deadline.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    let bytes: std::vec::Vec<u8> = (0..msg.len()).map(|i| msg.get_unchecked(i)).collect();
+    let signature = BytesN::from_array(&ctx.env, &signing_key.sign(&bytes).to_bytes());
+
+    let relayer = Address::generate(&ctx.env);
+    let paid = ctx.client().delegated_withdraw(
+        &id,
+        &relayer,
+        &BytesN::from_array(&ctx.env, &pk),
+        &0,
+        &deadline,
+        &0,
+        &0,
+        &signature,
+    );
+    assert_eq!(paid, 250);
+    assert_eq!(ctx.balance(&recipient), 250);
+    assert_eq!(ctx.client().get_total_liabilities(), 1_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 1_000);
+}
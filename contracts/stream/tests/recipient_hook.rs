@@ -0,0 +1,378 @@
+//! Integration tests for recipient payout hooks.
+//!
+//! # What is tested
+//!
+//! 1. `withdraw` and `trigger_auto_claim` call the registered hook with the
+//!    stream id, token, amount paid and `PayoutReason`.
+//! 2. `cancel_stream` reports the balance still claimable and ignores hook
+//!    failures.
+//! 3. A failing hook makes `withdraw` return `RecipientHookFailed` with no
+//!    funds moved; clearing the hook restores plain withdrawals.
+//! 4. A hook that re-enters `withdraw` is blocked while the reentrancy lock
+//!    is held.
+//! 5. `set_recipient_hook` rejects the stream contract itself, emits
+//!    `rcpt_hook`, and recipient rotation clears the hook.
+//! 6. A claim-to-balance withdrawal reports `PayoutReason::Credited`.
+//! 7. Once the position is transferred only its claim owner may set the
+//!    hook, and the transfer clears the previous owner's hook.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test recipient_hook
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    hooks::PayoutReason, ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient,
+    StreamKind,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HookCall {
+    pub stream_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub reason: PayoutReason,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookMode {
+    Record = 0,
+    Fail = 1,
+    Reenter = 2,
+}
+
+#[contracttype]
+enum HookKey {
+    Mode,
+    Stream,
+    Calls,
+    ReentryBlocked,
+}
+
+/// Hook that records every call and can be told to fail or re-enter.
+#[contract]
+pub struct MockHook;
+
+#[contractimpl]
+impl MockHook {
+    pub fn configure(env: Env, stream_contract: Address, mode: HookMode) {
+        env.storage()
+            .instance()
+            .set(&HookKey::Stream, &stream_contract);
+        env.storage().instance().set(&HookKey::Mode, &mode);
+    }
+
+    pub fn on_stream_payout(
+        env: Env,
+        stream_id: u64,
+        token: Address,
+        amount: i128,
+        reason: PayoutReason,
+    ) {
+        let mode: HookMode = env.storage().instance().get(&HookKey::Mode).unwrap();
+        match mode {
+            HookMode::Fail => panic!("hook failure"),
+            HookMode::Reenter => {
+                let stream_contract: Address =
+                    env.storage().instance().get(&HookKey::Stream).unwrap();
+                let result = FluxoraStreamClient::new(&env, &stream_contract)
+                    .try_withdraw(&stream_id, &None);
+                env.storage()
+                    .instance()
+                    .set(&HookKey::ReentryBlocked, &result.is_err());
+            }
+            HookMode::Record => {}
+        }
+        let mut calls: Vec<HookCall> = env
+            .storage()
+            .instance()
+            .get(&HookKey::Calls)
+            .unwrap_or(Vec::new(&env));
+        calls.push_back(HookCall {
+            stream_id,
+            token,
+            amount,
+            reason,
+        });
+        env.storage().instance().set(&HookKey::Calls, &calls);
+    }
+
+    pub fn calls(env: Env) -> Vec<HookCall> {
+        env.storage()
+            .instance()
+            .get(&HookKey::Calls)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn reentry_blocked(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&HookKey::ReentryBlocked)
+            .unwrap_or(false)
+    }
+}
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn create(&self) -> u64 {
+        self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: self.recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn hook(&self, stream_id: u64, mode: HookMode) -> MockHookClient<'_> {
+        let hook_id = self.env.register_contract(None, MockHook);
+        let hook = MockHookClient::new(&self.env, &hook_id);
+        hook.configure(&self.contract_id, &mode);
+        self.client()
+            .set_recipient_hook(&stream_id, &Some(hook_id.clone()));
+        hook
+    }
+
+    fn call(&self, stream_id: u64, amount: i128, reason: PayoutReason) -> HookCall {
+        HookCall {
+            stream_id,
+            token: self.token_id.clone(),
+            amount,
+            reason,
+        }
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+#[test]
+fn withdraw_and_auto_claim_notify_hook() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let hook = ctx.hook(id, HookMode::Record);
+    assert_eq!(
+        ctx.client().get_recipient_hook(&id),
+        Some(hook.address.clone())
+    );
+
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw(&id, &None), 300);
+
+    let destination = Address::generate(&ctx.env);
+    ctx.client().set_auto_claim(&id, &destination);
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().trigger_auto_claim(&id), 700);
+
+    assert_eq!(
+        hook.calls(),
+        vec![
+            &ctx.env,
+            ctx.call(id, 300, PayoutReason::Withdraw),
+            ctx.call(id, 700, PayoutReason::AutoClaim),
+        ]
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+    assert_eq!(ctx.token().balance(&destination), 700);
+}
+
+#[test]
+fn cancel_reports_claimable_and_ignores_failures() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let hook = ctx.hook(id, HookMode::Record);
+    let failing = ctx.create();
+    ctx.hook(failing, HookMode::Fail);
+
+    ctx.advance(100);
+    ctx.client().withdraw(&id, &None);
+    ctx.advance(400);
+    ctx.client().cancel_stream(&id);
+    assert_eq!(
+        hook.calls().last().unwrap(),
+        ctx.call(id, 300, PayoutReason::Cancel)
+    );
+
+    let sender_before = ctx.token().balance(&ctx.sender);
+    ctx.client().cancel_stream(&failing);
+    assert_eq!(ctx.token().balance(&ctx.sender) - sender_before, 600);
+}
+
+#[test]
+fn failing_hook_reverts_withdraw() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    ctx.hook(id, HookMode::Fail);
+
+    ctx.advance(300);
+    assert_eq!(
+        ctx.client().try_withdraw(&id, &None),
+        Err(Ok(ContractError::RecipientHookFailed))
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+    assert_eq!(ctx.client().get_stream_state(&id).withdrawn_amount, 0);
+
+    ctx.client().set_recipient_hook(&id, &None);
+    assert_eq!(ctx.client().withdraw(&id, &None), 300);
+}
+
+#[test]
+fn hook_cannot_reenter_withdraw() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let hook = ctx.hook(id, HookMode::Reenter);
+
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw(&id, &None), 300);
+    assert!(hook.reentry_blocked());
+    assert_eq!(ctx.token().balance(&ctx.recipient), 300);
+}
+
+#[test]
+fn set_hook_validation_event_and_rotation() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+
+    assert_eq!(
+        ctx.client()
+            .try_set_recipient_hook(&id, &Some(ctx.contract_id.clone())),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_set_recipient_hook(&99, &None),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+
+    ctx.hook(id, HookMode::Record);
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("rcpt_hook"));
+
+    let new_recipient = Address::generate(&ctx.env);
+    ctx.client().update_recipient(&id, &new_recipient);
+    ctx.client().accept_recipient_update(&id);
+    assert_eq!(ctx.client().get_recipient_hook(&id), None);
+}
+
+#[test]
+fn claim_to_balance_withdraw_reports_credit() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let hook = ctx.hook(id, HookMode::Record);
+    ctx.client().set_claim_to_balance(&ctx.recipient, &true);
+
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw(&id, &None), 300);
+
+    assert_eq!(
+        hook.calls(),
+        vec![&ctx.env, ctx.call(id, 300, PayoutReason::Credited)]
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 0);
+}
+
+#[test]
+fn claim_owner_controls_hook() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    ctx.hook(id, HookMode::Record);
+
+    let buyer = Address::generate(&ctx.env);
+    ctx.client()
+        .transfer_claim_ownership(&id, &ctx.recipient, &buyer);
+    assert_eq!(ctx.client().get_recipient_hook(&id), None);
+
+    let hook_id = ctx.env.register_contract(None, MockHook);
+    let hook = Some(hook_id.clone());
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_recipient_hook",
+            args: (id, hook.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx.client().try_set_recipient_hook(&id, &hook).is_err());
+
+    ctx.env.mock_auths(&[MockAuth {
+        address: &buyer,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_recipient_hook",
+            args: (id, hook.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    ctx.client().set_recipient_hook(&id, &hook);
+    assert_eq!(ctx.client().get_recipient_hook(&id), Some(hook_id));
+}
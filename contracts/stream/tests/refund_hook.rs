@@ -0,0 +1,372 @@
+//! Integration tests for sender refund hooks.
+//!
+//! # What is tested
+//!
+//! 1. `cancel_stream`, `shorten_stream_end_time` and `decrease_rate_per_second`
+//!    call the registered hook with the stream id, refunded amount and
+//!    `RefundReason` after the refund lands.
+//! 2. With `HookFailurePolicy::Ignore` a failing hook does not stop the
+//!    cancellation; with `HookFailurePolicy::Revert` the call returns
+//!    `RefundHookFailed` and nothing changes.
+//! 3. `set_refund_hook` rejects the stream contract itself, emits
+//!    `rfnd_hook`, and can be cleared.
+//! 4. Admin and keeper cancellations still notify the hook but
+//!    ignore its failures, even under `HookFailurePolicy::Revert`.
+//! 5. `bulk_cancel_streams` notifies each stream's hook with that stream's
+//!    refund and honours `HookFailurePolicy::Revert`.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test refund_hook
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    hooks::{HookFailurePolicy, RefundHookConfig, RefundReason},
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind,
+    StreamStatus, TerminationPolicy,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundCall {
+    pub stream_id: u64,
+    pub amount: i128,
+    pub reason: RefundReason,
+}
+
+#[contracttype]
+enum HookKey {
+    Fail,
+    Calls,
+}
+
+/// Hook that records every refund, or panics when told to fail.
+#[contract]
+pub struct MockRefundHook;
+
+#[contractimpl]
+impl MockRefundHook {
+    pub fn set_fail(env: Env, fail: bool) {
+        env.storage().instance().set(&HookKey::Fail, &fail);
+    }
+
+    pub fn on_stream_refund(env: Env, stream_id: u64, amount: i128, reason: RefundReason) {
+        if env
+            .storage()
+            .instance()
+            .get(&HookKey::Fail)
+            .unwrap_or(false)
+        {
+            panic!("hook failure");
+        }
+        let mut calls = Self::calls(env.clone());
+        calls.push_back(RefundCall {
+            stream_id,
+            amount,
+            reason,
+        });
+        env.storage().instance().set(&HookKey::Calls, &calls);
+    }
+
+    pub fn calls(env: Env) -> Vec<RefundCall> {
+        env.storage()
+            .instance()
+            .get(&HookKey::Calls)
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn sender_balance(&self) -> i128 {
+        TokenClient::new(&self.env, &self.token_id).balance(&self.sender)
+    }
+
+    fn create(&self, rate: i128) -> u64 {
+        self.client()
+            .create_stream(&self.sender, &self.params(rate))
+    }
+
+    fn params(&self, rate: i128) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: rate * 1_000,
+            rate_per_second: rate,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn hook(&self, stream_id: u64, on_failure: HookFailurePolicy) -> MockRefundHookClient<'_> {
+        let hook_id = self.env.register_contract(None, MockRefundHook);
+        self.client().set_refund_hook(
+            &stream_id,
+            &Some(RefundHookConfig {
+                hook: hook_id.clone(),
+                on_failure,
+            }),
+        );
+        MockRefundHookClient::new(&self.env, &hook_id)
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+fn refund(stream_id: u64, amount: i128, reason: RefundReason) -> RefundCall {
+    RefundCall {
+        stream_id,
+        amount,
+        reason,
+    }
+}
+
+#[test]
+fn refunds_notify_hook_with_reason() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(2);
+    let hook = ctx.hook(id, HookFailurePolicy::Revert);
+
+    ctx.advance(100);
+    // Deposit 2_000 -> 1_600 (2/s until 800).
+    ctx.client().shorten_stream_end_time(&id, &800);
+    // 200 accrued + 1/s for the remaining 700s -> deposit 900.
+    ctx.client().decrease_rate_per_second(&id, &1);
+    ctx.advance(400);
+    // 500 accrued -> 400 refunded.
+    ctx.client().cancel_stream(&id);
+
+    assert_eq!(
+        hook.calls(),
+        vec![
+            &ctx.env,
+            refund(id, 400, RefundReason::ShortenEndTime),
+            refund(id, 700, RefundReason::DecreaseRate),
+            refund(id, 400, RefundReason::Cancel),
+        ]
+    );
+}
+
+#[test]
+fn ignore_policy_lets_cancel_through() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(1);
+    ctx.hook(id, HookFailurePolicy::Ignore).set_fail(&true);
+
+    ctx.advance(250);
+    let before = ctx.sender_balance();
+    ctx.client().cancel_stream(&id);
+    assert_eq!(ctx.sender_balance() - before, 750);
+    assert_eq!(
+        ctx.client().get_stream_state(&id).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn revert_policy_reverts_refunding_calls() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(1);
+    ctx.hook(id, HookFailurePolicy::Revert).set_fail(&true);
+
+    ctx.advance(250);
+    let before = ctx.sender_balance();
+    assert_eq!(
+        ctx.client().try_cancel_stream(&id),
+        Err(Ok(ContractError::RefundHookFailed))
+    );
+    assert_eq!(
+        ctx.client().try_shorten_stream_end_time(&id, &500),
+        Err(Ok(ContractError::RefundHookFailed))
+    );
+    assert_eq!(ctx.sender_balance(), before);
+    let state = ctx.client().get_stream_state(&id);
+    assert_eq!(state.status, StreamStatus::Active);
+    assert_eq!(state.end_time, 1_000);
+
+    ctx.client().set_refund_hook(&id, &None);
+    ctx.client().cancel_stream(&id);
+    assert_eq!(ctx.sender_balance() - before, 750);
+}
+
+#[test]
+fn set_refund_hook_validation_and_event() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(1);
+
+    assert_eq!(
+        ctx.client().try_set_refund_hook(
+            &id,
+            &Some(RefundHookConfig {
+                hook: ctx.contract_id.clone(),
+                on_failure: HookFailurePolicy::Ignore,
+            }),
+        ),
+        Err(Ok(ContractError::InvalidParams))
+    );
+
+    let hook = ctx.hook(id, HookFailurePolicy::Ignore);
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("rfnd_hook"));
+    assert_eq!(
+        ctx.client().get_refund_hook(&id),
+        Some(RefundHookConfig {
+            hook: hook.address.clone(),
+            on_failure: HookFailurePolicy::Ignore,
+        })
+    );
+
+    ctx.client().set_refund_hook(&id, &None);
+    assert_eq!(ctx.client().get_refund_hook(&id), None);
+}
+
+#[test]
+fn admin_and_keeper_cancels_ignore_revert_policy() {
+    let ctx = Ctx::setup();
+    let by_admin = ctx.create(1);
+    let admin_hook = ctx.hook(by_admin, HookFailurePolicy::Revert);
+    admin_hook.set_fail(&true);
+
+    // Keeper cancels run after end_time, so only a bad-leaver floor leaves
+    // a refund to report.
+    let recorded = ctx.client().create_stream_with_termination(
+        &ctx.sender,
+        &ctx.params(1),
+        &TerminationPolicy::BadLeaver(300),
+    );
+    let failing = ctx.client().create_stream_with_termination(
+        &ctx.sender,
+        &ctx.params(1),
+        &TerminationPolicy::BadLeaver(300),
+    );
+    let hook = ctx.hook(recorded, HookFailurePolicy::Revert);
+    ctx.hook(failing, HookFailurePolicy::Revert).set_fail(&true);
+
+    ctx.advance(250);
+    let before = ctx.sender_balance();
+    ctx.client().cancel_stream_as_admin(&by_admin);
+    assert_eq!(ctx.sender_balance() - before, 750);
+
+    ctx.advance(1_000 + 604_800);
+    let keeper = Address::generate(&ctx.env);
+    let (_, sender_refund) = ctx.client().get_keeper_fee_split(&recorded);
+    assert!(sender_refund > 0);
+    ctx.client().keeper_cancel(&recorded, &keeper);
+    ctx.client().keeper_cancel(&failing, &keeper);
+
+    assert_eq!(
+        hook.calls(),
+        vec![
+            &ctx.env,
+            refund(recorded, sender_refund, RefundReason::Cancel)
+        ]
+    );
+    assert_eq!(
+        ctx.client().get_stream_state(&failing).status,
+        StreamStatus::Cancelled
+    );
+}
+
+#[test]
+fn bulk_cancel_notifies_each_hook() {
+    let ctx = Ctx::setup();
+    let first = ctx.create(1);
+    let second = ctx.create(2);
+    let first_hook = ctx.hook(first, HookFailurePolicy::Revert);
+    let second_hook = ctx.hook(second, HookFailurePolicy::Ignore);
+
+    ctx.advance(250);
+    ctx.client()
+        .bulk_cancel_streams(&ctx.sender, &vec![&ctx.env, first, second]);
+
+    assert_eq!(
+        first_hook.calls(),
+        vec![&ctx.env, refund(first, 750, RefundReason::Cancel)]
+    );
+    assert_eq!(
+        second_hook.calls(),
+        vec![&ctx.env, refund(second, 1_500, RefundReason::Cancel)]
+    );
+}
+
+#[test]
+fn bulk_cancel_honours_revert_policy() {
+    let ctx = Ctx::setup();
+    let ok = ctx.create(1);
+    let failing = ctx.create(1);
+    ctx.hook(failing, HookFailurePolicy::Revert).set_fail(&true);
+
+    ctx.advance(250);
+    let before = ctx.sender_balance();
+    assert_eq!(
+        ctx.client()
+            .try_bulk_cancel_streams(&ctx.sender, &vec![&ctx.env, ok, failing]),
+        Err(Ok(ContractError::RefundHookFailed))
+    );
+    assert_eq!(ctx.sender_balance(), before);
+    assert_eq!(
+        ctx.client().get_stream_state(&ok).status,
+        StreamStatus::Active
+    );
+}
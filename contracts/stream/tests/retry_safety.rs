@@ -548,7 +548,7 @@ fn retry_safety_version_endpoint_deterministic() {
 
     assert_eq!(v1, v2);
     assert_eq!(v2, v3);
-    assert_eq!(v1, 9); // Current CONTRACT_VERSION
+    assert_eq!(v1, 10); // Current CONTRACT_VERSION
 }
 
 // ============================================================================
@@ -0,0 +1,292 @@
+//! Tests for the sender stream index views and the status-filtered index views.
+//!
+//! * `get_sender_streams_paginated` walks every stream a sender created, in
+//!   ascending order, with the same inclusive cursor as the recipient view.
+//! * `get_sender_stream_count` tracks creation and `close_completed_stream`.
+//! * A stream created by accepting an offer joins the sender index, like the
+//!   recipient index, but a pending offer does not.
+//! * `get_sender_streams_by_status` / `get_recipient_streams_by_status` return
+//!   only matching IDs, and paging through them never drops a match.
+
+extern crate std;
+
+use fluxora_stream::{
+    CreateStreamParams, FluxoraStream, FluxoraStreamClient, Page, PauseReason, StreamKind,
+    StreamStatus,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: FluxoraStreamClient<'static>,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000);
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let client = FluxoraStreamClient::new(&env, &contract_id);
+        client.init(&token_id, &Address::generate(&env));
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &1_000_000, &200_000);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        // Safety: env lives as long as the returned Ctx; we only hold one Ctx at a time.
+        let client: FluxoraStreamClient<'static> = unsafe { core::mem::transmute(client) };
+
+        Ctx {
+            env,
+            client,
+            sender,
+            recipient,
+        }
+    }
+
+    fn create_for(&self, recipient: &Address) -> u64 {
+        let now = self.env.ledger().timestamp();
+        self.client.create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: recipient.clone(),
+                deposit_amount: 100,
+                rate_per_second: 1,
+                start_time: now,
+                cliff_time: now,
+                end_time: now + 100,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn create_n(&self, n: u32) -> Vec<u64> {
+        let mut ids = Vec::new(&self.env);
+        for _ in 0..n {
+            ids.push_back(self.create_for(&self.recipient));
+        }
+        ids
+    }
+
+    /// Create four streams for `self.recipient` and leave them
+    /// Active, Paused, Cancelled and Completed, in that order.
+    fn create_mixed(&self) -> [u64; 4] {
+        let ids = self.create_n(4);
+        let ids = [
+            ids.get(0).unwrap(),
+            ids.get(1).unwrap(),
+            ids.get(2).unwrap(),
+            ids.get(3).unwrap(),
+        ];
+        self.env.ledger().set_timestamp(50);
+        self.env.ledger().set_sequence_number(1_000);
+        self.client.pause_stream(&ids[1], &PauseReason::Operational);
+        self.client.cancel_stream(&ids[2]);
+        self.env.ledger().set_timestamp(100);
+        self.client.withdraw(&ids[3], &None);
+        ids
+    }
+}
+
+/// Follow `next_cursor` until it is 0 and concatenate every page.
+fn collect(mut fetch: impl FnMut(u64) -> Page, env: &Env) -> Vec<u64> {
+    let mut out = Vec::new(env);
+    let mut cursor = 0u64;
+    loop {
+        let page = fetch(cursor);
+        out.append(&page.stream_ids);
+        if page.next_cursor == 0 {
+            return out;
+        }
+        cursor = page.next_cursor;
+    }
+}
+
+#[test]
+fn test_sender_paginated_walks_all_streams() {
+    let ctx = Ctx::setup();
+    assert_eq!(ctx.client.get_sender_stream_count(&ctx.sender), 0);
+    let empty = ctx
+        .client
+        .get_sender_streams_paginated(&ctx.sender, &0, &10);
+    assert_eq!(empty.stream_ids.len(), 0);
+    assert_eq!(empty.next_cursor, 0);
+
+    let ids = ctx.create_n(5);
+    let other = Address::generate(&ctx.env);
+    let mut all = ids.clone();
+    all.push_back(ctx.create_for(&other));
+
+    assert_eq!(ctx.client.get_sender_stream_count(&ctx.sender), 6);
+    assert_eq!(ctx.client.get_recipient_stream_count(&ctx.recipient), 5);
+
+    let first = ctx.client.get_sender_streams_paginated(&ctx.sender, &0, &2);
+    assert_eq!(
+        first.stream_ids,
+        vec![&ctx.env, all.get(0).unwrap(), all.get(1).unwrap()]
+    );
+    assert_eq!(first.next_cursor, all.get(2).unwrap());
+
+    let walked = collect(
+        |cursor| {
+            ctx.client
+                .get_sender_streams_paginated(&ctx.sender, &cursor, &4)
+        },
+        &ctx.env,
+    );
+    assert_eq!(walked, all);
+}
+
+#[test]
+fn test_sender_count_drops_when_stream_closed() {
+    let ctx = Ctx::setup();
+    let [active, _, _, completed] = ctx.create_mixed();
+
+    ctx.client.close_completed_stream(&completed);
+    assert_eq!(ctx.client.get_sender_stream_count(&ctx.sender), 3);
+    let page = ctx
+        .client
+        .get_sender_streams_paginated(&ctx.sender, &0, &10);
+    assert_eq!(page.stream_ids.len(), 3);
+    assert_eq!(page.stream_ids.get(0).unwrap(), active);
+    assert!(!page.stream_ids.contains(completed));
+}
+
+#[test]
+fn test_accepted_offer_joins_sender_index() {
+    let ctx = Ctx::setup();
+    let direct = ctx.create_for(&ctx.recipient);
+    let offer_id = ctx.client.create_stream_offer(
+        &ctx.sender,
+        &CreateStreamParams {
+            recipient: ctx.recipient.clone(),
+            deposit_amount: 100,
+            rate_per_second: 1,
+            start_time: 10,
+            cliff_time: 10,
+            end_time: 110,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        },
+        &None,
+    );
+    assert_eq!(ctx.client.get_sender_stream_count(&ctx.sender), 1);
+
+    let stream_id = ctx.client.accept_stream_offer(&ctx.recipient, &offer_id);
+    let expected = vec![&ctx.env, direct, stream_id];
+    assert_eq!(ctx.client.get_sender_stream_count(&ctx.sender), 2);
+    assert_eq!(
+        ctx.client
+            .get_sender_streams_paginated(&ctx.sender, &0, &10)
+            .stream_ids,
+        expected
+    );
+    assert_eq!(
+        ctx.client
+            .get_sender_streams_by_status(&ctx.sender, &StreamStatus::Active, &0, &10)
+            .stream_ids,
+        ctx.client
+            .get_recipient_streams_by_status(&ctx.recipient, &StreamStatus::Active, &0, &10)
+            .stream_ids
+    );
+}
+
+#[test]
+fn test_status_filters_on_sender_and_recipient_views() {
+    let ctx = Ctx::setup();
+    let ids = ctx.create_mixed();
+    let statuses = [
+        StreamStatus::Active,
+        StreamStatus::Paused,
+        StreamStatus::Cancelled,
+        StreamStatus::Completed,
+    ];
+
+    for (id, status) in ids.iter().zip(statuses) {
+        let by_sender = ctx
+            .client
+            .get_sender_streams_by_status(&ctx.sender, &status, &0, &10);
+        assert_eq!(by_sender.stream_ids, vec![&ctx.env, *id]);
+        assert_eq!(by_sender.next_cursor, 0);
+
+        let by_recipient =
+            ctx.client
+                .get_recipient_streams_by_status(&ctx.recipient, &status, &0, &10);
+        assert_eq!(by_recipient.stream_ids, vec![&ctx.env, *id]);
+    }
+}
+
+#[test]
+fn test_status_filter_pages_never_drop_matches() {
+    let ctx = Ctx::setup();
+    let [active, paused, _, _] = ctx.create_mixed();
+    let late_active = ctx.create_for(&ctx.recipient);
+
+    // A window of two index entries may hold no match at all, but the
+    // cursor still advances.
+    let second = ctx.client.get_sender_streams_by_status(
+        &ctx.sender,
+        &StreamStatus::Active,
+        &ids_at(&ctx, 2),
+        &2,
+    );
+    assert_eq!(second.stream_ids.len(), 0);
+    assert_eq!(second.next_cursor, late_active);
+
+    let walked = collect(
+        |cursor| {
+            ctx.client
+                .get_sender_streams_by_status(&ctx.sender, &StreamStatus::Active, &cursor, &2)
+        },
+        &ctx.env,
+    );
+    assert_eq!(walked, vec![&ctx.env, active, late_active]);
+
+    let walked = collect(
+        |cursor| {
+            ctx.client.get_recipient_streams_by_status(
+                &ctx.recipient,
+                &StreamStatus::Paused,
+                &cursor,
+                &1,
+            )
+        },
+        &ctx.env,
+    );
+    assert_eq!(walked, vec![&ctx.env, paused]);
+}
+
+/// Stream ID at `index` in the sender's index.
+fn ids_at(ctx: &Ctx, index: u32) -> u64 {
+    ctx.client
+        .get_sender_streams_paginated(&ctx.sender, &0, &10)
+        .stream_ids
+        .get(index)
+        .unwrap()
+}
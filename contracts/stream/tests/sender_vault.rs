@@ -0,0 +1,264 @@
+//! Integration tests for per-sender vault balances.
+//!
+//! # What is tested
+//!
+//! 1. `deposit_to_vault` / `withdraw_from_vault` move tokens and keep the vault
+//!    counted in `TotalLiabilities`.
+//! 2. With vault funding on, creation and top-ups debit the vault and refunds
+//!    credit it, with no token transfer to or from the sender.
+//! 3. A short vault fails creation with `InsufficientBalance`, and
+//!    `create_streams_partial` reports it per entry.
+//! 4. `sweep_excess` only sweeps tokens above streams plus vaults.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test sender_vault
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, CreateStreamResult, FluxoraStream, FluxoraStreamClient,
+    StreamKind, VaultBalanceChanged,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn params(&self) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: 1_000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn vault(&self) -> i128 {
+        self.client()
+            .get_vault_balance(&self.sender, &self.token_id)
+    }
+
+    /// Deposit `amount` into the sender's vault and switch vault funding on.
+    fn fund_vault(&self, amount: i128) {
+        self.client()
+            .deposit_to_vault(&self.sender, &self.token_id, &amount);
+        self.client().set_vault_funding(&self.sender, &true);
+    }
+}
+
+#[test]
+fn deposit_and_withdraw_track_liabilities() {
+    let ctx = Ctx::setup();
+    assert_eq!(ctx.vault(), 0);
+
+    assert_eq!(
+        ctx.client()
+            .deposit_to_vault(&ctx.sender, &ctx.token_id, &5_000),
+        5_000
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 5_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 5_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 5_000);
+
+    assert_eq!(
+        ctx.client()
+            .withdraw_from_vault(&ctx.sender, &ctx.token_id, &2_000),
+        3_000
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 7_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 3_000);
+
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("vault_wdr"));
+    let payload = VaultBalanceChanged::try_from_val(&ctx.env, &last.2).unwrap();
+    assert_eq!(payload.amount, 2_000);
+    assert_eq!(payload.balance, 3_000);
+
+    assert_eq!(
+        ctx.client()
+            .try_withdraw_from_vault(&ctx.sender, &ctx.token_id, &3_001),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_deposit_to_vault(&ctx.sender, &ctx.token_id, &0),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let other_token = Address::generate(&ctx.env);
+    assert_eq!(
+        ctx.client()
+            .try_deposit_to_vault(&ctx.sender, &other_token, &10),
+        Err(Ok(ContractError::TokenNotAllowed))
+    );
+}
+
+#[test]
+fn vault_funds_creation_top_up_and_refund() {
+    let ctx = Ctx::setup();
+    ctx.fund_vault(3_000);
+    assert!(ctx.client().get_vault_funding(&ctx.sender));
+    let wallet = ctx.token().balance(&ctx.sender);
+
+    let id = ctx.client().create_stream(&ctx.sender, &ctx.params());
+    ctx.client().top_up_stream(&id, &ctx.sender, &500);
+    assert_eq!(ctx.vault(), 1_500);
+    assert_eq!(ctx.client().get_total_liabilities(), 3_000);
+
+    ctx.env.ledger().set_timestamp(100);
+    ctx.client().cancel_stream(&id);
+
+    // 1_400 unstreamed refunded into the vault; 100 still owed to the recipient.
+    assert_eq!(ctx.vault(), 2_900);
+    assert_eq!(ctx.client().get_total_liabilities(), 3_000);
+    assert_eq!(ctx.token().balance(&ctx.sender), wallet);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 3_000);
+
+    let credit = ctx
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            let topic: Symbol = e.1.get(0).unwrap().into_val(&ctx.env);
+            topic == symbol_short!("vault_crd")
+        })
+        .unwrap();
+    let payload = VaultBalanceChanged::try_from_val(&ctx.env, &credit.2).unwrap();
+    assert_eq!(payload.amount, 1_400);
+
+    // Switching vault funding off sends refunds back as tokens again.
+    ctx.client().set_vault_funding(&ctx.sender, &false);
+    let id = ctx.client().create_stream(&ctx.sender, &{
+        let mut params = ctx.params();
+        params.start_time = 100;
+        params.cliff_time = 100;
+        params.end_time = 1_100;
+        params
+    });
+    assert_eq!(ctx.token().balance(&ctx.sender), wallet - 1_000);
+    ctx.client().cancel_stream(&id);
+    assert_eq!(ctx.token().balance(&ctx.sender), wallet);
+    assert_eq!(ctx.vault(), 2_900);
+}
+
+#[test]
+fn short_vault_rejects_creation() {
+    let ctx = Ctx::setup();
+    ctx.fund_vault(1_500);
+
+    ctx.client().create_stream(&ctx.sender, &ctx.params());
+    assert_eq!(
+        ctx.client().try_create_stream(&ctx.sender, &ctx.params()),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    assert_eq!(
+        ctx.client().create_streams_partial(
+            &ctx.sender,
+            &vec![
+                &ctx.env,
+                {
+                    let mut params = ctx.params();
+                    params.deposit_amount = 500;
+                    params.end_time = 500;
+                    params
+                },
+                ctx.params()
+            ],
+        ),
+        vec![
+            &ctx.env,
+            CreateStreamResult {
+                success: true,
+                stream_id: Some(1),
+                error: None,
+            },
+            CreateStreamResult {
+                success: false,
+                stream_id: None,
+                error: Some(ContractError::InsufficientBalance as u32),
+            },
+        ]
+    );
+    assert_eq!(ctx.vault(), 0);
+    assert_eq!(ctx.token().balance(&ctx.sender), 8_500);
+}
+
+#[test]
+fn sweep_excess_leaves_vaults_untouched() {
+    let ctx = Ctx::setup();
+    ctx.fund_vault(2_000);
+    ctx.client().create_stream(&ctx.sender, &ctx.params());
+
+    StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&ctx.contract_id, &700);
+    let treasury = Address::generate(&ctx.env);
+    assert_eq!(ctx.client().sweep_excess(&treasury), 700);
+    assert_eq!(ctx.token().balance(&treasury), 700);
+    assert_eq!(ctx.client().sweep_excess(&treasury), 0);
+
+    assert_eq!(
+        ctx.client()
+            .withdraw_from_vault(&ctx.sender, &ctx.token_id, &1_000),
+        0
+    );
+}
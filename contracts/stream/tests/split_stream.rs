@@ -0,0 +1,256 @@
+//! Integration tests for `split_stream`.
+//!
+//! # What is tested
+//!
+//! 1. A basis-point split divides deposit, rate and withdrawn amount, keeps
+//!    the schedule, cliff and kind, and leaves `TotalLiabilities` unchanged.
+//! 2. Both outputs are independent: no `parent_stream_id`, separate
+//!    cancellation, and together they pay out exactly the original deposit.
+//! 3. `SplitShare::Amount` converts an absolute balance into a share.
+//! 4. Out-of-range shares, a new recipient equal to the sender or the
+//!    contract, paused streams and unknown IDs are rejected. (Rounding and
+//!    unsupported-kind rejection are covered by the unit tests in
+//!    `accrual::split_terms`.)
+//! 5. The split emits `created` for the new stream, then `split`.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test split_stream
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, PauseReason, SplitShare,
+    StreamCreated, StreamKind,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env, IntoVal, Symbol,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn create(&self, kind: StreamKind, cliff_time: u64) -> u64 {
+        self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: self.recipient.clone(),
+                deposit_amount: 10_000,
+                rate_per_second: 10,
+                start_time: 0,
+                cliff_time,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+}
+
+#[test]
+fn bps_split_divides_balances_and_keeps_schedule() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(StreamKind::Linear, 100);
+    ctx.advance(400);
+    ctx.client().withdraw(&id, &None);
+
+    let other = Address::generate(&ctx.env);
+    let new_id = ctx
+        .client()
+        .split_stream(&id, &SplitShare::Bps(2_000), &other);
+    let events = ctx.env.events().all();
+    let last = events.last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("split"));
+    let created = events.get(events.len() - 2).unwrap();
+    assert_eq!(
+        created.1,
+        (symbol_short!("created"), new_id).into_val(&ctx.env)
+    );
+    let created: StreamCreated = created.2.into_val(&ctx.env);
+    assert_eq!(
+        (
+            created.sender,
+            created.recipient.clone(),
+            created.deposit_amount
+        ),
+        (ctx.sender.clone(), other.clone(), 2_000)
+    );
+
+    let kept = ctx.client().get_stream_state(&id);
+    let split = ctx.client().get_stream_state(&new_id);
+    assert_eq!(split.recipient, other);
+    assert_eq!(split.parent_stream_id, None);
+    assert_eq!(
+        (
+            split.start_time,
+            split.cliff_time,
+            split.end_time,
+            split.kind
+        ),
+        (kept.start_time, kept.cliff_time, kept.end_time, kept.kind)
+    );
+    assert_eq!(split.deposit_amount, 2_000);
+    assert_eq!(split.rate_per_second, 2);
+    assert_eq!(split.withdrawn_amount, 800);
+    assert_eq!(kept.deposit_amount + split.deposit_amount, 10_000);
+    assert_eq!(kept.rate_per_second + split.rate_per_second, 10);
+    assert_eq!(kept.withdrawn_amount + split.withdrawn_amount, 4_000);
+    assert_eq!(ctx.client().get_total_liabilities(), 6_000);
+    assert_eq!(ctx.client().get_stream_token(&new_id), ctx.token_id);
+}
+
+#[test]
+fn outputs_settle_independently_and_exactly() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(StreamKind::Linear, 0);
+    ctx.advance(300);
+
+    let other = Address::generate(&ctx.env);
+    let new_id = ctx
+        .client()
+        .split_stream(&id, &SplitShare::Bps(3_333), &other);
+
+    // Cancelling the new stream does not touch the original.
+    ctx.advance(500);
+    let sender_before = ctx.token().balance(&ctx.sender);
+    ctx.client().cancel_stream(&new_id);
+    let refund = ctx.token().balance(&ctx.sender) - sender_before;
+    ctx.client().withdraw(&new_id, &None);
+
+    ctx.advance(1_000);
+    ctx.client().withdraw(&id, &None);
+
+    let paid = ctx.token().balance(&ctx.recipient) + ctx.token().balance(&other);
+    assert_eq!(paid + refund, 10_000);
+    assert_eq!(ctx.token().balance(&ctx.contract_id), 0);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+}
+
+#[test]
+fn amount_split_targets_unwithdrawn_balance() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(StreamKind::Linear, 0);
+    ctx.advance(200);
+    ctx.client().withdraw(&id, &None);
+
+    let other = Address::generate(&ctx.env);
+    let new_id = ctx
+        .client()
+        .split_stream(&id, &SplitShare::Amount(4_000), &other);
+    let split = ctx.client().get_stream_state(&new_id);
+    assert_eq!(split.deposit_amount - split.withdrawn_amount, 4_000);
+    assert_eq!(split.rate_per_second, 5);
+}
+
+#[test]
+fn cliff_only_split_unlocks_both_at_cliff() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(StreamKind::CliffOnly, 500);
+    let other = Address::generate(&ctx.env);
+    let new_id = ctx
+        .client()
+        .split_stream(&id, &SplitShare::Bps(4_000), &other);
+
+    ctx.advance(500);
+    assert_eq!(ctx.client().withdraw(&id, &None), 6_000);
+    assert_eq!(ctx.client().withdraw(&new_id, &None), 4_000);
+}
+
+#[test]
+fn rejects_invalid_splits() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(StreamKind::Linear, 0);
+    let other = Address::generate(&ctx.env);
+
+    for share in [
+        SplitShare::Bps(0),
+        SplitShare::Bps(10_000),
+        SplitShare::Amount(0),
+        SplitShare::Amount(10_000),
+    ] {
+        assert_eq!(
+            ctx.client().try_split_stream(&id, &share, &other),
+            Err(Ok(ContractError::InvalidParams))
+        );
+    }
+
+    for new_recipient in [&ctx.sender, &ctx.contract_id] {
+        assert_eq!(
+            ctx.client()
+                .try_split_stream(&id, &SplitShare::Bps(5_000), new_recipient),
+            Err(Ok(ContractError::InvalidParams))
+        );
+    }
+
+    ctx.client().pause_stream(&id, &PauseReason::Operational);
+    assert_eq!(
+        ctx.client()
+            .try_split_stream(&id, &SplitShare::Bps(5_000), &other),
+        Err(Ok(ContractError::InvalidState))
+    );
+    assert_eq!(
+        ctx.client()
+            .try_split_stream(&99, &SplitShare::Bps(5_000), &other),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
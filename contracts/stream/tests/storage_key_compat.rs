@@ -12,9 +12,9 @@
 //! 3. Asserting that V6-only keys (discriminants 15–20) are absent on a
 //!    V5-seeded instance, confirming no phantom reads.
 //! 4. Cross-checking `CONTRACT_VERSION` against the live `DataKey` variant count
-//!    (currently 37) to ensure versioning discipline when new variants are added.
+//!    (currently 50) to ensure versioning discipline when new variants are added.
 //!
-//! # Discriminant Table Overview (37 variants: 0–36)
+//! # Discriminant Table Overview (50 variants: 0–49)
 //!
 //! | Disc | Variant                     | Storage    | Added in |
 //! |-----:|:----------------------------|:-----------|:----------|
@@ -79,7 +79,7 @@
 //! |   27 | `PausedStreamCount`                | Instance   |
 //! |   28 | `TotalKeeperFeesPaid`              | Instance   |
 //!
-//! # Post-V7 additive variants (discriminants 29–36)
+//! # Post-V7 additive variants (discriminants 29–49)
 //!
 //! | Disc | Variant                                | Storage    |
 //! |-----:|:---------------------------------------|:-----------|
@@ -91,8 +91,21 @@
 //! |   34 | `PooledStreamShares(u64)`              | Persistent |
 //! |   35 | `PooledStreamWithdrawn(u64, Address)`  | Persistent |
 //! |   36 | `DelegatedCancelNonce(Address)`        | Persistent |
+//! |   37 | `RateSchedule(u64)`                    | Persistent |
+//! |   38 | `StreamToken(u64)`                     | Persistent |
+//! |   39 | `AllowedToken(Address)`                | Persistent |
+//! |   40 | `TokenLiabilities(Address)`            | Instance   |
+//! |   41 | `ProtocolFee`                          | Instance   |
+//! |   42 | `Treasury`                             | Instance   |
+//! |   43 | `ProtocolFeeLiabilities(Address)`      | Instance   |
+//! |   44 | `OwnerPositions(Address)`              | Persistent |
+//! |   45 | `PositionApproval(u64)`                | Persistent |
+//! |   46 | `RecipientHook(u64)`                   | Persistent |
+//! |   47 | `RefundHook(u64)`                      | Persistent |
+//! |   48 | `UpgradeCount`                         | Instance   |
+//! |   49 | `UpgradeRecord(u32)`                   | Persistent |
 //!
-//! Total live `DataKey` variant count: **37** (discriminants 0–36).
+//! Total live `DataKey` variant count: **50** (discriminants 0–49).
 //!
 //! # Version Mapping Table (`CONTRACT_VERSION` => Expected DataKey Count)
 //!
@@ -100,7 +113,8 @@
 //! |------------------|------------------------|---------------|-------|
 //! | 5                | 15                     | 0..=14        | V5 frozen layout |
 //! | 6                | 29                     | 0..=28        | V6 freeze + 8 post-freeze additive variants |
-//! | 9                | 37                     | 0..=36        | Current live count |
+//! | 9                | 37                     | 0..=36        | Adds `DelegatedCancelNonce` |
+//! | 10               | 50                     | 0..=49        | Current live count |
 //!
 //! # Companion Documentation
 //! - `contracts/stream/src/checksum.rs` (WASM checksum & key layout documentation)
@@ -1338,6 +1352,55 @@ fn discriminant_36_delegated_cancel_nonce_round_trips() {
     });
 }
 
+/// Discriminant 37 (RateSchedule) is append-only and persistent.
+#[test]
+fn discriminant_37_rate_schedule_round_trips() {
+    let ctx = Ctx::setup();
+    let cid = ctx.contract_id.clone();
+    ctx.env.as_contract(&cid, || {
+        let key = DataKey::RateSchedule(4);
+        let packed = soroban_sdk::vec![&ctx.env, 1u128, 2u128];
+        ctx.env.storage().persistent().set(&key, &packed);
+        let value: soroban_sdk::Vec<u128> = ctx
+            .env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("RateSchedule must round-trip at discriminant 37");
+        assert_eq!(value, packed);
+    });
+}
+
+/// Discriminants 38–40 (multi-token keys) are append-only and round-trip.
+#[test]
+fn discriminants_38_to_40_multi_token_keys_round_trip() {
+    let ctx = Ctx::setup();
+    let token = Address::generate(&ctx.env);
+    let cid = ctx.contract_id.clone();
+    ctx.env.as_contract(&cid, || {
+        let persistent = ctx.env.storage().persistent();
+        persistent.set(&DataKey::StreamToken(4), &token);
+        persistent.set(&DataKey::AllowedToken(token.clone()), &true);
+        ctx.env
+            .storage()
+            .instance()
+            .set(&DataKey::TokenLiabilities(token.clone()), &55_i128);
+
+        let stored: Address = persistent
+            .get(&DataKey::StreamToken(4))
+            .expect("StreamToken must round-trip at discriminant 38");
+        assert_eq!(stored, token);
+        assert!(persistent.has(&DataKey::AllowedToken(token.clone())));
+        let liabilities: i128 = ctx
+            .env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenLiabilities(token.clone()))
+            .expect("TokenLiabilities must round-trip at discriminant 40");
+        assert_eq!(liabilities, 55);
+    });
+}
+
 /// Discriminant 30 (MaxLookbackLedgers) round-trips correctly.
 #[test]
 fn discriminant_30_max_lookback_ledgers_round_trips() {
@@ -1457,7 +1520,8 @@ fn version_entry_point_is_stable_before_and_after_init() {
 /// |------------------|--------------------------------|--------------------|-------|
 /// | 5                | 15                             | 0..=14             | V5 release freeze |
 /// | 6                | 29                             | 0..=28             | V6 freeze (21) + 8 post-freeze additive variants |
-/// | 9                | 37                             | 0..=36             | Current live count |
+/// | 9                | 37                             | 0..=36             | Adds `DelegatedCancelNonce` |
+/// | 10               | 50                             | 0..=49             | Current live count |
 ///
 /// # Security Safeguard & Maintenance Protocol
 /// When a new `DataKey` variant is appended or `CONTRACT_VERSION` is bumped:
@@ -1473,6 +1537,13 @@ pub fn expected_datakey_count_for_version(version: u32) -> usize {
         7 | 8 => 36,
         // V9 includes append-only DelegatedCancelNonce at discriminant 36.
         9 => 37,
+        // V10 appends RateSchedule (Piecewise segment schedules) at discriminant 37
+        // StreamToken / AllowedToken / TokenLiabilities (multi-token) at 38–40,
+        // ProtocolFee / Treasury / ProtocolFeeLiabilities (protocol fees) at 41–43,
+        // OwnerPositions / PositionApproval (stream positions) at 44–45,
+        // RecipientHook / RefundHook (payout and refund hooks) at 46–47,
+        // and UpgradeCount / UpgradeRecord (upgrade history) at 48–49.
+        10 => 50,
         other => panic!(
             "Unhandled CONTRACT_VERSION = {other} in expected_datakey_count_for_version. \
              When incrementing CONTRACT_VERSION, you must update the version mapping table in \
@@ -1482,7 +1553,7 @@ pub fn expected_datakey_count_for_version(version: u32) -> usize {
     }
 }
 
-/// Constructs a vector containing sample instances of all 37 live `DataKey`
+/// Constructs a vector containing sample instances of all 50 live `DataKey`
 /// variants in declaration order.
 ///
 /// Includes an exhaustive `match` on `DataKey` so that adding any new variant
@@ -1530,6 +1601,19 @@ pub fn all_live_datakey_variants(env: &Env) -> soroban_sdk::Vec<DataKey> {
         DataKey::PooledStreamShares(0),                        // 34
         DataKey::PooledStreamWithdrawn(0, dummy_addr.clone()), // 35
         DataKey::DelegatedCancelNonce(dummy_addr.clone()),     // 36
+        DataKey::RateSchedule(0),                              // 37
+        DataKey::StreamToken(0),                               // 38
+        DataKey::AllowedToken(dummy_addr.clone()),             // 39
+        DataKey::TokenLiabilities(dummy_addr.clone()),         // 40
+        DataKey::ProtocolFee,                                  // 41
+        DataKey::Treasury,                                     // 42
+        DataKey::ProtocolFeeLiabilities(dummy_addr.clone()),   // 43
+        DataKey::OwnerPositions(dummy_addr.clone()),           // 44
+        DataKey::PositionApproval(0),                          // 45
+        DataKey::RecipientHook(0),                             // 46
+        DataKey::RefundHook(0),                                // 47
+        DataKey::UpgradeCount,                                 // 48
+        DataKey::UpgradeRecord(0),                             // 49
     ];
 
     // Exhaustive match check — compile error if any DataKey variant is missing here.
@@ -1573,6 +1657,19 @@ pub fn all_live_datakey_variants(env: &Env) -> soroban_sdk::Vec<DataKey> {
         DataKey::PooledStreamShares(_) => {}
         DataKey::PooledStreamWithdrawn(_, _) => {}
         DataKey::DelegatedCancelNonce(_) => {}
+        DataKey::RateSchedule(_) => {}
+        DataKey::StreamToken(_) => {}
+        DataKey::AllowedToken(_) => {}
+        DataKey::TokenLiabilities(_) => {}
+        DataKey::ProtocolFee => {}
+        DataKey::Treasury => {}
+        DataKey::ProtocolFeeLiabilities(_) => {}
+        DataKey::OwnerPositions(_) => {}
+        DataKey::PositionApproval(_) => {}
+        DataKey::RecipientHook(_) => {}
+        DataKey::RefundHook(_) => {}
+        DataKey::UpgradeCount => {}
+        DataKey::UpgradeRecord(_) => {}
     };
     // Suppress unused-variable warning — the closure is only here for compile-time exhaustiveness.
     let _ = _check_exhaustive;
@@ -1637,14 +1734,20 @@ fn test_expected_datakey_count_mapping_unhandled_version_panics() {
     expected_datakey_count_for_version(999);
 }
 
-/// Assert exact live variant count is 37 (discriminants 0..=36).
+/// Edge case: V10 version mapping expected count is 50.
+#[test]
+fn test_expected_datakey_count_mapping_v10() {
+    assert_eq!(expected_datakey_count_for_version(10), 50);
+}
+
+/// Assert exact live variant count is 50 (discriminants 0..=49).
 #[test]
-fn test_datakey_variant_count_exact_37() {
+fn test_datakey_variant_count_exact_50() {
     let env = Env::default();
     let live_variants = all_live_datakey_variants(&env);
     assert_eq!(
         live_variants.len() as usize,
-        37,
+        50,
         "DataKey variant count changed without updating storage_key_compat test suite. \
          Add the new variant to all_live_datakey_variants() and update \
          expected_datakey_count_for_version()."
@@ -1654,7 +1757,7 @@ fn test_datakey_variant_count_exact_37() {
 /// Regression test: Verifies that synthetic version drift triggers an explicit assertion failure.
 #[test]
 fn test_regression_staleness_mismatch_detection() {
-    let live_count = 37usize; // current live variant count
+    let live_count = 50usize; // current live variant count
     let stale_version_expected_count = expected_datakey_count_for_version(5); // V5 expects 15
 
     assert_ne!(
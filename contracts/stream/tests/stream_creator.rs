@@ -0,0 +1,222 @@
+//! Integration tests for locking stream creation to a creator.
+//!
+//! # What is tested
+//!
+//! 1. `init` leaves creation open, and `init_with_creator` records the creator
+//!    and cannot re-initialise the contract.
+//! 2. On a locked contract, `create_stream`, `create_streams`,
+//!    `create_pooled_stream` and `create_stream_offer` are rejected when only
+//!    the sender authorizes them, and no stream ID is allocated.
+//! 3. With the creator's authorization as well, creation succeeds.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test stream_creator
+//! ```
+
+#![cfg(test)]
+
+extern crate std;
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Val, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    admin: Address,
+    creator: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            admin,
+            creator,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn params(&self) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: 1_000,
+            rate_per_second: 10,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 100,
+            withdraw_dust_threshold: None,
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    /// Authorize the next call as the sender only.
+    fn sender_only(&self, fn_name: &str, args: Vec<Val>) {
+        self.env.mock_auths(&[MockAuth {
+            address: &self.sender,
+            invoke: &MockAuthInvoke {
+                contract: &self.contract_id,
+                fn_name,
+                args,
+                sub_invokes: &[],
+            },
+        }]);
+    }
+}
+
+#[test]
+fn init_leaves_creation_open() {
+    let ctx = Ctx::setup();
+    ctx.client().init(&ctx.token_id, &ctx.admin);
+    assert_eq!(ctx.client().get_stream_creator(), None);
+
+    let params = ctx.params();
+    ctx.sender_only(
+        "create_stream",
+        (&ctx.sender, params.clone()).into_val(&ctx.env),
+    );
+    ctx.client().create_stream(&ctx.sender, &params);
+    assert_eq!(ctx.client().get_stream_count(), 1);
+}
+
+#[test]
+fn init_with_creator_records_the_creator_once() {
+    let ctx = Ctx::setup();
+    ctx.client()
+        .init_with_creator(&ctx.token_id, &ctx.admin, &ctx.creator);
+    assert_eq!(ctx.client().get_stream_creator(), Some(ctx.creator.clone()));
+    assert_eq!(
+        ctx.client()
+            .try_init_with_creator(&ctx.token_id, &ctx.admin, &ctx.sender),
+        Err(Ok(ContractError::AlreadyInitialised))
+    );
+    assert_eq!(ctx.client().get_stream_creator(), Some(ctx.creator.clone()));
+}
+
+#[test]
+fn locked_creation_rejects_sender_only_calls() {
+    let ctx = Ctx::setup();
+    ctx.client()
+        .init_with_creator(&ctx.token_id, &ctx.admin, &ctx.creator);
+    let params = ctx.params();
+
+    ctx.sender_only(
+        "create_stream",
+        (&ctx.sender, params.clone()).into_val(&ctx.env),
+    );
+    assert!(ctx
+        .client()
+        .try_create_stream(&ctx.sender, &params)
+        .is_err());
+
+    let batch = vec![&ctx.env, params.clone()];
+    ctx.sender_only(
+        "create_streams",
+        (&ctx.sender, batch.clone()).into_val(&ctx.env),
+    );
+    assert!(ctx
+        .client()
+        .try_create_streams(&ctx.sender, &batch)
+        .is_err());
+
+    let members = vec![&ctx.env, (ctx.recipient.clone(), 1u32)];
+    let pooled_args: Vec<Val> = vec![
+        &ctx.env,
+        ctx.sender.into_val(&ctx.env),
+        members.into_val(&ctx.env),
+        1_000_i128.into_val(&ctx.env),
+        10_i128.into_val(&ctx.env),
+        0u64.into_val(&ctx.env),
+        0u64.into_val(&ctx.env),
+        100u64.into_val(&ctx.env),
+        0_i128.into_val(&ctx.env),
+        Option::<soroban_sdk::Bytes>::None.into_val(&ctx.env),
+        StreamKind::Linear.into_val(&ctx.env),
+    ];
+    ctx.sender_only("create_pooled_stream", pooled_args);
+    assert!(ctx
+        .client()
+        .try_create_pooled_stream(
+            &ctx.sender,
+            &members,
+            &1_000,
+            &10,
+            &0,
+            &0,
+            &100,
+            &0,
+            &None,
+            &StreamKind::Linear,
+        )
+        .is_err());
+
+    ctx.sender_only(
+        "create_stream_offer",
+        (&ctx.sender, params.clone(), Option::<u64>::None).into_val(&ctx.env),
+    );
+    assert!(ctx
+        .client()
+        .try_create_stream_offer(&ctx.sender, &params, &None)
+        .is_err());
+
+    assert_eq!(ctx.client().get_stream_count(), 0);
+}
+
+#[test]
+fn locked_creation_accepts_creator_authorized_calls() {
+    let ctx = Ctx::setup();
+    ctx.client()
+        .init_with_creator(&ctx.token_id, &ctx.admin, &ctx.creator);
+
+    let stream_id = ctx.client().create_stream(&ctx.sender, &ctx.params());
+    assert!(ctx
+        .env
+        .auths()
+        .iter()
+        .any(|(address, _)| *address == ctx.creator));
+    assert_eq!(
+        ctx.client().get_stream_state(&stream_id).recipient,
+        ctx.recipient
+    );
+}
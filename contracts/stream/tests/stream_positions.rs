@@ -0,0 +1,511 @@
+//! Integration tests for the NFT-style stream position interface.
+//!
+//! # What is tested
+//!
+//! 1. `owner_of` resolves to the recipient until ownership is transferred.
+//! 2. `approve` / `get_approved` manage a single per-position approval and
+//!    only the current owner may approve.
+//! 3. `transfer_from` works for the owner and the approved spender, rejects
+//!    anyone else, clears the approval and moves withdrawal rights.
+//! 4. `transfer_claim_ownership` and `transfer_from` keep the owner index
+//!    used by `get_owner_positions` / `balance_of` in sync.
+//! 5. Transfers emit `pos_xfer`; approvals emit `pos_appr`.
+//! 6. Closing a stream drops it from the owner index.
+//! 7. Accepting a recipient rotation moves an untransferred position, emits
+//!    `pos_xfer` and leaves transferred positions with their claim owner.
+//! 8. `balance_of` and `get_owner_positions_paginated` cover every position,
+//!    including owners holding more than one page.
+//! 9. A transfer clears the seller's auto-claim destination, and only the new
+//!    owner can set a new one.
+//! 10. After a transfer, `withdraw`, `keeper_cancel`, `bulk_cancel_streams` and
+//!     `delegated_withdraw` pay the new owner (credited in claim-to-balance
+//!     mode), and only the new owner's key can sign a relayed withdrawal.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test stream_positions
+//! ```
+
+#![cfg(test)]
+
+use ed25519_dalek::{Signer, SigningKey};
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec,
+    xdr::{AccountId, PublicKey, ScAddress, Uint256},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryIntoVal,
+};
+
+/// Mirrors the stream contract's private keeper grace period (7 days).
+const KEEPER_GRACE_PERIOD_SECONDS: u64 = 604_800;
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &1_000_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn create(&self) -> u64 {
+        self.create_for(&self.recipient)
+    }
+
+    fn create_for(&self, recipient: &Address) -> u64 {
+        self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+
+    fn last_topic(&self) -> Symbol {
+        let last = self.env.events().all().last().unwrap();
+        last.1.get(0).unwrap().into_val(&self.env)
+    }
+}
+
+/// Account address of an ed25519 signing key.
+fn account_of(env: &Env, signing_key: &SigningKey) -> Address {
+    let pk = signing_key.verifying_key().to_bytes();
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk))))
+        .try_into_val(env)
+        .unwrap()
+}
+
+/// Signature over the `delegated_withdraw` message with a zero minimum and
+/// relayer fee.
+fn sign_withdrawal(
+    env: &Env,
+    signing_key: &SigningKey,
+    stream_id: u64,
+    nonce: u64,
+    deadline: u64,
+) -> BytesN<64> {
+    let mut msg = Bytes::new(env);
+    msg.extend_from_array(&stream_id.to_be_bytes());
+    msg.extend_from_array(&nonce.to_be_bytes());
+    msg.extend_from_array(&deadline.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    msg.extend_from_array(&0i128.to_be_bytes());
+    let bytes: std::vec::Vec<u8> = (0..msg.len()).map(|i| msg.get_unchecked(i)).collect();
+    BytesN::from_array(env, &signing_key.sign(&bytes).to_bytes())
+}
+
+#[test]
+fn owner_defaults_to_recipient() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+
+    assert_eq!(ctx.client().owner_of(&id), ctx.recipient);
+    assert_eq!(ctx.client().get_approved(&id), None);
+    assert_eq!(
+        ctx.client().get_owner_positions(&ctx.recipient),
+        vec![&ctx.env, id]
+    );
+    assert_eq!(ctx.client().balance_of(&ctx.recipient), 1);
+    assert_eq!(
+        ctx.client().try_owner_of(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
+
+#[test]
+fn approve_is_owner_only() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let spender = Address::generate(&ctx.env);
+
+    assert_eq!(
+        ctx.client()
+            .try_approve(&ctx.sender, &Some(spender.clone()), &id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    ctx.client()
+        .approve(&ctx.recipient, &Some(spender.clone()), &id);
+    assert_eq!(ctx.last_topic(), symbol_short!("pos_appr"));
+    assert_eq!(ctx.client().get_approved(&id), Some(spender));
+
+    ctx.client().approve(&ctx.recipient, &None, &id);
+    assert_eq!(ctx.client().get_approved(&id), None);
+}
+
+#[test]
+fn approved_spender_can_transfer() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let spender = Address::generate(&ctx.env);
+    let buyer = Address::generate(&ctx.env);
+
+    assert_eq!(
+        ctx.client()
+            .try_transfer_from(&spender, &ctx.recipient, &buyer, &id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    ctx.client()
+        .approve(&ctx.recipient, &Some(spender.clone()), &id);
+    ctx.client()
+        .transfer_from(&spender, &ctx.recipient, &buyer, &id);
+    assert_eq!(ctx.last_topic(), symbol_short!("pos_xfer"));
+
+    assert_eq!(ctx.client().owner_of(&id), buyer);
+    assert_eq!(ctx.client().get_approved(&id), None);
+
+    // The old approval does not survive the transfer.
+    assert_eq!(
+        ctx.client()
+            .try_transfer_from(&spender, &buyer, &spender, &id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Withdrawal rights moved with the position.
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw_to(&id, &buyer), 300);
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.token_id).balance(&buyer),
+        300
+    );
+}
+
+#[test]
+fn transfer_from_rejects_wrong_from() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let other = Address::generate(&ctx.env);
+
+    assert_eq!(
+        ctx.client()
+            .try_transfer_from(&other, &other, &ctx.sender, &id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(ctx.client().owner_of(&id), ctx.recipient);
+}
+
+#[test]
+fn enumeration_follows_transfers() {
+    let ctx = Ctx::setup();
+    let first = ctx.create();
+    let second = ctx.create();
+    let third = ctx.create();
+    let alice = Address::generate(&ctx.env);
+    let bob = Address::generate(&ctx.env);
+
+    ctx.client()
+        .transfer_claim_ownership(&second, &ctx.recipient, &alice);
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &alice, &third);
+
+    assert_eq!(
+        ctx.client().get_owner_positions(&ctx.recipient),
+        vec![&ctx.env, first]
+    );
+    assert_eq!(
+        ctx.client().get_owner_positions(&alice),
+        vec![&ctx.env, second, third]
+    );
+
+    ctx.client().transfer_from(&alice, &alice, &bob, &second);
+    assert_eq!(
+        ctx.client().get_owner_positions(&alice),
+        vec![&ctx.env, third]
+    );
+    assert_eq!(
+        ctx.client().get_owner_positions(&bob),
+        vec![&ctx.env, second]
+    );
+
+    // Transferring back to the recipient keeps a single, sorted entry.
+    ctx.client()
+        .transfer_from(&bob, &bob, &ctx.recipient, &second);
+    assert_eq!(
+        ctx.client().get_owner_positions(&ctx.recipient),
+        vec![&ctx.env, first, second]
+    );
+    assert_eq!(ctx.client().balance_of(&ctx.recipient), 2);
+    assert_eq!(ctx.client().balance_of(&bob), 0);
+}
+
+#[test]
+fn closing_stream_drops_position() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let buyer = Address::generate(&ctx.env);
+
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &buyer, &id);
+    ctx.advance(1_000);
+    ctx.client().withdraw(&id, &None);
+    ctx.client().close_completed_stream(&id);
+
+    assert_eq!(ctx.client().balance_of(&buyer), 0);
+    assert_eq!(
+        ctx.client().try_owner_of(&id),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
+
+#[test]
+fn recipient_rotation_moves_untransferred_position() {
+    let ctx = Ctx::setup();
+    let held = ctx.create();
+    let sold = ctx.create();
+    let buyer = Address::generate(&ctx.env);
+    let spender = Address::generate(&ctx.env);
+    let new_recipient = Address::generate(&ctx.env);
+
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &buyer, &sold);
+    ctx.client()
+        .approve(&ctx.recipient, &Some(spender.clone()), &held);
+
+    ctx.client().update_recipient(&held, &new_recipient);
+    ctx.client().accept_recipient_update(&held);
+    assert_eq!(ctx.last_topic(), symbol_short!("pos_xfer"));
+
+    assert_eq!(ctx.client().owner_of(&held), new_recipient);
+    assert_eq!(ctx.client().get_approved(&held), None);
+    assert_eq!(ctx.client().balance_of(&ctx.recipient), 0);
+    assert_eq!(ctx.client().balance_of(&new_recipient), 1);
+    assert_eq!(
+        ctx.client().get_owner_positions(&new_recipient),
+        vec![&ctx.env, held]
+    );
+
+    // A transferred position stays with its claim owner.
+    ctx.client().update_recipient(&sold, &new_recipient);
+    ctx.client().accept_recipient_update(&sold);
+    assert_eq!(ctx.last_topic(), symbol_short!("recp_upd"));
+    assert_eq!(ctx.client().owner_of(&sold), buyer);
+    assert_eq!(ctx.client().balance_of(&buyer), 1);
+    assert_eq!(ctx.client().balance_of(&new_recipient), 1);
+}
+
+#[test]
+fn balance_and_pages_cover_every_position() {
+    let ctx = Ctx::setup();
+    let owner = Address::generate(&ctx.env);
+    let total = 120u32;
+    ctx.env.budget().reset_unlimited();
+    for _ in 0..total {
+        ctx.create_for(&owner);
+    }
+
+    assert_eq!(ctx.client().balance_of(&owner), total);
+    assert_eq!(ctx.client().get_owner_positions(&owner).len(), 100);
+
+    let mut seen = 0u32;
+    let mut cursor = 0u64;
+    loop {
+        let page = ctx
+            .client()
+            .get_owner_positions_paginated(&owner, &cursor, &50);
+        seen += page.stream_ids.len();
+        if page.next_cursor == 0 {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    assert_eq!(seen, total);
+}
+
+#[test]
+fn seller_cannot_redirect_auto_claim_after_transfer() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let buyer = Address::generate(&ctx.env);
+
+    ctx.client().set_auto_claim(&id, &ctx.recipient);
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &buyer, &id);
+    assert_eq!(ctx.client().get_auto_claim_destination(&id), None);
+
+    // The seller's own signature no longer sets the destination.
+    ctx.env.mock_auths(&[MockAuth {
+        address: &ctx.recipient,
+        invoke: &MockAuthInvoke {
+            contract: &ctx.contract_id,
+            fn_name: "set_auto_claim",
+            args: (id, ctx.recipient.clone()).into_val(&ctx.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(ctx
+        .client()
+        .try_set_auto_claim(&id, &ctx.recipient)
+        .is_err());
+    assert_eq!(ctx.client().get_auto_claim_destination(&id), None);
+
+    ctx.env.mock_all_auths();
+    ctx.client().set_auto_claim(&id, &buyer);
+    assert_eq!(ctx.env.auths().first().unwrap().0, buyer);
+
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().trigger_auto_claim(&id), 1_000);
+    let token = TokenClient::new(&ctx.env, &ctx.token_id);
+    assert_eq!(token.balance(&buyer), 1_000);
+    assert_eq!(token.balance(&ctx.recipient), 0);
+}
+
+#[test]
+fn payouts_follow_transferred_position() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let buyer = Address::generate(&ctx.env);
+    let keeper = Address::generate(&ctx.env);
+    let token = TokenClient::new(&ctx.env, &ctx.token_id);
+
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &buyer, &id);
+
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw(&id, &None), 300);
+    assert_eq!(token.balance(&buyer), 300);
+
+    ctx.advance(1_000 + KEEPER_GRACE_PERIOD_SECONDS);
+    ctx.client().keeper_cancel(&id, &keeper);
+    assert_eq!(token.balance(&buyer), 1_000);
+    assert_eq!(token.balance(&ctx.recipient), 0);
+}
+
+#[test]
+fn bulk_cancel_credits_transferred_owner() {
+    let ctx = Ctx::setup();
+    let id = ctx.create();
+    let buyer = Address::generate(&ctx.env);
+
+    ctx.client()
+        .transfer_from(&ctx.recipient, &ctx.recipient, &buyer, &id);
+    ctx.client().set_claim_to_balance(&buyer, &true);
+
+    ctx.advance(400);
+    ctx.client()
+        .bulk_cancel_streams(&ctx.sender, &vec![&ctx.env, id]);
+    assert_eq!(
+        ctx.client().get_recipient_balance(&buyer, &ctx.token_id),
+        400
+    );
+    assert_eq!(
+        ctx.client()
+            .get_recipient_balance(&ctx.recipient, &ctx.token_id),
+        0
+    );
+    assert_eq!(
+        TokenClient::new(&ctx.env, &ctx.token_id).balance(&ctx.contract_id),
+        400
+    );
+}
+
+#[test]
+fn delegated_withdraw_follows_transferred_position() {
+    let ctx = Ctx::setup();
+    let seller_key = SigningKey::from_bytes(&[0x51u8; 32]);
+    let buyer_key = SigningKey::from_bytes(&[0x52u8; 32]);
+    let seller = account_of(&ctx.env, &seller_key);
+    let buyer = account_of(&ctx.env, &buyer_key);
+    let relayer = Address::generate(&ctx.env);
+    let id = ctx.create_for(&seller);
+
+    ctx.client().transfer_from(&seller, &seller, &buyer, &id);
+    // Account addresses hold no trustline here, so credit the payout.
+    ctx.client().set_claim_to_balance(&buyer, &true);
+    ctx.advance(500);
+    let deadline = 10_000;
+
+    let seller_sig = sign_withdrawal(&ctx.env, &seller_key, id, 0, deadline);
+    assert_eq!(
+        ctx.client().try_delegated_withdraw(
+            &id,
+            &relayer,
+            &BytesN::from_array(&ctx.env, &seller_key.verifying_key().to_bytes()),
+            &0,
+            &deadline,
+            &0,
+            &0,
+            &seller_sig,
+        ),
+        Err(Ok(ContractError::InvalidSignature))
+    );
+
+    let nonce = ctx.client().get_delegated_nonce(&buyer);
+    let buyer_sig = sign_withdrawal(&ctx.env, &buyer_key, id, nonce, deadline);
+    assert_eq!(
+        ctx.client().delegated_withdraw(
+            &id,
+            &relayer,
+            &BytesN::from_array(&ctx.env, &buyer_key.verifying_key().to_bytes()),
+            &nonce,
+            &deadline,
+            &0,
+            &0,
+            &buyer_sig,
+        ),
+        500
+    );
+    assert_eq!(
+        ctx.client().get_recipient_balance(&buyer, &ctx.token_id),
+        500
+    );
+    assert_eq!(ctx.client().get_delegated_nonce(&buyer), nonce + 1);
+    assert_eq!(ctx.client().get_delegated_nonce(&seller), 0);
+}
@@ -0,0 +1,228 @@
+//! Integration tests for subscription streams renewed in place.
+//!
+//! # What is tested
+//!
+//! 1. `renew_subscription` is rejected before the final period, then pulls
+//!    one period's deposit and extends `end_time` without changing the
+//!    stream ID.
+//! 2. Renewal stops with `sub_stop` and clears the subscription when the
+//!    sender's allowance or balance no longer covers a period.
+//! 3. `set_subscription` validation and clearing.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test subscription
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, StreamKind,
+    SubscriptionStopReason, SubscriptionStopped,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env, IntoVal, Symbol, TryFromVal,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+    keeper: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let keeper = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+            keeper,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 1 token/s from 0 to 1_000, renewed every 500 seconds.
+    fn create_subscription(&self) -> u64 {
+        let id = self.client().create_stream(
+            &self.sender,
+            &CreateStreamParams {
+                recipient: self.recipient.clone(),
+                deposit_amount: 1_000,
+                rate_per_second: 1,
+                start_time: 0,
+                cliff_time: 0,
+                end_time: 1_000,
+                withdraw_dust_threshold: Some(0),
+                memo: None,
+                metadata: None,
+                kind: StreamKind::Linear,
+                irrevocable: None,
+                witness: None,
+            },
+        );
+        self.client().set_subscription(&id, &Some(500));
+        id
+    }
+
+    fn renew(&self, stream_id: u64) -> bool {
+        self.client().renew_subscription(&stream_id, &self.keeper)
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10);
+    }
+
+    fn last_stop(&self) -> SubscriptionStopped {
+        let last = self.env.events().all().last().unwrap();
+        let topic: Symbol = last.1.get(0).unwrap().into_val(&self.env);
+        assert_eq!(topic, symbol_short!("sub_stop"));
+        SubscriptionStopped::try_from_val(&self.env, &last.2).unwrap()
+    }
+}
+
+#[test]
+fn renewal_extends_stream_in_place() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_subscription();
+
+    ctx.advance(499);
+    assert_eq!(
+        ctx.client().try_renew_subscription(&id, &ctx.keeper),
+        Err(Ok(ContractError::KeeperGracePeriodNotElapsed))
+    );
+
+    ctx.advance(500);
+    let sender_before = ctx.token().balance(&ctx.sender);
+    assert!(ctx.renew(id));
+    assert_eq!(sender_before - ctx.token().balance(&ctx.sender), 500);
+    let last = ctx.env.events().all().last().unwrap();
+    let topic: Symbol = last.1.get(0).unwrap().into_val(&ctx.env);
+    assert_eq!(topic, symbol_short!("sub_renew"));
+
+    let stream = ctx.client().get_stream_state(&id);
+    assert_eq!(stream.deposit_amount, 1_500);
+    assert_eq!(stream.end_time, 1_500);
+    assert_eq!(ctx.client().get_stream_count(), 1);
+
+    // The next period is not due until 1_000.
+    assert_eq!(
+        ctx.client().try_renew_subscription(&id, &ctx.keeper),
+        Err(Ok(ContractError::KeeperGracePeriodNotElapsed))
+    );
+
+    ctx.advance(1_200);
+    assert_eq!(ctx.client().withdraw(&id, &None), 1_200);
+}
+
+#[test]
+fn renewal_stops_when_allowance_runs_out() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_subscription();
+    ctx.token()
+        .approve(&ctx.sender, &ctx.contract_id, &500, &200_000u32);
+
+    ctx.advance(500);
+    assert!(ctx.renew(id));
+
+    ctx.advance(1_000);
+    let sender_before = ctx.token().balance(&ctx.sender);
+    assert!(!ctx.renew(id));
+    let stop = ctx.last_stop();
+    assert_eq!(stop.required, 500);
+    assert_eq!(stop.reason, SubscriptionStopReason::InsufficientAllowance);
+
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before);
+    assert_eq!(ctx.client().get_subscription(&id), None);
+    assert_eq!(ctx.client().get_stream_state(&id).end_time, 1_500);
+    assert_eq!(
+        ctx.client().try_renew_subscription(&id, &ctx.keeper),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
+
+#[test]
+fn renewal_stops_when_balance_runs_out() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_subscription();
+    let elsewhere = Address::generate(&ctx.env);
+    let balance = ctx.token().balance(&ctx.sender);
+    ctx.token()
+        .transfer(&ctx.sender, &elsewhere, &(balance - 499));
+
+    ctx.advance(600);
+    assert!(!ctx.renew(id));
+    assert_eq!(
+        ctx.last_stop().reason,
+        SubscriptionStopReason::InsufficientBalance
+    );
+    assert_eq!(ctx.token().balance(&ctx.sender), 499);
+    assert_eq!(ctx.client().get_stream_state(&id).end_time, 1_000);
+}
+
+#[test]
+fn set_subscription_validation() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_subscription();
+    assert_eq!(ctx.client().get_subscription(&id), Some(500));
+
+    assert_eq!(
+        ctx.client().try_set_subscription(&id, &Some(0)),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_set_subscription(&99, &Some(10)),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+
+    ctx.client().set_subscription(&id, &None);
+    assert_eq!(ctx.client().get_subscription(&id), None);
+    assert_eq!(
+        ctx.client().try_renew_subscription(&id, &ctx.keeper),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    ctx.client().cancel_stream(&id);
+    assert_eq!(
+        ctx.client().try_set_subscription(&id, &Some(10)),
+        Err(Ok(ContractError::InvalidState))
+    );
+}
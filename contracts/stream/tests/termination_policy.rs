@@ -0,0 +1,318 @@
+//! Integration tests for per-stream termination policies.
+//!
+//! # What is tested
+//!
+//! 1. `GoodLeaver` keeps vesting on schedule for its window after the
+//!    cancellation, `Accelerate` vests a share of the remainder at once, and
+//!    both fix the sender refund at cancellation.
+//! 2. `BadLeaver` forfeits accrual above the floor but never what was
+//!    already withdrawn, including through `cancel_stream_as_admin`.
+//! 3. `keeper_cancel` and its `get_keeper_fee_split` preview honour the policy.
+//! 4. Policy validation and the `get_termination_policy` view.
+//! 5. `bulk_cancel_streams` pays a good leaver only what has vested so far.
+//! 6. `merge_streams` requires a common policy and `split_stream` copies it.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test termination_policy
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::{
+    ContractError, CreateStreamParams, FluxoraStream, FluxoraStreamClient, SplitShare, StreamKind,
+    TerminationApplied, TerminationPolicy,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    /// 1 token/s from 0 to 1_000 under `policy`.
+    fn create(&self, policy: TerminationPolicy) -> u64 {
+        self.client()
+            .create_stream_with_termination(&self.sender, &self.params(), &policy)
+    }
+
+    fn params(&self) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: 1_000,
+            rate_per_second: 1,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: 1_000,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Linear,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+
+    /// Cancel at `timestamp` and return `(vested, sender_refund)`.
+    fn cancel_at(&self, stream_id: u64, timestamp: u64) -> (i128, i128) {
+        self.advance(timestamp);
+        let before = self.token().balance(&self.sender);
+        self.client().cancel_stream(&stream_id);
+        let refund = self.token().balance(&self.sender) - before;
+        (self.client().calculate_accrued(&stream_id), refund)
+    }
+}
+
+#[test]
+fn good_leaver_and_acceleration_vest_extra() {
+    let ctx = Ctx::setup();
+    let good = ctx.create(TerminationPolicy::GoodLeaver(100));
+    let fast = ctx.create(TerminationPolicy::Accelerate(5_000));
+
+    // The refund is settled at once; the extra 100 vests over the window.
+    assert_eq!(ctx.cancel_at(good, 200), (200, 700));
+    let event = ctx
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|e| {
+            let topic: Symbol = e.1.get(0).unwrap().into_val(&ctx.env);
+            topic == symbol_short!("term_appl")
+        })
+        .unwrap();
+    let applied = TerminationApplied::try_from_val(&ctx.env, &event.2).unwrap();
+    assert_eq!((applied.accrued, applied.vested), (200, 300));
+
+    ctx.advance(250);
+    assert_eq!(ctx.client().calculate_accrued(&good), 250);
+    assert_eq!(ctx.client().withdraw(&good, &None), 250);
+    assert_eq!(
+        ctx.client().try_close_cancelled_stream(&good),
+        Err(Ok(ContractError::InvalidState))
+    );
+
+    // Half of the 700 still unvested at 300 vests on cancellation.
+    assert_eq!(ctx.cancel_at(fast, 300), (650, 350));
+
+    // Vesting stops at the end of the window.
+    ctx.advance(900);
+    assert_eq!(ctx.client().calculate_accrued(&good), 300);
+    assert_eq!(ctx.client().withdraw(&good, &None), 50);
+    assert_eq!(ctx.client().withdraw(&fast, &None), 650);
+    ctx.client().close_cancelled_stream(&good);
+}
+
+#[test]
+fn bad_leaver_forfeits_above_floor_but_keeps_withdrawn() {
+    let ctx = Ctx::setup();
+    let kept = ctx.create(TerminationPolicy::BadLeaver(150));
+    let withdrawn = ctx.create(TerminationPolicy::BadLeaver(150));
+
+    ctx.advance(300);
+    assert_eq!(ctx.client().withdraw(&withdrawn, &None), 300);
+
+    assert_eq!(ctx.cancel_at(kept, 400), (150, 850));
+
+    ctx.advance(500);
+    let before = ctx.token().balance(&ctx.sender);
+    ctx.client().cancel_stream_as_admin(&withdrawn);
+    assert_eq!(ctx.client().calculate_accrued(&withdrawn), 300);
+    assert_eq!(ctx.token().balance(&ctx.sender) - before, 700);
+
+    ctx.advance(600);
+    assert_eq!(ctx.client().withdraw(&kept, &None), 150);
+    assert_eq!(ctx.client().withdraw(&withdrawn, &None), 0);
+}
+
+#[test]
+fn keeper_cancel_and_preview_apply_policy() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(TerminationPolicy::BadLeaver(400));
+    let keeper = Address::generate(&ctx.env);
+
+    ctx.advance(1_000 + 7 * 24 * 60 * 60);
+    let (keeper_fee, sender_refund) = ctx.client().get_keeper_fee_split(&id);
+    assert_eq!(keeper_fee + sender_refund, 600);
+
+    let sender_before = ctx.token().balance(&ctx.sender);
+    ctx.client().keeper_cancel(&id, &keeper);
+    assert_eq!(ctx.token().balance(&keeper), keeper_fee);
+    assert_eq!(
+        ctx.token().balance(&ctx.sender) - sender_before,
+        sender_refund
+    );
+    assert_eq!(ctx.token().balance(&ctx.recipient), 400);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+}
+
+#[test]
+fn policy_validation_and_view() {
+    let ctx = Ctx::setup();
+    for policy in [
+        TerminationPolicy::Accelerate(0),
+        TerminationPolicy::Accelerate(10_001),
+        TerminationPolicy::BadLeaver(-1),
+    ] {
+        assert_eq!(
+            ctx.client().try_create_stream_with_termination(
+                &ctx.sender,
+                &CreateStreamParams {
+                    recipient: ctx.recipient.clone(),
+                    deposit_amount: 1_000,
+                    rate_per_second: 1,
+                    start_time: 0,
+                    cliff_time: 0,
+                    end_time: 1_000,
+                    withdraw_dust_threshold: Some(0),
+                    memo: None,
+                    metadata: None,
+                    kind: StreamKind::Linear,
+                    irrevocable: None,
+                    witness: None,
+                },
+                &policy,
+            ),
+            Err(Ok(ContractError::InvalidParams))
+        );
+    }
+    assert_eq!(ctx.client().get_stream_count(), 0);
+
+    let standard = ctx.create(TerminationPolicy::Standard);
+    let good = ctx.create(TerminationPolicy::GoodLeaver(60));
+    assert_eq!(
+        ctx.client().get_termination_policy(&standard),
+        TerminationPolicy::Standard
+    );
+    assert_eq!(
+        ctx.client().get_termination_policy(&good),
+        TerminationPolicy::GoodLeaver(60)
+    );
+    assert_eq!(ctx.cancel_at(standard, 100), (100, 900));
+    assert_eq!(
+        ctx.client().try_get_termination_policy(&99),
+        Err(Ok(ContractError::StreamNotFound))
+    );
+}
+
+#[test]
+fn bulk_cancel_pays_good_leaver_as_it_vests() {
+    let ctx = Ctx::setup();
+    let id = ctx.create(TerminationPolicy::GoodLeaver(100));
+
+    ctx.advance(200);
+    ctx.client()
+        .bulk_cancel_streams(&ctx.sender, &vec![&ctx.env, id]);
+    assert_eq!(ctx.token().balance(&ctx.recipient), 200);
+
+    ctx.advance(400);
+    assert_eq!(ctx.client().withdraw(&id, &None), 100);
+    ctx.client().close_cancelled_stream(&id);
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+}
+
+#[test]
+fn merge_requires_common_policy_and_split_copies_it() {
+    let ctx = Ctx::setup();
+    let plain = ctx.client().create_stream(&ctx.sender, &ctx.params());
+    let standard = ctx.create(TerminationPolicy::Standard);
+    let bad = ctx.create(TerminationPolicy::BadLeaver(150));
+    let good = ctx.create(TerminationPolicy::GoodLeaver(60));
+    let other_good = ctx.create(TerminationPolicy::GoodLeaver(60));
+    ctx.advance(100);
+
+    assert_eq!(
+        ctx.client()
+            .try_merge_streams(&vec![&ctx.env, standard, bad]),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        ctx.client().try_merge_streams(&vec![&ctx.env, good, bad]),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    // No policy and `Standard` are the same thing.
+    ctx.client().merge_streams(&vec![&ctx.env, plain, standard]);
+
+    let merged = ctx
+        .client()
+        .merge_streams(&vec![&ctx.env, good, other_good]);
+    assert_eq!(
+        ctx.client().get_termination_policy(&merged),
+        TerminationPolicy::GoodLeaver(60)
+    );
+
+    // Splitting needs a rate that divides into two non-zero halves.
+    let splittable = ctx.client().create_stream_with_termination(
+        &ctx.sender,
+        &CreateStreamParams {
+            deposit_amount: 2_000,
+            rate_per_second: 2,
+            start_time: 100,
+            cliff_time: 100,
+            ..ctx.params()
+        },
+        &TerminationPolicy::BadLeaver(150),
+    );
+    let new_recipient = Address::generate(&ctx.env);
+    let child = ctx
+        .client()
+        .split_stream(&splittable, &SplitShare::Bps(5_000), &new_recipient);
+    assert_eq!(
+        ctx.client().get_termination_policy(&child),
+        TerminationPolicy::BadLeaver(150)
+    );
+}
@@ -0,0 +1,303 @@
+//! Integration tests for `StreamKind::Tranched` and `create_stream_with_tranches`.
+//!
+//! # What is tested
+//!
+//! 1. A grant unlocking 25% at t=400 and then every 200s steps through
+//!    `calculate_accrued`, `get_withdrawable` and `get_claimable_at`.
+//! 2. `get_cliff_status` and `get_next_unlock` report the next pending tranche.
+//! 3. `withdraw` and `cancel_stream` settle against the schedule, and the
+//!    schedule is removed when the stream is closed.
+//! 4. Creation rejects malformed schedules and non-Tranched kinds;
+//!    `create_stream` rejects `Tranched`, and mutators reject Tranched streams.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test -p fluxora_stream --test tranched_schedule
+//! ```
+
+#![cfg(test)]
+
+use fluxora_stream::accrual::{CliffStatus, Tranche};
+use fluxora_stream::{
+    ContractError, CreateStreamParams, DataKeyExt, FluxoraStream, FluxoraStreamClient, StreamKind,
+    StreamStatus,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    contract_id: Address,
+    token_id: Address,
+    sender: Address,
+    recipient: Address,
+}
+
+impl Ctx {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, FluxoraStream);
+        let token_admin = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        FluxoraStreamClient::new(&env, &contract_id).init(&token_id, &admin);
+        StellarAssetClient::new(&env, &token_id).mint(&sender, &10_000_i128);
+        TokenClient::new(&env, &token_id).approve(&sender, &contract_id, &i128::MAX, &200_000u32);
+
+        env.ledger().set_timestamp(0);
+        env.ledger().set_sequence_number(100);
+
+        Ctx {
+            env,
+            contract_id,
+            token_id,
+            sender,
+            recipient,
+        }
+    }
+
+    fn client(&self) -> FluxoraStreamClient<'_> {
+        FluxoraStreamClient::new(&self.env, &self.contract_id)
+    }
+
+    fn token(&self) -> TokenClient<'_> {
+        TokenClient::new(&self.env, &self.token_id)
+    }
+
+    fn params(&self, deposit: i128, end: u64) -> CreateStreamParams {
+        CreateStreamParams {
+            recipient: self.recipient.clone(),
+            deposit_amount: deposit,
+            rate_per_second: 0,
+            start_time: 0,
+            cliff_time: 0,
+            end_time: end,
+            withdraw_dust_threshold: Some(0),
+            memo: None,
+            metadata: None,
+            kind: StreamKind::Tranched,
+            irrevocable: None,
+            witness: None,
+        }
+    }
+
+    /// 250 of 1_000 at t=400, then another 250 every 200s.
+    fn grant(&self) -> Vec<Tranche> {
+        let mut tranches = Vec::new(&self.env);
+        for (i, unlock_time) in [400u64, 600, 800, 1_000].into_iter().enumerate() {
+            tranches.push_back(Tranche {
+                unlock_time,
+                cumulative_amount: 250 * (i as i128 + 1),
+            });
+        }
+        tranches
+    }
+
+    fn create_grant(&self) -> u64 {
+        self.client().create_stream_with_tranches(
+            &self.sender,
+            &self.params(1_000, 1_000),
+            &self.grant(),
+        )
+    }
+
+    fn advance(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + 10_000);
+    }
+}
+
+#[test]
+fn grant_unlocks_tranche_by_tranche() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_grant();
+
+    let stream = ctx.client().get_stream_state(&id);
+    assert_eq!(stream.kind, StreamKind::Tranched);
+    assert_eq!(stream.rate_per_second, 0);
+    assert_eq!(ctx.client().get_tranche_schedule(&id), ctx.grant());
+
+    for (t, expected) in [
+        (0u64, 0i128),
+        (399, 0),
+        (400, 250),
+        (599, 250),
+        (600, 500),
+        (999, 750),
+        (1_000, 1_000),
+        (5_000, 1_000),
+    ] {
+        ctx.advance(t);
+        assert_eq!(ctx.client().calculate_accrued(&id), expected, "t={t}");
+        assert_eq!(ctx.client().get_withdrawable(&id), expected, "t={t}");
+    }
+    assert_eq!(ctx.client().get_claimable_at(&id, &700), 500);
+}
+
+#[test]
+fn cliff_status_tracks_next_unlock() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_grant();
+
+    assert_eq!(ctx.client().get_cliff_status(&id), CliffStatus::Pending);
+    assert_eq!(
+        ctx.client().get_next_unlock(&id),
+        Some(ctx.grant().get(0).unwrap())
+    );
+
+    ctx.advance(395);
+    assert_eq!(
+        ctx.client().get_cliff_status(&id),
+        CliffStatus::WithinSkewWindow
+    );
+
+    // Past the first tranche the status follows the next one.
+    ctx.advance(400);
+    assert_eq!(ctx.client().get_cliff_status(&id), CliffStatus::Pending);
+    assert_eq!(
+        ctx.client().get_next_unlock(&id),
+        Some(ctx.grant().get(1).unwrap())
+    );
+
+    ctx.advance(1_000);
+    assert_eq!(ctx.client().get_cliff_status(&id), CliffStatus::Unlocked);
+    assert_eq!(ctx.client().get_next_unlock(&id), None);
+}
+
+#[test]
+fn withdraw_cancel_and_close_settle_against_schedule() {
+    let ctx = Ctx::setup();
+    let id = ctx.create_grant();
+    let sender_before = ctx.token().balance(&ctx.sender);
+
+    ctx.advance(650);
+    assert_eq!(ctx.client().withdraw(&id, &None), 500);
+
+    ctx.advance(750);
+    ctx.client().cancel_stream(&id);
+    // Only the two unlocked tranches vest; the rest is refunded.
+    assert_eq!(ctx.token().balance(&ctx.sender), sender_before + 500);
+    assert_eq!(ctx.client().get_withdrawable(&id), 0);
+    assert_eq!(
+        ctx.client().get_next_unlock(&id),
+        Some(ctx.grant().get(2).unwrap())
+    );
+    assert_eq!(ctx.client().get_total_liabilities(), 0);
+
+    let stream = ctx.client().get_stream_state(&id);
+    assert_eq!(stream.status, StreamStatus::Cancelled);
+    ctx.client().close_cancelled_stream(&id);
+    let cid = ctx.contract_id.clone();
+    ctx.env.as_contract(&cid, || {
+        assert!(!ctx
+            .env
+            .storage()
+            .persistent()
+            .has(&DataKeyExt::TrancheSchedule(id)));
+    });
+}
+
+#[test]
+fn creation_validates_schedule() {
+    let ctx = Ctx::setup();
+    let client = ctx.client();
+    let one = |unlock_time: u64, cumulative_amount: i128| {
+        vec![
+            &ctx.env,
+            Tranche {
+                unlock_time,
+                cumulative_amount,
+            },
+        ]
+    };
+
+    // The last unlock must land on end_time.
+    assert_eq!(
+        client.try_create_stream_with_tranches(
+            &ctx.sender,
+            &ctx.params(1_000, 1_200),
+            &ctx.grant()
+        ),
+        Err(Ok(ContractError::RateScheduleInvalid))
+    );
+    // Deposit must cover the final cumulative amount.
+    assert_eq!(
+        client.try_create_stream_with_tranches(&ctx.sender, &ctx.params(999, 1_000), &ctx.grant()),
+        Err(Ok(ContractError::InsufficientDeposit))
+    );
+    // Amounts must strictly increase.
+    let mut flat = ctx.grant();
+    flat.set(
+        1,
+        Tranche {
+            unlock_time: 600,
+            cumulative_amount: 250,
+        },
+    );
+    assert_eq!(
+        client.try_create_stream_with_tranches(&ctx.sender, &ctx.params(1_000, 1_000), &flat),
+        Err(Ok(ContractError::RateScheduleInvalid))
+    );
+    // An unlock before start_time is rejected.
+    let mut late_start = ctx.params(100, 100);
+    late_start.start_time = 10;
+    late_start.cliff_time = 10;
+    let mut early = one(5, 50);
+    early.push_back(Tranche {
+        unlock_time: 100,
+        cumulative_amount: 100,
+    });
+    assert_eq!(
+        client.try_create_stream_with_tranches(&ctx.sender, &late_start, &early),
+        Err(Ok(ContractError::RateScheduleInvalid))
+    );
+    // Empty schedules, base rates and other kinds are rejected.
+    assert_eq!(
+        client.try_create_stream_with_tranches(
+            &ctx.sender,
+            &ctx.params(100, 100),
+            &Vec::new(&ctx.env)
+        ),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let mut with_rate = ctx.params(100, 100);
+    with_rate.rate_per_second = 1;
+    assert_eq!(
+        client.try_create_stream_with_tranches(&ctx.sender, &with_rate, &one(100, 100)),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    let mut linear = ctx.params(100, 100);
+    linear.kind = StreamKind::Linear;
+    assert_eq!(
+        client.try_create_stream_with_tranches(&ctx.sender, &linear, &one(100, 100)),
+        Err(Ok(ContractError::InvalidParams))
+    );
+    assert_eq!(
+        client.try_create_stream(&ctx.sender, &ctx.params(100, 100)),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+    assert_eq!(client.get_total_liabilities(), 0);
+
+    let id = ctx.create_grant();
+    assert_eq!(
+        client.try_update_rate_per_second(&id, &5),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+    assert_eq!(
+        client.try_top_up_stream(&id, &ctx.sender, &100),
+        Err(Ok(ContractError::UnsupportedStreamKind))
+    );
+}
@@ -27,12 +27,12 @@
 //! environment with deployable WASM artifacts is available.
 
 use fluxora_stream::{
-    ContractError, ContractUpgraded, DataKey, FluxoraStream, FluxoraStreamClient,
+    ContractError, ContractUpgraded, DataKey, FluxoraStream, FluxoraStreamClient, UpgradeRecord,
 };
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
     token::{Client as TokenClient, StellarAssetClient},
-    Address, BytesN, Env, IntoVal,
+    Address, Bytes, BytesN, Env, IntoVal,
 };
 
 /// Test context for upgrade tests
@@ -297,16 +297,16 @@ fn test_version_budget_is_lower_than_storage_backed_view() {
     );
 }
 
-/// `CONTRACT_VERSION` constant equals exactly 9 (the current release).
+/// `CONTRACT_VERSION` constant equals exactly 10 (the current release).
 ///
 /// This pins the expected value so any accidental bump or rollback is
 /// immediately visible as a test failure rather than silent drift.
 #[test]
-fn test_contract_version_constant_is_9() {
+fn test_contract_version_constant_is_10() {
     assert_eq!(
         fluxora_stream::CONTRACT_VERSION,
-        9,
-        "CONTRACT_VERSION must be 9 for this release"
+        10,
+        "CONTRACT_VERSION must be 10 for this release"
     );
 }
 
@@ -503,3 +503,95 @@ fn test_admin_rotation_possible_after_failed_upgrade() {
         "admin rotation must succeed after failed upgrade"
     );
 }
+
+// -----------------------------------------------------------------------
+// Upgrade history
+// -----------------------------------------------------------------------
+
+/// Smallest module the host accepts for upload: an exported memory plus the
+/// `contractenvmetav0` interface-version section. `tag` varies the hash.
+///
+/// Once a contract's executable points at this module, calls through the
+/// generated client hit the (empty) WASM, so history reads after an upgrade go
+/// through `env.as_contract` and the native entrypoints instead.
+fn stub_wasm(env: &Env, tag: u8) -> Bytes {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.extend_from_slice(&[5, 3, 1, 0, 1]);
+    wasm.extend_from_slice(&[7, 10, 1, 6]);
+    wasm.extend_from_slice(b"memory");
+    wasm.extend_from_slice(&[2, 0]);
+    let name = b"contractenvmetav0";
+    let mut payload = vec![name.len() as u8];
+    payload.extend_from_slice(name);
+    payload.extend_from_slice(&0u32.to_be_bytes());
+    payload.extend_from_slice(&(21u64 << 32).to_be_bytes());
+    payload.push(tag);
+    wasm.push(0);
+    wasm.push(payload.len() as u8);
+    wasm.extend_from_slice(&payload);
+    Bytes::from_slice(env, &wasm)
+}
+
+#[test]
+fn test_upgrade_history_empty_and_unchanged_by_failed_upgrade() {
+    let ctx = UpgradeTestCtx::setup();
+    assert_eq!(ctx.client.get_upgrade_count(), 0);
+
+    let invalid_hash = BytesN::from_array(&ctx.env, &[0u8; 32]);
+    assert!(ctx.client.try_upgrade(&invalid_hash).is_err());
+
+    assert_eq!(ctx.client.get_upgrade_count(), 0);
+    assert_eq!(ctx.client.get_upgrade_history(&0, &10).len(), 0);
+}
+
+#[test]
+fn test_upgrade_history_chains_hashes() {
+    let ctx = UpgradeTestCtx::setup();
+    let first = ctx
+        .env
+        .deployer()
+        .upload_contract_wasm(stub_wasm(&ctx.env, 1));
+    let second = ctx
+        .env
+        .deployer()
+        .upload_contract_wasm(stub_wasm(&ctx.env, 2));
+
+    ctx.env.ledger().set_sequence_number(500);
+    ctx.client.upgrade(&first);
+    ctx.env.ledger().set_timestamp(1_000_100);
+    ctx.env.ledger().set_sequence_number(520);
+    ctx.env.as_contract(&ctx.contract_id, || {
+        fluxora_stream::upgrade(ctx.env.clone(), second.clone()).unwrap()
+    });
+
+    let (count, history, tail) = ctx.env.as_contract(&ctx.contract_id, || {
+        (
+            FluxoraStream::get_upgrade_count(ctx.env.clone()),
+            FluxoraStream::get_upgrade_history(ctx.env.clone(), 0, 10),
+            FluxoraStream::get_upgrade_history(ctx.env.clone(), 1, 10),
+        )
+    });
+    assert_eq!(count, 2);
+    assert_eq!(
+        history,
+        soroban_sdk::vec![
+            &ctx.env,
+            UpgradeRecord {
+                old_wasm_hash: BytesN::from_array(&ctx.env, &[0u8; 32]),
+                new_wasm_hash: first.clone(),
+                ledger: 500,
+                upgraded_at: 1_000_000,
+                contract_version: fluxora_stream::CONTRACT_VERSION,
+            },
+            UpgradeRecord {
+                old_wasm_hash: first,
+                new_wasm_hash: second,
+                ledger: 520,
+                upgraded_at: 1_000_100,
+                contract_version: fluxora_stream::CONTRACT_VERSION,
+            },
+        ]
+    );
+    assert_eq!(tail.len(), 1);
+    assert_eq!(tail.get(0), history.get(1));
+}